@@ -2,16 +2,15 @@ extern crate mcclient;
 
 use std::io;
 
-use mcclient::mc::connection::{OfflineConnection, MinecraftStream};
+use mcclient::mc::connection::MinecraftStream;
 
 fn main() -> Result<(), io::Error> {
     const DOMAIN: &str = "localhost";
     const PORT: u16 = 25565;
-    const USERNAME: &str = "MonkeyDLuffy";
 
     println!("Connecting...");
     
-    let connection = MinecraftStream::connect(format!("{}:{}", DOMAIN, PORT))?;
+    let _connection = MinecraftStream::connect(format!("{}:{}", DOMAIN, PORT))?;
     
     println!("Connection successful. Requesting status...");
 
@@ -0,0 +1,318 @@
+//! Derive macros for the packet structures of `mcclient`. Packets derive their
+//! serialization from the order of their fields, each of which is encoded as the
+//! `MCType` it is declared as.
+//! <br> <br>
+//! The packet's ID and protocol state are given with a `#[packet(id = .., state = ..)]`
+//! attribute on the struct, from which `PacketId` is derived. Outbound packets which move
+//! the connection to another state also give `next_state = ..`. Fields may be annotated with:
+//! - `#[varint]`, to encode an `i32` field as a `VarInt`.
+//! - `#[max_len = N]`, to bound a `MCString` field to `N` characters.
+//! - `#[when(condition)]`, to only include an `Option` field if `condition` holds for the
+//!   protocol number `version`, as in `#[when(version >= 759)]`.
+//!
+//! The `version` is the one given to `OutboundPacket::encode_for` and
+//! `InboundPacket::from_data_for`, which streams call with the version they negotiated;
+//! `encode` and `from_data` lay packets out as in `PROTOCOL_VERSION`.
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{quote, quote_spanned};
+use syn::{parse_macro_input, spanned::Spanned, Data, DeriveInput, Expr, Fields, Ident, LitInt};
+
+/// Derives `PacketId` from the `#[packet(id = .., state = ..)]` attribute of a packet.
+#[proc_macro_derive(PacketId, attributes(packet, varint, max_len, when))]
+pub fn derive_packet_id(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(packet_id(&input))
+}
+
+/// Derives `OutboundPacket`, encoding the fields of a packet in their declared order.
+#[proc_macro_derive(McEncode, attributes(packet, varint, max_len, when))]
+pub fn derive_mc_encode(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(mc_encode(&input))
+}
+
+/// Derives `InboundPacket`, decoding the fields of a packet in their declared order.
+#[proc_macro_derive(McDecode, attributes(packet, varint, max_len, when))]
+pub fn derive_mc_decode(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(mc_decode(&input))
+}
+
+fn expand(result: syn::Result<TokenStream2>) -> TokenStream {
+    result.unwrap_or_else(syn::Error::into_compile_error).into()
+}
+
+/// A field of a packet, with its attributes.
+struct PacketField {
+    ident: Ident,
+    varint: bool,
+    max_len: Option<LitInt>,
+    when: Option<Expr>
+}
+
+impl PacketField {
+    /// The expression evaluating the field's `#[when]` condition for the `ProtocolVersion`
+    /// bound to `version`, which holds if it has none.
+    fn condition(&self) -> TokenStream2 {
+        match &self.when {
+            Some(condition) => quote! {
+                {
+                    #[allow(unused_variables)]
+                    let version: i32 = version.number();
+                    #condition
+                }
+            },
+            None => quote! { true }
+        }
+    }
+
+    /// The statement encoding the value `value` of the field to the writer `w`.
+    fn encode(&self, value: TokenStream2) -> TokenStream2 {
+        let name = self.ident.to_string();
+
+        let check = self.max_len.as_ref().map(|max_len| quote! {
+            if (#value).string().encode_utf16().count() > #max_len as usize {
+                return ::std::result::Result::Err(::std::io::Error::new(
+                    ::std::io::ErrorKind::InvalidInput,
+                    ::std::format!("Field {} exceeds {} characters.", #name, #max_len)
+                ));
+            }
+        });
+        let encode = match self.varint {
+            true => quote! { ::mcclient::mc::mctypes::MCType::encode(&::mcclient::mc::mctypes::VarInt::from(*#value), w)?; },
+            false => quote! { ::mcclient::mc::mctypes::MCType::encode(#value, w)?; }
+        };
+
+        quote! { #check #encode }
+    }
+
+    /// The expression computing the encoded size of the value `value` of the field.
+    fn size(&self, value: TokenStream2) -> TokenStream2 {
+        match self.varint {
+            true => quote! { ::mcclient::mc::mctypes::MCType::size(&::mcclient::mc::mctypes::VarInt::from(*#value)) },
+            false => quote! { ::mcclient::mc::mctypes::MCType::size(#value) }
+        }
+    }
+
+    /// The expression reading the field from `cursor`.
+    fn decode(&self) -> TokenStream2 {
+        if self.varint {
+            return quote! { ::mcclient::mc::mctypes::MCTypeDecode::read(&mut cursor).map(|v: ::mcclient::mc::mctypes::VarInt| v.value())? };
+        }
+
+        match &self.max_len {
+            Some(max_len) => quote! { ::mcclient::mc::mctypes::MCString::read_bounded(&mut cursor, #max_len)? },
+            None => quote! { ::mcclient::mc::mctypes::MCTypeDecode::read(&mut cursor)? }
+        }
+    }
+}
+
+fn fields(input: &DeriveInput) -> syn::Result<Vec<PacketField>> {
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => fields.named.iter().collect::<Vec<_>>(),
+            Fields::Unit => vec![],
+            Fields::Unnamed(fields) => return Err(syn::Error::new(fields.span(), "Packets must have named fields."))
+        },
+        _ => return Err(syn::Error::new(input.span(), "Packets must be structs."))
+    };
+
+    fields.into_iter().map(|field| {
+        let mut packet_field = PacketField {
+            ident: field.ident.clone().expect("Named fields have identifiers."),
+            varint: false,
+            max_len: None,
+            when: None
+        };
+
+        for attr in &field.attrs {
+            if attr.path().is_ident("varint") {
+                attr.meta.require_path_only()?;
+                packet_field.varint = true;
+            } else if attr.path().is_ident("max_len") {
+                let value = &attr.meta.require_name_value()?.value;
+                packet_field.max_len = Some(syn::parse2(quote! { #value })?);
+            } else if attr.path().is_ident("when") {
+                packet_field.when = Some(attr.parse_args()?);
+            }
+        }
+
+        if packet_field.varint && packet_field.max_len.is_some() {
+            return Err(syn::Error::new(field.span(), "A field cannot be both #[varint] and #[max_len]."));
+        }
+
+        Ok(packet_field)
+    }).collect()
+}
+
+/// The arguments of the `#[packet(..)]` attribute of a packet.
+struct PacketAttribute {
+    id: Expr,
+    state: Ident,
+    next_state: Option<Ident>
+}
+
+fn packet_attribute(input: &DeriveInput) -> syn::Result<PacketAttribute> {
+    let mut id: Option<Expr> = None;
+    let mut state: Option<Ident> = None;
+    let mut next_state: Option<Ident> = None;
+
+    for attr in input.attrs.iter().filter(|attr| attr.path().is_ident("packet")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("id") {
+                id = Some(meta.value()?.parse()?);
+            } else if meta.path.is_ident("state") {
+                state = Some(meta.value()?.parse()?);
+            } else if meta.path.is_ident("next_state") {
+                next_state = Some(meta.value()?.parse()?);
+            } else {
+                return Err(meta.error("Expected `id`, `state` or `next_state`."));
+            }
+            Ok(())
+        })?;
+    }
+
+    let missing = |key: &str| syn::Error::new(input.span(), format!("Missing #[packet({} = ..)] attribute.", key));
+    Ok(PacketAttribute {
+        id: id.ok_or_else(|| missing("id"))?,
+        state: state.ok_or_else(|| missing("state"))?,
+        next_state
+    })
+}
+
+fn packet_id(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    let PacketAttribute { id, state, .. } = packet_attribute(input)?;
+    let name = &input.ident;
+
+    Ok(quote! {
+        impl ::mcclient::mc::packet::PacketId for #name {
+            const ID: i32 = #id;
+            const STATE: ::mcclient::mc::connection::ConnectionState = ::mcclient::mc::connection::ConnectionState::#state;
+        }
+    })
+}
+
+fn mc_encode(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    let name = &input.ident;
+    let fields = fields(input)?;
+    let next_state = packet_attribute(input)?.next_state.map(|next_state| quote! {
+        fn next_state(&self) -> ::std::option::Option<::mcclient::mc::connection::ConnectionState> {
+            ::std::option::Option::Some(::mcclient::mc::connection::ConnectionState::#next_state)
+        }
+    });
+
+    let encodes = fields.iter().map(|field| {
+        let ident = &field.ident;
+        match &field.when {
+            Some(_) => {
+                let condition = field.condition();
+                let encode = field.encode(quote! { value });
+                let message = format!("Field {} is required by the protocol version.", ident);
+                quote_spanned! { ident.span() =>
+                    if #condition {
+                        match &self.#ident {
+                            ::std::option::Option::Some(value) => { #encode },
+                            ::std::option::Option::None => return ::std::result::Result::Err(
+                                ::std::io::Error::new(::std::io::ErrorKind::InvalidInput, #message)
+                            )
+                        }
+                    }
+                }
+            },
+            None => field.encode(quote! { &self.#ident })
+        }
+    });
+
+    let sizes = fields.iter().map(|field| {
+        let ident = &field.ident;
+        match &field.when {
+            Some(_) => {
+                let condition = field.condition();
+                let size = field.size(quote! { value });
+                quote! {
+                    size += match &self.#ident {
+                        ::std::option::Option::Some(value) if #condition => #size,
+                        _ => 0
+                    };
+                }
+            },
+            None => {
+                let size = field.size(quote! { &self.#ident });
+                quote! { size += #size; }
+            }
+        }
+    });
+
+    Ok(quote! {
+        impl ::mcclient::mc::packet::OutboundPacket for #name {
+            fn encode(&self, w: &mut dyn ::std::io::Write) -> ::std::io::Result<()> {
+                ::mcclient::mc::packet::OutboundPacket::encode_for(self, ::mcclient::mc::version::ProtocolVersion::from(::mcclient::mc::PROTOCOL_VERSION), w)
+            }
+
+            #[allow(unused_variables)]
+            fn encode_for(&self, version: ::mcclient::mc::version::ProtocolVersion, w: &mut dyn ::std::io::Write) -> ::std::io::Result<()> {
+                #(#encodes)*
+                ::std::result::Result::Ok(())
+            }
+
+            #next_state
+
+            fn len(&self) -> i32 {
+                ::mcclient::mc::packet::OutboundPacket::len_for(self, ::mcclient::mc::version::ProtocolVersion::from(::mcclient::mc::PROTOCOL_VERSION))
+            }
+
+            #[allow(unused_variables)]
+            fn len_for(&self, version: ::mcclient::mc::version::ProtocolVersion) -> i32 {
+                #[allow(unused_mut)]
+                let mut size: i32 = 0;
+                #(#sizes)*
+                size
+            }
+        }
+    })
+}
+
+fn mc_decode(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    let name = &input.ident;
+    let fields = fields(input)?;
+
+    let decodes = fields.iter().map(|field| {
+        let ident = &field.ident;
+        let decode = field.decode();
+        match &field.when {
+            Some(_) => {
+                let condition = field.condition();
+                quote! {
+                    let #ident = match #condition {
+                        true => ::std::option::Option::Some(#decode),
+                        false => ::std::option::Option::None
+                    };
+                }
+            },
+            None => quote! { let #ident = #decode; }
+        }
+    });
+    let idents = fields.iter().map(|field| &field.ident);
+
+    Ok(quote! {
+        impl ::mcclient::mc::packet::InboundPacket for #name {
+            fn from_data(packet: &::mcclient::mc::packet::MCPacket) -> ::std::result::Result<Self, ::std::io::Error> {
+                <Self as ::mcclient::mc::packet::InboundPacket>::from_data_for(packet, ::mcclient::mc::version::ProtocolVersion::from(::mcclient::mc::PROTOCOL_VERSION))
+            }
+
+            #[allow(unused_variables)]
+            fn from_data_for(packet: &::mcclient::mc::packet::MCPacket, version: ::mcclient::mc::version::ProtocolVersion) -> ::std::result::Result<Self, ::std::io::Error> {
+                if !packet.is::<Self>() {
+                    return ::std::result::Result::Err(::std::io::Error::new(::std::io::ErrorKind::InvalidData, "Invalid packet ID."));
+                }
+
+                #[allow(unused_mut, unused_variables)]
+                let mut cursor = packet.cursor();
+                #(#decodes)*
+
+                ::std::result::Result::Ok(#name { #(#idents),* })
+            }
+        }
+    })
+}
@@ -1,2 +1,16 @@
+//! Without the default `std` feature, only the encoding and decoding of protocol types and
+//! NBT is built, with `no_std` and `alloc`. The `wasm` feature adds the server status ping
+//! over an `AsyncTransport` to that, as `mc::status::status_over`.
+#![cfg_attr(not(any(feature = "std", feature = "wasm")), no_std)]
+
+extern crate alloc;
+
+// Lets the derive macros refer to this crate as `::mcclient` from within it.
+extern crate self as mcclient;
+
 pub mod mc;
-pub mod tests;
\ No newline at end of file
+#[cfg(feature = "std")]
+pub mod tests;
+
+#[cfg(feature = "std")]
+pub use mc::{builder::ClientBuilder, capture::replay, client::Client, status::{status, ServerStatus}};
\ No newline at end of file
@@ -1,24 +1,105 @@
-use mcclient::mc::connection::{OfflineConnection, Connection};
+//! A command-line tool for pinging, querying and joining servers, built with the `cli`
+//! feature.
+use std::{env, io, process::ExitCode};
 
-mod mc;
-mod tests;
+use mcclient::{mc::{connection::Connection, error::ClientError, events::Event, query::QueryClient}, ClientBuilder, Client};
 
-fn main() {
-    const DOMAIN: &str = "localhost";
-    const PORT: u16 = 25565;
-    const USERNAME: &str = "MonkeyDLuffy";
+const USAGE: &str = "\
+Usage: mcclient <command> <address> [options]
 
-    println!("Connecting...");
+Commands:
+    ping <address>                    Prints the status of the server.
+    query <address>                   Prints the full query stat of the server.
+    login <address> --offline <name>  Joins the server, and stays until disconnected.
+    chatlog <address> --offline <name>
+                                      Joins the server, and prints its chat.
 
-    let mut connection = OfflineConnection::connect(DOMAIN, PORT)
-        .expect("Could not connect.");
-    
-    println!("Connection successful. Requesting status...");
+Addresses are given as host or host:port, and looked up through SRV records.";
 
-    let status_response = connection.status().expect("Could not get status.");
-    // TODO: Refactor MCTypes as per mctypes repo.
-    println!("Response: {}", status_response.json_response.string());
+fn main() -> ExitCode {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let (command, address) = match (args.first(), args.get(1)) {
+        (Some(command), Some(address)) => (command.as_str(), address.as_str()),
+        _ => return usage()
+    };
 
-    let _ping_response = connection.ping().expect("Could not ping.");
+    let result = match command {
+        "ping" => ping(address),
+        "query" => query(address),
+        "login" | "chatlog" => match offline_name(&args[2..]) {
+            Some(username) => join(address, username, command == "chatlog"),
+            None => return usage()
+        },
+        _ => return usage()
+    };
 
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("{}", ClientError::from(err));
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn usage() -> ExitCode {
+    eprintln!("{}", USAGE);
+    ExitCode::from(2)
+}
+
+/// Finds the username given with `--offline` among `options`. Only offline-mode logins are
+/// supported, as the tool does not sign in to a Microsoft account.
+fn offline_name(options: &[String]) -> Option<&str> {
+    let position = options.iter().position(|option| option == "--offline")?;
+    options.get(position + 1).map(String::as_str)
+}
+
+fn ping(address: &str) -> Result<(), io::Error> {
+    let status = mcclient::status(address)?;
+    println!("{}", status.motd);
+    println!("Version: {} (protocol {})", status.version_name, status.protocol);
+    println!("Players: {}/{}", status.players_online, status.players_max);
+    for player in &status.sample {
+        println!("    {}", player.name());
+    }
+    println!("Latency: {} ms", status.latency.as_millis());
+    Ok(())
+}
+
+fn query(address: &str) -> Result<(), io::Error> {
+    let stat = QueryClient::connect(address)?.full_stat()?;
+    println!("{}", stat.motd());
+    println!("Version: {}", stat.version());
+    println!("Map: {}", stat.map());
+    println!("Players: {}/{}", stat.players_online(), stat.players_max());
+    for player in &stat.players {
+        println!("    {}", player);
+    }
+    let (software, plugins) = stat.plugins();
+    if let Some(software) = software {
+        println!("Software: {}", software);
+    }
+    if !plugins.is_empty() {
+        println!("Plugins: {}", plugins.join(", "));
+    }
+    Ok(())
+}
+
+/// Joins the server at `address` as `username`, and reads on until the server disconnects
+/// the player, printing the chat if `chatlog` is set.
+fn join(address: &str, username: &str, chatlog: bool) -> Result<(), io::Error> {
+    let mut client: Client = ClientBuilder::new(address, username).connect()?;
+    eprintln!("Joined as {} ({}).", client.username(), client.uuid());
+    loop {
+        let packet = client.next_packet()?;
+        if !chatlog {
+            continue;
+        }
+        let version = client.connection().sock().version();
+        for event in Event::from_packet_for(&packet, version) {
+            if let Event::ChatReceived { message, overlay: false, .. } = event {
+                println!("{}", message);
+            }
+        }
+    }
 }
@@ -0,0 +1,558 @@
+//! Async counterparts of the blocking connection layer, built on Tokio and available
+//! with the `tokio` feature. Packets are framed, encrypted, compressed and translated
+//! between protocol versions exactly as by `MinecraftStream`, over any transport which
+//! is `AsyncRead + AsyncWrite`.
+//! ```no_run
+//! # async fn run() -> std::io::Result<()> {
+//! use mcclient::mc::async_connection::AsyncOfflineConnection;
+//!
+//! let mut connection = AsyncOfflineConnection::negotiate("localhost", 25565, None).await?;
+//! let profile = connection.login("Makoto").await?;
+//! # Ok(())
+//! # }
+//! ```
+use std::{future::Future, io, mem, path::Path, time::{Instant, SystemTime, UNIX_EPOCH}};
+
+use tokio::{io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt, BufReader}, net::{TcpStream, ToSocketAddrs}};
+use uuid::Uuid;
+
+use super::{auth::AuthSession, capture::Capture, dns, limits::DecodeLimits, metrics::TrafficMetrics, pool::BufferPool, connection::{answer_encryption, offline_uuid, online_mode_error, version_for, ConnectionState, GameConfiguration, LoginStep, StreamCodec}, encryption::{StreamCipher, SHARED_SECRET_LENGTH}, mctypes::VarInt, packet::{clientbound::{login_play::LoginPlay, login_success::LoginSuccess, ping_response::PingResponse, status_response::StatusResponse}, serverbound::{acknowledge_finish_configuration::AcknowledgeFinishConfiguration, config_plugin_message::ConfigPluginMessage, config_resource_pack_response::ConfigResourcePackResponse, plugin_message::PluginMessage, handshake::{Handshake, NextState}, login_acknowledged::LoginAcknowledged, login_start::LoginStart, ping_request::PingRequest, status_request::StatusRequest}, InboundPacket, MCPacket, OutboundPacket}, resource_pack::ResourcePackPolicy, status::{self, ServerStatus}, version::ProtocolVersion};
+
+/// The most bytes the length prefix of a packet can span.
+const MAX_LENGTH_BYTES: usize = 5;
+
+/// The async counterpart of `MinecraftStream`, over TCP unless another transport is given.
+/// Packets are written to the outbound buffer without blocking, and sent with `flush`.
+/// <br> <br>
+/// `read` and `flush` are cancel-safe: what a cancelled call has received or sent is kept,
+/// and the next call resumes from it, so either can be raced against other futures, as in
+/// `tokio::select!`.
+pub struct AsyncMinecraftStream<T = TcpStream> {
+    reader: BufReader<T>,
+    outbound: Vec<u8>,
+    /// The number of bytes at the start of `outbound` which are already encrypted.
+    sealed: usize,
+    codec: StreamCodec,
+    partial: PartialFrame,
+    /// A packet read whose Keep Alive answer has yet to be flushed.
+    answered: Option<MCPacket>
+}
+
+/// The frame being read, kept across cancelled reads.
+#[derive(Default)]
+struct PartialFrame {
+    prefix: [u8; MAX_LENGTH_BYTES],
+    prefix_length: usize,
+    /// The body of the frame once its length is known, and how many of its bytes have
+    /// been received.
+    body: Option<(Vec<u8>, usize)>
+}
+
+impl AsyncMinecraftStream<TcpStream> {
+    /// Connects to the server at `addr` over TCP.
+    /// # Errors
+    /// This function will return an error if the connection cannot be established.
+    pub async fn connect<A: ToSocketAddrs>(addr: A) -> Result<Self, io::Error> {
+        Ok(AsyncMinecraftStream::new(TcpStream::connect(addr).await?))
+    }
+}
+
+impl<T: AsyncRead + AsyncWrite + Unpin> AsyncMinecraftStream<T> {
+    /// Creates a stream which exchanges packets over `transport`.
+    pub fn new(transport: T) -> Self {
+        AsyncMinecraftStream { reader: BufReader::new(transport), outbound: Vec::new(), sealed: 0, codec: StreamCodec::new(), partial: PartialFrame::default(), answered: None }
+    }
+
+    /// Writes to the outbound buffer, as per `MinecraftStream::write`.
+    /// # Errors
+    /// An `io::Error` of kind `InvalidInput` will be returned if the packet belongs to a
+    /// different protocol state than the stream is in, and of kind `Unsupported` if the
+    /// packet does not exist in the stream's protocol version.
+    pub fn write(&mut self, packet: &dyn OutboundPacket) -> Result<(), io::Error> {
+        self.codec.frame(packet, &mut self.outbound)
+    }
+
+    /// Writes to the outbound buffer, and flushes the buffer.
+    /// # Errors
+    /// An `io::Error` of any kind will be returned if the packet cannot be sent or the
+    /// stream cannot be flushed.
+    pub fn send(&mut self, packet: &dyn OutboundPacket) -> impl Future<Output = Result<(), io::Error>> + '_ {
+        // The packet is written before the future is created, so that the future does not
+        // hold it, and can be sent between threads.
+        let written = self.write(packet);
+        async move {
+            written?;
+            self.flush().await
+        }
+    }
+
+    /// Flushes the outbound stream.
+    /// # Errors
+    /// An `io::Error` of any kind will be returned if the bytes cannot be sent to the
+    /// target server.
+    pub async fn flush(&mut self) -> Result<(), io::Error> {
+        self.codec.seal(&mut self.outbound[self.sealed..]);
+        self.sealed = self.outbound.len();
+
+        let transport = self.reader.get_mut();
+        while !self.outbound.is_empty() {
+            let written = transport.write(&self.outbound).await?;
+            if written == 0 {
+                return Err(io::Error::from(io::ErrorKind::WriteZero));
+            }
+            self.outbound.drain(..written);
+            self.sealed -= written;
+        }
+        transport.flush().await
+    }
+
+    /// Reads the next packet from the inbound stream, as per `MinecraftStream::read`.
+    /// # Errors
+    /// An `io::Error` of kind `UnexpectedEof` will be returned if the server closes the
+    /// connection before a full packet arrives, of kind `InvalidData` if the received
+    /// bytes are not a well-formed packet, and of kind `ConnectionAborted` if the server
+    /// sends a Disconnect packet for the current state, carrying its `DisconnectReason`
+    /// as per `ClientError::from`.
+    /// <br> <br>
+    /// Cancelling the future loses nothing: the bytes of the packet received so far are
+    /// kept, and the next read picks up from them.
+    pub async fn read(&mut self) -> Result<MCPacket, io::Error> {
+        let packet = match self.answered.take() {
+            Some(packet) => packet,
+            None => {
+                let (frame, wire_length) = self.read_frame().await?;
+                let packet = self.codec.unpack(frame)?;
+                let packet = self.codec.accept(packet, wire_length)?;
+                if !self.codec.answer_keep_alive(&packet, &mut self.outbound)? {
+                    return Ok(packet);
+                }
+                packet
+            }
+        };
+
+        // The packet is kept until its answer is flushed, so that it is returned by the
+        // next read if this one is cancelled meanwhile.
+        self.answered = Some(packet);
+        self.flush().await?;
+        Ok(self.answered.take().expect("The answered packet is kept until flushed."))
+    }
+
+    /// Reads the next frame, decrypted, along with the number of bytes it spans with its
+    /// length prefix, resuming the frame a cancelled read left partly received.
+    async fn read_frame(&mut self) -> Result<(Vec<u8>, usize), io::Error> {
+        let partial = &mut self.partial;
+        while partial.body.is_none() {
+            let mut byte = [0];
+            if self.reader.read(&mut byte).await? == 0 {
+                return Err(io::Error::from(io::ErrorKind::UnexpectedEof));
+            }
+            if let Some(cipher) = &mut self.codec.cipher {
+                cipher.decrypt(&mut byte);
+            }
+            partial.prefix[partial.prefix_length] = byte[0];
+            partial.prefix_length += 1;
+            if byte[0] & 0x80 != 0 && partial.prefix_length < MAX_LENGTH_BYTES {
+                continue;
+            }
+
+            let prefix = &partial.prefix[..partial.prefix_length];
+            let length = VarInt::read_from(&mut &prefix[..])
+                .and_then(|length| self.codec.limits.packet_size(length.value()).map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err)));
+            let length = match length {
+                Ok(length) => length,
+                Err(err) => {
+                    *partial = PartialFrame::default();
+                    return Err(err);
+                }
+            };
+            let mut frame = self.codec.pool.take(length);
+            frame.resize(length, 0);
+            partial.body = Some((frame, 0));
+        }
+
+        let (frame, received) = partial.body.as_mut().expect("The length of the frame is known.");
+        while *received < frame.len() {
+            let count = self.reader.read(&mut frame[*received..]).await?;
+            if count == 0 {
+                return Err(io::Error::from(io::ErrorKind::UnexpectedEof));
+            }
+            if let Some(cipher) = &mut self.codec.cipher {
+                cipher.decrypt(&mut frame[*received..*received + count]);
+            }
+            *received += count;
+        }
+
+        let PartialFrame { prefix_length, body, .. } = mem::take(partial);
+        let (frame, _) = body.expect("The frame has been received.");
+        let wire_length = prefix_length + frame.len();
+        Ok((frame, wire_length))
+    }
+
+    /// Whether the stream answers the Keep Alives of the server, as per
+    /// `MinecraftStream::auto_keep_alive`.
+    pub fn auto_keep_alive(&self) -> bool {
+        self.codec.auto_keep_alive
+    }
+
+    /// Sets whether the stream answers the Keep Alives of the server as soon as they are read.
+    pub fn set_auto_keep_alive(&mut self, enabled: bool) {
+        self.codec.auto_keep_alive = enabled;
+    }
+
+    /// Gets the protocol state the stream is in.
+    pub fn state(&self) -> ConnectionState {
+        self.codec.state
+    }
+
+    /// Sets the protocol state of the stream, as per `MinecraftStream::set_state`.
+    pub fn set_state(&mut self, state: ConnectionState) {
+        self.codec.state = state;
+    }
+
+    /// Gets the protocol version the stream exchanges packets in.
+    pub fn version(&self) -> ProtocolVersion {
+        self.codec.version
+    }
+
+    /// Sets the protocol version the stream exchanges packets in. This should be set
+    /// before the handshake, which announces the version to the server.
+    pub fn set_version(&mut self, version: ProtocolVersion) {
+        self.codec.version = version;
+    }
+
+    /// Encrypts the stream in both directions with `shared_secret`. Packets written
+    /// before encryption is enabled are flushed unencrypted first.
+    /// # Errors
+    /// An `io::Error` of any kind will be returned if the pending packets cannot be flushed.
+    pub async fn enable_encryption(&mut self, shared_secret: &[u8; SHARED_SECRET_LENGTH]) -> Result<(), io::Error> {
+        self.flush().await?;
+        self.codec.cipher = Some(StreamCipher::new(shared_secret));
+        Ok(())
+    }
+
+    /// Whether the stream is encrypted.
+    pub fn is_encrypted(&self) -> bool {
+        self.codec.cipher.is_some()
+    }
+
+    /// Sets the compression threshold of the stream, as per `MinecraftStream::set_compression`.
+    pub fn set_compression(&mut self, threshold: Option<i32>) {
+        self.codec.compression_threshold = threshold;
+    }
+
+    /// Gets the compression threshold of the stream, or `None` if packets are not compressed.
+    pub fn compression_threshold(&self) -> Option<i32> {
+        self.codec.compression_threshold
+    }
+
+    /// Sets the zlib level outbound packets are compressed at, as per
+    /// `MinecraftStream::set_compression_level`.
+    pub fn set_compression_level(&mut self, level: u32) {
+        self.codec.compression_level = level.min(9);
+    }
+
+    /// Gets the limits the frames read from the stream are checked against, as per
+    /// `MinecraftStream::decode_limits`.
+    pub fn decode_limits(&self) -> DecodeLimits {
+        self.codec.limits
+    }
+
+    /// Checks the frames read from the stream from now on against `limits`, as per
+    /// `MinecraftStream::set_decode_limits`.
+    pub fn set_decode_limits(&mut self, limits: DecodeLimits) {
+        self.codec.limits = limits;
+    }
+
+    /// Records every packet exchanged from now on to a dump at `path`, as per
+    /// `MinecraftStream::start_capture`. The dump is written to synchronously, through a
+    /// buffer.
+    /// # Errors
+    /// An `io::Error` of any kind will be returned if the dump cannot be created.
+    pub fn start_capture<P: AsRef<Path>>(&mut self, path: P) -> Result<(), io::Error> {
+        self.stop_capture()?;
+        self.codec.capture = Some(Capture::create(path, self.codec.version, self.codec.state)?);
+        Ok(())
+    }
+
+    /// Gets the traffic the stream has exchanged since it was opened.
+    pub fn metrics(&self) -> &TrafficMetrics {
+        &self.codec.metrics
+    }
+
+    /// Gets the pool of buffers of the stream, as per `MinecraftStream::buffer_pool`.
+    pub fn buffer_pool(&self) -> &BufferPool {
+        &self.codec.pool
+    }
+
+    /// Gets the pool of buffers of the stream mutably, to change its limits.
+    pub fn buffer_pool_mut(&mut self) -> &mut BufferPool {
+        &mut self.codec.pool
+    }
+
+    /// Hands the buffer of `packet` back to the pool, as per `MinecraftStream::recycle`.
+    pub fn recycle(&mut self, packet: MCPacket) {
+        self.codec.pool.recycle(packet.data);
+    }
+
+    /// Stops recording packets, writing out the dump.
+    /// # Errors
+    /// An `io::Error` of any kind will be returned if the dump cannot be written.
+    pub fn stop_capture(&mut self) -> Result<(), io::Error> {
+        match self.codec.capture.take() {
+            Some(mut capture) => capture.flush(),
+            None => Ok(())
+        }
+    }
+}
+
+/// The async counterpart of `OfflineConnection`, which joins offline-mode servers, and
+/// online-mode servers with `login_online`.
+pub struct AsyncOfflineConnection<T = TcpStream> {
+    stream: AsyncMinecraftStream<T>,
+    domain: String,
+    port: u16,
+    username: Option<String>,
+    entity_id: Option<i32>,
+    configuration: GameConfiguration,
+    resource_pack_policy: ResourcePackPolicy
+}
+
+impl AsyncOfflineConnection<TcpStream> {
+    /// Attempts to connect to a Minecraft server over TCP.
+    /// # Errors
+    /// This function will return an error if the connection cannot be established.
+    pub async fn connect<D: Into<String>>(domain: D, port: u16) -> Result<Self, io::Error> {
+        let domain = domain.into();
+        let stream = AsyncMinecraftStream::connect((domain.as_str(), port)).await?;
+
+        Ok(AsyncOfflineConnection::with_stream(stream, domain, port))
+    }
+
+    /// Attempts to connect to the Minecraft server at `address`, resolving it as per
+    /// `OfflineConnection::connect_address`. The SRV lookup blocks, and is made on a
+    /// blocking thread of the runtime.
+    /// # Errors
+    /// This function will return an `InvalidInput` error if the address is malformed, or
+    /// an error if the connection cannot be established.
+    pub async fn connect_address(address: &str) -> Result<Self, io::Error> {
+        let (host, port) = resolve(address).await?;
+        AsyncOfflineConnection::connect(host, port).await
+    }
+
+    /// Attempts to connect to a Minecraft server over TCP in the protocol version `version`,
+    /// or, if `version` is `None`, in the version the server reports, as per
+    /// `OfflineConnection::negotiate`.
+    /// # Errors
+    /// This function will return an error if either connection cannot be established, if
+    /// the server's status cannot be read, or an `Unsupported` error if the server reports
+    /// a version older than every version known to this library.
+    pub async fn negotiate<D: Into<String>>(domain: D, port: u16, version: Option<ProtocolVersion>) -> Result<Self, io::Error> {
+        let domain = domain.into();
+        let version = match version {
+            Some(version) => version,
+            None => negotiate_version(&domain, port).await?
+        };
+
+        let mut connection = AsyncOfflineConnection::connect(domain, port).await?;
+        connection.stream.set_version(version);
+        Ok(connection)
+    }
+}
+
+/// Requests the status of the server at `domain` and `port`, and picks the version to join
+/// it with, as per `connection::negotiate_version`.
+/// # Errors
+/// This function will return an error if the server cannot be reached or its status cannot
+/// be read, or an `Unsupported` error if it reports a version older than every known release.
+pub async fn negotiate_version(domain: &str, port: u16) -> Result<ProtocolVersion, io::Error> {
+    let status = AsyncOfflineConnection::connect(domain, port).await?.status().await?;
+    version_for(&status)
+}
+
+/// Fetches the status of the server at `address`, as per `status::status`. Servers which
+/// only answer the legacy Server List Ping are not supported.
+/// # Errors
+/// This function will return an error if the address is malformed, if the server cannot
+/// be reached, or if its response cannot be parsed.
+pub async fn status(address: &str) -> Result<ServerStatus, io::Error> {
+    let (host, port) = resolve(address).await?;
+    let mut stream = TcpStream::connect((host.as_str(), port)).await?;
+
+    let start = Instant::now();
+    status::status_over(&mut stream, &host, port, || start.elapsed()).await
+}
+
+impl<T: AsyncRead + AsyncWrite + Unpin> AsyncOfflineConnection<T> {
+    /// Creates a connection over an established `stream`. The `domain` and `port` are only
+    /// used to describe the server in the handshake.
+    pub fn with_stream<D: Into<String>>(stream: AsyncMinecraftStream<T>, domain: D, port: u16) -> Self {
+        AsyncOfflineConnection { stream, domain: domain.into(), port, username: None, entity_id: None, configuration: GameConfiguration::default(), resource_pack_policy: ResourcePackPolicy::default() }
+    }
+
+    /// Attempts to fetch a status report of the server.
+    /// # Errors
+    /// This function will return an error if the status cannot be received.
+    pub async fn status(&mut self) -> Result<StatusResponse, io::Error> {
+        self.stream.send(&self.handshake(NextState::STATUS)).await?;
+        self.stream.send(&StatusRequest).await?;
+
+        StatusResponse::from_data(&self.stream.read().await?)
+    }
+
+    /// Attempts to ping the server. This must follow a `status` request.
+    /// # Errors
+    /// This function will return an error if the ping fails, or is not echoed.
+    pub async fn ping(&mut self) -> Result<PingResponse, io::Error> {
+        let payload = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|time| time.as_millis() as i64)
+            .unwrap_or_default();
+
+        self.stream.send(&PingRequest { payload: payload.into() }).await?;
+
+        let response = PingResponse::from_data(&self.stream.read().await?)?;
+        if response.payload.value() != payload {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, "Ping response does not echo the ping payload."));
+        }
+
+        Ok(response)
+    }
+
+    /// Attempts to log into an offline-mode server as `username`, as per `Connection::login`.
+    /// # Errors
+    /// This function will return an error if the login attempt fails.
+    pub async fn login(&mut self, username: &str) -> Result<LoginSuccess, io::Error> {
+        self.login_as(username, offline_uuid(username), None).await
+    }
+
+    /// Attempts to log into an online-mode server as the player of `session`, as per
+    /// `OfflineConnection::login_online`. The session server is contacted on a blocking
+    /// thread of the runtime.
+    /// # Errors
+    /// This function will return an error if the session server rejects the join, or if
+    /// the login attempt otherwise fails.
+    pub async fn login_online(&mut self, session: &AuthSession) -> Result<LoginSuccess, io::Error> {
+        self.login_as(&session.profile.name, session.profile.uuid, Some(session)).await
+    }
+
+    /// Logs in as `username` with `uuid`, authenticating with `session` if the server is in
+    /// online mode.
+    async fn login_as(&mut self, username: &str, uuid: Uuid, session: Option<&AuthSession>) -> Result<LoginSuccess, io::Error> {
+        self.stream.send(&self.handshake(NextState::LOGIN)).await?;
+        self.stream.send(&LoginStart::new(self.stream.version(), username, uuid)).await?;
+
+        let success = loop {
+            match LoginStep::of(&self.stream.read().await?)? {
+                LoginStep::Success(success) => break success,
+                LoginStep::Encrypt(request) => {
+                    let session = session.ok_or_else(online_mode_error)?.clone();
+                    let (response, shared_secret) = tokio::task::spawn_blocking(move || answer_encryption(&request, &session))
+                        .await
+                        .map_err(io::Error::other)??;
+                    self.stream.send(&response).await?;
+                    self.stream.enable_encryption(&shared_secret).await?;
+                },
+                LoginStep::Compress(threshold) => self.stream.set_compression(threshold),
+                LoginStep::Reply(response) => self.stream.send(&response).await?
+            }
+        };
+
+        if self.stream.version() >= ProtocolVersion::V1_20_2 {
+            self.stream.send(&LoginAcknowledged).await?;
+            self.configure().await?;
+        }
+
+        let login_play = LoginPlay::from_data(&self.stream.read().await?)?;
+        if self.stream.version() < ProtocolVersion::V1_20_2 {
+            self.stream.send(&PluginMessage::brand(self.stream.version())).await?;
+        }
+
+        self.username = Some(success.username.string().clone());
+        self.entity_id = Some(login_play.entity_id.value());
+        Ok(success)
+    }
+
+    /// Reports the client's brand, and receives the configuration of the game until the
+    /// server finishes configuration. Resource packs are answered as the
+    /// `resource_pack_policy` calls for.
+    async fn configure(&mut self) -> Result<(), io::Error> {
+        self.configuration = GameConfiguration::default();
+        self.stream.send(&ConfigPluginMessage::brand()).await?;
+
+        let version = self.stream.version();
+        let mut answered = 0;
+        loop {
+            let finished = self.configuration.receive(self.stream.read().await?, version)?;
+            for pack in &self.configuration.resource_packs[answered..] {
+                for result in self.resource_pack_policy.results(version) {
+                    self.stream.send(&ConfigResourcePackResponse::new(version, pack.uuid.unwrap_or_default(), result)).await?;
+                }
+            }
+            answered = self.configuration.resource_packs.len();
+            if finished {
+                break;
+            }
+        }
+
+        self.stream.send(&AcknowledgeFinishConfiguration).await
+    }
+
+    /// Creates the handshake moving the connection to `next_state`.
+    fn handshake(&self, next_state: NextState) -> Handshake {
+        Handshake {
+            protocol_version: self.stream.version().number().into(),
+            server_addr: self.domain.clone().into(),
+            port: self.port,
+            next_state
+        }
+    }
+
+    /// Gets the stream managed by this connection, which is used to send and receive packets.
+    pub fn sock(&mut self) -> &mut AsyncMinecraftStream<T> {
+        &mut self.stream
+    }
+
+    /// Gets the domain of the connection.
+    pub fn domain(&self) -> &str {
+        &self.domain
+    }
+
+    /// Gets the port of the connection.
+    pub fn port(&self) -> u16 {
+        self.port
+    }
+
+    /// Gets the username of the connection if it is set. This is set by a login.
+    pub fn username(&self) -> &Option<String> {
+        &self.username
+    }
+
+    /// Gets the entity ID the server assigned to the player, if the connection has entered
+    /// the Play state.
+    pub fn entity_id(&self) -> Option<i32> {
+        self.entity_id
+    }
+
+    /// Gets the configuration of the game received during login, which is empty for servers
+    /// older than 1.20.2.
+    pub fn configuration(&self) -> &GameConfiguration {
+        &self.configuration
+    }
+
+    /// Gets how the resource packs pushed by the server during configuration are answered.
+    pub fn resource_pack_policy(&self) -> ResourcePackPolicy {
+        self.resource_pack_policy
+    }
+
+    /// Sets how the resource packs pushed by the server during configuration are answered,
+    /// which is to accept them by default.
+    pub fn set_resource_pack_policy(&mut self, policy: ResourcePackPolicy) {
+        self.resource_pack_policy = policy;
+    }
+}
+
+/// Resolves `address` as per `dns::resolve`, on a blocking thread of the runtime.
+pub(crate) async fn resolve(address: &str) -> Result<(String, u16), io::Error> {
+    let address = address.to_owned();
+    tokio::task::spawn_blocking(move || dns::resolve(&address)).await.map_err(io::Error::other)?
+}
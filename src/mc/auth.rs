@@ -0,0 +1,678 @@
+use std::{fmt, fs, io, path::{Path, PathBuf}, sync::{Arc, Condvar, Mutex, PoisonError}, thread::{self, JoinHandle}, time::{Duration, SystemTime, UNIX_EPOCH}};
+
+use aes::Aes128;
+use cfb8::cipher::{generic_array::GenericArray, BlockDecryptMut, BlockEncryptMut, KeyIvInit};
+use hmac::{Hmac, Mac};
+use rand::RngCore;
+use sha1::Sha1;
+
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+use super::error::AuthError;
+
+const DEVICE_CODE_URL: &str = "https://login.microsoftonline.com/consumers/oauth2/v2.0/devicecode";
+const TOKEN_URL: &str = "https://login.microsoftonline.com/consumers/oauth2/v2.0/token";
+const XBOX_LIVE_URL: &str = "https://user.auth.xboxlive.com/user/authenticate";
+const XSTS_URL: &str = "https://xsts.auth.xboxlive.com/xsts/authorize";
+const MINECRAFT_LOGIN_URL: &str = "https://api.minecraftservices.com/authentication/login_with_xbox";
+const MINECRAFT_PROFILE_URL: &str = "https://api.minecraftservices.com/minecraft/profile";
+const SESSION_JOIN_URL: &str = "https://sessionserver.mojang.com/session/minecraft/join";
+
+/// The OAuth scopes requested from Microsoft. `offline_access` grants the refresh token.
+const SCOPE: &str = "XboxLive.signin offline_access";
+
+/// The `XErr` code XSTS responds with for accounts without an Xbox account.
+const XERR_NO_XBOX_ACCOUNT: u64 = 2148916233;
+/// The `XErr` code XSTS responds with for child accounts outside of a family.
+const XERR_CHILD_ACCOUNT: u64 = 2148916238;
+
+/// The code a user enters at `verification_uri` to sign in, as issued by the
+/// Microsoft device code flow.
+#[derive(Clone, Debug)]
+pub struct DeviceCode {
+    /// The code the user enters to sign in.
+    pub user_code: String,
+    /// The page the user signs in at.
+    pub verification_uri: String,
+    /// Instructions for the user, localized by Microsoft.
+    pub message: String,
+    device_code: String,
+    interval: Duration,
+    expires_at: SystemTime
+}
+
+/// The Minecraft profile of an account.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct GameProfile {
+    pub uuid: Uuid,
+    pub name: String
+}
+
+/// A signed-in Minecraft account. The `access_token` authenticates the player with
+/// online-mode servers, and the `refresh_token` renews the session once it expires.
+/// Both tokens are redacted from its `Debug` output, so that sessions can be logged.
+#[derive(Clone, PartialEq, Eq)]
+pub struct AuthSession {
+    pub access_token: String,
+    pub refresh_token: String,
+    pub expires_at: SystemTime,
+    pub profile: GameProfile
+}
+
+impl fmt::Debug for AuthSession {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AuthSession")
+            .field("access_token", &"<redacted>")
+            .field("refresh_token", &"<redacted>")
+            .field("expires_at", &self.expires_at)
+            .field("profile", &self.profile)
+            .finish()
+    }
+}
+
+impl AuthSession {
+    /// Whether the access token has expired, and the session must be refreshed.
+    pub fn is_expired(&self) -> bool {
+        SystemTime::now() >= self.expires_at
+    }
+
+    /// Caches the session in the JSON file at `path`. The file is not encrypted: it holds
+    /// the tokens of the account in plain text, and anyone who can read it can sign in as
+    /// the player. On Unix, it is created readable and writable by its owner only. Use an
+    /// `EncryptedStore` to keep the tokens encrypted instead.
+    /// # Errors
+    /// This function will return `AuthError::Io` if the file cannot be written.
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<(), AuthError> {
+        Ok(write_private(path.as_ref(), self.to_json().to_string().as_bytes())?)
+    }
+
+    /// Loads a session cached with `save` from the file at `path`. The session may have
+    /// expired since it was cached.
+    /// # Errors
+    /// This function will return `AuthError::Io` if the file cannot be read, or
+    /// `AuthError::InvalidResponse` if it does not contain a session.
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self, AuthError> {
+        AuthSession::from_json(&fs::read(path)?)
+    }
+
+    /// Encodes the session as cached by `save`.
+    fn to_json(&self) -> Value {
+        let expires_at = self.expires_at.duration_since(UNIX_EPOCH).unwrap_or_default().as_secs();
+        json!({
+            "access_token": self.access_token,
+            "refresh_token": self.refresh_token,
+            "expires_at": expires_at,
+            "profile": { "id": self.profile.uuid.simple().to_string(), "name": self.profile.name }
+        })
+    }
+
+    /// Decodes a session cached by `save`.
+    fn from_json(bytes: &[u8]) -> Result<Self, AuthError> {
+        let json: Value = serde_json::from_slice(bytes)
+            .map_err(|err| AuthError::InvalidResponse(err.to_string()))?;
+
+        Ok(AuthSession {
+            access_token: string_field(&json, "access_token")?,
+            refresh_token: string_field(&json, "refresh_token")?,
+            expires_at: UNIX_EPOCH + Duration::from_secs(json["expires_at"].as_u64().unwrap_or_default()),
+            profile: parse_profile(&json["profile"])?
+        })
+    }
+}
+
+/// Replaces the file at `path` with `bytes`, which on Unix only its owner may read or
+/// write. The bytes are written to a temporary file beside it, which is then renamed over
+/// it, so that the file is never left partly written, such as if the process is killed
+/// while a session is saved. Each save has a temporary file of its own, so that saves
+/// from several threads or processes at once do not write over each other.
+fn write_private(path: &Path, bytes: &[u8]) -> io::Result<()> {
+    let name = path.file_name().ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "The path has no file name."))?;
+
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+
+    let (temporary, mut file) = loop {
+        let mut temporary_name = name.to_owned();
+        temporary_name.push(format!(".{}.{:08x}.tmp", std::process::id(), rand::random::<u32>()));
+        let temporary = path.with_file_name(temporary_name);
+        match options.open(&temporary) {
+            Ok(file) => break (temporary, file),
+            Err(err) if err.kind() == io::ErrorKind::AlreadyExists => continue,
+            Err(err) => return Err(err)
+        }
+    };
+
+    let written = io::Write::write_all(&mut file, bytes)
+        .and_then(|()| file.sync_all())
+        .and_then(|()| fs::rename(&temporary, path));
+    if written.is_err() {
+        let _ = fs::remove_file(&temporary);
+    }
+    written
+}
+
+/// Reports to the session server that the player of `session` is joining the server
+/// identified by `server_hash`, as computed by `encryption::server_hash`. Online-mode
+/// servers verify this with the session server before letting the player in.
+/// # Errors
+/// This function will return an `AuthError::Http` error if the session server rejects
+/// the join, such as when the access token has expired.
+pub fn join_server(session: &AuthSession, server_hash: &str) -> Result<(), AuthError> {
+    let body = json!({
+        "accessToken": session.access_token,
+        "selectedProfile": session.profile.uuid.simple().to_string(),
+        "serverId": server_hash
+    });
+
+    match ureq::post(SESSION_JOIN_URL).send_json(body) {
+        Ok(_) => Ok(()),
+        Err(err) => Err(http_error(err))
+    }
+}
+
+/// Signs Microsoft accounts into Minecraft. Signing in takes the Microsoft device code
+/// flow, followed by the exchange of the Microsoft token for an Xbox Live token, an
+/// XSTS token, and finally a Minecraft access token.
+/// # Example
+/// ```no_run
+/// use mcclient::mc::auth::Authenticator;
+///
+/// let authenticator = Authenticator::new("<Azure application client ID>");
+/// let session = authenticator.sign_in(|code| println!("{}", code.message)).expect("Could not sign in");
+/// session.save("session.json").expect("Could not cache session");
+/// ```
+#[derive(Clone)]
+pub struct Authenticator {
+    client_id: String
+}
+
+impl Authenticator {
+    /// Creates an authenticator for the Azure application `client_id`, which must be
+    /// permitted to use the Minecraft services.
+    pub fn new<S: Into<String>>(client_id: S) -> Self {
+        Authenticator { client_id: client_id.into() }
+    }
+
+    /// Signs an account in with the device code flow. `prompt` is given the code the user
+    /// must enter, and this function blocks until the user has signed in.
+    /// # Errors
+    /// This function will return an `AuthError` if the user does not sign in, or if any
+    /// step of the token exchange fails.
+    pub fn sign_in<F: FnOnce(&DeviceCode)>(&self, prompt: F) -> Result<AuthSession, AuthError> {
+        let code = self.request_device_code()?;
+        prompt(&code);
+        let microsoft = self.poll_device_code(&code)?;
+        self.exchange(microsoft)
+    }
+
+    /// Requests a device code for the user to sign in with.
+    /// # Errors
+    /// This function will return an `AuthError` if the request fails.
+    pub fn request_device_code(&self) -> Result<DeviceCode, AuthError> {
+        let json = post_form(DEVICE_CODE_URL, &[("client_id", &self.client_id), ("scope", SCOPE)])?;
+
+        Ok(DeviceCode {
+            user_code: string_field(&json, "user_code")?,
+            verification_uri: string_field(&json, "verification_uri")?,
+            message: string_field(&json, "message")?,
+            device_code: string_field(&json, "device_code")?,
+            interval: Duration::from_secs(json["interval"].as_u64().unwrap_or(5)),
+            expires_at: SystemTime::now() + Duration::from_secs(json["expires_in"].as_u64().unwrap_or(900))
+        })
+    }
+
+    /// Blocks until the user has signed in with `code`, returning the Microsoft tokens.
+    fn poll_device_code(&self, code: &DeviceCode) -> Result<MicrosoftToken, AuthError> {
+        let mut interval = code.interval;
+
+        loop {
+            if SystemTime::now() >= code.expires_at {
+                return Err(AuthError::DeviceCodeExpired);
+            }
+            thread::sleep(interval);
+
+            let response = post_form(TOKEN_URL, &[
+                ("grant_type", "urn:ietf:params:oauth:grant-type:device_code"),
+                ("client_id", &self.client_id),
+                ("device_code", &code.device_code)
+            ]);
+
+            match response {
+                Ok(json) => return MicrosoftToken::from_json(&json),
+                Err(AuthError::Http(msg)) if msg.contains("authorization_pending") => {},
+                Err(AuthError::Http(msg)) if msg.contains("slow_down") => interval += Duration::from_secs(5),
+                Err(AuthError::Http(msg)) if msg.contains("authorization_declined") => return Err(AuthError::AuthorizationDeclined),
+                Err(AuthError::Http(msg)) if msg.contains("expired_token") => return Err(AuthError::DeviceCodeExpired),
+                Err(err) => return Err(err)
+            }
+        }
+    }
+
+    /// Renews `session` with its refresh token, without the user signing in again.
+    /// # Errors
+    /// This function will return an `AuthError` if the refresh token has been revoked or
+    /// expired, or if any step of the token exchange fails.
+    pub fn refresh(&self, session: &AuthSession) -> Result<AuthSession, AuthError> {
+        let json = post_form(TOKEN_URL, &[
+            ("grant_type", "refresh_token"),
+            ("client_id", &self.client_id),
+            ("scope", SCOPE),
+            ("refresh_token", &session.refresh_token)
+        ])?;
+
+        self.exchange(MicrosoftToken::from_json(&json)?)
+    }
+
+    /// Creates a refresher which renews `session` with this authenticator, as per
+    /// `refresh`, once it is spawned.
+    pub fn refresher(&self, session: AuthSession) -> TokenRefresher {
+        let authenticator = self.clone();
+        TokenRefresher::new(session, move |session| authenticator.refresh(session))
+    }
+
+    /// Exchanges Microsoft tokens for a Minecraft session.
+    fn exchange(&self, microsoft: MicrosoftToken) -> Result<AuthSession, AuthError> {
+        let xbox_live = post_json(XBOX_LIVE_URL, json!({
+            "Properties": {
+                "AuthMethod": "RPS",
+                "SiteName": "user.auth.xboxlive.com",
+                "RpsTicket": format!("d={}", microsoft.access_token)
+            },
+            "RelyingParty": "http://auth.xboxlive.com",
+            "TokenType": "JWT"
+        }))?;
+        let user_hash = xbox_live["DisplayClaims"]["xui"][0]["uhs"].as_str()
+            .ok_or_else(|| AuthError::InvalidResponse("Xbox Live response has no user hash.".to_owned()))?;
+
+        let xsts = post_json(XSTS_URL, json!({
+            "Properties": {
+                "SandboxId": "RETAIL",
+                "UserTokens": [string_field(&xbox_live, "Token")?]
+            },
+            "RelyingParty": "rp://api.minecraftservices.com/",
+            "TokenType": "JWT"
+        }));
+        let xsts = match xsts {
+            Err(AuthError::Http(msg)) if msg.contains(&XERR_NO_XBOX_ACCOUNT.to_string()) => return Err(AuthError::NoXboxAccount),
+            Err(AuthError::Http(msg)) if msg.contains(&XERR_CHILD_ACCOUNT.to_string()) => return Err(AuthError::ChildAccount),
+            xsts => xsts?
+        };
+
+        let minecraft = post_json(MINECRAFT_LOGIN_URL, json!({
+            "identityToken": format!("XBL3.0 x={};{}", user_hash, string_field(&xsts, "Token")?)
+        }))?;
+        let access_token = string_field(&minecraft, "access_token")?;
+        let expires_in = minecraft["expires_in"].as_u64().unwrap_or_default();
+
+        let profile = match ureq::get(MINECRAFT_PROFILE_URL).set("Authorization", &format!("Bearer {}", access_token)).call() {
+            Ok(response) => parse_profile(&response.into_json()?)?,
+            Err(ureq::Error::Status(404, _)) => return Err(AuthError::NoMinecraftProfile),
+            Err(err) => return Err(http_error(err))
+        };
+
+        Ok(AuthSession {
+            access_token,
+            refresh_token: microsoft.refresh_token,
+            expires_at: SystemTime::now() + Duration::from_secs(expires_in),
+            profile
+        })
+    }
+}
+
+/// Somewhere sessions are kept between runs, such as an `EncryptedStore`. Implement this
+/// to keep sessions in the keyring of the operating system instead.
+pub trait CredentialStore: Send {
+    /// Loads the session kept, or `None` if none has been saved.
+    /// # Errors
+    /// This function will return an `AuthError` if the session cannot be read.
+    fn load(&self) -> Result<Option<AuthSession>, AuthError>;
+
+    /// Keeps `session`, replacing the session kept before.
+    /// # Errors
+    /// This function will return an `AuthError` if the session cannot be written.
+    fn save(&self, session: &AuthSession) -> Result<(), AuthError>;
+}
+
+/// A session kept in a file encrypted with a passphrase. The key is derived from the
+/// passphrase with PBKDF2-HMAC-SHA1 and a random salt, the session is encrypted with
+/// AES-128-CFB8, and the file is authenticated with HMAC-SHA1, so that a wrong
+/// passphrase or a tampered file is detected rather than decrypted into garbage.
+/// <br>
+/// The file is replaced whole each time a session is saved, so a refresh interrupted
+/// midway leaves the previous session in place, and on Unix only its owner may read it.
+/// # Example
+/// ```no_run
+/// use mcclient::mc::auth::{Authenticator, CredentialStore, EncryptedStore};
+///
+/// let store = EncryptedStore::new("session.bin", "correct horse battery staple");
+/// let session = match store.load().expect("Could not read the store") {
+///     Some(session) => session,
+///     None => Authenticator::new("<Azure application client ID>").sign_in(|code| println!("{}", code.message)).expect("Could not sign in")
+/// };
+/// store.save(&session).expect("Could not save the session");
+/// ```
+pub struct EncryptedStore {
+    path: PathBuf,
+    passphrase: String,
+    iterations: u32
+}
+
+impl EncryptedStore {
+    /// The number of PBKDF2 iterations new stores derive their key with.
+    pub const DEFAULT_ITERATIONS: u32 = 100_000;
+    /// The most PBKDF2 iterations a store is saved or loaded with. The iterations are
+    /// read before the file can be authenticated, so a tampered file could otherwise
+    /// stall a load for as long as it likes.
+    pub const MAX_ITERATIONS: u32 = 10_000_000;
+    /// The bytes every encrypted store starts with.
+    const MAGIC: &'static [u8; 4] = b"MCCS";
+    const SALT_LENGTH: usize = 16;
+    /// The length of the magic, the iterations, the salt and the IV.
+    const HEADER_LENGTH: usize = 4 + 4 + EncryptedStore::SALT_LENGTH + 16;
+
+    /// Creates a store in the file at `path`, encrypted with `passphrase`.
+    pub fn new<P: Into<PathBuf>, S: Into<String>>(path: P, passphrase: S) -> Self {
+        EncryptedStore { path: path.into(), passphrase: passphrase.into(), iterations: EncryptedStore::DEFAULT_ITERATIONS }
+    }
+
+    /// Derives the key of sessions saved from then on with `iterations` PBKDF2
+    /// iterations, which makes guessing the passphrase slower the more there are. The
+    /// iterations are saved with the session, so that it loads whatever they were. They
+    /// are kept between 1 and `MAX_ITERATIONS`.
+    pub fn iterations(mut self, iterations: u32) -> Self {
+        self.iterations = iterations.clamp(1, EncryptedStore::MAX_ITERATIONS);
+        self
+    }
+
+    /// Derives the AES key and the HMAC key from the passphrase.
+    fn derive_keys(&self, salt: &[u8], iterations: u32) -> ([u8; 16], [u8; 20]) {
+        let mut keys = [0_u8; 36];
+        pbkdf2::pbkdf2_hmac::<Sha1>(self.passphrase.as_bytes(), salt, iterations, &mut keys);
+        let (cipher_key, mac_key) = keys.split_at(16);
+        (cipher_key.try_into().unwrap(), mac_key.try_into().unwrap())
+    }
+
+    /// Begins the HMAC-SHA1 of `contents`, which authenticates the store.
+    fn mac(key: &[u8], contents: &[u8]) -> Hmac<Sha1> {
+        let mut mac = Hmac::<Sha1>::new_from_slice(key).expect("HMAC takes keys of any length.");
+        mac.update(contents);
+        mac
+    }
+}
+
+impl CredentialStore for EncryptedStore {
+    fn load(&self) -> Result<Option<AuthSession>, AuthError> {
+        let bytes = match fs::read(&self.path) {
+            Ok(bytes) => bytes,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(None),
+            Err(err) => return Err(err.into())
+        };
+        if bytes.len() < EncryptedStore::HEADER_LENGTH + 20 || &bytes[..4] != EncryptedStore::MAGIC {
+            return Err(AuthError::InvalidResponse("The file is not an encrypted credential store.".to_owned()));
+        }
+
+        let (contents, mac) = bytes.split_at(bytes.len() - 20);
+        let iterations = u32::from_be_bytes(contents[4..8].try_into().unwrap());
+        if !(1..=EncryptedStore::MAX_ITERATIONS).contains(&iterations) {
+            return Err(AuthError::InvalidResponse(format!("The store claims {} PBKDF2 iterations.", iterations)));
+        }
+        let (salt, iv) = contents[8..EncryptedStore::HEADER_LENGTH].split_at(EncryptedStore::SALT_LENGTH);
+        let (cipher_key, mac_key) = self.derive_keys(salt, iterations);
+        EncryptedStore::mac(&mac_key, contents).verify_slice(mac).map_err(|_| AuthError::Decryption)?;
+
+        let mut session = contents[EncryptedStore::HEADER_LENGTH..].to_vec();
+        let mut decryptor = cfb8::Decryptor::<Aes128>::new(GenericArray::from_slice(&cipher_key), GenericArray::from_slice(iv));
+        for byte in session.chunks_mut(1) {
+            decryptor.decrypt_block_mut(GenericArray::from_mut_slice(byte));
+        }
+        AuthSession::from_json(&session).map(Some)
+    }
+
+    fn save(&self, session: &AuthSession) -> Result<(), AuthError> {
+        let mut salt = [0_u8; EncryptedStore::SALT_LENGTH];
+        let mut iv = [0_u8; 16];
+        rand::thread_rng().fill_bytes(&mut salt);
+        rand::thread_rng().fill_bytes(&mut iv);
+        let (cipher_key, mac_key) = self.derive_keys(&salt, self.iterations);
+
+        let mut contents = EncryptedStore::MAGIC.to_vec();
+        contents.extend(self.iterations.to_be_bytes());
+        contents.extend(salt);
+        contents.extend(iv);
+        let start = contents.len();
+        contents.extend(session.to_json().to_string().into_bytes());
+        let mut encryptor = cfb8::Encryptor::<Aes128>::new(GenericArray::from_slice(&cipher_key), GenericArray::from_slice(&iv));
+        for byte in contents[start..].chunks_mut(1) {
+            encryptor.encrypt_block_mut(GenericArray::from_mut_slice(byte));
+        }
+        let mac = EncryptedStore::mac(&mac_key, &contents).finalize().into_bytes();
+        contents.extend(mac);
+
+        Ok(write_private(&self.path, &contents)?)
+    }
+}
+
+type RefreshFn = Box<dyn FnMut(&AuthSession) -> Result<AuthSession, AuthError> + Send>;
+
+/// A session shared with the thread of a `RefreshHandle`, which always holds its latest
+/// tokens. Clones share the same session.
+#[derive(Clone, Debug)]
+pub struct SharedSession(Arc<Mutex<AuthSession>>);
+
+impl SharedSession {
+    /// Shares `session`.
+    pub fn new(session: AuthSession) -> Self {
+        SharedSession(Arc::new(Mutex::new(session)))
+    }
+
+    /// Gets the latest tokens of the session.
+    pub fn get(&self) -> AuthSession {
+        self.0.lock().unwrap_or_else(PoisonError::into_inner).clone()
+    }
+
+    fn set(&self, session: AuthSession) {
+        *self.0.lock().unwrap_or_else(PoisonError::into_inner) = session;
+    }
+}
+
+/// Renews a session on a background thread shortly before it expires, so that a
+/// long-running bot always has a valid access token when it reconnects. Each renewed
+/// session is saved to the store, if any, as refresh tokens are replaced when they are
+/// used. A failed renewal is retried every `retry_delay`.
+/// # Example
+/// ```no_run
+/// use mcclient::{Client, mc::auth::{Authenticator, CredentialStore, EncryptedStore}};
+///
+/// let authenticator = Authenticator::new("<Azure application client ID>");
+/// let store = EncryptedStore::new("session.bin", "correct horse battery staple");
+/// let session = store.load().expect("Could not read the store").expect("Not signed in");
+/// let refresher = authenticator.refresher(session.clone()).store(store).spawn();
+///
+/// let mut client = Client::login_online("localhost", 25565, &session).expect("Could not join");
+/// client.follow_session(refresher.session());
+/// ```
+pub struct TokenRefresher {
+    session: AuthSession,
+    refresh: RefreshFn,
+    margin: Duration,
+    retry_delay: Duration,
+    store: Option<Box<dyn CredentialStore>>
+}
+
+impl TokenRefresher {
+    /// How long before a session expires it is renewed by default.
+    pub const DEFAULT_MARGIN: Duration = Duration::from_secs(300);
+    /// How long after a failed renewal it is retried by default.
+    pub const DEFAULT_RETRY_DELAY: Duration = Duration::from_secs(60);
+
+    /// Creates a refresher of `session` which renews it with `refresh`, as
+    /// `Authenticator::refresher` does with `Authenticator::refresh`.
+    pub fn new<F>(session: AuthSession, refresh: F) -> Self
+    where F: FnMut(&AuthSession) -> Result<AuthSession, AuthError> + Send + 'static {
+        TokenRefresher {
+            session,
+            refresh: Box::new(refresh),
+            margin: TokenRefresher::DEFAULT_MARGIN,
+            retry_delay: TokenRefresher::DEFAULT_RETRY_DELAY,
+            store: None
+        }
+    }
+
+    /// Renews the session `margin` before it expires.
+    pub fn margin(mut self, margin: Duration) -> Self {
+        self.margin = margin;
+        self
+    }
+
+    /// Waits `retry_delay` after each renewal before the next one, and before retrying
+    /// a failed one.
+    pub fn retry_delay(mut self, retry_delay: Duration) -> Self {
+        self.retry_delay = retry_delay;
+        self
+    }
+
+    /// Saves each renewed session to `store`.
+    pub fn store<S: CredentialStore + 'static>(mut self, store: S) -> Self {
+        self.store = Some(Box::new(store));
+        self
+    }
+
+    /// Starts renewing the session on a new thread, which runs until the handle is
+    /// dropped. A session which expires within the margin is renewed immediately.
+    pub fn spawn(mut self) -> RefreshHandle {
+        let state = Arc::new(RefreshState {
+            session: SharedSession::new(self.session.clone()),
+            stopped: Mutex::new(false),
+            wake: Condvar::new(),
+            error: Mutex::new(None)
+        });
+        let thread_state = state.clone();
+        let thread = thread::spawn(move || self.run(&thread_state));
+        RefreshHandle { state, thread: Some(thread) }
+    }
+
+    /// Renews the session whenever it is due, until stopped.
+    fn run(&mut self, state: &RefreshState) {
+        let mut not_before = UNIX_EPOCH;
+        loop {
+            let due = state.session.get().expires_at.checked_sub(self.margin).unwrap_or(UNIX_EPOCH).max(not_before);
+            let wait = due.duration_since(SystemTime::now()).unwrap_or_default();
+            let stopped = state.stopped.lock().unwrap_or_else(PoisonError::into_inner);
+            let (stopped, _) = state.wake.wait_timeout_while(stopped, wait, |stopped| !*stopped)
+                .unwrap_or_else(PoisonError::into_inner);
+            if *stopped {
+                return;
+            }
+            drop(stopped);
+
+            not_before = SystemTime::now() + self.retry_delay;
+            let renewed = (self.refresh)(&state.session.get()).and_then(|session| {
+                state.session.set(session.clone());
+                self.store.as_ref().map_or(Ok(()), |store| store.save(&session))
+            });
+            if let Err(err) = renewed {
+                *state.error.lock().unwrap_or_else(PoisonError::into_inner) = Some(err);
+            }
+        }
+    }
+}
+
+/// The state shared by a `RefreshHandle` and its thread.
+struct RefreshState {
+    session: SharedSession,
+    stopped: Mutex<bool>,
+    wake: Condvar,
+    error: Mutex<Option<AuthError>>
+}
+
+/// A running `TokenRefresher`, which stops once the handle is dropped.
+pub struct RefreshHandle {
+    state: Arc<RefreshState>,
+    thread: Option<JoinHandle<()>>
+}
+
+impl RefreshHandle {
+    /// Gets the session being renewed, which holds the latest tokens.
+    pub fn session(&self) -> SharedSession {
+        self.state.session.clone()
+    }
+
+    /// Takes the error of the last renewal or save which failed since this was last
+    /// called, if any.
+    pub fn take_error(&self) -> Option<AuthError> {
+        self.state.error.lock().unwrap_or_else(PoisonError::into_inner).take()
+    }
+
+    /// Stops renewing the session, waiting for a renewal in progress to end.
+    pub fn stop(self) {}
+}
+
+impl Drop for RefreshHandle {
+    fn drop(&mut self) {
+        *self.state.stopped.lock().unwrap_or_else(PoisonError::into_inner) = true;
+        self.state.wake.notify_all();
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+/// The tokens of a Microsoft account signed in with OAuth.
+struct MicrosoftToken {
+    access_token: String,
+    refresh_token: String
+}
+
+impl MicrosoftToken {
+    fn from_json(json: &Value) -> Result<Self, AuthError> {
+        Ok(MicrosoftToken {
+            access_token: string_field(json, "access_token")?,
+            refresh_token: string_field(json, "refresh_token")?
+        })
+    }
+}
+
+/// Posts a form to `url`, returning the JSON response.
+fn post_form(url: &str, form: &[(&str, &str)]) -> Result<Value, AuthError> {
+    match ureq::post(url).send_form(form) {
+        Ok(response) => Ok(response.into_json()?),
+        Err(err) => Err(http_error(err))
+    }
+}
+
+/// Posts the JSON `body` to `url`, returning the JSON response.
+fn post_json(url: &str, body: Value) -> Result<Value, AuthError> {
+    match ureq::post(url).set("Accept", "application/json").send_json(body) {
+        Ok(response) => Ok(response.into_json()?),
+        Err(err) => Err(http_error(err))
+    }
+}
+
+/// Describes a failed request, including the response body of error statuses, which
+/// the authentication services use to report the reason of the failure.
+fn http_error(err: ureq::Error) -> AuthError {
+    match err {
+        ureq::Error::Status(status, response) => {
+            let body = response.into_string().unwrap_or_default();
+            AuthError::Http(format!("Status {}: {}", status, body))
+        },
+        err => AuthError::Http(err.to_string())
+    }
+}
+
+fn string_field(json: &Value, field: &str) -> Result<String, AuthError> {
+    json[field].as_str()
+        .map(str::to_owned)
+        .ok_or_else(|| AuthError::InvalidResponse(format!("Response has no {} field.", field)))
+}
+
+/// Parses a profile, whose `id` is an unhyphenated UUID.
+fn parse_profile(json: &Value) -> Result<GameProfile, AuthError> {
+    let uuid = Uuid::parse_str(&string_field(json, "id")?)
+        .map_err(|err| AuthError::InvalidResponse(err.to_string()))?;
+
+    Ok(GameProfile { uuid, name: string_field(json, "name")? })
+}
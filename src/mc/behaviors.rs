@@ -0,0 +1,156 @@
+//! Actions a client performs by itself as it ticks, such as the idle movements which keep
+//! servers from kicking a long-lived bot for being AFK.
+use std::{io, time::Duration};
+
+use rand::Rng;
+
+use super::{client::Client, connection::Connection, physics::TICK, transport::Transport};
+
+/// Something a client does by itself each time it ticks, added with `Client::add_behavior`.
+/// Behaviors run at the start of the tick, in the order they were added, so that the
+/// movement and rotation they cause are sent with the position of that tick.
+pub trait Behavior<T> {
+    /// Acts on `client` for a tick.
+    /// # Errors
+    /// An error returned here is returned by `Client::tick`.
+    fn tick(&mut self, client: &mut Client<T>) -> io::Result<()>;
+}
+
+impl<T, F> Behavior<T> for F
+where F: FnMut(&mut Client<T>) -> io::Result<()> {
+    fn tick(&mut self, client: &mut Client<T>) -> io::Result<()> {
+        self(client)
+    }
+}
+
+/// Runs a behavior once every `interval` rather than every tick, each time after up to
+/// `jitter` more at random, so that the actions of a bot do not follow a pattern.
+/// Intervals are counted in ticks, and are at least one tick long.
+/// # Example
+/// ```no_run
+/// use std::time::Duration;
+/// use mcclient::{Client, mc::behaviors::{anti_afk, Periodic}};
+///
+/// let mut client = Client::login_offline("localhost", 25565, "Makoto").expect("Could not join");
+/// client.add_behavior(anti_afk(Duration::from_secs(60)).jitter(Duration::from_secs(20)));
+/// client.add_behavior(Periodic::new(Duration::from_secs(300), |client: &mut Client| client.swing_arm()));
+/// ```
+pub struct Periodic<B> {
+    behavior: B,
+    interval: u32,
+    jitter: u32,
+    countdown: u32
+}
+
+impl<B> Periodic<B> {
+    /// Runs `behavior` once every `interval`, first once `interval` has passed.
+    pub fn new(interval: Duration, behavior: B) -> Self {
+        let interval = ticks(interval);
+        Periodic { behavior, interval, jitter: 0, countdown: interval }
+    }
+
+    /// Delays each run by up to `jitter` at random, after the interval.
+    pub fn jitter(mut self, jitter: Duration) -> Self {
+        self.jitter = (jitter.as_millis() / TICK.as_millis()) as u32;
+        self.reset();
+        self
+    }
+
+    /// Gets the behavior run.
+    pub fn behavior(&self) -> &B {
+        &self.behavior
+    }
+
+    /// Counts down the interval and its jitter again.
+    fn reset(&mut self) {
+        self.countdown = self.interval + rand::thread_rng().gen_range(0..=self.jitter);
+    }
+}
+
+impl<T, B: Behavior<T>> Behavior<T> for Periodic<B> {
+    fn tick(&mut self, client: &mut Client<T>) -> io::Result<()> {
+        self.countdown = self.countdown.saturating_sub(1);
+        if self.countdown > 0 {
+            return Ok(());
+        }
+        self.reset();
+        self.behavior.tick(client)
+    }
+}
+
+/// The number of ticks spanning `duration`, at least one.
+fn ticks(duration: Duration) -> u32 {
+    (duration.as_millis() / TICK.as_millis()).clamp(1, u32::MAX as u128) as u32
+}
+
+/// A movement of an idle player, which servers count as activity.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum IdleAction {
+    /// Turns the head by up to `max_degrees` left or right and up or down, at random. The
+    /// pitch is kept within 45 degrees of level.
+    Look { max_degrees: f32 },
+    /// Crouches and stands back up.
+    Sneak,
+    /// Jumps, if the player stands on the ground.
+    Jump,
+    /// Swings the arm of the main hand.
+    SwingArm
+}
+
+impl<T: Transport> Behavior<T> for IdleAction {
+    fn tick(&mut self, client: &mut Client<T>) -> io::Result<()> {
+        match *self {
+            IdleAction::Look { max_degrees } => {
+                let max_degrees = max_degrees.abs();
+                let mut random = rand::thread_rng();
+                let location = &client.player().location;
+                let yaw = location.yaw + random.gen_range(-max_degrees..=max_degrees);
+                let pitch = (location.pitch + random.gen_range(-max_degrees..=max_degrees)).clamp(-45.0, 45.0);
+                client.look(yaw, pitch);
+                Ok(())
+            },
+            IdleAction::Sneak => {
+                client.connection().set_sneaking(true)?;
+                client.connection().set_sneaking(false)
+            },
+            IdleAction::Jump => {
+                client.jump();
+                Ok(())
+            },
+            IdleAction::SwingArm => client.swing_arm()
+        }
+    }
+}
+
+/// A set of idle actions performed together, each time the behavior runs.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Idle {
+    pub actions: Vec<IdleAction>
+}
+
+impl Idle {
+    /// Performs `actions`, in order, each time the behavior runs.
+    pub fn new(actions: Vec<IdleAction>) -> Self {
+        Idle { actions }
+    }
+}
+
+impl Default for Idle {
+    /// Looks around by up to 30 degrees, sneaks and jumps.
+    fn default() -> Self {
+        Idle::new(vec![IdleAction::Look { max_degrees: 30.0 }, IdleAction::Sneak, IdleAction::Jump])
+    }
+}
+
+impl<T: Transport> Behavior<T> for Idle {
+    fn tick(&mut self, client: &mut Client<T>) -> io::Result<()> {
+        self.actions.iter_mut().try_for_each(|action| action.tick(client))
+    }
+}
+
+/// Performs the default idle actions every `interval`, which keeps servers from kicking
+/// the player for being AFK as long as it is shorter than their idle timeout, which is
+/// usually several minutes.
+pub fn anti_afk(interval: Duration) -> Periodic<Idle> {
+    Periodic::new(interval, Idle::default())
+}
@@ -0,0 +1,98 @@
+//! The boss bars shown at the top of the screen, which minigames often show their
+//! information in.
+use uuid::Uuid;
+
+use super::{chat::ChatComponent, error::MCTypeError, packets::play::{Clientbound, clientbound::BossBarAction}, version::ProtocolVersion};
+
+/// A boss bar shown at the top of the screen.
+#[derive(Clone, Debug, PartialEq)]
+pub struct BossBar {
+    pub uuid: Uuid,
+    pub title: ChatComponent,
+    /// How full the bar is, from 0 to 1.
+    pub health: f32,
+    /// The color of the bar: pink (0), blue, red, green, yellow, purple or white (6).
+    pub color: i32,
+    /// The notches the bar is divided by: none (0), or 6, 10, 12 or 20 (4).
+    pub division: i32,
+    /// The flags of the bar, such as `BossBar::DARKEN_SKY`.
+    pub flags: u8
+}
+
+impl BossBar {
+    /// The flag of bars darkening the sky.
+    pub const DARKEN_SKY: u8 = 0x1;
+    /// The flag of bars playing the music of the end.
+    pub const DRAGON_BAR: u8 = 0x2;
+    /// The flag of bars creating fog.
+    pub const CREATE_FOG: u8 = 0x4;
+
+    /// Whether the bar has `flag`.
+    pub fn has(&self, flag: u8) -> bool {
+        self.flags & flag != 0
+    }
+}
+
+/// The boss bars shown to the player, kept up to date with the Boss Bar packets sent by
+/// the server.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct BossBars {
+    bars: Vec<BossBar>
+}
+
+impl BossBars {
+    /// Creates an empty set of bars.
+    pub fn new() -> Self {
+        BossBars::default()
+    }
+
+    /// Gets the bar `uuid`, if it is shown.
+    pub fn get(&self, uuid: Uuid) -> Option<&BossBar> {
+        self.bars.iter().find(|bar| bar.uuid == uuid)
+    }
+
+    /// Gets the bars shown, from the top of the screen.
+    pub fn iter(&self) -> impl Iterator<Item = &BossBar> {
+        self.bars.iter()
+    }
+
+    pub fn len(&self) -> usize {
+        self.bars.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.bars.is_empty()
+    }
+
+    /// Applies the Play packet `packet` of `version` to the bars, if it concerns them.
+    /// Updates to bars which were never added are ignored.
+    /// # Errors
+    /// This function will return an `MCTypeError` if a Boss Bar cannot be decoded.
+    pub fn handle(&mut self, packet: &Clientbound, version: ProtocolVersion) -> Result<(), MCTypeError> {
+        let Clientbound::BossBar(packet) = packet else {
+            return Ok(());
+        };
+        let action = packet.action(version)?;
+        if let BossBarAction::Add { title, health, color, division, flags } = action {
+            self.bars.retain(|bar| bar.uuid != packet.uuid);
+            self.bars.push(BossBar { uuid: packet.uuid, title, health, color, division, flags });
+            return Ok(());
+        }
+        if action == BossBarAction::Remove {
+            self.bars.retain(|bar| bar.uuid != packet.uuid);
+            return Ok(());
+        }
+
+        let Some(bar) = self.bars.iter_mut().find(|bar| bar.uuid == packet.uuid) else {
+            return Ok(());
+        };
+        match action {
+            BossBarAction::UpdateHealth(health) => bar.health = health,
+            BossBarAction::UpdateTitle(title) => bar.title = title,
+            BossBarAction::UpdateStyle { color, division } => (bar.color, bar.division) = (color, division),
+            BossBarAction::UpdateFlags(flags) => bar.flags = flags,
+            BossBarAction::Add { .. } | BossBarAction::Remove => ()
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,241 @@
+//! The configuration of how a `Client` connects to a server, and reconnects to it.
+use std::{io, net::{TcpStream, ToSocketAddrs}, time::Duration};
+
+use super::{auth::AuthSession, client::Client, connection::{version_for, Connection, MinecraftStream, OfflineConnection}, dns, limits::DecodeLimits, proxy::ProxyConfig, resource_pack::ResourcePackPolicy, version::ProtocolVersion};
+
+/// When and how often a client reconnects after losing its connection. The delay before
+/// each attempt grows by `factor` from `initial_delay`, up to `max_delay`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ReconnectPolicy {
+    /// The delay before the first attempt.
+    pub initial_delay: Duration,
+    /// The longest delay between two attempts.
+    pub max_delay: Duration,
+    /// The factor the delay grows by after each failed attempt.
+    pub factor: u32,
+    /// The number of attempts after which the client gives up, or `None` to retry
+    /// forever.
+    pub max_attempts: Option<u32>,
+    /// Whether the client also reconnects after the server kicks the player with a
+    /// Disconnect packet, rather than only when the connection is lost.
+    pub after_kick: bool
+}
+
+impl ReconnectPolicy {
+    /// Gets the delay before the attempt numbered `attempt`, from 0.
+    pub fn delay(&self, attempt: u32) -> Duration {
+        let mut delay = self.initial_delay.min(self.max_delay);
+        for _ in 0..attempt {
+            if delay >= self.max_delay {
+                break;
+            }
+            delay = delay.saturating_mul(self.factor).min(self.max_delay);
+        }
+        delay
+    }
+
+    /// Whether the client gives up once `attempts` attempts have failed.
+    pub fn gives_up(&self, attempts: u32) -> bool {
+        self.max_attempts.is_some_and(|max_attempts| attempts >= max_attempts)
+    }
+}
+
+impl Default for ReconnectPolicy {
+    /// Retries forever, a second after losing the connection and twice as late after each
+    /// failed attempt, up to a minute. Kicks are not retried.
+    fn default() -> Self {
+        ReconnectPolicy { initial_delay: Duration::from_secs(1), max_delay: Duration::from_secs(60), factor: 2, max_attempts: None, after_kick: false }
+    }
+}
+
+/// Configures how a `Client` connects to a server, then joins it with `connect`.
+/// # Example
+/// ```no_run
+/// use std::time::Duration;
+/// use mcclient::{ClientBuilder, mc::builder::ReconnectPolicy};
+///
+/// let mut client = ClientBuilder::new("play.example.com", "Makoto")
+///     .connect_timeout(Duration::from_secs(5))
+///     .read_timeout(Duration::from_secs(30))
+///     .reconnect(ReconnectPolicy::default())
+///     .connect()
+///     .expect("Could not join");
+/// ```
+#[derive(Clone, Debug)]
+pub struct ClientBuilder {
+    address: String,
+    username: String,
+    session: Option<AuthSession>,
+    version: Option<ProtocolVersion>,
+    connect_timeout: Option<Duration>,
+    read_timeout: Option<Duration>,
+    write_timeout: Option<Duration>,
+    compression_level: Option<u32>,
+    decode_limits: Option<DecodeLimits>,
+    proxy: Option<ProxyConfig>,
+    resource_pack_policy: ResourcePackPolicy,
+    reconnect: Option<ReconnectPolicy>
+}
+
+impl ClientBuilder {
+    /// Configures a client joining the offline-mode server at `address` as `username`.
+    /// The address is given as `host` or `host:port`, and resolved as per `dns::resolve`.
+    pub fn new<A: Into<String>>(address: A, username: &str) -> Self {
+        ClientBuilder {
+            address: address.into(),
+            username: username.to_owned(),
+            session: None,
+            version: None,
+            connect_timeout: None,
+            read_timeout: None,
+            write_timeout: None,
+            compression_level: None,
+            decode_limits: None,
+            proxy: None,
+            resource_pack_policy: ResourcePackPolicy::default(),
+            reconnect: None
+        }
+    }
+
+    /// Joins an online-mode server as the player of `session` instead.
+    pub fn session(mut self, session: AuthSession) -> Self {
+        self.username = session.profile.name.clone();
+        self.session = Some(session);
+        self
+    }
+
+    /// Joins in the protocol version `version`, rather than in the version the server
+    /// reports in its status.
+    pub fn version(mut self, version: ProtocolVersion) -> Self {
+        self.version = Some(version);
+        self
+    }
+
+    /// Gives up connecting to the server, or to the proxy, after `timeout`.
+    pub fn connect_timeout(mut self, timeout: Duration) -> Self {
+        self.connect_timeout = Some(timeout);
+        self
+    }
+
+    /// Fails reads which receive nothing for `timeout`, as when the server stops
+    /// responding.
+    pub fn read_timeout(mut self, timeout: Duration) -> Self {
+        self.read_timeout = Some(timeout);
+        self
+    }
+
+    /// Fails writes which cannot be sent for `timeout`.
+    pub fn write_timeout(mut self, timeout: Duration) -> Self {
+        self.write_timeout = Some(timeout);
+        self
+    }
+
+    /// Compresses outbound packets at the zlib `level` once the server enables
+    /// compression, as per `MinecraftStream::set_compression_level`. The server decides
+    /// whether packets are compressed, and which.
+    pub fn compression_level(mut self, level: u32) -> Self {
+        self.compression_level = Some(level);
+        self
+    }
+
+    /// Decodes what the server sends within `limits`, as per
+    /// `MinecraftStream::set_decode_limits`. The client enters them while it handles each
+    /// packet, so that the fields of the packet are checked against them as well.
+    pub fn decode_limits(mut self, limits: DecodeLimits) -> Self {
+        self.decode_limits = Some(limits);
+        self
+    }
+
+    /// Connects through `proxy`, for both the status request and the login.
+    pub fn proxy(mut self, proxy: ProxyConfig) -> Self {
+        self.proxy = Some(proxy);
+        self
+    }
+
+    /// Answers resource packs as `policy` calls for.
+    pub fn resource_pack_policy(mut self, policy: ResourcePackPolicy) -> Self {
+        self.resource_pack_policy = policy;
+        self
+    }
+
+    /// Reconnects and joins again as `policy` calls for whenever the connection is lost, as
+    /// per `Client::set_reconnect`.
+    pub fn reconnect(mut self, policy: ReconnectPolicy) -> Self {
+        self.reconnect = Some(policy);
+        self
+    }
+
+    /// Connects to the server and joins it.
+    /// # Errors
+    /// This function will return an `InvalidInput` error if the address is malformed, or
+    /// an error if the server cannot be reached, if its version is not supported, or if
+    /// it does not let the player join. A client which reconnects only retries once it
+    /// has joined.
+    pub fn connect(&self) -> Result<Client, io::Error> {
+        let connection = self.open()?;
+        let mut client = match &self.session {
+            Some(session) => Client::login_online_with(connection, session)?,
+            None => Client::login_offline_with(connection, &self.username)?
+        };
+        if let Some(policy) = self.reconnect {
+            let builder = self.clone();
+            client.set_reconnect(policy, move || builder.open());
+        }
+        Ok(client)
+    }
+
+    /// Opens a connection to the server in the configured or negotiated version, ready to
+    /// log in.
+    /// # Errors
+    /// This function will return an error if the server cannot be reached, or if its
+    /// version cannot be negotiated.
+    pub fn open(&self) -> Result<OfflineConnection, io::Error> {
+        let (host, port) = dns::resolve(&self.address)?;
+        let version = match self.version {
+            Some(version) => version,
+            None => version_for(&self.stream(&host, port)?.status()?)?
+        };
+
+        let mut connection = self.stream(&host, port)?;
+        connection.sock().set_version(version);
+        if let Some(level) = self.compression_level {
+            connection.sock().set_compression_level(level);
+        }
+        if let Some(limits) = self.decode_limits {
+            connection.sock().set_decode_limits(limits);
+        }
+        connection.set_resource_pack_policy(self.resource_pack_policy);
+        Ok(connection)
+    }
+
+    /// Connects to `host` and `port`, through the proxy if there is one, with the
+    /// configured timeouts.
+    fn stream(&self, host: &str, port: u16) -> Result<OfflineConnection, io::Error> {
+        let mut stream = match &self.proxy {
+            Some(proxy) => self.tcp(proxy.address())?,
+            None => self.tcp((host, port))?
+        };
+        stream.set_read_timeout(self.read_timeout)?;
+        stream.set_write_timeout(self.write_timeout)?;
+        if let Some(proxy) = &self.proxy {
+            proxy.tunnel(&mut stream, host, port)?;
+        }
+
+        Ok(OfflineConnection::with_stream(MinecraftStream::new(stream), host, port))
+    }
+
+    fn tcp<A: ToSocketAddrs>(&self, address: A) -> Result<TcpStream, io::Error> {
+        let Some(timeout) = self.connect_timeout else {
+            return TcpStream::connect(address);
+        };
+
+        let mut last_error = io::Error::new(io::ErrorKind::NotFound, "The address did not resolve to any socket address.");
+        for address in address.to_socket_addrs()? {
+            match TcpStream::connect_timeout(&address, timeout) {
+                Ok(stream) => return Ok(stream),
+                Err(err) => last_error = err
+            }
+        }
+        Err(last_error)
+    }
+}
@@ -0,0 +1,156 @@
+//! Dumps of the packets of a session, recorded as they are exchanged and replayed through
+//! the decoder later, so that a session against a live server can be reproduced offline.
+//! <br> <br>
+//! A dump starts with a header of the magic bytes `MCCAP`, the format version, the
+//! protocol version as an `i32`, the protocol state the session was in and the time it
+//! started, in milliseconds since the Unix epoch. Each packet follows as its direction,
+//! the milliseconds elapsed since the start, and the packet framed uncompressed under its
+//! raw ID. Numbers are big-endian, as in the protocol.
+use std::{fs::File, io::{self, BufReader, BufWriter, Read, Write}, path::Path, time::{Duration, Instant, SystemTime, UNIX_EPOCH}};
+
+use super::{connection::ConnectionState, packet::{read_packet, write_packet, OutboundPacket}, registry::Direction, sniffer::{Session, SniffedPacket}, version::ProtocolVersion};
+
+/// The bytes every dump starts with.
+const MAGIC: &[u8; 5] = b"MCCAP";
+/// The version of the format dumps are written in.
+const FORMAT_VERSION: u8 = 1;
+
+/// The header of a dump, describing the session recorded.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CaptureHeader {
+    pub version: ProtocolVersion,
+    /// The protocol state the session was in when the recording started.
+    pub state: ConnectionState,
+    /// The time the recording started.
+    pub started: SystemTime
+}
+
+/// A recording of the packets of a session, written as they are exchanged. Recordings are
+/// usually started with `MinecraftStream::start_capture`.
+pub struct Capture {
+    writer: Box<dyn Write + Send>,
+    started: Instant
+}
+
+impl Capture {
+    /// Starts recording a session in `version` and `state` to a new file at `path`, which
+    /// is truncated if it exists.
+    /// # Errors
+    /// This function will return an error if the file cannot be created or written.
+    pub fn create<P: AsRef<Path>>(path: P, version: ProtocolVersion, state: ConnectionState) -> Result<Self, io::Error> {
+        Capture::to_writer(BufWriter::new(File::create(path)?), version, state)
+    }
+
+    /// Starts recording a session in `version` and `state` to `writer`.
+    /// # Errors
+    /// This function will return an error if the header cannot be written.
+    pub fn to_writer<W: Write + Send + 'static>(mut writer: W, version: ProtocolVersion, state: ConnectionState) -> Result<Self, io::Error> {
+        let started = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default();
+        writer.write_all(MAGIC)?;
+        writer.write_all(&[FORMAT_VERSION])?;
+        writer.write_all(&version.number().to_be_bytes())?;
+        writer.write_all(&[state_byte(state)])?;
+        writer.write_all(&(started.as_millis() as u64).to_be_bytes())?;
+        Ok(Capture { writer: Box::new(writer), started: Instant::now() })
+    }
+
+    /// Records `packet`, sent in `direction` under its raw ID, stamped with the time
+    /// elapsed since the recording started.
+    /// # Errors
+    /// This function will return an error if the record cannot be written.
+    pub fn record(&mut self, direction: Direction, packet: &dyn OutboundPacket) -> Result<(), io::Error> {
+        let elapsed = self.started.elapsed().as_millis() as u64;
+        let mut record = vec![direction_byte(direction)];
+        record.extend(elapsed.to_be_bytes());
+        write_packet(&mut record, packet)?;
+        self.writer.write_all(&record)
+    }
+
+    /// Writes out the records buffered so far.
+    /// # Errors
+    /// This function will return an error if the writer cannot be flushed.
+    pub fn flush(&mut self) -> Result<(), io::Error> {
+        self.writer.flush()
+    }
+}
+
+/// Replays the dump at `path` through the decoder, handing every packet of it to
+/// `handler` along with the time it was recorded at since the start, as per `replay_from`.
+/// # Errors
+/// This function will return an error if the file cannot be read, or as per
+/// `replay_from`.
+pub fn replay<P, F>(path: P, handler: F) -> Result<CaptureHeader, io::Error>
+where P: AsRef<Path>, F: FnMut(Duration, &SniffedPacket) -> io::Result<()> {
+    replay_from(BufReader::new(File::open(path)?), handler)
+}
+
+/// Replays the dump read from `reader`. Packets are decoded as `MinecraftStream::read`
+/// would, under their canonical ID in the state the session was in, which is tracked
+/// from the packets as a `Sniffer` tracks it. Returns the header of the dump once every
+/// packet has been handed to `handler`.
+/// # Errors
+/// This function will return an `InvalidData` error if the dump is malformed or written
+/// in an unknown format, or the first error returned by `handler`.
+pub fn replay_from<R, F>(mut reader: R, mut handler: F) -> Result<CaptureHeader, io::Error>
+where R: Read, F: FnMut(Duration, &SniffedPacket) -> io::Result<()> {
+    let invalid = |message: &str| io::Error::new(io::ErrorKind::InvalidData, message.to_owned());
+
+    let mut header = [0; 19];
+    reader.read_exact(&mut header).map_err(|_| invalid("The dump is too short to have a header."))?;
+    if &header[..5] != MAGIC {
+        return Err(invalid("The file is not a packet dump."));
+    }
+    if header[5] != FORMAT_VERSION {
+        return Err(invalid(&format!("Unknown dump format version {}.", header[5])));
+    }
+    let version = ProtocolVersion::from(i32::from_be_bytes([header[6], header[7], header[8], header[9]]));
+    let state = state_of(header[10]).ok_or_else(|| invalid(&format!("Unknown protocol state {}.", header[10])))?;
+    let started = UNIX_EPOCH + Duration::from_millis(u64::from_be_bytes(header[11..19].try_into().expect("The slice has 8 bytes.")));
+
+    let mut session = Session::starting(version, state);
+    let mut direction = [0];
+    while reader.read(&mut direction)? != 0 {
+        let direction = direction_of(direction[0]).ok_or_else(|| invalid(&format!("Unknown packet direction {}.", direction[0])))?;
+        let mut elapsed = [0; 8];
+        reader.read_exact(&mut elapsed)?;
+        let packet = session.decode(direction, read_packet(&mut reader)?)?;
+        handler(Duration::from_millis(u64::from_be_bytes(elapsed)), &packet)?;
+    }
+    Ok(CaptureHeader { version, state, started })
+}
+
+fn state_byte(state: ConnectionState) -> u8 {
+    match state {
+        ConnectionState::Handshaking => 0,
+        ConnectionState::Status => 1,
+        ConnectionState::Login => 2,
+        ConnectionState::Configuration => 3,
+        ConnectionState::Play => 4
+    }
+}
+
+fn state_of(byte: u8) -> Option<ConnectionState> {
+    Some(match byte {
+        0 => ConnectionState::Handshaking,
+        1 => ConnectionState::Status,
+        2 => ConnectionState::Login,
+        3 => ConnectionState::Configuration,
+        4 => ConnectionState::Play,
+        _ => return None
+    })
+}
+
+fn direction_byte(direction: Direction) -> u8 {
+    match direction {
+        Direction::Serverbound => 0,
+        Direction::Clientbound => 1
+    }
+}
+
+fn direction_of(byte: u8) -> Option<Direction> {
+    match byte {
+        0 => Some(Direction::Serverbound),
+        1 => Some(Direction::Clientbound),
+        _ => None
+    }
+}
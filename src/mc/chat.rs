@@ -0,0 +1,547 @@
+use std::{fmt, io};
+
+use serde::{Deserialize, Deserializer, Serialize};
+use serde_json::Value;
+
+use super::{error::MCTypeError, mctypes::MCString, nbt::NbtTag, translation::{parse_format, FormatPiece, Translations}, version::ProtocolVersion};
+
+/// The first protocol version (1.20.3) encoding text components as NBT rather than JSON.
+pub const NBT_TEXT_SINCE: i32 = 765;
+
+/// The section sign which introduces a legacy formatting code, such as `§c` for red.
+pub const SECTION_SIGN: char = '§';
+
+/// Removes legacy formatting codes from `text`. A formatting code is a section sign
+/// followed by a single character; a trailing section sign is removed as well.
+/// # Example
+/// ```
+/// use mcclient::mc::chat::strip_section_codes;
+/// assert_eq!(strip_section_codes("§cRed §lbold§r text"), "Red bold text");
+/// ```
+pub fn strip_section_codes(text: &str) -> String {
+    let mut stripped = String::with_capacity(text.len());
+    let mut chars = text.chars();
+
+    while let Some(c) = chars.next() {
+        if c == SECTION_SIGN {
+            chars.next();
+        } else {
+            stripped.push(c);
+        }
+    }
+
+    stripped
+}
+
+/// Removes ANSI escape sequences, such as `\x1b[31m`, from `text`. Control Sequence
+/// Introducer sequences are removed up to and including their final byte, while other
+/// escapes are removed along with the character following the escape.
+pub fn strip_ansi_codes(text: &str) -> String {
+    const ESCAPE: char = '\x1b';
+    let mut stripped = String::with_capacity(text.len());
+    let mut chars = text.chars();
+
+    while let Some(c) = chars.next() {
+        if c != ESCAPE {
+            stripped.push(c);
+            continue;
+        }
+
+        if chars.next() == Some('[') {
+            // Parameter and intermediate bytes run until a final byte in '@'..='~'.
+            for c in chars.by_ref() {
+                if ('@'..='~').contains(&c) {
+                    break;
+                }
+            }
+        }
+    }
+
+    stripped
+}
+
+/// A JSON text component, as used for chat messages, titles, disconnect reasons and
+/// MOTDs. Besides its own content, a component carries its formatting and `extra`
+/// children, which inherit the formatting of their parent.
+/// <br> <br>
+/// Components are parsed from any of their JSON forms: a plain string, an array whose
+/// first element is the parent of the rest, or an object.
+/// # Example
+/// ```
+/// use mcclient::mc::chat::{ChatComponent, ClickEvent};
+///
+/// let component = ChatComponent::from_json(r#"{"text":"Hello ","extra":[{"text":"world","bold":true}]}"#).unwrap();
+/// assert_eq!(component.to_plain_text(), "Hello world");
+///
+/// let link = ChatComponent::text("Click here").color("aqua").underlined(true)
+///     .click(ClickEvent::open_url("https://minecraft.net"));
+/// println!("{}", link.to_json());
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Serialize)]
+pub struct ChatComponent {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub text: Option<String>,
+    /// The key of a translated message, whose `%s` placeholders are filled with `with`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub translate: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub with: Vec<ChatComponent>,
+    /// The message shown in place of `translate` if the key has no translation.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fallback: Option<String>,
+    /// The name of a key binding, such as `key.jump`, shown as the key it is bound to.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub keybind: Option<String>,
+    /// The name of a color, such as `red`, or a `#RRGGBB` hex color.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub color: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bold: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub italic: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub underlined: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub strikethrough: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub obfuscated: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub font: Option<String>,
+    /// Text inserted into the chat input when the component is shift-clicked.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub insertion: Option<String>,
+    #[serde(rename = "clickEvent", skip_serializing_if = "Option::is_none")]
+    pub click_event: Option<ClickEvent>,
+    #[serde(rename = "hoverEvent", skip_serializing_if = "Option::is_none")]
+    pub hover_event: Option<HoverEvent>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub extra: Vec<ChatComponent>
+}
+
+/// The object form of a text component, which the other forms are parsed around.
+#[derive(Deserialize)]
+#[serde(remote = "ChatComponent")]
+struct ComponentObject {
+    #[serde(default)]
+    text: Option<String>,
+    #[serde(default)]
+    translate: Option<String>,
+    #[serde(default)]
+    with: Vec<ChatComponent>,
+    #[serde(default)]
+    fallback: Option<String>,
+    #[serde(default)]
+    keybind: Option<String>,
+    #[serde(default)]
+    color: Option<String>,
+    #[serde(default)]
+    bold: Option<bool>,
+    #[serde(default)]
+    italic: Option<bool>,
+    #[serde(default)]
+    underlined: Option<bool>,
+    #[serde(default)]
+    strikethrough: Option<bool>,
+    #[serde(default)]
+    obfuscated: Option<bool>,
+    #[serde(default)]
+    font: Option<String>,
+    #[serde(default)]
+    insertion: Option<String>,
+    #[serde(default, rename = "clickEvent")]
+    click_event: Option<ClickEvent>,
+    #[serde(default, rename = "hoverEvent")]
+    hover_event: Option<HoverEvent>,
+    #[serde(default)]
+    extra: Vec<ChatComponent>
+}
+
+/// The JSON forms a text component may take.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum ComponentForm {
+    Text(String),
+    Number(serde_json::Number),
+    Boolean(bool),
+    List(Vec<ChatComponent>),
+    Object(#[serde(deserialize_with = "ComponentForm::object")] Box<ChatComponent>)
+}
+
+impl ComponentForm {
+    fn object<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Box<ChatComponent>, D::Error> {
+        ComponentObject::deserialize(deserializer).map(Box::new)
+    }
+}
+
+impl<'de> Deserialize<'de> for ChatComponent {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Ok(match ComponentForm::deserialize(deserializer)? {
+            ComponentForm::Text(text) => ChatComponent::text(text),
+            ComponentForm::Number(number) => ChatComponent::text(number.to_string()),
+            ComponentForm::Boolean(boolean) => ChatComponent::text(boolean.to_string()),
+            ComponentForm::List(mut components) => match components.is_empty() {
+                true => ChatComponent::text(""),
+                false => {
+                    let mut parent = components.remove(0);
+                    parent.extra.extend(components);
+                    parent
+                }
+            },
+            ComponentForm::Object(component) => *component
+        })
+    }
+}
+
+impl ChatComponent {
+    /// Creates a component of plain `text`, without formatting.
+    pub fn text<T: Into<String>>(text: T) -> Self {
+        ChatComponent { text: Some(text.into()), ..Default::default() }
+    }
+
+    /// Creates a component of the translated message `key`, filled with `with`.
+    pub fn translate<T: Into<String>>(key: T, with: Vec<ChatComponent>) -> Self {
+        ChatComponent { translate: Some(key.into()), with, ..Default::default() }
+    }
+
+    /// Parses a component from `json`.
+    /// # Errors
+    /// This function will return an `InvalidData` error if `json` is not a text component.
+    pub fn from_json(json: &str) -> Result<Self, io::Error> {
+        Ok(serde_json::from_str(json)?)
+    }
+
+    /// Parses a component encoded in the format of `version` from the front of `bytes`, as
+    /// JSON before 1.20.3 and as NBT since, returning the component along with the bytes
+    /// following it.
+    /// # Errors
+    /// This function will return an `MCTypeError` if the bytes do not encode a component.
+    pub fn parse_for(bytes: &[u8], version: ProtocolVersion) -> Result<(Self, &[u8]), MCTypeError> {
+        if version.number() >= NBT_TEXT_SINCE {
+            let (tag, rest) = NbtTag::parse_for(bytes, version)?;
+            let component = tag.ok_or_else(|| MCTypeError::InvalidValue("Missing text component.".to_owned()))?.deserialize_into()?;
+            return Ok((component, rest));
+        }
+
+        let (json, rest) = MCString::parse_bounded(bytes, 262144)?;
+        let component = ChatComponent::from_json(json.string()).map_err(|err| MCTypeError::InvalidValue(err.to_string()))?;
+        Ok((component, rest))
+    }
+
+    /// Serializes the component to JSON, as it is sent to servers.
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).expect("Text components serialize to JSON.")
+    }
+
+    /// Flattens the component and its children into the text they show, without their
+    /// formatting. Legacy formatting codes within the text are kept. Translated messages
+    /// are resolved with the builtin translations, falling back to their `fallback` text
+    /// or their key if the key is not among them.
+    pub fn to_plain_text(&self) -> String {
+        self.to_plain_text_with(Translations::builtin())
+    }
+
+    /// Flattens the component as per `to_plain_text`, resolving translated messages with
+    /// `translations`.
+    pub fn to_plain_text_with(&self, translations: &Translations) -> String {
+        let mut text = String::new();
+        self.push_plain_text(&mut text, translations);
+        text
+    }
+
+    fn push_plain_text(&self, text: &mut String, translations: &Translations) {
+        for piece in self.content(translations) {
+            match piece {
+                ContentPiece::Text(content) => text.push_str(content),
+                ContentPiece::Argument(argument) => argument.push_plain_text(text, translations)
+            }
+        }
+        for child in &self.extra {
+            child.push_plain_text(text, translations);
+        }
+    }
+
+    /// The text the component itself shows before its children, with the arguments of a
+    /// translated message in the places of their placeholders.
+    fn content<'a>(&'a self, translations: &'a Translations) -> Vec<ContentPiece<'a>> {
+        let Some(key) = &self.translate else {
+            let text = self.text.as_deref().or(self.keybind.as_deref()).unwrap_or_default();
+            return vec![ContentPiece::Text(text)];
+        };
+
+        let format = translations.get(key).or(self.fallback.as_deref()).unwrap_or(key);
+        parse_format(format).into_iter().map(|piece| match piece {
+            FormatPiece::Literal(text) => ContentPiece::Text(text),
+            FormatPiece::Argument(index) => self.with.get(index).map_or(ContentPiece::Text(""), ContentPiece::Argument)
+        }).collect()
+    }
+
+    /// Renders the component and its children as text with ANSI escape sequences for their
+    /// colors and formatting, for display in a terminal. Legacy formatting codes within
+    /// the text are rendered as well. Named colors use the 16 standard terminal colors,
+    /// while hex colors need a terminal supporting 24-bit color. Obfuscated text is shown
+    /// as is, as terminals have no equivalent. Translated messages are resolved as per
+    /// `to_plain_text`.
+    /// # Example
+    /// ```
+    /// use mcclient::mc::chat::ChatComponent;
+    ///
+    /// let warning = ChatComponent::text("Warning: ").color("red").bold(true).append(ChatComponent::text("§ohot"));
+    /// assert_eq!(warning.to_ansi(), "\x1b[0;91;1mWarning: \x1b[0;91;1;3mhot\x1b[0m");
+    /// ```
+    pub fn to_ansi(&self) -> String {
+        self.to_ansi_with(Translations::builtin())
+    }
+
+    /// Renders the component as per `to_ansi`, resolving translated messages with
+    /// `translations`.
+    pub fn to_ansi_with(&self, translations: &Translations) -> String {
+        let mut rendered = String::new();
+        let mut current = AnsiStyle::default();
+        self.push_ansi(&mut rendered, translations, &AnsiStyle::default(), &mut current);
+        if current != AnsiStyle::default() {
+            rendered.push_str(ANSI_RESET);
+        }
+        rendered
+    }
+
+    fn push_ansi(&self, rendered: &mut String, translations: &Translations, inherited: &AnsiStyle, current: &mut AnsiStyle) {
+        let style = inherited.apply(self);
+        let mut segment_style = style.clone();
+
+        for piece in self.content(translations) {
+            let text = match piece {
+                ContentPiece::Text(text) => text,
+                ContentPiece::Argument(argument) => {
+                    argument.push_ansi(rendered, translations, &style, current);
+                    continue;
+                }
+            };
+
+            let mut chars = text.chars();
+            while let Some(c) = chars.next() {
+                if c != SECTION_SIGN {
+                    if *current != segment_style {
+                        rendered.push_str(&segment_style.escape());
+                        *current = segment_style.clone();
+                    }
+                    rendered.push(c);
+                    continue;
+                }
+
+                match chars.next().map(|code| code.to_ascii_lowercase()) {
+                    Some('l') => segment_style.bold = true,
+                    Some('o') => segment_style.italic = true,
+                    Some('n') => segment_style.underlined = true,
+                    Some('m') => segment_style.strikethrough = true,
+                    Some('r') => segment_style = AnsiStyle::default(),
+                    // Colors reset the formatting before them.
+                    Some(code) => if let Some(color) = legacy_color(code) {
+                        segment_style = AnsiStyle { color: ansi_color(color), ..AnsiStyle::default() };
+                    },
+                    None => {}
+                }
+            }
+        }
+
+        for child in &self.extra {
+            child.push_ansi(rendered, translations, &style, current);
+        }
+    }
+
+    /// Sets the color of the component, as a color name or a `#RRGGBB` hex color.
+    pub fn color<T: Into<String>>(mut self, color: T) -> Self {
+        self.color = Some(color.into());
+        self
+    }
+
+    pub fn bold(mut self, bold: bool) -> Self {
+        self.bold = Some(bold);
+        self
+    }
+
+    pub fn italic(mut self, italic: bool) -> Self {
+        self.italic = Some(italic);
+        self
+    }
+
+    pub fn underlined(mut self, underlined: bool) -> Self {
+        self.underlined = Some(underlined);
+        self
+    }
+
+    pub fn strikethrough(mut self, strikethrough: bool) -> Self {
+        self.strikethrough = Some(strikethrough);
+        self
+    }
+
+    pub fn obfuscated(mut self, obfuscated: bool) -> Self {
+        self.obfuscated = Some(obfuscated);
+        self
+    }
+
+    /// Sets the action taken when the component is clicked.
+    pub fn click(mut self, event: ClickEvent) -> Self {
+        self.click_event = Some(event);
+        self
+    }
+
+    /// Sets what is shown when the component is hovered over.
+    pub fn hover(mut self, event: HoverEvent) -> Self {
+        self.hover_event = Some(event);
+        self
+    }
+
+    /// Appends `child` to the children of the component.
+    pub fn append(mut self, child: ChatComponent) -> Self {
+        self.extra.push(child);
+        self
+    }
+}
+
+/// A piece of the text a component shows: either text, or an argument of a translated
+/// message, which is shown as a child of the component.
+enum ContentPiece<'a> {
+    Text(&'a str),
+    Argument(&'a ChatComponent)
+}
+
+/// The escape sequence resetting the colors and formatting of a terminal.
+const ANSI_RESET: &str = "\x1b[0m";
+
+/// The names of the colors of the legacy formatting codes `0` to `f`.
+const LEGACY_COLORS: [&str; 16] = [
+    "black", "dark_blue", "dark_green", "dark_aqua", "dark_red", "dark_purple", "gold", "gray",
+    "dark_gray", "blue", "green", "aqua", "red", "light_purple", "yellow", "white"
+];
+
+/// Gets the name of the color of the legacy formatting code `code`, if it is a color.
+fn legacy_color(code: char) -> Option<&'static str> {
+    code.to_digit(16).map(|index| LEGACY_COLORS[index as usize])
+}
+
+/// Gets the SGR parameters of the foreground color `color`, a color name or a `#RRGGBB`
+/// hex color, or `None` if it is neither.
+fn ansi_color(color: &str) -> Option<String> {
+    if let Some(hex) = color.strip_prefix('#') {
+        let rgb = u32::from_str_radix(hex, 16).ok().filter(|_| hex.len() == 6)?;
+        return Some(format!("38;2;{};{};{}", rgb >> 16, (rgb >> 8) & 0xFF, rgb & 0xFF));
+    }
+
+    const CODES: [u8; 16] = [30, 34, 32, 36, 31, 35, 33, 37, 90, 94, 92, 96, 91, 95, 93, 97];
+    LEGACY_COLORS.iter().position(|name| *name == color).map(|index| CODES[index].to_string())
+}
+
+/// The colors and formatting text is rendered with in a terminal.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+struct AnsiStyle {
+    color: Option<String>,
+    bold: bool,
+    italic: bool,
+    underlined: bool,
+    strikethrough: bool
+}
+
+impl AnsiStyle {
+    /// The style of `component`, which inherits this style wherever it sets none.
+    fn apply(&self, component: &ChatComponent) -> AnsiStyle {
+        AnsiStyle {
+            color: component.color.as_deref().and_then(ansi_color).or_else(|| self.color.clone()),
+            bold: component.bold.unwrap_or(self.bold),
+            italic: component.italic.unwrap_or(self.italic),
+            underlined: component.underlined.unwrap_or(self.underlined),
+            strikethrough: component.strikethrough.unwrap_or(self.strikethrough)
+        }
+    }
+
+    /// The escape sequence switching a terminal to this style from any other.
+    fn escape(&self) -> String {
+        let mut parameters = vec!["0".to_owned()];
+        parameters.extend(self.color.clone());
+        for (set, parameter) in [(self.bold, "1"), (self.italic, "3"), (self.underlined, "4"), (self.strikethrough, "9")] {
+            if set {
+                parameters.push(parameter.to_owned());
+            }
+        }
+
+        format!("\x1b[{}m", parameters.join(";"))
+    }
+}
+
+impl fmt::Display for ChatComponent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.to_plain_text())
+    }
+}
+
+/// The actions a component may take when clicked.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ClickAction {
+    OpenUrl,
+    OpenFile,
+    RunCommand,
+    SuggestCommand,
+    ChangePage,
+    CopyToClipboard
+}
+
+/// An action taken when a component is clicked, with the value it acts upon.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ClickEvent {
+    pub action: ClickAction,
+    pub value: String
+}
+
+impl ClickEvent {
+    pub fn open_url<T: Into<String>>(url: T) -> Self {
+        ClickEvent { action: ClickAction::OpenUrl, value: url.into() }
+    }
+
+    pub fn run_command<T: Into<String>>(command: T) -> Self {
+        ClickEvent { action: ClickAction::RunCommand, value: command.into() }
+    }
+
+    pub fn suggest_command<T: Into<String>>(command: T) -> Self {
+        ClickEvent { action: ClickAction::SuggestCommand, value: command.into() }
+    }
+}
+
+/// The things a component may show when hovered over.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HoverAction {
+    ShowText,
+    ShowItem,
+    ShowEntity
+}
+
+/// What is shown when a component is hovered over. Since 1.16, the shown text, item or
+/// entity is given as `contents`; older versions give it as `value`.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct HoverEvent {
+    pub action: HoverAction,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub contents: Option<Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub value: Option<Value>
+}
+
+impl HoverEvent {
+    /// Creates a hover event showing the component `text`.
+    pub fn show_text(text: ChatComponent) -> Self {
+        HoverEvent {
+            action: HoverAction::ShowText,
+            contents: Some(serde_json::to_value(text).expect("Text components serialize to JSON.")),
+            value: None
+        }
+    }
+
+    /// Gets the text shown by a `ShowText` event, from either of its forms.
+    pub fn text(&self) -> Option<ChatComponent> {
+        match self.action {
+            HoverAction::ShowText => self.contents.as_ref().or(self.value.as_ref())
+                .and_then(|text| serde_json::from_value(text.clone()).ok()),
+            _ => None
+        }
+    }
+}
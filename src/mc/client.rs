@@ -0,0 +1,835 @@
+use std::{collections::VecDeque, io, mem, net::TcpStream, thread, time::{Duration, Instant}};
+
+use uuid::Uuid;
+
+use super::{auth::{AuthSession, SharedSession}, behaviors::Behavior, boss_bar::BossBars, builder::ReconnectPolicy, combat::AttackCooldown, connection::{Connection, MinecraftStream, OfflineConnection}, digging::{Dig, Hardness, Tool}, dns, entities::EntityTracker, error::{DisconnectReason, MCTypeError}, events::Event, local_player::LocalPlayer, metrics::{Metrics, TickTimings}, mctypes::{Location, MCBoolean, MCDouble, MCFloat, MCPosition, MCString, MCUnsignedByte}, packet::{clientbound::login_success::LoginSuccess, serverbound::plugin_message::brand_channel}, packets::play::{Clientbound, clientbound::Suggestions, serverbound::{self, BlockFace, ClientCommand, ClientCommandAction, CloseContainer, CommandSuggestionsRequest, ConfirmTeleportation, Hand, Interact, PlayerAction, PlayerActionStatus, PluginMessage, SetPlayerPositionAndRotation, SwingArm, UseItemOn}}, pathfinding::Navigator, physics::{EYE_HEIGHT, Physics}, plugins::Plugin, registry::{self, Direction}, scoreboard::{DisplaySlot, Objective, Scoreboard}, tab_list::TabList, title::Titles, transport::Transport, windows::{ClickAction, Windows}, world::{World, chunk::OVERWORLD_MIN_Y}};
+
+/// The first protocol version (1.18) whose overworld extends below 0.
+const MIN_Y_BELOW_ZERO_SINCE: i32 = 757;
+
+type PluginMessageHandler<T> = Box<dyn FnMut(&mut MinecraftStream<T>, &[u8]) -> io::Result<()>>;
+type CompletionHandler = Box<dyn FnOnce(&Suggestions)>;
+type Connector<T> = Box<dyn FnMut() -> io::Result<OfflineConnection<T>>>;
+type MetricsCallback = Box<dyn FnMut(&Metrics)>;
+type Behaviors<T> = Vec<Box<dyn Behavior<T>>>;
+type Plugins<T> = Vec<Box<dyn Plugin<T>>>;
+
+/// A request for completions awaiting the server's suggestions.
+struct PendingCompletion {
+    transaction_id: i32,
+    /// The range of the last word of the text, which servers older than 1.13 complete
+    /// without telling.
+    last_word: (i32, i32),
+    handler: CompletionHandler
+}
+
+/// How a client opens a new connection once its connection is lost, and how often.
+struct Reconnection<T> {
+    policy: ReconnectPolicy,
+    connect: Connector<T>
+}
+
+/// How often a client hands its metrics to a callback.
+struct MetricsExport {
+    interval: Duration,
+    last: Instant,
+    callback: MetricsCallback
+}
+
+/// A player which has joined a server, and whose connection is in the Play state.
+/// <br> <br>
+/// The protocol version is negotiated with the server before joining. To join in a
+/// specific version instead, pass a connection from `OfflineConnection::negotiate` with
+/// the version to `login_offline_with` or `login_online_with`, or join with a
+/// `ClientBuilder`, which also configures timeouts, a proxy and reconnection.
+/// <br> <br>
+/// The client keeps track of the state of the game, such as the tab list, the world and
+/// the player's own health and position, from the packets read with `next_packet`. The
+/// player moves when `tick` is called, which should be done every `physics::TICK`.
+/// # Example
+/// ```no_run
+/// use mcclient::Client;
+///
+/// let client = Client::login_offline("localhost", 25565, "Makoto").expect("Could not join");
+/// println!("Joined as {} ({})", client.username(), client.uuid());
+/// ```
+pub struct Client<T = TcpStream> {
+    connection: OfflineConnection<T>,
+    profile: LoginSuccess,
+    tab_list: TabList,
+    scoreboard: Scoreboard,
+    boss_bars: BossBars,
+    titles: Titles,
+    world: World,
+    entities: EntityTracker,
+    player: LocalPlayer,
+    physics: Physics,
+    navigator: Navigator,
+    tool: Option<Tool>,
+    digging: Option<Dig>,
+    windows: Windows,
+    attack_cooldown: AttackCooldown,
+    auto_respawn: bool,
+    respawning: bool,
+    server_brand: Option<String>,
+    plugin_handlers: Vec<(String, PluginMessageHandler<T>)>,
+    completions: VecDeque<PendingCompletion>,
+    next_transaction_id: i32,
+    session: Option<AuthSession>,
+    followed_session: Option<SharedSession>,
+    reconnection: Option<Reconnection<T>>,
+    reconnects: u32,
+    tracking_failures: u64,
+    ticks: TickTimings,
+    metrics_export: Option<MetricsExport>,
+    behaviors: Behaviors<T>,
+    plugins: Plugins<T>
+}
+
+impl Client<TcpStream> {
+    /// Joins the offline-mode server at `host` and `port` as `username`, performing the
+    /// handshake and login in the version the server reports. The player is assigned the
+    /// offline UUID of `username`.
+    /// # Errors
+    /// This function will return an error if the server cannot be reached, if its version
+    /// is not supported, if it disconnects the player, or if it requires authentication.
+    pub fn login_offline<H: Into<String> + Clone>(host: H, port: u16, username: &str) -> Result<Self, io::Error> {
+        Client::login_offline_with(OfflineConnection::negotiate(host, port, None)?, username)
+    }
+
+    /// Joins the online-mode server at `host` and `port` as the player of `session`,
+    /// performing the handshake, encrypted login, and session server authentication in the
+    /// version the server reports.
+    /// # Errors
+    /// This function will return an error if the server cannot be reached, if its version
+    /// is not supported, if it disconnects the player, or if the session server rejects the join.
+    pub fn login_online<H: Into<String> + Clone>(host: H, port: u16, session: &AuthSession) -> Result<Self, io::Error> {
+        Client::login_online_with(OfflineConnection::negotiate(host, port, None)?, session)
+    }
+
+    /// Joins the offline-mode server at `address`, given as `host` or `host:port`, as
+    /// `username`, as per `login_offline`. Addresses without a port are resolved through
+    /// their SRV record, as per `dns::resolve`.
+    /// # Errors
+    /// This function will return an `InvalidInput` error if the address is malformed, or
+    /// the errors of `login_offline`.
+    pub fn login_offline_address(address: &str, username: &str) -> Result<Self, io::Error> {
+        let (host, port) = dns::resolve(address)?;
+        Client::login_offline(host, port, username)
+    }
+}
+
+impl<T: Transport> Client<T> {
+    fn joined(mut connection: OfflineConnection<T>, profile: LoginSuccess) -> Self {
+        let version = connection.sock().version();
+        let min_y = match version.number() >= MIN_Y_BELOW_ZERO_SINCE {
+            true => OVERWORLD_MIN_Y,
+            false => 0
+        };
+        // Since 1.20.2, servers send their brand during configuration.
+        let server_brand = connection.configuration().plugin_messages.iter()
+            .find(|(channel, _)| channel == brand_channel(version))
+            .and_then(|(_, data)| parse_brand(data));
+        Client { connection, profile, tab_list: TabList::new(), scoreboard: Scoreboard::new(), boss_bars: BossBars::new(), titles: Titles::new(), world: World::new(version, min_y), entities: EntityTracker::new(version), player: LocalPlayer::new(), physics: Physics::new(), navigator: Navigator::new(), tool: None, digging: None, windows: Windows::new(version), attack_cooldown: AttackCooldown::new(version), auto_respawn: false, respawning: false, server_brand, plugin_handlers: Vec::new(), completions: VecDeque::new(), next_transaction_id: 0, session: None, followed_session: None, reconnection: None, reconnects: 0, tracking_failures: 0, ticks: TickTimings::default(), metrics_export: None, behaviors: Vec::new(), plugins: Vec::new() }
+    }
+
+    /// Joins the server as `username` over an established `connection`, as per
+    /// `login_offline`.
+    /// # Errors
+    /// This function will return an error if the server disconnects the player, or if
+    /// it requires authentication.
+    pub fn login_offline_with(mut connection: OfflineConnection<T>, username: &str) -> Result<Self, io::Error> {
+        let profile = connection.login(username)?;
+        Ok(Client::joined(connection, profile))
+    }
+
+    /// Joins the server as the player of `session` over an established `connection`, as
+    /// per `login_online`.
+    /// # Errors
+    /// This function will return an error if the server disconnects the player, or if
+    /// the session server rejects the join.
+    pub fn login_online_with(mut connection: OfflineConnection<T>, session: &AuthSession) -> Result<Self, io::Error> {
+        let profile = connection.login_online(session)?;
+        let mut client = Client::joined(connection, profile);
+        client.session = Some(session.clone());
+        Ok(client)
+    }
+
+    /// Gets the connection of the client, which is used to send and receive packets.
+    pub fn connection(&mut self) -> &mut OfflineConnection<T> {
+        &mut self.connection
+    }
+
+    /// Gets the profile the server confirmed the player joined with.
+    pub fn profile(&self) -> &LoginSuccess {
+        &self.profile
+    }
+
+    /// Gets the username the server confirmed, which may differ from the one requested.
+    pub fn username(&self) -> &str {
+        self.profile.username.string()
+    }
+
+    /// Gets the UUID the server assigned to the player.
+    pub fn uuid(&self) -> Uuid {
+        self.profile.uuid
+    }
+
+    /// Sends `message` to the chat, or runs it as a command if it starts with a slash, in
+    /// the packet the server's version expects.
+    /// # Errors
+    /// This function will return an error as per `Connection::send_chat`.
+    pub fn send_chat(&mut self, message: &str) -> Result<(), io::Error> {
+        self.connection.send_chat(message)
+    }
+
+    /// Gets the players of the tab list, as of the last packet read with `next_packet`.
+    pub fn players(&self) -> &TabList {
+        &self.tab_list
+    }
+
+    /// Gets the objective the scoreboard displays in `display_slot`, such as the sidebar,
+    /// as of the last packet read with `next_packet`.
+    pub fn scoreboard(&self, display_slot: DisplaySlot) -> Option<&Objective> {
+        self.scoreboard.displayed(display_slot)
+    }
+
+    /// Gets every objective of the scoreboard, as of the last packet read with
+    /// `next_packet`.
+    pub fn objectives(&self) -> &Scoreboard {
+        &self.scoreboard
+    }
+
+    /// Gets the boss bars shown to the player, as of the last packet read with
+    /// `next_packet`.
+    pub fn bossbars(&self) -> &BossBars {
+        &self.boss_bars
+    }
+
+    /// Gets the title and the message above the hotbar shown to the player, as of the
+    /// last packet read with `next_packet` and the last tick.
+    pub fn titles(&self) -> &Titles {
+        &self.titles
+    }
+
+    /// Gets the state of the player, as of the last packet read with `next_packet`.
+    pub fn player(&self) -> &LocalPlayer {
+        &self.player
+    }
+
+    /// Gets the world around the player, as of the last packet read with `next_packet`.
+    pub fn world(&self) -> &World {
+        &self.world
+    }
+
+    /// Gets the entities in view of the player, as of the last packet read with
+    /// `next_packet`.
+    pub fn entities(&self) -> &EntityTracker {
+        &self.entities
+    }
+
+    /// Gets the world around the player mutably, such as to set its block registry.
+    pub fn world_mut(&mut self) -> &mut World {
+        &mut self.world
+    }
+
+    /// Gets the simulation of the player's movement.
+    pub fn physics(&self) -> &Physics {
+        &self.physics
+    }
+
+    /// Gets the navigator leading the player to the goal of `navigate_to`.
+    pub fn navigator(&self) -> &Navigator {
+        &self.navigator
+    }
+
+    /// Gets the player's inventory and the window open on top of it, as of the last packet
+    /// read with `next_packet`.
+    pub fn windows(&self) -> &Windows {
+        &self.windows
+    }
+
+    /// Reads the next Play packet from the server, and applies it to the state of the game
+    /// tracked by the client. When the server teleports the player, the teleport is
+    /// confirmed, and the player's new position sent back, before the packet is returned.
+    /// Clicks rejected by servers older than 1.17 are acknowledged likewise, resource packs
+    /// are answered as the connection's `resource_pack_policy` calls for, and the player
+    /// respawns if it died and `set_auto_respawn` is on. The packet is then handed to the
+    /// plugins of `add_plugin`. The packet is decoded and handled within the decode
+    /// limits of the stream, as per `DecodeLimits::enter`.
+    /// <br> <br>
+    /// If the connection is lost while reading and `set_reconnect` was called, the client
+    /// reconnects and joins again before reading on.
+    /// <br> <br>
+    /// A part of the state of the game, such as the world or the scoreboard, which cannot
+    /// be updated from the packet is left as it was, and the failure is handed to the
+    /// plugins' `on_tracking_error` and counted in `tracking_failures`. The rest of the
+    /// state is updated, and the packet handled and returned, all the same.
+    /// # Errors
+    /// This function will return an error if the packet cannot be read, such as when the
+    /// server disconnects the player, or an `InvalidData` error if it cannot be decoded.
+    /// A client which reconnects returns the error of its last attempt once its policy
+    /// gives up.
+    pub fn next_packet(&mut self) -> Result<Clientbound, io::Error> {
+        let _limits = self.connection.sock().decode_limits().enter();
+        let inbound = loop {
+            match self.connection.sock().read() {
+                Ok(inbound) => break inbound,
+                Err(err) if self.reconnection.as_ref().is_some_and(|reconnection| reconnects_after(&reconnection.policy, &err)) => self.reconnect()?,
+                Err(err) => return Err(err)
+            }
+        };
+        let packet = Clientbound::decode(inbound)?;
+        let version = self.connection.sock().version();
+        let mut failures = Vec::new();
+        let mut track = |result: Result<(), MCTypeError>| failures.extend(result.err());
+        self.tab_list.handle(&packet);
+        track(self.scoreboard.handle(&packet, version));
+        track(self.boss_bars.handle(&packet, version));
+        track(self.titles.handle(&packet, version));
+        track(self.world.handle(&packet));
+        track(self.entities.handle(&packet));
+        self.navigator.handle(&packet);
+        track(self.windows.handle(&packet));
+        self.tracking_failures += failures.len() as u64;
+        if let Clientbound::LegacyWindowConfirmation(confirmation) = &packet {
+            if !confirmation.accepted.value() {
+                self.connection.sock().send(&serverbound::LegacyWindowConfirmation {
+                    window_id: confirmation.window_id,
+                    action: confirmation.action,
+                    accepted: MCBoolean::from(true)
+                })?;
+            }
+        }
+        if let Clientbound::AcknowledgeBlockChange(ack) = &packet {
+            self.connection.block_sequence().acknowledge(ack);
+        }
+        if self.player.handle(&packet) {
+            self.physics.stop();
+            self.confirm_teleport()?;
+        }
+        let died = match &packet {
+            Clientbound::CombatDeath(death) => death.death(version)?.is_some(),
+            Clientbound::SetHealth(_) if !self.player.is_dead() => {
+                self.respawning = false;
+                false
+            },
+            Clientbound::SetHealth(_) => true,
+            _ => false
+        };
+        if died && self.auto_respawn && !self.respawning {
+            self.respawn()?;
+        }
+        if let Clientbound::ResourcePack(push) = &packet {
+            let pack = push.pack(version)?;
+            let policy = self.connection.resource_pack_policy();
+            self.connection.answer_resource_pack(&pack, policy)?;
+        }
+        if let Clientbound::CommandSuggestions(response) = &packet {
+            let mut suggestions = response.suggestions(version)?;
+            // Servers older than 1.13 answer requests in order, without numbering them.
+            let pending = match suggestions.transaction_id {
+                Some(id) => self.completions.iter().position(|pending| pending.transaction_id == id),
+                None => (!self.completions.is_empty()).then_some(0)
+            };
+            if let Some(pending) = pending.and_then(|index| self.completions.remove(index)) {
+                if suggestions.transaction_id.is_none() {
+                    (suggestions.start, suggestions.length) = pending.last_word;
+                }
+                (pending.handler)(&suggestions);
+            }
+        }
+        if let Clientbound::PluginMessage(message) = &packet {
+            let channel = message.channel.string();
+            if channel == brand_channel(version) {
+                self.server_brand = parse_brand(&message.data);
+            }
+            let stream = self.connection.sock();
+            for (_, handler) in self.plugin_handlers.iter_mut().filter(|(handled, _)| handled == channel) {
+                handler(stream, &message.data)?;
+            }
+        }
+        for err in &failures {
+            self.run_plugins(|plugin, client| plugin.on_tracking_error(client, &packet, err))?;
+        }
+        self.run_plugins(|plugin, client| plugin.on_packet(client, &packet))?;
+        if !self.plugins.is_empty() {
+            for event in Event::from_packet_for(&packet, version) {
+                if let Event::ChatReceived { sender, message, overlay: false, .. } = event {
+                    self.run_plugins(|plugin, client| plugin.on_chat(client, sender, &message))?;
+                }
+            }
+        }
+        self.export_metrics();
+        Ok(packet)
+    }
+
+    /// Reconnects with `connect` whenever the connection is lost while reading a packet, or
+    /// the server kicks the player if `policy` is `after_kick`, waiting between attempts as
+    /// `policy` calls for, and joins again the way the client
+    /// first joined, or with the session of `follow_session`. The state of the game is tracked anew, while the plugin message
+    /// handlers, the behaviors, the plugins, the tool and `set_auto_respawn` are kept. Completions still awaiting
+    /// their suggestions are dropped.
+    pub fn set_reconnect<F>(&mut self, policy: ReconnectPolicy, connect: F)
+    where F: FnMut() -> io::Result<OfflineConnection<T>> + 'static {
+        self.reconnection = Some(Reconnection { policy, connect: Box::new(connect) });
+    }
+
+    /// Joins again with the latest tokens of `session` whenever the client reconnects, such
+    /// as the session a `RefreshHandle` keeps renewed, rather than with the tokens it first
+    /// joined with, which may have expired meanwhile.
+    pub fn follow_session(&mut self, session: SharedSession) {
+        self.followed_session = Some(session);
+    }
+
+    /// Gets the number of times the client has reconnected.
+    pub fn reconnects(&self) -> u32 {
+        self.reconnects
+    }
+
+    /// Gets the number of times a part of the state of the game could not be updated from
+    /// a packet read with `next_packet`.
+    pub fn tracking_failures(&self) -> u64 {
+        self.tracking_failures
+    }
+
+    /// Gathers the metrics of the client: the traffic of its connection, the latency the
+    /// server measures and the time its ticks take.
+    pub fn metrics(&self) -> Metrics {
+        Metrics {
+            traffic: self.connection.metrics().clone(),
+            latency: self.tab_list.get(&self.uuid()).map(|entry| Duration::from_millis(entry.latency.max(0) as u64)),
+            ticks: self.ticks
+        }
+    }
+
+    /// Hands the metrics of the client to `callback` every `interval`, as checked whenever
+    /// a packet is read with `next_packet` or the client ticks, so that they can be
+    /// exported to a monitoring system or logged. The metrics are first handed over once
+    /// `interval` has passed.
+    pub fn on_metrics<F>(&mut self, interval: Duration, callback: F)
+    where F: FnMut(&Metrics) + 'static {
+        self.metrics_export = Some(MetricsExport { interval, last: Instant::now(), callback: Box::new(callback) });
+    }
+
+    /// Hands the metrics to the callback of `on_metrics` if its interval has passed.
+    fn export_metrics(&mut self) {
+        if self.metrics_export.as_ref().is_none_or(|export| export.last.elapsed() < export.interval) {
+            return;
+        }
+        let metrics = self.metrics();
+        if let Some(export) = &mut self.metrics_export {
+            export.last = Instant::now();
+            (export.callback)(&metrics);
+        }
+    }
+
+    /// Opens a new connection and joins again, as per `set_reconnect`.
+    fn reconnect(&mut self) -> Result<(), io::Error> {
+        let Some(mut reconnection) = self.reconnection.take() else {
+            return Ok(());
+        };
+        let username = self.username().to_owned();
+        let mut attempts = 0;
+        let mut client = loop {
+            thread::sleep(reconnection.policy.delay(attempts));
+            let session = self.followed_session.as_ref().map(SharedSession::get).or_else(|| self.session.clone());
+            let joined = (reconnection.connect)().and_then(|connection| match &session {
+                Some(session) => Client::login_online_with(connection, session),
+                None => Client::login_offline_with(connection, &username)
+            });
+            attempts += 1;
+            match joined {
+                Ok(client) => break client,
+                Err(err) if reconnection.policy.gives_up(attempts) => {
+                    self.reconnection = Some(reconnection);
+                    return Err(err);
+                },
+                Err(_) => ()
+            }
+        };
+
+        client.connection.set_resource_pack_policy(self.connection.resource_pack_policy());
+        client.plugin_handlers = mem::take(&mut self.plugin_handlers);
+        client.tool = self.tool;
+        client.auto_respawn = self.auto_respawn;
+        client.reconnection = Some(reconnection);
+        client.reconnects = self.reconnects + 1;
+        client.tracking_failures = self.tracking_failures;
+        client.ticks = self.ticks;
+        client.metrics_export = self.metrics_export.take();
+        client.behaviors = mem::take(&mut self.behaviors);
+        client.followed_session = self.followed_session.take();
+        client.plugins = mem::take(&mut self.plugins);
+        *self = client;
+        self.run_plugins(|plugin, client| plugin.on_connect(client))
+    }
+
+    /// Registers `handler` to be called with the data of each plugin message the server
+    /// sends on `channel`, such as `minecraft:brand`, as they are read with `next_packet`.
+    /// The handler is given the stream, so it may answer the server.
+    /// <br> <br>
+    /// An error returned by the handler is returned by `next_packet`.
+    pub fn on_plugin_message<F>(&mut self, channel: &str, handler: F)
+    where F: FnMut(&mut MinecraftStream<T>, &[u8]) -> io::Result<()> + 'static {
+        self.plugin_handlers.push((channel.to_string(), Box::new(handler)));
+    }
+
+    /// Sends `data` to the server on the plugin channel `channel`. The data is sent as is,
+    /// so it must be in the format the channel expects.
+    /// # Errors
+    /// This function will return an error if the packet cannot be sent.
+    pub fn send_plugin_message(&mut self, channel: &str, data: &[u8]) -> Result<(), io::Error> {
+        self.connection.sock().send(&PluginMessage { channel: channel.into(), data: data.to_vec() })
+    }
+
+    /// Asks the server to complete `text`, such as `/tp `, as when the player presses tab.
+    /// The `handler` is called with the server's suggestions once they are read with
+    /// `next_packet`. Returns the transaction ID of the request.
+    /// # Errors
+    /// This function will return an error if the packet cannot be sent.
+    pub fn tab_complete<F>(&mut self, text: &str, handler: F) -> Result<i32, io::Error>
+    where F: FnOnce(&Suggestions) + 'static {
+        let transaction_id = self.next_transaction_id;
+        self.next_transaction_id = self.next_transaction_id.wrapping_add(1);
+        let version = self.connection.sock().version();
+        self.connection.sock().send(&CommandSuggestionsRequest::new(version, transaction_id, text))?;
+
+        // Ranges are counted in UTF-16 code units, as Java strings are.
+        let (before, last_word) = text.split_at(text.rfind(' ').map_or(0, |space| space + 1));
+        let last_word = (before.encode_utf16().count() as i32, last_word.encode_utf16().count() as i32);
+        self.completions.push_back(PendingCompletion { transaction_id, last_word, handler: Box::new(handler) });
+        Ok(transaction_id)
+    }
+
+    /// Gets the brand the server reported, such as `vanilla` or `Paper`, if it has sent it.
+    pub fn server_brand(&self) -> Option<&str> {
+        self.server_brand.as_deref()
+    }
+
+    /// Whether the player respawns as soon as it dies, rather than waiting on the death
+    /// screen until `respawn` is called.
+    pub fn auto_respawn(&self) -> bool {
+        self.auto_respawn
+    }
+
+    /// Sets whether the player respawns as soon as it dies, which is off by default.
+    pub fn set_auto_respawn(&mut self, auto_respawn: bool) {
+        self.auto_respawn = auto_respawn;
+    }
+
+    /// Asks the server to respawn the player once it has died. The server then sends its
+    /// health back, along with the location it respawns at.
+    /// # Errors
+    /// This function will return an error if the packet cannot be sent.
+    pub fn respawn(&mut self) -> Result<(), io::Error> {
+        self.respawning = true;
+        self.connection.sock().send(&ClientCommand { action: ClientCommandAction::PerformRespawn })
+    }
+
+    /// Starts walking towards `x`, `y` and `z` in a straight line, rather than navigating.
+    /// The player moves a step each time `tick` is called, until it arrives.
+    pub fn walk_to(&mut self, x: f64, y: f64, z: f64) {
+        self.navigator.stop(&mut self.physics);
+        self.physics.walk_to(x, y, z);
+    }
+
+    /// Turns the head of the player to `yaw` and `pitch`, in degrees, which the server is
+    /// told of at the next tick.
+    pub fn look(&mut self, yaw: f32, pitch: f32) {
+        self.player.location.yaw = yaw;
+        self.player.location.pitch = pitch.clamp(-90.0, 90.0);
+    }
+
+    /// Makes the player jump at the next tick, if it stands on the ground.
+    pub fn jump(&mut self) {
+        self.physics.jump(&self.player);
+    }
+
+    /// Starts navigating to `goal`, the block the player's feet should end in, along a
+    /// path around the blocks in the way. The player moves a step each time `tick` is
+    /// called, until it arrives.
+    pub fn navigate_to(&mut self, goal: MCPosition) {
+        self.navigator.navigate_to(goal);
+    }
+
+    /// Moves the player to `location` at once, stopping it, and sends its new position.
+    /// Servers reject movements much further than the player could have walked in a tick.
+    /// # Errors
+    /// This function will return an error if the position cannot be sent.
+    pub fn set_position(&mut self, location: Location) -> Result<(), io::Error> {
+        self.player.location = location;
+        self.navigator.stop(&mut self.physics);
+        self.send_position()
+    }
+
+    /// Simulates a tick of the player's movement, and sends its position. Servers expect
+    /// a tick every `physics::TICK`, and consider players which do not move for a second
+    /// to have stopped sending their position. Digging, the attack cooldown and the time
+    /// titles are shown for progress with each tick as well, once the behaviors added with
+    /// `add_behavior` and the plugins of `add_plugin` have run.
+    /// # Example
+    /// ```no_run
+    /// use std::thread;
+    /// use mcclient::{Client, mc::physics::TICK};
+    ///
+    /// let mut client = Client::login_offline("localhost", 25565, "Makoto").expect("Could not join");
+    /// client.walk_to(10.5, 64.0, -3.5);
+    /// while client.physics().is_walking() {
+    ///     client.tick().expect("Disconnected");
+    ///     thread::sleep(TICK);
+    /// }
+    /// ```
+    /// # Errors
+    /// This function will return an error if the position cannot be sent.
+    pub fn tick(&mut self) -> Result<(), io::Error> {
+        let start = Instant::now();
+        self.run_behaviors()?;
+        self.run_plugins(|plugin, client| plugin.on_tick(client))?;
+        self.navigator.tick(&mut self.physics, &self.player, &self.world);
+        self.physics.tick(&mut self.player, &self.world);
+        self.send_position()?;
+        self.attack_cooldown.tick();
+        self.titles.tick();
+        if let Some(dig) = &mut self.digging {
+            dig.ticks_left = dig.ticks_left.saturating_sub(1);
+            if dig.ticks_left == 0 {
+                let Dig { position, face, .. } = *dig;
+                self.digging = None;
+                self.send_player_action(PlayerActionStatus::FinishedDigging, position, face)?;
+            }
+        }
+        self.ticks.record(start.elapsed());
+        self.export_metrics();
+        Ok(())
+    }
+
+    /// Adds `behavior`, which then acts on the client at the start of each tick, after
+    /// the behaviors added before it. Behaviors are kept when the client reconnects.
+    pub fn add_behavior<B: Behavior<T> + 'static>(&mut self, behavior: B) {
+        self.behaviors.push(Box::new(behavior));
+    }
+
+    /// Removes every behavior added with `add_behavior`.
+    pub fn clear_behaviors(&mut self) {
+        self.behaviors.clear();
+    }
+
+    /// Runs each behavior for a tick, keeping those they add meanwhile.
+    fn run_behaviors(&mut self) -> Result<(), io::Error> {
+        let mut behaviors = mem::take(&mut self.behaviors);
+        let result = behaviors.iter_mut().try_for_each(|behavior| behavior.tick(self));
+        behaviors.append(&mut self.behaviors);
+        self.behaviors = behaviors;
+        result
+    }
+
+    /// Adds `plugin`, whose hooks are then called as the client runs, after those of the
+    /// plugins added before it, starting with `on_connect`. Plugins are kept when the
+    /// client reconnects.
+    /// # Errors
+    /// This function will return the error of the `on_connect` hook of the plugin.
+    pub fn add_plugin<P: Plugin<T> + 'static>(&mut self, mut plugin: P) -> Result<(), io::Error> {
+        let connected = plugin.on_connect(self);
+        self.plugins.push(Box::new(plugin));
+        connected
+    }
+
+    /// Removes every plugin added with `add_plugin`.
+    pub fn clear_plugins(&mut self) {
+        self.plugins.clear();
+    }
+
+    /// Calls `hook` on each plugin, keeping those added meanwhile, and stopping at the
+    /// first error.
+    fn run_plugins<F>(&mut self, mut hook: F) -> Result<(), io::Error>
+    where F: FnMut(&mut dyn Plugin<T>, &mut Self) -> io::Result<()> {
+        let mut plugins = mem::take(&mut self.plugins);
+        let result = plugins.iter_mut().try_for_each(|plugin| hook(plugin.as_mut(), self));
+        plugins.append(&mut self.plugins);
+        self.plugins = plugins;
+        result
+    }
+
+    /// Gets the tool the player digs with, or `None` if it digs bare-handed.
+    pub fn tool(&self) -> Option<Tool> {
+        self.tool
+    }
+
+    /// Sets the tool the player digs with, which should be the one it holds.
+    pub fn set_tool(&mut self, tool: Option<Tool>) {
+        self.tool = tool;
+    }
+
+    /// Gets the block the player is digging, if any.
+    pub fn digging(&self) -> Option<&Dig> {
+        self.digging.as_ref()
+    }
+
+    /// Starts digging the block at `position`, on the face towards the player. The block
+    /// breaks after the time it takes to dig with the player's tool, counted in calls to
+    /// `tick`, or at once in creative mode. Digging another block cancels the last one.
+    /// <br> <br>
+    /// Returns `false` without digging if the block cannot be dug, such as bedrock. The
+    /// hardness of blocks which `Hardness::of` does not know is taken to be
+    /// `Hardness::UNKNOWN`, so the world's block registry should be set for known timings.
+    /// # Errors
+    /// This function will return an error if the packets cannot be sent.
+    pub fn dig_block(&mut self, position: MCPosition) -> Result<bool, io::Error> {
+        self.cancel_digging()?;
+        let hardness = self.world.block_at(position)
+            .and_then(|block| Hardness::of(&block.name))
+            .unwrap_or(Hardness::UNKNOWN);
+        let ticks = match self.player.is_creative() {
+            true => Some(0),
+            false => hardness.dig_ticks(self.tool, self.player.on_ground)
+        };
+        let Some(ticks) = ticks else {
+            return Ok(false);
+        };
+
+        let location = self.player.location;
+        let face = BlockFace::towards(position, location.x, location.y + EYE_HEIGHT, location.z);
+        self.send_player_action(PlayerActionStatus::StartedDigging, position, face)?;
+        self.swing_arm()?;
+        if ticks > 0 {
+            self.digging = Some(Dig { position, face, ticks_left: ticks });
+        }
+        Ok(true)
+    }
+
+    /// Stops digging the block the player is digging, if any, which the server then
+    /// restores.
+    /// # Errors
+    /// This function will return an error if the packet cannot be sent.
+    pub fn cancel_digging(&mut self) -> Result<(), io::Error> {
+        match self.digging.take() {
+            Some(dig) => self.send_player_action(PlayerActionStatus::CancelledDigging, dig.position, dig.face),
+            None => Ok(())
+        }
+    }
+
+    /// Places the block held in the main hand against the `face` of the block at
+    /// `position`, or uses the held item on it. Whether the block was placed is told by
+    /// the block changes which follow the acknowledgement of the server.
+    /// # Errors
+    /// This function will return an `Unsupported` error for servers older than 1.14, or
+    /// an error if the packets cannot be sent.
+    pub fn place_block(&mut self, position: MCPosition, face: BlockFace) -> Result<(), io::Error> {
+        let version = self.connection.sock().version();
+        let sequence = self.connection.block_sequence().advance();
+        self.connection.sock().send(&UseItemOn::new(version, Hand::MainHand, position, face, sequence))?;
+        self.swing_arm()
+    }
+
+    /// Clicks `slot` of the open window, or of the player's inventory if none is open, with
+    /// `action`. The outcome of the click is predicted at once, and corrected by the packets
+    /// the server sends back if it disagrees. Clicking `windows::OUTSIDE` drops the
+    /// carried items.
+    /// # Errors
+    /// This function will return an `InvalidInput` error if the window has no such slot,
+    /// or an error if the click cannot be sent.
+    pub fn click_slot(&mut self, slot: i16, action: ClickAction) -> Result<(), io::Error> {
+        let click = self.windows.click(slot, action)
+            .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, format!("The window has no slot {}.", slot)))?;
+        self.connection.sock().send(&click)
+    }
+
+    /// Closes the open window, or the player's inventory if none is open.
+    /// # Errors
+    /// This function will return an error if the packet cannot be sent.
+    pub fn close_window(&mut self) -> Result<(), io::Error> {
+        let window_id = self.windows.close();
+        self.connection.sock().send(&CloseContainer { window_id: MCUnsignedByte::from(window_id) })
+    }
+
+    /// Gets the cooldown of the player's attacks, as of the last call to `tick`.
+    pub fn attack_cooldown(&self) -> &AttackCooldown {
+        &self.attack_cooldown
+    }
+
+    /// Gets the cooldown of the player's attacks mutably, such as to set the attack speed
+    /// of the held weapon.
+    pub fn attack_cooldown_mut(&mut self) -> &mut AttackCooldown {
+        &mut self.attack_cooldown
+    }
+
+    /// Attacks the entity `entity_id` with the main hand, swinging the arm, and restarts
+    /// the attack cooldown. Since 1.9, the attack deals less damage if the cooldown has not
+    /// passed, which bots should wait for with `attack_cooldown().is_ready()`.
+    /// # Errors
+    /// This function will return an error if the packets cannot be sent.
+    pub fn attack(&mut self, entity_id: i32) -> Result<(), io::Error> {
+        let version = self.connection.sock().version();
+        self.connection.sock().send(&Interact::attack(version, entity_id, false))?;
+        self.swing_arm()?;
+        self.attack_cooldown.reset();
+        Ok(())
+    }
+
+    /// Interacts with the entity `entity_id` using the main hand, such as to trade with a
+    /// villager or to ride a horse.
+    /// # Errors
+    /// This function will return an error if the packet cannot be sent.
+    pub fn interact(&mut self, entity_id: i32) -> Result<(), io::Error> {
+        let version = self.connection.sock().version();
+        self.connection.sock().send(&Interact::using(version, entity_id, Hand::MainHand, false))
+    }
+
+    /// Swings the arm of the main hand, which 1.8 servers are not told of.
+    /// # Errors
+    /// This function will return an error if the packet cannot be sent.
+    pub fn swing_arm(&mut self) -> Result<(), io::Error> {
+        match registry::id_of::<SwingArm>(self.connection.sock().version(), Direction::Serverbound) {
+            Some(_) => self.connection.sock().send(&SwingArm::new(Hand::MainHand)),
+            None => Ok(())
+        }
+    }
+
+    /// Sends the Player Action `status` on the block at `position`, stamped with the next
+    /// block action sequence number.
+    fn send_player_action(&mut self, status: PlayerActionStatus, position: MCPosition, face: BlockFace) -> Result<(), io::Error> {
+        let version = self.connection.sock().version();
+        let sequence = self.connection.block_sequence().advance();
+        self.connection.sock().send(&PlayerAction::new(version, status, position, face, sequence))
+    }
+
+    /// Confirms the last teleport of the player, which 1.8 does not number, and sends its
+    /// position, which the server holds the player at until then.
+    fn confirm_teleport(&mut self) -> Result<(), io::Error> {
+        if let Some(teleport_id) = self.player.teleport_id() {
+            self.connection.sock().write(&ConfirmTeleportation { teleport_id: teleport_id.into() })?;
+        }
+        self.send_position()
+    }
+
+    /// Sends the position and rotation of the player.
+    fn send_position(&mut self) -> Result<(), io::Error> {
+        let location = &self.player.location;
+        self.connection.sock().send(&SetPlayerPositionAndRotation {
+            x: MCDouble::from(location.x),
+            feet_y: MCDouble::from(location.y),
+            z: MCDouble::from(location.z),
+            yaw: MCFloat::from(location.yaw),
+            pitch: MCFloat::from(location.pitch),
+            on_ground: MCBoolean::from(self.player.on_ground)
+        })
+    }
+}
+
+/// Whether a client reconnecting as `policy` calls for reconnects after reading failed with
+/// `err`: when the server kicked the player only if the policy says so, and otherwise when
+/// the connection was lost, rather than a packet being malformed or a read timing out.
+pub(crate) fn reconnects_after(policy: &ReconnectPolicy, err: &io::Error) -> bool {
+    if err.get_ref().is_some_and(|inner| inner.is::<DisconnectReason>()) {
+        return policy.after_kick;
+    }
+    matches!(err.kind(),
+        io::ErrorKind::UnexpectedEof | io::ErrorKind::ConnectionAborted | io::ErrorKind::ConnectionReset | io::ErrorKind::BrokenPipe
+        | io::ErrorKind::NotConnected)
+}
+
+/// Decodes the brand sent in the data of a plugin message on the brand channel.
+fn parse_brand(data: &[u8]) -> Option<String> {
+    MCString::read_bounded(&mut io::Cursor::new(data), 32767).ok().map(|brand| brand.string().clone())
+}
@@ -1,44 +1,311 @@
-use std::{net::{TcpStream, ToSocketAddrs}, io::{self, Write, Read, BufWriter, BufReader}};
+use std::{net::{TcpStream, ToSocketAddrs}, io::{self, BufReader, Read}, path::Path, time::{SystemTime, UNIX_EPOCH}};
 
-use super::{packet::{clientbound::{status_response::StatusResponse, login_success::LoginSuccess, ping_response::PingResponse}, serialize_packet, serverbound::{handshake::{Handshake, NextState}, status_request::StatusRequest}, OutboundPacket, MCPacket, InboundPacket}, mctypes::VarInt, PROTOCOL_VERSION};
+use bytes::Bytes;
+use flate2::{Compress, Compression, Decompress, FlushCompress, FlushDecompress, Status};
+use uuid::Uuid;
 
-/// Describes a two-way TCP connection to a Minecraft server. The internal
-/// buffer bytes are handled by a high-level serdes which encapsulates the
-/// Minecraft packets. No byte manipulation is necessary to send packets
-/// using a MinecraftStream.
-pub struct MinecraftStream {
-    writer: BufWriter<TcpStream>,
-    reader: BufReader<TcpStream>,
+use super::{auth::{self, AuthSession}, capture::Capture, dns, limits::DecodeLimits, metrics::{CountingReader, TrafficMetrics}, pool::BufferPool, encryption::{self, StreamCipher, SHARED_SECRET_LENGTH}};
+
+use super::packets::configuration::{self, clientbound::RegistryTags, serverbound::{AcknowledgeFinishConfiguration, ConfigKeepAlive, ConfigPluginMessage, ConfigResourcePackResponse}};
+
+use super::{packet::{clientbound::{status_response::StatusResponse, login_success::LoginSuccess, ping_response::PingResponse, acknowledge_block_change::AcknowledgeBlockChange, set_compression::SetCompression, keep_alive::KeepAlive, encryption_request::EncryptionRequest, login_plugin_request::LoginPluginRequest, login_play::LoginPlay}, write_packet, write_compressed_packet_at, serverbound::{handshake::{Handshake, NextState}, status_request::StatusRequest, ping_request::PingRequest, login_start::LoginStart, login_acknowledged::LoginAcknowledged, login_plugin_response::LoginPluginResponse, encryption_response::EncryptionResponse, update_sign::UpdateSign, resource_pack_response::{ResourcePackResponse, ResourcePackResult}, player_command::{PlayerCommand, PlayerCommandAction}, chat_message::ChatMessage, chat_command::ChatCommand, legacy_chat_message::LegacyChatMessage, keep_alive::KeepAlive as ServerboundKeepAlive, plugin_message::PluginMessage}, OutboundPacket, MCPacket, InboundPacket, PacketId}, mctypes::{VarInt, MCType, MCPosition, Identifier}, error::DisconnectReason, proxy::ProxyConfig, resource_pack::{ResourcePackEvent, ResourcePackPolicy}, transport::Transport, version::ProtocolVersion, registry::{self, Direction, Versioned, UNMAPPED_ID_BASE}, PROTOCOL_VERSION};
+
+/// The most characters a chat message or command may have.
+pub const MAX_CHAT_LENGTH: usize = 256;
+
+/// The protocol state of a connection, which determines how packet IDs are
+/// interpreted. Every connection begins in `Handshaking`, and moves to `Status`
+/// or `Login` depending on the handshake sent. Since 1.20.2, a successful login
+/// moves to `Configuration` before entering `Play`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ConnectionState {
+    #[default]
+    Handshaking,
+    Status,
+    Login,
+    Configuration,
+    Play
 }
 
+impl ConnectionState {
+    /// Gets the canonical ID of the clientbound Disconnect packet of this state, if the
+    /// state has one. Packets received in other versions must be translated to their
+    /// canonical IDs with `registry::canonical_id` before being compared with it.
+    pub fn disconnect_packet_id(&self) -> Option<i32> {
+        match self {
+            ConnectionState::Handshaking | ConnectionState::Status => None,
+            ConnectionState::Login => Some(0x00),
+            ConnectionState::Configuration => Some(0x01),
+            ConnectionState::Play => Some(0x17),
+        }
+    }
+}
 
-impl MinecraftStream {
-    pub fn connect<T: ToSocketAddrs>(addr: T) -> Result<Self, io::Error> {
-        let stream = TcpStream::connect(addr)?;
-        
-        let writer = BufWriter::new(stream.try_clone().unwrap());
-        let reader = BufReader::new(stream);
+/// Describes a two-way connection to a Minecraft server, over TCP unless another
+/// `Transport` is given. The internal buffer bytes are handled by a high-level
+/// serdes which encapsulates the Minecraft packets. No byte manipulation is
+/// necessary to send packets using a MinecraftStream.
+/// <br> <br>
+/// The stream tracks the protocol state of the connection, refusing to send packets
+/// which belong to another state and switching state on the packets which do so.
+/// <br> <br>
+/// Packets are exchanged under the IDs of the stream's protocol version, which is
+/// `PROTOCOL_VERSION` unless set otherwise. Typed packets are translated to and from
+/// their canonical IDs as per `registry`, so the same structures serve every version.
+pub struct MinecraftStream<T = TcpStream> {
+    reader: BufReader<T>,
+    outbound: Vec<u8>,
+    codec: StreamCodec
+}
+
+/// The protocol state of a stream, which frames outbound packets and interprets inbound
+/// packets. This is shared by `MinecraftStream` and its async counterpart, which only
+/// differ in how bytes are moved.
+pub(crate) struct StreamCodec {
+    pub state: ConnectionState,
+    pub version: ProtocolVersion,
+    pub cipher: Option<StreamCipher>,
+    pub compression_threshold: Option<i32>,
+    pub compression_level: u32,
+    pub auto_keep_alive: bool,
+    pub capture: Option<Capture>,
+    pub metrics: TrafficMetrics,
+    pub pool: BufferPool,
+    pub limits: DecodeLimits,
+    inflater: Decompress,
+    /// The compressor of outbound packets, along with the level it compresses at.
+    deflater: Option<(u32, Compress)>
+}
+
+impl StreamCodec {
+    pub fn new() -> Self {
+        StreamCodec { state: ConnectionState::default(), version: PROTOCOL_VERSION.into(), cipher: None, compression_threshold: None, compression_level: Compression::default().level(), auto_keep_alive: true, capture: None, metrics: TrafficMetrics::default(), pool: BufferPool::new(), limits: DecodeLimits::default(), inflater: Decompress::new(true), deflater: None }
+    }
+
+    /// Frames `packet` onto `outbound` as per `MinecraftStream::write`.
+    pub fn frame(&mut self, packet: &dyn OutboundPacket, outbound: &mut Vec<u8>) -> Result<(), io::Error> {
+        if let Some(state) = packet.state().filter(|state| *state != self.state) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("Packet 0x{:02X} belongs to the {:?} state, but the connection is in the {:?} state.", packet.packet_id(), state, self.state)
+            ));
+        }
+
+        let id = packet.packet_id();
+        let raw_id = match packet.state() {
+            Some(_) => registry::packet_id(self.version, self.state, Direction::Serverbound, id)
+                .ok_or_else(|| io::Error::new(
+                    io::ErrorKind::Unsupported,
+                    format!("Packet 0x{:02X} does not exist in protocol version {}.", id, self.version.number())
+                ))?,
+            None => id
+        };
+        let versioned = Versioned { packet, id: raw_id, version: self.version };
+        let packet: &dyn OutboundPacket = &versioned;
+
+        let start = outbound.len();
+        match self.compression_threshold {
+            Some(threshold) => self.frame_compressed(packet, threshold, outbound)?,
+            None => write_packet(outbound, packet)?
+        }
+        let data_length = packet.len() + VarInt::from(packet.packet_id()).size();
+        self.metrics.record_sent(self.state, id, data_length, outbound.len() - start);
+        if let Some(capture) = &mut self.capture {
+            capture.record(Direction::Serverbound, packet)?;
+        }
+        if let Some(state) = packet.next_state() {
+            self.state = state;
+        }
+
+        Ok(())
+    }
+
+    /// Encrypts `outbound` in place if the stream is encrypted.
+    pub fn seal(&mut self, outbound: &mut [u8]) {
+        if let Some(cipher) = &mut self.cipher {
+            cipher.encrypt(outbound);
+        }
+    }
+
+    /// Frames `packet` onto `outbound` as per `write_compressed_packet_at`, compressing it
+    /// in buffers of the pool with a compressor kept across packets.
+    fn frame_compressed(&mut self, packet: &dyn OutboundPacket, threshold: i32, outbound: &mut Vec<u8>) -> Result<(), io::Error> {
+        let packet_id = VarInt::from(packet.packet_id());
+        let data_length = packet.len() + packet_id.size();
+        if data_length < threshold {
+            return write_compressed_packet_at(outbound, packet, threshold, self.compression_level);
+        }
+
+        let mut data = self.pool.take(data_length as usize);
+        packet_id.encode(&mut data)?;
+        packet.encode(&mut data)?;
+        let mut compressed = self.pool.take(data.len() / 2 + 64);
+
+        let level = self.compression_level;
+        let deflater = match &mut self.deflater {
+            Some((deflater_level, deflater)) if *deflater_level == level => {
+                deflater.reset();
+                deflater
+            },
+            deflater => &mut deflater.insert((level, Compress::new(Compression::new(level.min(9)), true))).1
+        };
+        loop {
+            let consumed = deflater.total_in() as usize;
+            match deflater.compress_vec(&data[consumed..], &mut compressed, FlushCompress::Finish)? {
+                Status::StreamEnd => break,
+                _ => compressed.reserve(compressed.capacity().max(64))
+            }
+        }
+
+        let data_length = VarInt::from(data_length);
+        VarInt::from(data_length.size() + compressed.len() as i32).encode(outbound)?;
+        data_length.encode(outbound)?;
+        outbound.extend_from_slice(&compressed);
+        self.pool.give(data);
+        self.pool.give(compressed);
+        Ok(())
+    }
+
+    /// Reads the body of the next frame from the decrypted `reader` into a buffer of `pool`,
+    /// along with the number of bytes the frame spans with its length prefix. Frames
+    /// longer than `limits` allow are rejected.
+    pub fn read_frame(pool: &mut BufferPool, limits: &DecodeLimits, reader: &mut dyn Read) -> Result<(Vec<u8>, usize), io::Error> {
+        let mut reader = CountingReader { reader, count: 0 };
+        let length = limits.packet_size(VarInt::read_from(&mut reader)?.value())
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+
+        let mut frame = pool.take(length);
+        frame.resize(length, 0);
+        if let Err(err) = reader.read_exact(&mut frame) {
+            pool.give(frame);
+            return Err(err);
+        }
+        Ok((frame, reader.count))
+    }
+
+    /// Unpacks the body of a frame into its packet, decompressing it in a buffer of the
+    /// pool if the stream is compressed. The packet keeps the buffer it is unpacked in,
+    /// while a compressed frame is given back to the pool.
+    pub fn unpack(&mut self, frame: Vec<u8>) -> Result<MCPacket, io::Error> {
+        if self.compression_threshold.is_none() {
+            return Ok(MCPacket::from_frame(Bytes::from(frame))?);
+        }
+
+        let (data_length, rest) = VarInt::parse(&frame)?;
+        let data_length = data_length.value();
+        if data_length == 0 {
+            let offset = frame.len() - rest.len();
+            return Ok(MCPacket::from_frame(Bytes::from(frame).slice(offset..))?);
+        }
+        let data_length = match self.limits.uncompressed_size(data_length) {
+            Ok(data_length) => data_length,
+            Err(err) => {
+                self.pool.give(frame);
+                return Err(io::Error::new(io::ErrorKind::InvalidData, err));
+            }
+        };
+
+        let mut data = self.pool.take(data_length);
+        self.inflater.reset(true);
+        let offset = frame.len() - rest.len();
+        let result = self.inflater.decompress_vec(&frame[offset..], &mut data, FlushDecompress::Finish);
+        self.pool.give(frame);
+        let error = match result {
+            Ok(Status::StreamEnd) if data.len() == data_length => None,
+            Ok(_) => Some(format!("Packet does not decompress to the {} bytes it claims.", data_length)),
+            Err(err) => Some(format!("Could not decompress packet: {}", err))
+        };
+        if let Some(error) = error {
+            self.pool.give(data);
+            return Err(io::Error::new(io::ErrorKind::InvalidData, error));
+        }
+        Ok(MCPacket::from_frame(Bytes::from(data))?)
+    }
+
+    /// Frames the answer to `packet` onto `outbound` if it is a Keep Alive of the Play or
+    /// Configuration state which the stream answers automatically. Returns whether an
+    /// answer was framed, in which case it should be flushed right away.
+    pub fn answer_keep_alive(&mut self, packet: &MCPacket, outbound: &mut Vec<u8>) -> Result<bool, io::Error> {
+        if !self.auto_keep_alive {
+            return Ok(false);
+        }
+
+        if self.state == ConnectionState::Play && packet.is::<KeepAlive>() {
+            let keep_alive = KeepAlive::from_data(packet)?;
+            self.frame(&ServerboundKeepAlive { keep_alive_id: keep_alive.keep_alive_id }, outbound)?;
+        } else if self.state == ConnectionState::Configuration && packet.is::<configuration::clientbound::ConfigKeepAlive>() {
+            let keep_alive = configuration::clientbound::ConfigKeepAlive::from_data(packet)?;
+            self.frame(&ConfigKeepAlive { keep_alive_id: keep_alive.keep_alive_id }, outbound)?;
+        } else {
+            return Ok(false);
+        }
+
+        Ok(true)
+    }
+
+    /// Interprets an inbound `packet`, framed in `wire_length` bytes, as per
+    /// `MinecraftStream::read`, translating its ID, surfacing a Disconnect and switching
+    /// state on a Login Success.
+    pub fn accept(&mut self, packet: MCPacket, wire_length: usize) -> Result<MCPacket, io::Error> {
+        if let Some(capture) = &mut self.capture {
+            capture.record(Direction::Clientbound, &packet)?;
+        }
+        let id = packet.header.id.value();
+        let packet = match registry::canonical_id(self.version, self.state, Direction::Clientbound, id) {
+            Some(canonical) if canonical == id => packet,
+            Some(canonical) => MCPacket::new(canonical, packet.data),
+            None => MCPacket::new(UNMAPPED_ID_BASE + id, packet.data)
+        };
+        let data_length = packet.data.len() as i32 + VarInt::from(id).size();
+        self.metrics.record_received(self.state, packet.header.id.value(), data_length, wire_length);
+        if self.state.disconnect_packet_id() == Some(packet.header.id.value()) {
+            return Err(DisconnectReason::decode(self.state, self.version, &packet.data)?.into());
+        }
+        if self.state == LoginSuccess::STATE && packet.is::<LoginSuccess>() && self.version < ProtocolVersion::V1_20_2 {
+            self.state = ConnectionState::Play;
+        }
+
+        Ok(packet)
+    }
+}
+
+impl MinecraftStream<TcpStream> {
+    pub fn connect<A: ToSocketAddrs>(addr: A) -> Result<Self, io::Error> {
+        Ok(MinecraftStream::new(TcpStream::connect(addr)?))
+    }
+}
 
-        Ok(MinecraftStream{ writer, reader })
+impl<T: Transport> MinecraftStream<T> {
+    /// Creates a stream which exchanges packets over `transport`.
+    pub fn new(transport: T) -> Self {
+        MinecraftStream{ reader: BufReader::new(transport), outbound: Vec::new(), codec: StreamCodec::new() }
     }
 
-    /// Writes to the TCP outbound buffer. This should be used in tandem with
+    /// Writes to the outbound buffer. This should be used in tandem with
     /// `flush()` to send the outbound data to the target server. If you want
     /// to abstract this behavior, use `send(&mut self, packet: &dyn OutboundPacket)`.
+    /// <br> <br>
+    /// Packets which switch the protocol state, such as a `Handshake`, move the
+    /// stream to the next state as soon as they are written.
+    /// Typed packets are sent under their ID in the stream's protocol version, while
+    /// packets which do not declare a state, such as a raw `MCPacket`, are sent as-is.
     /// # Errors
-    /// An `io::Error` of any kind will be returned if the packet cannot be sent.
+    /// An `io::Error` of kind `InvalidInput` will be returned if the packet belongs to a
+    /// different protocol state than the stream is in, and of kind `Unsupported` if the
+    /// packet does not exist in the stream's protocol version, in which case nothing is
+    /// written. An `io::Error` of any other kind will be returned if the packet cannot be sent.
     pub fn write(&mut self, packet: &dyn OutboundPacket) -> Result<(), io::Error> {
-        Ok(self.writer.write_all(&serialize_packet(packet))?)
+        self.codec.frame(packet, &mut self.outbound)
     }
 
-    /// Writes to the TCP outbound buffer, and flushes the buffer.
+    /// Writes to the outbound buffer, and flushes the buffer.
     /// # Errors
     /// An `io::Error` of any kind will be returned if the packet cannot be sent or the
     /// stream cannot be flushed.
     pub fn send(&mut self, packet: &dyn OutboundPacket) -> Result<(), io::Error> {
-        self.writer.write_all(&serialize_packet(packet))?;
-        self.writer.flush()?;
-        Ok(())
+        self.write(packet)?;
+        self.flush()
     }
 
     /// Flushes the outbound stream.
@@ -46,26 +313,223 @@ impl MinecraftStream {
     /// An `io::Error` of any kind will be returned if the stream cannot be flushed, i.e.,
     /// the bytes cannot be sent to the target server.
     pub fn flush(&mut self) -> Result<(), io::Error> {
-        self.writer.flush()
+        self.codec.seal(&mut self.outbound);
+
+        let transport = self.reader.get_mut();
+        transport.write_all(&self.outbound)?;
+        self.outbound.clear();
+        transport.flush()
     }
 
+    /// Reads the next packet from the inbound stream, blocking until it has been
+    /// received in full.
+    /// # Errors
+    /// An `io::Error` of kind `UnexpectedEof` will be returned if the server closes the
+    /// connection before a full packet arrives, of kind `InvalidData` if the received
+    /// bytes are not a well-formed packet, and of kind `ConnectionAborted` if the server
+    /// sends a Disconnect packet for the current state, carrying its `DisconnectReason`
+    /// as per `ClientError::from`.
+    /// <br> <br>
+    /// Packets which switch the protocol state, such as a `LoginSuccess`, move the
+    /// stream to the next state as soon as they are read. Since 1.20.2, the client moves
+    /// on from Login itself, by sending a `LoginAcknowledged`.
+    /// <br> <br>
+    /// The packet read carries its canonical ID, so it can be matched against the typed
+    /// packets with `is`. Packets without a canonical counterpart carry their raw ID
+    /// offset by `UNMAPPED_ID_BASE`.
+    /// <br> <br>
+    /// Keep Alives are answered as soon as they are read, as per `set_auto_keep_alive`,
+    /// and are returned as well.
     pub fn read(&mut self) -> Result<MCPacket, io::Error> {
-        const MAX_HEADER_SIZE: usize = 6;
-        let mut header_buf: [u8; MAX_HEADER_SIZE] = [0; MAX_HEADER_SIZE];
-        let bytes_read = self.reader.read(&mut header_buf)?;
-
-        let mut received: Vec<u8> = header_buf.to_vec();
-        
-        let len = VarInt::from_bytes(&received)?.value() as usize;
-        let mut total_bytes_read = bytes_read;
-        let mut buf: [u8; 256] = [0; 256];
-        while total_bytes_read < len {
-            let bytes_read = self.reader.read(&mut buf)?;
-            received.extend_from_slice(&buf[..bytes_read]);
-            total_bytes_read += bytes_read;
+        let codec = &mut self.codec;
+        let (frame, wire_length) = match &mut codec.cipher {
+            Some(cipher) => StreamCodec::read_frame(&mut codec.pool, &codec.limits, &mut cipher.reader(&mut self.reader))?,
+            None => StreamCodec::read_frame(&mut codec.pool, &codec.limits, &mut self.reader)?
         };
 
-        MCPacket::from_bytes(&mut received)
+        let packet = codec.unpack(frame)?;
+        let packet = self.codec.accept(packet, wire_length)?;
+        if self.codec.answer_keep_alive(&packet, &mut self.outbound)? {
+            self.flush()?;
+        }
+
+        Ok(packet)
+    }
+
+    /// Whether the stream answers the Keep Alives of the server as soon as they are read,
+    /// which it does unless disabled.
+    pub fn auto_keep_alive(&self) -> bool {
+        self.codec.auto_keep_alive
+    }
+
+    /// Sets whether the stream answers the Keep Alives of the server as soon as they are
+    /// read. Servers disconnect clients which do not answer in time, so this should only
+    /// be disabled to answer them by hand, or for testing.
+    pub fn set_auto_keep_alive(&mut self, enabled: bool) {
+        self.codec.auto_keep_alive = enabled;
+    }
+
+    /// Gets the protocol state the stream is in.
+    pub fn state(&self) -> ConnectionState {
+        self.codec.state
+    }
+
+    /// Gets the protocol version the stream exchanges packets in.
+    pub fn version(&self) -> ProtocolVersion {
+        self.codec.version
+    }
+
+    /// Sets the protocol version the stream exchanges packets in. This should be set
+    /// before the handshake, which announces the version to the server.
+    pub fn set_version(&mut self, version: ProtocolVersion) {
+        self.codec.version = version;
+    }
+
+    /// Encrypts the stream in both directions with `shared_secret`, as done once the
+    /// client has sent an Encryption Response. Packets written before encryption is
+    /// enabled are flushed unencrypted first.
+    /// # Errors
+    /// An `io::Error` of any kind will be returned if the pending packets cannot be flushed.
+    pub fn enable_encryption(&mut self, shared_secret: &[u8; SHARED_SECRET_LENGTH]) -> Result<(), io::Error> {
+        self.flush()?;
+        self.codec.cipher = Some(StreamCipher::new(shared_secret));
+        Ok(())
+    }
+
+    /// Whether the stream is encrypted.
+    pub fn is_encrypted(&self) -> bool {
+        self.codec.cipher.is_some()
+    }
+
+    /// Frames packets in both directions in the compressed format, compressing outbound
+    /// packets of at least `threshold` bytes, as done once the server has sent Set
+    /// Compression. A `threshold` of `None` returns to the uncompressed format.
+    pub fn set_compression(&mut self, threshold: Option<i32>) {
+        self.codec.compression_threshold = threshold;
+    }
+
+    /// Gets the compression threshold of the stream, or `None` if packets are not compressed.
+    pub fn compression_threshold(&self) -> Option<i32> {
+        self.codec.compression_threshold
+    }
+
+    /// Sets the zlib level outbound packets are compressed at once compression is enabled,
+    /// from 0 (stored) to 9 (smallest). Lower levels spend less time compressing.
+    pub fn set_compression_level(&mut self, level: u32) {
+        self.codec.compression_level = level.min(9);
+    }
+
+    /// Gets the limits the frames read from the stream are checked against.
+    pub fn decode_limits(&self) -> DecodeLimits {
+        self.codec.limits
+    }
+
+    /// Checks the frames read from the stream from now on against `limits`. The fields of
+    /// the packets read are checked against the limits entered on the decoding thread, as
+    /// per `DecodeLimits::enter`.
+    pub fn set_decode_limits(&mut self, limits: DecodeLimits) {
+        self.codec.limits = limits;
+    }
+
+    /// Sets the protocol state of the stream. Packets which switch state update it
+    /// automatically, so this is only needed to override the state machine.
+    pub fn set_state(&mut self, state: ConnectionState) {
+        self.codec.state = state;
+    }
+
+    /// Records every packet exchanged from now on to a dump at `path`, decrypted and
+    /// decompressed, which `capture::replay` decodes offline. A recording already running
+    /// is stopped first.
+    /// # Errors
+    /// An `io::Error` of any kind will be returned if the dump cannot be created.
+    pub fn start_capture<P: AsRef<Path>>(&mut self, path: P) -> Result<(), io::Error> {
+        self.stop_capture()?;
+        self.codec.capture = Some(Capture::create(path, self.codec.version, self.codec.state)?);
+        Ok(())
+    }
+
+    /// Gets the traffic the stream has exchanged since it was opened.
+    pub fn metrics(&self) -> &TrafficMetrics {
+        &self.codec.metrics
+    }
+
+    /// Gets the pool of buffers the stream reads, decompresses and compresses packets in,
+    /// whose statistics show how well its limits suit the traffic.
+    pub fn buffer_pool(&self) -> &BufferPool {
+        &self.codec.pool
+    }
+
+    /// Gets the pool of buffers of the stream mutably, to change its limits.
+    pub fn buffer_pool_mut(&mut self) -> &mut BufferPool {
+        &mut self.codec.pool
+    }
+
+    /// Hands the buffer of `packet`, read off of the stream, back to its pool once the
+    /// packet is done with, so that the next packet can be read without allocating. The
+    /// buffer is dropped instead if a typed packet decoded from `packet` still refers to it.
+    pub fn recycle(&mut self, packet: MCPacket) {
+        self.codec.pool.recycle(packet.data);
+    }
+
+    /// Stops recording packets, writing out the dump.
+    /// # Errors
+    /// An `io::Error` of any kind will be returned if the dump cannot be written.
+    pub fn stop_capture(&mut self) -> Result<(), io::Error> {
+        match self.codec.capture.take() {
+            Some(mut capture) => capture.flush(),
+            None => Ok(())
+        }
+    }
+}
+
+/// Tracks the sequence numbers stamped on block actions (digging, placing, using
+/// items) since 1.19. Every outbound block action takes the next number in the
+/// sequence, and the server confirms actions with an Acknowledge Block Change
+/// carrying the latest sequence number it has processed. Block predictions made
+/// by the client should only be trusted once their sequence has been acknowledged.
+/// <br>
+/// The sequence starts at 0 for every new connection and play session.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct BlockSequence {
+    current: i32,
+    acknowledged: i32
+}
+
+impl BlockSequence {
+    /// The first protocol version (1.19) stamping block actions with a sequence number.
+    pub const SEQUENCED_SINCE: i32 = 759;
+
+    /// Advances the sequence and returns the number to stamp on the next outbound
+    /// block action.
+    pub fn advance(&mut self) -> VarInt {
+        self.current = self.current.wrapping_add(1);
+        VarInt::from(self.current)
+    }
+
+    /// Gets the sequence number of the most recent outbound block action.
+    pub fn current(&self) -> i32 {
+        self.current
+    }
+
+    /// Gets the latest sequence number acknowledged by the server.
+    pub fn acknowledged(&self) -> i32 {
+        self.acknowledged
+    }
+
+    /// Whether any block actions have been sent which the server has not yet acknowledged.
+    pub fn is_pending(&self) -> bool {
+        self.acknowledged != self.current
+    }
+
+    /// Records an Acknowledge Block Change received from the server. Acknowledgements
+    /// older than one already received are ignored.
+    pub fn acknowledge(&mut self, ack: &AcknowledgeBlockChange) {
+        self.acknowledged = self.acknowledged.max(ack.sequence_id.value());
+    }
+
+    /// Resets the sequence to 0, as the server expects upon a new play session.
+    pub fn reset(&mut self) {
+        *self = BlockSequence::default();
     }
 }
 
@@ -76,23 +540,32 @@ impl MinecraftStream {
 /// the domain and port are known when the initial connection attempt is made, and the username will
 /// be inferred once a user attempts to login.
 /// # Example
-/// ```
+/// ```no_run
+/// use mcclient::mc::connection::{Connection, OfflineConnection};
+///
 /// let mut connection = OfflineConnection::connect("localhost", 25565).expect("Could not connect");
 /// connection.username(); // -> Returns `None`
 /// let login_success = connection.login("Makoto").expect("Could not log in");
 /// connection.username(); // -> Returns `Some` of String "Makoto"
 /// ```
 pub trait Connection: Sized {
-    /// Attempts to connect to a Minecraft server. On success, the `Connection` is returned.
+    /// The transport the connection's stream exchanges packets over.
+    type Transport: Transport;
+
+    /// Attempts to connect to a Minecraft server over TCP. On success, the `Connection` is
+    /// returned. Connections over other transports, such as a `MemoryTransport`, are made
+    /// from an established stream with `OfflineConnection::with_stream`.
     /// # Errors
     /// This function will return an error if the connection cannot be established.
-    fn connect<T: Into<String> + Clone>(domain: T, port: u16) -> Result<Self, io::Error>;
+    fn connect<T: Into<String> + Clone>(domain: T, port: u16) -> Result<Self, io::Error>
+    where Self::Transport: From<TcpStream>;
     /// Attempts to fetch a status report of the server.
     /// # Errors
     /// This function will return an error if the connection cannot be established. It can be
     /// inferred that failure to receive this packet means the connection cannot continue.
     fn status(&mut self) -> Result<StatusResponse, io::Error>;
-    /// Attempts to ping the recipient server.
+    /// Attempts to ping the recipient server. This must follow a `status` request, and the
+    /// server closes the connection once it has responded.
     /// # Errors
     /// This function will return an error if the ping fails. It can be inferred that failure
     /// to receive this packet means the connection cannot continue.
@@ -100,14 +573,86 @@ pub trait Connection: Sized {
     /// Attempts to log into the recipient server. The steps for this varies by connection type.
     /// For offline connections, a Login Request packet is followed immediately by a Login Success,
     /// while an online connection may require Mojang server authentication, and in newer versions,
-    /// encryption authentication for Microsoft clients.
+    /// encryption authentication for Microsoft clients. On success, the connection has
+    /// entered the Play state and the player has joined the game.
     /// # Errors
     /// This function will return an error if the login attempt fails. It can be inferred that
     /// failure to receive this packet means the connection cannot continue.
     fn login<T: Into<String> + Clone>(&mut self, username: T) -> Result<LoginSuccess, io::Error>;
 
     /// Gets the stream managed by this connection, which is used to send and receive packets.
-    fn sock(&mut self) -> &mut MinecraftStream; 
+    fn sock(&mut self) -> &mut MinecraftStream<Self::Transport>;
+
+    /// Gets the block action sequence of this connection. Packets for block actions should
+    /// be stamped with `block_sequence().advance()`.
+    fn block_sequence(&mut self) -> &mut BlockSequence;
+
+    /// Writes the four `lines` onto the front of the sign at `position`. The sign must
+    /// have been opened for editing by the server, as announced by an Open Sign Editor.
+    /// # Errors
+    /// This function will return an error if the packet cannot be sent.
+    fn write_sign(&mut self, position: MCPosition, lines: [&str; 4]) -> Result<(), io::Error> {
+        let is_front_text = self.sock().version() >= ProtocolVersion::V1_20_1;
+
+        self.sock().send(&UpdateSign {
+            position,
+            is_front_text: is_front_text.then(|| true.into()),
+            lines: lines.map(|line| line.into())
+        })
+    }
+
+    /// Responds to the resource pack `uuid` pushed by the server with `result`. The UUID
+    /// is only sent to servers running 1.20.3 or newer.
+    /// # Errors
+    /// This function will return an error if the packet cannot be sent.
+    fn respond_resource_pack(&mut self, uuid: Uuid, result: ResourcePackResult) -> Result<(), io::Error> {
+        let version = self.sock().version();
+        self.sock().send(&ResourcePackResponse::new(version, uuid, result))
+    }
+
+    /// Accepts the resource pack `uuid` pushed by the server, reporting it as accepted and
+    /// then successfully loaded without downloading it. This satisfies servers which
+    /// require a resource pack.
+    /// # Errors
+    /// This function will return an error if either packet cannot be sent.
+    fn accept_resource_pack(&mut self, uuid: Uuid) -> Result<(), io::Error> {
+        self.respond_resource_pack(uuid, ResourcePackResult::Accepted)?;
+        self.respond_resource_pack(uuid, ResourcePackResult::SuccessfullyLoaded)
+    }
+
+    /// Answers the resource pack `pack` pushed by the server with the statuses `policy`
+    /// calls for.
+    /// # Errors
+    /// This function will return an error if any packet cannot be sent.
+    fn answer_resource_pack(&mut self, pack: &ResourcePackEvent, policy: ResourcePackPolicy) -> Result<(), io::Error> {
+        let version = self.sock().version();
+        for result in policy.results(version) {
+            self.sock().send(&ResourcePackResponse::to(version, pack, result))?;
+        }
+        Ok(())
+    }
+
+    /// Sends `message` to the chat, or runs it as a command if it starts with a slash.
+    /// Since 1.19.3, messages and commands are sent unsigned, which servers enforcing
+    /// secure chat reject by disconnecting the player.
+    /// # Errors
+    /// This function will return an `InvalidInput` error if the message exceeds 256
+    /// characters, or an error if the packet cannot be sent.
+    fn send_chat(&mut self, message: &str) -> Result<(), io::Error> {
+        if message.encode_utf16().count() > MAX_CHAT_LENGTH {
+            return Err(io::Error::new(io::ErrorKind::InvalidInput, format!("Chat messages cannot exceed {} characters.", MAX_CHAT_LENGTH)));
+        }
+
+        if self.sock().version() < ProtocolVersion::V1_19_3 {
+            return self.sock().send(&LegacyChatMessage { message: message.into() });
+        }
+
+        let timestamp = SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |elapsed| elapsed.as_millis() as i64);
+        match message.strip_prefix('/') {
+            Some(command) => self.sock().send(&ChatCommand::unsigned(command.into(), timestamp)),
+            None => self.sock().send(&ChatMessage::unsigned(message.into(), timestamp))
+        }
+    }
 
     /// Gets the domain of the connection. This retrieves the domain passed to the initial connection
     /// attempt, not the endpoint IP resolved by the underlying TCP stream object.
@@ -116,32 +661,367 @@ pub trait Connection: Sized {
     fn port(&self) -> u16;
     /// Gets the username of the connection if it is set. This is set by a `login` invocation.
     fn username(&self) -> &Option<String>;
+    /// Gets the entity ID the server assigned to the player, if the connection has entered
+    /// the Play state.
+    fn entity_id(&self) -> Option<i32>;
+
+    /// Starts or stops sneaking.
+    /// # Errors
+    /// This function will return a `NotConnected` error if the player has not yet joined
+    /// the game, or an error if the packet cannot be sent.
+    fn set_sneaking(&mut self, sneaking: bool) -> Result<(), io::Error> {
+        match sneaking {
+            true => self.player_command(PlayerCommandAction::StartSneaking),
+            false => self.player_command(PlayerCommandAction::StopSneaking)
+        }
+    }
+
+    /// Starts or stops sprinting.
+    /// # Errors
+    /// This function will return a `NotConnected` error if the player has not yet joined
+    /// the game, or an error if the packet cannot be sent.
+    fn set_sprinting(&mut self, sprinting: bool) -> Result<(), io::Error> {
+        match sprinting {
+            true => self.player_command(PlayerCommandAction::StartSprinting),
+            false => self.player_command(PlayerCommandAction::StopSprinting)
+        }
+    }
+
+    /// Sends a Player Command performing `action` for the player.
+    /// # Errors
+    /// This function will return a `NotConnected` error if the player has not yet joined
+    /// the game, or an error if the packet cannot be sent.
+    fn player_command(&mut self, action: PlayerCommandAction) -> Result<(), io::Error> {
+        let entity_id = self.entity_id()
+            .ok_or_else(|| io::Error::new(io::ErrorKind::NotConnected, "Player has not joined the game."))?;
+
+        self.sock().send(&PlayerCommand::new(entity_id, action))
+    }
+}
+
+/// The configuration of the game a server sends in the Configuration state, which servers
+/// running 1.20.2 or newer enter after login.
+#[derive(Clone, Debug, Default)]
+pub struct GameConfiguration {
+    /// The NBT-encoded contents of each Registry Data received.
+    pub registries: Vec<Bytes>,
+    /// The tags of the server's registries.
+    pub tags: Vec<RegistryTags>,
+    /// The experimental features enabled on the server.
+    pub feature_flags: Vec<Identifier>,
+    /// The channel and data of each plugin message received, such as the server's brand.
+    pub plugin_messages: Vec<(String, Bytes)>,
+    /// The resource packs the server pushed, which the connection answered as its
+    /// `resource_pack_policy` calls for.
+    pub resource_packs: Vec<ResourcePackEvent>
 }
 
-/// Represents a connection stream to an offline Minecraft server.
+/// Represents a connection stream to an offline Minecraft server. Online-mode servers
+/// can be joined over the same connection with `login_online`.
 /// <br>
 /// The handshake packet is sent when either a status or login request
 /// is made. The stream itself attempts to open upon construction of
 /// the object.
-#[allow(unused)]
-pub struct OfflineConnection {
-    stream: MinecraftStream,
+pub struct OfflineConnection<T = TcpStream> {
+    stream: MinecraftStream<T>,
     domain: String,
     port: u16,
-    username: Option<String>
+    username: Option<String>,
+    entity_id: Option<i32>,
+    sequence: BlockSequence,
+    configuration: GameConfiguration,
+    resource_pack_policy: ResourcePackPolicy
 }
 
-#[allow(unused)]
-impl Connection for OfflineConnection {
-    fn connect<T: Into<String> + Clone>(domain: T, port: u16) -> Result<Self, io::Error> {
-        let mut stream = MinecraftStream::connect(format!("{}:{}", domain.clone().into(), port))?;
-        
-        Ok(OfflineConnection { stream, domain: domain.into(), port, username: None })
+impl OfflineConnection<TcpStream> {
+    /// Attempts to connect to a Minecraft server over TCP, as per `Connection::connect`.
+    /// This lets the connection be named without its transport, which defaults to TCP.
+    /// # Errors
+    /// This function will return an error if the connection cannot be established.
+    pub fn connect<D: Into<String> + Clone>(domain: D, port: u16) -> Result<Self, io::Error> {
+        <Self as Connection>::connect(domain, port)
+    }
+
+    /// Attempts to connect to the Minecraft server at `address`, given as `host` or
+    /// `host:port`. Addresses without a port are resolved through their SRV record, as per
+    /// `dns::resolve`, and the handshake describes the server as it resolved to.
+    /// # Errors
+    /// This function will return an `InvalidInput` error if the address is malformed, or
+    /// an error if the connection cannot be established.
+    pub fn connect_address(address: &str) -> Result<Self, io::Error> {
+        let (host, port) = dns::resolve(address)?;
+        OfflineConnection::connect(host, port)
+    }
+
+    /// Attempts to connect to a Minecraft server through `proxy`, which resolves `domain`
+    /// itself.
+    /// # Errors
+    /// This function will return an error if the proxy cannot be reached, or if it fails
+    /// or refuses to connect to the server, as per `ProxyConfig::tunnel`.
+    pub fn connect_via<D: Into<String>>(domain: D, port: u16, proxy: &ProxyConfig) -> Result<Self, io::Error> {
+        let domain = domain.into();
+        let stream = MinecraftStream::new(proxy.connect(&domain, port)?);
+
+        Ok(OfflineConnection::with_stream(stream, domain, port))
+    }
+}
+
+impl OfflineConnection<TcpStream> {
+    /// Attempts to connect to a Minecraft server over TCP in the protocol version `version`,
+    /// or, if `version` is `None`, in the version the server reports in its status response.
+    /// The status is requested over a separate connection, since the server closes it once
+    /// it has responded.
+    /// # Errors
+    /// This function will return an error if either connection cannot be established, if
+    /// the server's status cannot be read, or an `Unsupported` error if the server reports
+    /// a version older than every version known to this library.
+    pub fn negotiate<D: Into<String> + Clone>(domain: D, port: u16, version: Option<ProtocolVersion>) -> Result<Self, io::Error> {
+        let version = match version {
+            Some(version) => version,
+            None => negotiate_version(domain.clone(), port)?
+        };
+
+        let mut connection = OfflineConnection::connect(domain, port)?;
+        connection.stream.set_version(version);
+        Ok(connection)
+    }
+}
+
+/// Requests the status of the server at `domain` and `port`, and picks the version to join
+/// it with as per `ProtocolVersion::nearest_known`.
+/// # Errors
+/// This function will return an error if the server cannot be reached or its status cannot
+/// be read, or an `Unsupported` error if it reports a version older than every known release.
+pub fn negotiate_version<D: Into<String> + Clone>(domain: D, port: u16) -> Result<ProtocolVersion, io::Error> {
+    let status = OfflineConnection::connect(domain, port)?.status()?;
+    version_for(&status)
+}
+
+/// Picks the version to join the server of `status` with, as per
+/// `ProtocolVersion::nearest_known`.
+/// # Errors
+/// This function will return an `InvalidData` error if the status cannot be parsed, or an
+/// `Unsupported` error if it reports a version older than every known release.
+pub fn version_for(status: &StatusResponse) -> Result<ProtocolVersion, io::Error> {
+    let reported = ProtocolVersion::from_status(status)?;
+
+    reported.nearest_known().ok_or_else(|| io::Error::new(
+        io::ErrorKind::Unsupported,
+        format!("Server protocol version {} is older than every supported version.", reported.number())
+    ))
+}
+
+impl<T: Transport> OfflineConnection<T> {
+    /// Creates a connection over an established `stream`. The `domain` and `port` are only
+    /// used to describe the server in the handshake.
+    pub fn with_stream<D: Into<String>>(stream: MinecraftStream<T>, domain: D, port: u16) -> Self {
+        OfflineConnection { stream, domain: domain.into(), port, username: None, entity_id: None, sequence: BlockSequence::default(), configuration: GameConfiguration::default(), resource_pack_policy: ResourcePackPolicy::default() }
+    }
+
+    /// Attempts to log into an online-mode server as the player of `session`, which must
+    /// not have expired. The stream is encrypted once the server requests encryption.
+    /// Offline-mode servers are joined as per `login`, but with the profile of `session`.
+    /// # Errors
+    /// This function will return an error if the session server rejects the join, or if
+    /// the login attempt otherwise fails.
+    pub fn login_online(&mut self, session: &AuthSession) -> Result<LoginSuccess, io::Error> {
+        self.login_as(&session.profile.name, session.profile.uuid, Some(session))
+    }
+
+    /// Logs in as `username` with `uuid`, authenticating with `session` if the server is in
+    /// online mode.
+    fn login_as(&mut self, username: &str, uuid: Uuid, session: Option<&AuthSession>) -> Result<LoginSuccess, io::Error> {
+        let handshake = Handshake {
+            protocol_version: self.stream.version().number().into(),
+            server_addr: self.domain.clone().into(),
+            port: self.port,
+            next_state: NextState::LOGIN
+        };
+
+        self.stream.send(&handshake)?;
+        self.stream.send(&LoginStart::new(self.stream.version(), username, uuid))?;
+
+        let success = loop {
+            match LoginStep::of(&self.stream.read()?)? {
+                LoginStep::Success(success) => break success,
+                LoginStep::Encrypt(request) => {
+                    let session = session.ok_or_else(online_mode_error)?;
+                    let (response, shared_secret) = answer_encryption(&request, session)?;
+                    self.stream.send(&response)?;
+                    self.stream.enable_encryption(&shared_secret)?;
+                },
+                LoginStep::Compress(threshold) => self.stream.set_compression(threshold),
+                LoginStep::Reply(response) => self.stream.send(&response)?
+            }
+        };
+
+        if self.stream.version() >= ProtocolVersion::V1_20_2 {
+            self.stream.send(&LoginAcknowledged)?;
+            self.configure()?;
+        }
+
+        let login_play = LoginPlay::from_data(&self.stream.read()?)?;
+        if self.stream.version() < ProtocolVersion::V1_20_2 {
+            self.stream.send(&PluginMessage::brand(self.stream.version()))?;
+        }
+
+        self.username = Some(success.username.string().clone());
+        self.entity_id = Some(login_play.entity_id.value());
+        self.sequence.reset();
+        Ok(success)
+    }
+
+    /// Gets the configuration of the game received during login, which is empty for servers
+    /// older than 1.20.2.
+    pub fn configuration(&self) -> &GameConfiguration {
+        &self.configuration
+    }
+
+    /// Gets the traffic the connection has exchanged, as per `MinecraftStream::metrics`.
+    pub fn metrics(&self) -> &TrafficMetrics {
+        self.stream.metrics()
+    }
+
+    /// Gets how the resource packs pushed by the server are answered.
+    pub fn resource_pack_policy(&self) -> ResourcePackPolicy {
+        self.resource_pack_policy
+    }
+
+    /// Sets how the resource packs pushed by the server are answered, which is to accept
+    /// them by default. During configuration, packs are answered as they are received;
+    /// in the Play state, `Client::next_packet` answers them.
+    pub fn set_resource_pack_policy(&mut self, policy: ResourcePackPolicy) {
+        self.resource_pack_policy = policy;
+    }
+
+    /// Reports the client's brand, and receives the configuration of the game until the
+    /// server finishes configuration, and enters the Play state. Keep Alives are answered
+    /// by the stream, and resource packs as the `resource_pack_policy` calls for.
+    fn configure(&mut self) -> Result<(), io::Error> {
+        self.configuration = GameConfiguration::default();
+        self.stream.send(&ConfigPluginMessage::brand())?;
+
+        let version = self.stream.version();
+        let mut answered = 0;
+        loop {
+            let finished = self.configuration.receive(self.stream.read()?, version)?;
+            for pack in &self.configuration.resource_packs[answered..] {
+                for result in self.resource_pack_policy.results(version) {
+                    self.stream.send(&ConfigResourcePackResponse::new(version, pack.uuid.unwrap_or_default(), result))?;
+                }
+            }
+            answered = self.configuration.resource_packs.len();
+            if finished {
+                break;
+            }
+        }
+
+        self.stream.send(&AcknowledgeFinishConfiguration)
+    }
+}
+
+/// What the client must do in response to a packet received during login. Shared by the
+/// blocking and async connections, which only differ in how packets are moved.
+pub(crate) enum LoginStep {
+    /// The login succeeded, and the client moves to the next state.
+    Success(LoginSuccess),
+    /// The server requests encryption, which is answered with `answer_encryption`.
+    Encrypt(EncryptionRequest),
+    /// The server set the compression threshold of the connection.
+    Compress(Option<i32>),
+    /// The packet must be answered with a Login Plugin Response.
+    Reply(LoginPluginResponse)
+}
+
+impl LoginStep {
+    /// Interprets `inbound`, received during login.
+    /// # Errors
+    /// This function will return an `InvalidData` error if the packet is not a login packet,
+    /// or cannot be parsed.
+    pub fn of(inbound: &MCPacket) -> Result<Self, io::Error> {
+        if inbound.is::<LoginSuccess>() {
+            Ok(LoginStep::Success(LoginSuccess::from_data(inbound)?))
+        } else if inbound.is::<EncryptionRequest>() {
+            Ok(LoginStep::Encrypt(EncryptionRequest::from_data(inbound)?))
+        } else if inbound.is::<SetCompression>() {
+            let threshold = SetCompression::from_data(inbound)?.threshold.value();
+            Ok(LoginStep::Compress((threshold >= 0).then_some(threshold)))
+        } else if inbound.is::<LoginPluginRequest>() {
+            let request = LoginPluginRequest::from_data(inbound)?;
+            Ok(LoginStep::Reply(LoginPluginResponse { message_id: request.message_id, data: None }))
+        } else {
+            Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("Unexpected packet 0x{:02X} during login.", inbound.header.id.value())
+            ))
+        }
+    }
+}
+
+/// The error of an online-mode server asking a connection without a session for encryption.
+pub(crate) fn online_mode_error() -> io::Error {
+    io::Error::new(io::ErrorKind::PermissionDenied, "Server is in online mode, and requires an authenticated session.")
+}
+
+/// Answers the Encryption Request `request`, reporting the join to the session server. The
+/// stream must be encrypted with the returned shared secret once the response is sent.
+/// # Errors
+/// This function will return an error if the session server rejects the join, or if the
+/// server's public key is malformed.
+pub(crate) fn answer_encryption(request: &EncryptionRequest, session: &AuthSession) -> Result<(EncryptionResponse, [u8; SHARED_SECRET_LENGTH]), io::Error> {
+    let shared_secret = encryption::generate_shared_secret();
+    let server_hash = encryption::server_hash(request.server_id.string(), &shared_secret, &request.public_key);
+    auth::join_server(session, &server_hash)?;
+
+    let response = EncryptionResponse {
+        shared_secret: encryption::encrypt_with_public_key(&request.public_key, &shared_secret)?,
+        verify_token: encryption::encrypt_with_public_key(&request.public_key, &request.verify_token)?
+    };
+    Ok((response, shared_secret))
+}
+
+impl GameConfiguration {
+    /// Records `inbound`, received during configuration in `version`. Returns whether it
+    /// finished the configuration, which the client must acknowledge.
+    /// # Errors
+    /// This function will return an `InvalidData` error if a configuration packet cannot
+    /// be parsed.
+    pub(crate) fn receive(&mut self, inbound: MCPacket, version: ProtocolVersion) -> Result<bool, io::Error> {
+        match configuration::Clientbound::decode(inbound)? {
+            configuration::Clientbound::FinishConfiguration(_) => return Ok(true),
+            configuration::Clientbound::ConfigKeepAlive(_) => {},
+            configuration::Clientbound::RegistryData(registry) => self.registries.push(registry.data),
+            configuration::Clientbound::FeatureFlags(flags) => self.feature_flags = flags.flags.into_inner(),
+            configuration::Clientbound::UpdateTags(tags) => self.tags = tags.registries,
+            configuration::Clientbound::ConfigPluginMessage(message) => self.plugin_messages.push((message.channel.string().clone(), message.data)),
+            configuration::Clientbound::ConfigResourcePack(pack) => self.resource_packs.push(pack.pack(version)?),
+            configuration::Clientbound::Unknown(_) => {}
+        }
+
+        Ok(false)
+    }
+}
+
+/// Derives the UUID an offline-mode server assigns to `username`, which is the version 3
+/// UUID of the MD5 hash of `OfflinePlayer:<username>`.
+pub fn offline_uuid(username: &str) -> Uuid {
+    let digest = md5::compute(format!("OfflinePlayer:{}", username));
+    uuid::Builder::from_md5_bytes(digest.0).into_uuid()
+}
+
+impl<T: Transport> Connection for OfflineConnection<T> {
+    type Transport = T;
+
+    fn connect<D: Into<String> + Clone>(domain: D, port: u16) -> Result<Self, io::Error>
+    where T: From<TcpStream> {
+        let stream = TcpStream::connect((domain.clone().into(), port))?;
+
+        Ok(OfflineConnection::with_stream(MinecraftStream::new(T::from(stream)), domain, port))
     }
 
     fn status(&mut self) -> Result<StatusResponse, io::Error> {
         let handshake = Handshake {
-            protocol_version: PROTOCOL_VERSION.into(),
+            protocol_version: self.stream.version().number().into(),
             server_addr: self.domain.clone().into(), // TODO change to String type after MCType refactor
             port: self.port,
             next_state: NextState::STATUS
@@ -151,27 +1031,42 @@ impl Connection for OfflineConnection {
         self.stream.send(&StatusRequest)?;
 
         let inbound = self.stream.read()?;
-        if inbound.header.id.value() != 0x00 {
-            return Err(io::Error::new(io::ErrorKind::InvalidData, "Bad packet ID."));
-        }
         let response = StatusResponse::from_data(&inbound)?;
 
         Ok(response)
     }
 
     fn ping(&mut self) -> Result<PingResponse, io::Error> {
-        Ok(PingResponse {  })
+        let payload = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|time| time.as_millis() as i64)
+            .unwrap_or_default();
+
+        self.stream.send(&PingRequest { payload: payload.into() })?;
+
+        let inbound = self.stream.read()?;
+        let response = PingResponse::from_data(&inbound)?;
+        if response.payload.value() != payload {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, "Ping response does not echo the ping payload."));
+        }
+
+        Ok(response)
     }
 
-    fn login<T: Into<String> + Clone>(&mut self, username: T) -> Result<LoginSuccess, io::Error> {
-        self.username = Some(username.into());
-        Ok(LoginSuccess {  })
+    fn login<U: Into<String> + Clone>(&mut self, username: U) -> Result<LoginSuccess, io::Error> {
+        let username: String = username.into();
+        let uuid = offline_uuid(&username);
+        self.login_as(&username, uuid, None)
     }
 
-    fn sock(&mut self) -> &mut MinecraftStream {
+    fn sock(&mut self) -> &mut MinecraftStream<T> {
         &mut self.stream
     }
 
+    fn block_sequence(&mut self) -> &mut BlockSequence {
+        &mut self.sequence
+    }
+
     fn domain(&self) -> &str {
         &self.domain
     }
@@ -183,4 +1078,8 @@ impl Connection for OfflineConnection {
     fn username(&self) -> &Option<String> {
         &self.username
     }
+
+    fn entity_id(&self) -> Option<i32> {
+        self.entity_id
+    }
 }
@@ -0,0 +1,133 @@
+//! The time blocks take to dig, depending on the tool used.
+use super::{mctypes::MCPosition, packets::play::serverbound::BlockFace};
+
+/// The kinds of tools which dig some blocks faster.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ToolKind {
+    Pickaxe,
+    Axe,
+    Shovel,
+    Hoe
+}
+
+/// The materials tools are made of, which set how fast they dig.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ToolMaterial {
+    Wood,
+    Stone,
+    Iron,
+    Diamond,
+    Netherite,
+    Gold
+}
+
+impl ToolMaterial {
+    /// The factor digging the blocks the tool is meant for is sped up by.
+    pub fn speed(&self) -> f32 {
+        match self {
+            ToolMaterial::Wood => 2.0,
+            ToolMaterial::Stone => 4.0,
+            ToolMaterial::Iron => 6.0,
+            ToolMaterial::Diamond => 8.0,
+            ToolMaterial::Netherite => 9.0,
+            ToolMaterial::Gold => 12.0
+        }
+    }
+}
+
+/// A tool held to dig blocks.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Tool {
+    pub kind: ToolKind,
+    pub material: ToolMaterial
+}
+
+/// How hard a block is to dig.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Hardness {
+    /// The hardness of the block. Blocks with a negative hardness, such as bedrock,
+    /// cannot be dug.
+    pub value: f32,
+    /// The kind of tool which digs the block faster, if any.
+    pub tool: Option<ToolKind>,
+    /// Whether the block only drops anything when dug with its tool, and takes much longer
+    /// to dig otherwise.
+    pub requires_tool: bool
+}
+
+impl Hardness {
+    /// The hardness assumed of the blocks which `Hardness::of` does not know: that of
+    /// stone, without requiring a tool.
+    pub const UNKNOWN: Hardness = Hardness { value: 1.5, tool: None, requires_tool: false };
+
+    const fn of_tool(value: f32, tool: ToolKind, requires_tool: bool) -> Self {
+        Hardness { value, tool: Some(tool), requires_tool }
+    }
+
+    /// Gets the hardness of the common blocks of the overworld, by name such as
+    /// `minecraft:stone`, or `None` for other blocks. The tiers of tools which may dig a
+    /// block are not told apart, so any pickaxe is taken to dig obsidian.
+    pub fn of(name: &str) -> Option<Hardness> {
+        use ToolKind::*;
+
+        let name = name.strip_prefix("minecraft:").unwrap_or(name);
+        Some(match name {
+            "air" | "cave_air" | "void_air" | "grass" | "short_grass" | "tall_grass" | "fern" | "dandelion" | "poppy" | "torch" => Hardness { value: 0.0, tool: None, requires_tool: false },
+            "bedrock" | "barrier" | "end_portal_frame" => Hardness { value: -1.0, tool: None, requires_tool: false },
+            "stone" | "granite" | "polished_granite" | "diorite" | "polished_diorite" | "andesite" | "polished_andesite" | "stone_bricks" => Hardness::of_tool(1.5, Pickaxe, true),
+            "cobblestone" | "mossy_cobblestone" | "bricks" => Hardness::of_tool(2.0, Pickaxe, true),
+            "deepslate" | "coal_ore" | "iron_ore" | "copper_ore" | "gold_ore" | "redstone_ore" | "lapis_ore" | "diamond_ore" | "emerald_ore" => Hardness::of_tool(3.0, Pickaxe, true),
+            "obsidian" => Hardness::of_tool(50.0, Pickaxe, true),
+            "netherrack" => Hardness::of_tool(0.4, Pickaxe, true),
+            "sandstone" => Hardness::of_tool(0.8, Pickaxe, true),
+            "grass_block" | "mycelium" | "gravel" | "clay" | "farmland" => Hardness::of_tool(0.6, Shovel, false),
+            "dirt" | "coarse_dirt" | "podzol" | "rooted_dirt" | "sand" | "red_sand" | "soul_sand" => Hardness::of_tool(0.5, Shovel, false),
+            "snow_block" => Hardness::of_tool(0.2, Shovel, true),
+            "crafting_table" | "chest" => Hardness::of_tool(2.5, Axe, false),
+            "bookshelf" => Hardness::of_tool(1.5, Axe, false),
+            "glass" | "glowstone" => Hardness { value: 0.3, tool: None, requires_tool: false },
+            _ if name.ends_with("_planks") || name.ends_with("_log") || name.ends_with("_wood") => Hardness::of_tool(2.0, Axe, false),
+            _ if name.ends_with("_leaves") => Hardness::of_tool(0.2, Hoe, false),
+            _ if name.ends_with("_wool") => Hardness { value: 0.8, tool: None, requires_tool: false },
+            _ => return None
+        })
+    }
+
+    /// Gets the number of ticks digging the block takes with `tool`, or bare-handed, or
+    /// `None` if it cannot be dug. Digging is 5 times slower while not on the ground.
+    /// Blocks which take no ticks break as soon as digging starts.
+    pub fn dig_ticks(&self, tool: Option<Tool>, on_ground: bool) -> Option<u32> {
+        if self.value < 0.0 {
+            return None;
+        }
+        if self.value == 0.0 {
+            return Some(0);
+        }
+
+        let suited = tool.filter(|tool| self.tool == Some(tool.kind));
+        let mut speed = suited.map_or(1.0, |tool| tool.material.speed());
+        if !on_ground {
+            speed /= 5.0;
+        }
+        let divisor = match !self.requires_tool || suited.is_some() {
+            true => 30.0,
+            false => 100.0
+        };
+        // A block breaks at once if a tick of digging is enough.
+        let ticks = self.value * divisor / speed;
+        match ticks <= 1.0 {
+            true => Some(0),
+            false => Some(ticks.ceil() as u32)
+        }
+    }
+}
+
+/// A block the player is digging, which breaks once enough ticks have passed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Dig {
+    pub position: MCPosition,
+    /// The face of the block the player digs into.
+    pub face: BlockFace,
+    /// The number of ticks until the block breaks.
+    pub ticks_left: u32
+}
@@ -0,0 +1,212 @@
+//! The resolution of server addresses through the `_minecraft._tcp` SRV records vanilla
+//! clients look up, so that servers can be reached without knowing their real port.
+use std::{fs, io, net::{IpAddr, SocketAddr, UdpSocket}, time::Duration};
+
+use super::status::{parse_address, DEFAULT_PORT};
+
+/// The prefix of the name of the SRV record of a Minecraft server.
+pub const SRV_PREFIX: &str = "_minecraft._tcp.";
+/// The port DNS servers listen on.
+const DNS_PORT: u16 = 53;
+/// The time a nameserver is given to answer.
+const QUERY_TIMEOUT: Duration = Duration::from_secs(3);
+/// The type of SRV records.
+const TYPE_SRV: u16 = 33;
+/// The Internet class of records.
+const CLASS_IN: u16 = 1;
+/// The size of the header of a DNS message.
+const HEADER_LENGTH: usize = 12;
+/// The most compression pointers followed while reading a name, so that pointer loops
+/// end.
+const MAX_POINTERS: usize = 16;
+
+/// A record locating a service at a port of a host.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SrvRecord {
+    /// The priority of the record. Records of a lower priority are tried first.
+    pub priority: u16,
+    /// The share of connections the record receives among those of its priority.
+    pub weight: u16,
+    pub port: u16,
+    /// The host the service runs on, without the trailing dot.
+    pub target: String
+}
+
+impl SrvRecord {
+    /// Chooses the record to connect to among `records`: the one of the lowest priority,
+    /// and of those, the one of the greatest weight.
+    pub fn choose(records: &[SrvRecord]) -> Option<&SrvRecord> {
+        records.iter().min_by_key(|record| (record.priority, u16::MAX - record.weight))
+    }
+}
+
+/// Resolves `address`, given as `host` or `host:port`, to the host and port to connect
+/// to, as vanilla clients do. Addresses without a port are looked up as the SRV record
+/// `_minecraft._tcp.<host>`, and connect to `DEFAULT_PORT` of `host` if it has none or the
+/// lookup fails. Addresses with a port and IP addresses are taken as they are.
+/// # Errors
+/// This function will return an `InvalidInput` error if the address is malformed, as per
+/// `status::split_address`. Failed lookups are not errors.
+pub fn resolve(address: &str) -> Result<(String, u16), io::Error> {
+    let (host, port) = parse_address(address)?;
+    if let Some(port) = port {
+        return Ok((host.to_owned(), port));
+    }
+    if host.parse::<IpAddr>().is_ok() {
+        return Ok((host.to_owned(), DEFAULT_PORT));
+    }
+
+    Ok(match lookup_srv(host).ok().as_deref().and_then(SrvRecord::choose) {
+        Some(record) => (record.target.clone(), record.port),
+        None => (host.to_owned(), DEFAULT_PORT)
+    })
+}
+
+/// Looks up the SRV records of the Minecraft server at `host`, asking the nameservers of
+/// the system in turn until one answers.
+/// # Errors
+/// This function will return a `NotFound` error if the system has no nameserver, or the
+/// error of the last nameserver asked if none answers.
+pub fn lookup_srv(host: &str) -> Result<Vec<SrvRecord>, io::Error> {
+    let name = format!("{}{}", SRV_PREFIX, host.trim_end_matches('.'));
+    let mut last_error = io::Error::new(io::ErrorKind::NotFound, "No nameserver is configured.");
+    for nameserver in nameservers() {
+        match query_srv(nameserver, &name) {
+            Ok(records) => return Ok(records),
+            Err(err) => last_error = err
+        }
+    }
+    Err(last_error)
+}
+
+/// Gets the nameservers of the system, as listed in `/etc/resolv.conf`.
+pub fn nameservers() -> Vec<SocketAddr> {
+    let Ok(config) = fs::read_to_string("/etc/resolv.conf") else {
+        return Vec::new();
+    };
+    config.lines()
+        .filter_map(|line| line.trim().strip_prefix("nameserver"))
+        .filter_map(|address| address.trim().parse::<IpAddr>().ok())
+        .map(|address| SocketAddr::new(address, DNS_PORT))
+        .collect()
+}
+
+/// Asks `nameserver` for the SRV records of `name`.
+fn query_srv(nameserver: SocketAddr, name: &str) -> Result<Vec<SrvRecord>, io::Error> {
+    let local: SocketAddr = match nameserver {
+        SocketAddr::V4(_) => ([0, 0, 0, 0], 0).into(),
+        SocketAddr::V6(_) => ([0u16; 8], 0).into()
+    };
+    let socket = UdpSocket::bind(local)?;
+    socket.set_read_timeout(Some(QUERY_TIMEOUT))?;
+    socket.connect(nameserver)?;
+
+    let id = rand::random();
+    socket.send(&srv_query(id, name)?)?;
+    let mut response = [0; 512];
+    let length = socket.recv(&mut response)?;
+    parse_srv_response(&response[..length], id)
+}
+
+/// Encodes a recursive query for the SRV records of `name`, numbered `id`.
+/// # Errors
+/// This function will return an `InvalidInput` error if a label of `name` is empty or
+/// longer than 63 bytes.
+pub fn srv_query(id: u16, name: &str) -> Result<Vec<u8>, io::Error> {
+    let mut query = Vec::with_capacity(HEADER_LENGTH + name.len() + 6);
+    query.extend(id.to_be_bytes());
+    // Recursion desired, and a single question.
+    query.extend([0x01, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00]);
+    for label in name.trim_end_matches('.').split('.') {
+        if label.is_empty() || label.len() > 63 {
+            return Err(io::Error::new(io::ErrorKind::InvalidInput, format!("Invalid domain name {}.", name)));
+        }
+        query.push(label.len() as u8);
+        query.extend(label.as_bytes());
+    }
+    query.push(0);
+    query.extend(TYPE_SRV.to_be_bytes());
+    query.extend(CLASS_IN.to_be_bytes());
+    Ok(query)
+}
+
+/// Decodes the SRV records answering the query numbered `id` out of `response`.
+/// Answers of other types are skipped, and a name which does not exist has no records.
+/// # Errors
+/// This function will return an `InvalidData` error if the response is truncated or does
+/// not answer the query, or an `Other` error if the nameserver failed to answer it.
+pub fn parse_srv_response(response: &[u8], id: u16) -> Result<Vec<SrvRecord>, io::Error> {
+    let invalid = || io::Error::new(io::ErrorKind::InvalidData, "Malformed DNS response.");
+    let u16_at = |offset: usize| response.get(offset..offset + 2).map(|bytes| u16::from_be_bytes([bytes[0], bytes[1]])).ok_or_else(invalid);
+
+    if response.len() < HEADER_LENGTH {
+        return Err(invalid());
+    }
+    if u16_at(0)? != id || response[2] & 0x80 == 0 {
+        return Err(io::Error::new(io::ErrorKind::InvalidData, "The DNS response does not answer the query."));
+    }
+    match response[3] & 0x0F {
+        0 => (),
+        // The name does not exist.
+        3 => return Ok(Vec::new()),
+        code => return Err(io::Error::other(format!("The nameserver failed to answer, with code {}.", code)))
+    }
+
+    let questions = u16_at(4)?;
+    let answers = u16_at(6)?;
+    let mut offset = HEADER_LENGTH;
+    for _ in 0..questions {
+        offset = read_name(response, offset)?.1 + 4;
+    }
+
+    let mut records = Vec::new();
+    for _ in 0..answers {
+        offset = read_name(response, offset)?.1;
+        let kind = u16_at(offset)?;
+        let length = u16_at(offset + 8)? as usize;
+        let data = offset + 10;
+        if response.len() < data + length {
+            return Err(invalid());
+        }
+        if kind == TYPE_SRV {
+            records.push(SrvRecord {
+                priority: u16_at(data)?,
+                weight: u16_at(data + 2)?,
+                port: u16_at(data + 4)?,
+                target: read_name(response, data + 6)?.0
+            });
+        }
+        offset = data + length;
+    }
+    Ok(records)
+}
+
+/// Reads the name at `offset` of `message`, following compression pointers. Returns the
+/// name, without its trailing dot, and the offset following it.
+fn read_name(message: &[u8], mut offset: usize) -> Result<(String, usize), io::Error> {
+    let invalid = || io::Error::new(io::ErrorKind::InvalidData, "Malformed name in DNS response.");
+    let mut labels = Vec::new();
+    let mut end = None;
+    let mut pointers = 0;
+    loop {
+        let length = *message.get(offset).ok_or_else(invalid)? as usize;
+        match length {
+            0 => break,
+            _ if length & 0xC0 == 0xC0 => {
+                let low = *message.get(offset + 1).ok_or_else(invalid)? as usize;
+                end.get_or_insert(offset + 2);
+                pointers += 1;
+                if pointers > MAX_POINTERS {
+                    return Err(invalid());
+                }
+                offset = (length & 0x3F) << 8 | low;
+            },
+            _ => {
+                let label = message.get(offset + 1..offset + 1 + length).ok_or_else(invalid)?;
+                labels.push(String::from_utf8_lossy(label).into_owned());
+                offset += 1 + length;
+            }
+        }
+    }
+    Ok((labels.join("."), end.unwrap_or(offset + 1)))
+}
@@ -0,0 +1,97 @@
+use std::io::{self, Read};
+
+use aes::Aes128;
+use cfb8::cipher::{generic_array::GenericArray, BlockDecryptMut, BlockEncryptMut, KeyIvInit};
+use num_bigint::BigInt;
+use rand::RngCore;
+use rsa::{pkcs8::DecodePublicKey, Pkcs1v15Encrypt, RsaPublicKey};
+use sha1::{Digest, Sha1};
+
+/// The length of the shared secret, which is the key and IV of the stream cipher.
+pub const SHARED_SECRET_LENGTH: usize = 16;
+
+/// The AES-128-CFB8 cipher which encrypts a connection in both directions once the
+/// client has sent an Encryption Response. The shared secret is used as both the key
+/// and the IV, and the cipher runs continuously over the stream rather than over
+/// individual packets.
+pub struct StreamCipher {
+    encryptor: cfb8::Encryptor<Aes128>,
+    decryptor: cfb8::Decryptor<Aes128>
+}
+
+impl StreamCipher {
+    /// Creates the cipher for `shared_secret`.
+    pub fn new(shared_secret: &[u8; SHARED_SECRET_LENGTH]) -> Self {
+        let key = GenericArray::from_slice(shared_secret);
+        StreamCipher {
+            encryptor: cfb8::Encryptor::new(key, key),
+            decryptor: cfb8::Decryptor::new(key, key)
+        }
+    }
+
+    /// Encrypts the outbound `bytes` in place.
+    pub fn encrypt(&mut self, bytes: &mut [u8]) {
+        for byte in bytes.chunks_mut(1) {
+            self.encryptor.encrypt_block_mut(GenericArray::from_mut_slice(byte));
+        }
+    }
+
+    /// Decrypts the inbound `bytes` in place.
+    pub fn decrypt(&mut self, bytes: &mut [u8]) {
+        for byte in bytes.chunks_mut(1) {
+            self.decryptor.decrypt_block_mut(GenericArray::from_mut_slice(byte));
+        }
+    }
+
+    /// Wraps `reader`, decrypting the bytes read from it.
+    pub fn reader<'a, R: Read>(&'a mut self, reader: &'a mut R) -> DecryptingReader<'a, R> {
+        DecryptingReader { reader, cipher: self }
+    }
+}
+
+/// Decrypts the bytes of an encrypted stream as they are read. Bytes are decrypted as
+/// they leave the inner reader, so a buffered reader holding ciphertext which arrived
+/// before encryption was enabled is decrypted correctly.
+pub struct DecryptingReader<'a, R> {
+    reader: &'a mut R,
+    cipher: &'a mut StreamCipher
+}
+
+impl<R: Read> Read for DecryptingReader<'_, R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let read = self.reader.read(buf)?;
+        self.cipher.decrypt(&mut buf[..read]);
+        Ok(read)
+    }
+}
+
+/// Generates a random shared secret for a new encrypted connection.
+pub fn generate_shared_secret() -> [u8; SHARED_SECRET_LENGTH] {
+    let mut secret = [0_u8; SHARED_SECRET_LENGTH];
+    rand::thread_rng().fill_bytes(&mut secret);
+    secret
+}
+
+/// Encrypts `data` with the server's RSA `public_key`, as sent in its Encryption
+/// Request in DER-encoded X.509 form, using PKCS#1 v1.5 padding.
+/// # Errors
+/// This function will return an `InvalidData` error if the public key cannot be parsed.
+pub fn encrypt_with_public_key(public_key: &[u8], data: &[u8]) -> Result<Vec<u8>, io::Error> {
+    let key = RsaPublicKey::from_public_key_der(public_key)
+        .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err.to_string()))?;
+
+    key.encrypt(&mut rand::thread_rng(), Pkcs1v15Encrypt, data)
+        .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err.to_string()))
+}
+
+/// Computes the server hash the client reports to the session server when joining an
+/// online-mode server. The hash is the SHA-1 digest of the server ID, shared secret and
+/// public key, formatted as a signed hexadecimal number as per Java's `BigInteger`.
+pub fn server_hash(server_id: &str, shared_secret: &[u8], public_key: &[u8]) -> String {
+    let mut hasher = Sha1::new();
+    hasher.update(server_id.as_bytes());
+    hasher.update(shared_secret);
+    hasher.update(public_key);
+
+    BigInt::from_signed_bytes_be(&hasher.finalize()).to_str_radix(16)
+}
@@ -0,0 +1,231 @@
+use std::collections::HashMap;
+
+use uuid::Uuid;
+
+use super::{error::MCTypeError, metadata::EntityMetadata, mctypes::{Angle, Location, MCShort}, packets::play::Clientbound, version::ProtocolVersion};
+
+/// The number of units in a block of the position deltas of entity movement.
+const DELTA_UNITS_PER_BLOCK: f64 = 4096.0;
+/// The number of units in a block per tick of entity velocities.
+const VELOCITY_UNITS_PER_BLOCK: f64 = 8000.0;
+
+/// Gets the ID of the player entity type of `version`, for the versions which spawn
+/// players like other entities rather than with a Spawn Player of their own.
+fn player_kind(version: ProtocolVersion) -> Option<i32> {
+    match version.number() {
+        764 => Some(122),
+        765 => Some(124),
+        _ => None
+    }
+}
+
+/// An entity in view of the player, as last reported by the server.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Entity {
+    pub id: i32,
+    pub uuid: Uuid,
+    /// The ID of the entity's type in the registry of the server's version. Players
+    /// spawned before 1.20.2 have no type ID.
+    pub kind: Option<i32>,
+    /// Whether the entity is another player.
+    pub player: bool,
+    pub location: Location,
+    pub head_yaw: f32,
+    pub on_ground: bool,
+    /// The velocity along each axis, in blocks per tick.
+    pub velocity: [f64; 3],
+    pub metadata: EntityMetadata
+}
+
+impl Entity {
+    /// Gets the distance from the entity to `location`, in blocks.
+    pub fn distance_to(&self, location: &Location) -> f64 {
+        let (x, y, z) = (self.location.x - location.x, self.location.y - location.y, self.location.z - location.z);
+        (x * x + y * y + z * z).sqrt()
+    }
+
+    fn move_by(&mut self, x: &MCShort, y: &MCShort, z: &MCShort) {
+        self.location.x += x.value() as f64 / DELTA_UNITS_PER_BLOCK;
+        self.location.y += y.value() as f64 / DELTA_UNITS_PER_BLOCK;
+        self.location.z += z.value() as f64 / DELTA_UNITS_PER_BLOCK;
+    }
+
+    fn turn(&mut self, yaw: &Angle, pitch: &Angle) {
+        self.location.yaw = signed_degrees(yaw);
+        self.location.pitch = signed_degrees(pitch);
+    }
+}
+
+/// Tracks the entities in view of the player, from the packets which spawn, move and
+/// remove them.
+/// <br> <br>
+/// Entities are only tracked for versions since 1.9; the movement of entities of 1.8 is
+/// not decoded, and entities other than players are only spawned since 1.19.3.
+/// # Example
+/// ```no_run
+/// use mcclient::{Client, mc::{connection::Connection, entities::EntityTracker, mctypes::Location, packets::play::Clientbound, version::ProtocolVersion}};
+///
+/// let mut client = Client::login_offline("localhost", 25565, "Makoto").expect("Could not join");
+/// let mut entities = EntityTracker::new(ProtocolVersion::V1_19_3);
+/// loop {
+///     let packet = Clientbound::decode(client.connection().sock().read().expect("Disconnected")).expect("Invalid packet");
+///     entities.handle(&packet).expect("Invalid metadata");
+///     if let Some(player) = entities.nearest_player(&Location::default()) {
+///         println!("Nearest player: {} at {:?}", player.uuid, player.location);
+///     }
+/// }
+/// ```
+#[derive(Clone, Debug)]
+pub struct EntityTracker {
+    version: ProtocolVersion,
+    entities: HashMap<i32, Entity>
+}
+
+impl EntityTracker {
+    /// Creates a tracker without entities, whose packets are decoded in the format of
+    /// `version`.
+    pub fn new(version: ProtocolVersion) -> Self {
+        EntityTracker { version, entities: HashMap::new() }
+    }
+
+    pub fn version(&self) -> ProtocolVersion {
+        self.version
+    }
+
+    /// Applies the Play packet `packet` to the entities, if it concerns them. Packets about
+    /// entities which were not spawned are ignored.
+    /// # Errors
+    /// This function will return an `MCTypeError` if the packet carries metadata which
+    /// cannot be decoded.
+    pub fn handle(&mut self, packet: &Clientbound) -> Result<(), MCTypeError> {
+        match packet {
+            Clientbound::SpawnEntity(spawn) => {
+                self.entities.insert(spawn.entity_id, Entity {
+                    id: spawn.entity_id,
+                    uuid: spawn.uuid.uuid(),
+                    kind: Some(spawn.kind),
+                    player: player_kind(self.version) == Some(spawn.kind),
+                    location: Location { x: spawn.x.value(), y: spawn.y.value(), z: spawn.z.value(), yaw: signed_degrees(&spawn.yaw), pitch: signed_degrees(&spawn.pitch) },
+                    head_yaw: signed_degrees(&spawn.head_yaw),
+                    on_ground: false,
+                    velocity: velocity(&spawn.velocity_x, &spawn.velocity_y, &spawn.velocity_z),
+                    metadata: EntityMetadata::default()
+                });
+            },
+            Clientbound::SpawnPlayer(spawn) => {
+                self.entities.insert(spawn.entity_id, Entity {
+                    id: spawn.entity_id,
+                    uuid: spawn.uuid.uuid(),
+                    kind: None,
+                    player: true,
+                    location: Location { x: spawn.x.value(), y: spawn.y.value(), z: spawn.z.value(), yaw: signed_degrees(&spawn.yaw), pitch: signed_degrees(&spawn.pitch) },
+                    head_yaw: signed_degrees(&spawn.yaw),
+                    on_ground: false,
+                    velocity: [0.0; 3],
+                    metadata: EntityMetadata::default()
+                });
+            },
+            Clientbound::UpdateEntityPosition(update) => {
+                if let Some(entity) = self.entities.get_mut(&update.entity_id) {
+                    entity.move_by(&update.delta_x, &update.delta_y, &update.delta_z);
+                    entity.on_ground = update.on_ground.value();
+                }
+            },
+            Clientbound::UpdateEntityPositionAndRotation(update) => {
+                if let Some(entity) = self.entities.get_mut(&update.entity_id) {
+                    entity.move_by(&update.delta_x, &update.delta_y, &update.delta_z);
+                    entity.turn(&update.yaw, &update.pitch);
+                    entity.on_ground = update.on_ground.value();
+                }
+            },
+            Clientbound::UpdateEntityRotation(update) => {
+                if let Some(entity) = self.entities.get_mut(&update.entity_id) {
+                    entity.turn(&update.yaw, &update.pitch);
+                    entity.on_ground = update.on_ground.value();
+                }
+            },
+            Clientbound::TeleportEntity(teleport) => {
+                if let Some(entity) = self.entities.get_mut(&teleport.entity_id) {
+                    (entity.location.x, entity.location.y, entity.location.z) = (teleport.x.value(), teleport.y.value(), teleport.z.value());
+                    entity.turn(&teleport.yaw, &teleport.pitch);
+                    entity.on_ground = teleport.on_ground.value();
+                }
+            },
+            Clientbound::SetEntityVelocity(update) => {
+                if let Some(entity) = self.entities.get_mut(&update.entity_id) {
+                    entity.velocity = velocity(&update.velocity_x, &update.velocity_y, &update.velocity_z);
+                }
+            },
+            Clientbound::SetEntityMetadata(update) => {
+                if let Some(entity) = self.entities.get_mut(&update.entity_id.value()) {
+                    entity.metadata.update(update.metadata(self.version)?);
+                }
+            },
+            Clientbound::RemoveEntities(remove) => {
+                for entity_id in &remove.entity_ids {
+                    self.entities.remove(entity_id);
+                }
+            },
+            _ => ()
+        }
+        Ok(())
+    }
+
+    /// Gets the entity `id`, if it is in view.
+    pub fn get(&self, id: i32) -> Option<&Entity> {
+        self.entities.get(&id)
+    }
+
+    /// Iterates over the entities in view, in no particular order.
+    pub fn iter(&self) -> impl Iterator<Item = &Entity> {
+        self.entities.values()
+    }
+
+    /// Iterates over the other players in view, in no particular order.
+    pub fn players(&self) -> impl Iterator<Item = &Entity> {
+        self.iter().filter(|entity| entity.player)
+    }
+
+    /// The number of entities in view.
+    pub fn len(&self) -> usize {
+        self.entities.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entities.is_empty()
+    }
+
+    /// Forgets every entity, as when the player changes dimensions.
+    pub fn clear(&mut self) {
+        self.entities.clear();
+    }
+
+    /// Gets the player nearest to `location`, if any is in view.
+    pub fn nearest_player(&self, location: &Location) -> Option<&Entity> {
+        self.players().min_by(|a, b| a.distance_to(location).total_cmp(&b.distance_to(location)))
+    }
+
+    /// Gets the entities within `radius` blocks of `location`, from the nearest.
+    pub fn entities_within(&self, location: &Location, radius: f64) -> Vec<&Entity> {
+        let mut entities: Vec<(f64, &Entity)> = self.iter()
+            .map(|entity| (entity.distance_to(location), entity))
+            .filter(|(distance, _)| *distance <= radius)
+            .collect();
+        entities.sort_by(|(a, _), (b, _)| a.total_cmp(b));
+        entities.into_iter().map(|(_, entity)| entity).collect()
+    }
+}
+
+/// Converts a velocity sent by the server into blocks per tick.
+fn velocity(x: &MCShort, y: &MCShort, z: &MCShort) -> [f64; 3] {
+    [x, y, z].map(|axis| axis.value() as f64 / VELOCITY_UNITS_PER_BLOCK)
+}
+
+/// Converts an angle into degrees from -180 to 180, as the rotations of entities are
+/// given by the rest of the protocol.
+fn signed_degrees(angle: &Angle) -> f32 {
+    match angle.degrees() {
+        degrees if degrees >= 180.0 => degrees - 360.0,
+        degrees => degrees
+    }
+}
@@ -0,0 +1,354 @@
+use alloc::string::String;
+use core::{error::Error, fmt};
+
+use super::io;
+#[cfg(feature = "std")]
+use super::{chat::ChatComponent, connection::ConnectionState, mctypes::{MCString, MCType}, version::ProtocolVersion};
+
+/// Errors raised while exchanging packets with a server. Transport failures
+/// are wrapped as-is, while the remaining variants describe conditions where
+/// the bytes received cannot form a valid Minecraft packet.
+#[cfg(feature = "std")]
+#[derive(Debug)]
+pub enum ProtocolError {
+    /// The underlying stream failed.
+    Io(io::Error),
+    /// The connection was closed before a full packet was received.
+    UnexpectedEof,
+    /// The bytes received could not be parsed as a packet.
+    InvalidData(String),
+    /// The server closed the connection with a Disconnect packet, for the reason it gave.
+    Disconnected(Box<DisconnectReason>),
+}
+
+#[cfg(feature = "std")]
+impl fmt::Display for ProtocolError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ProtocolError::Io(err) => write!(f, "I/O error: {}", err),
+            ProtocolError::UnexpectedEof => write!(f, "Connection closed before a full packet was received."),
+            ProtocolError::InvalidData(msg) => write!(f, "Invalid packet data: {}", msg),
+            ProtocolError::Disconnected(reason) => write!(f, "{}", reason),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl Error for ProtocolError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            ProtocolError::Io(err) => Some(err),
+            ProtocolError::Disconnected(reason) => Some(reason.as_ref()),
+            _ => None
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl From<io::Error> for ProtocolError {
+    /// Wraps an `io::Error`. Errors of kind `UnexpectedEof` are reported as
+    /// `ProtocolError::UnexpectedEof`.
+    fn from(err: io::Error) -> Self {
+        match err.kind() {
+            io::ErrorKind::UnexpectedEof => ProtocolError::UnexpectedEof,
+            _ => ProtocolError::Io(err)
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl From<ProtocolError> for io::Error {
+    fn from(err: ProtocolError) -> Self {
+        match err {
+            ProtocolError::Io(err) => err,
+            ProtocolError::UnexpectedEof => io::Error::new(io::ErrorKind::UnexpectedEof, ProtocolError::UnexpectedEof.to_string()),
+            ProtocolError::InvalidData(_) => io::Error::new(io::ErrorKind::InvalidData, err.to_string()),
+            ProtocolError::Disconnected(reason) => (*reason).into(),
+        }
+    }
+}
+
+/// Why the server ended the connection with a Disconnect packet, and the reason it gave.
+#[cfg(feature = "std")]
+#[derive(Clone, Debug, PartialEq)]
+pub enum DisconnectReason {
+    /// The server refused the login, such as for a whitelist or a ban.
+    Login(ChatComponent),
+    /// The server disconnected the player during configuration, since 1.20.2.
+    Configuration(ChatComponent),
+    /// The server kicked the player during play.
+    Kicked(ChatComponent)
+}
+
+#[cfg(feature = "std")]
+impl DisconnectReason {
+    /// Decodes the reason of the Disconnect packet of `state` carrying `data`, in the
+    /// format of `version`. Reasons which are not text components, as some servers send,
+    /// are taken as plain text.
+    /// # Errors
+    /// This function will return an `MCTypeError` if the data does not encode a reason.
+    pub fn decode(state: ConnectionState, version: ProtocolVersion, data: &[u8]) -> Result<Self, MCTypeError> {
+        // Login disconnects are always sent as JSON.
+        let parsed = match state {
+            ConnectionState::Login => ChatComponent::parse_for(data, ProtocolVersion::V1_19_3),
+            _ => ChatComponent::parse_for(data, version)
+        };
+        let reason = match parsed {
+            Ok((reason, _)) => reason,
+            Err(_) => ChatComponent::text(MCString::parse(data)?.0.string().clone())
+        };
+
+        Ok(match state {
+            ConnectionState::Login => DisconnectReason::Login(reason),
+            ConnectionState::Configuration => DisconnectReason::Configuration(reason),
+            _ => DisconnectReason::Kicked(reason)
+        })
+    }
+
+    /// Gets the reason the server gave.
+    pub fn message(&self) -> &ChatComponent {
+        match self {
+            DisconnectReason::Login(message) | DisconnectReason::Configuration(message) | DisconnectReason::Kicked(message) => message
+        }
+    }
+
+    /// Whether the player was kicked once it had joined, rather than refused.
+    pub fn is_kick(&self) -> bool {
+        matches!(self, DisconnectReason::Kicked(_))
+    }
+}
+
+#[cfg(feature = "std")]
+impl fmt::Display for DisconnectReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DisconnectReason::Login(message) => write!(f, "Login refused by server: {}", message.to_plain_text()),
+            DisconnectReason::Configuration(message) => write!(f, "Disconnected by server during configuration: {}", message.to_plain_text()),
+            DisconnectReason::Kicked(message) => write!(f, "Kicked by server: {}", message.to_plain_text()),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl Error for DisconnectReason {}
+
+#[cfg(feature = "std")]
+impl From<DisconnectReason> for io::Error {
+    /// Converts the reason to an `io::Error` of kind `ConnectionAborted`, which carries it
+    /// as per `ClientError::from`.
+    fn from(reason: DisconnectReason) -> Self {
+        io::Error::new(io::ErrorKind::ConnectionAborted, reason)
+    }
+}
+
+/// Why a connection to a server ended or failed, told apart from the `io::Error`s
+/// returned by this library with `ClientError::from`.
+/// # Example
+/// ```no_run
+/// use mcclient::{Client, mc::error::{ClientError, DisconnectReason}};
+///
+/// let mut client = Client::login_offline("localhost", 25565, "Makoto").expect("Could not join");
+/// let err = loop {
+///     if let Err(err) = client.next_packet() {
+///         break ClientError::from(err);
+///     }
+/// };
+/// match err.disconnect_reason() {
+///     Some(DisconnectReason::Kicked(reason)) => println!("Kicked: {}", reason.to_plain_text()),
+///     _ => println!("Stopped: {}", err)
+/// }
+/// ```
+#[cfg(feature = "std")]
+#[derive(Debug)]
+pub enum ClientError {
+    /// The connection failed or was closed, without the server saying why.
+    Io(io::Error),
+    /// The server ended the connection with a Disconnect packet.
+    Disconnected(Box<DisconnectReason>),
+    /// The server sent bytes which do not form a valid packet.
+    Protocol(String)
+}
+
+#[cfg(feature = "std")]
+impl ClientError {
+    /// Gets the reason the server disconnected the player for, if it did.
+    pub fn disconnect_reason(&self) -> Option<&DisconnectReason> {
+        match self {
+            ClientError::Disconnected(reason) => Some(reason),
+            _ => None
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl fmt::Display for ClientError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ClientError::Io(err) => write!(f, "I/O error: {}", err),
+            ClientError::Disconnected(reason) => write!(f, "{}", reason),
+            ClientError::Protocol(msg) => write!(f, "Protocol violation: {}", msg),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl Error for ClientError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            ClientError::Io(err) => Some(err),
+            ClientError::Disconnected(reason) => Some(reason.as_ref()),
+            ClientError::Protocol(_) => None
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl From<io::Error> for ClientError {
+    /// Sorts `err` by what it carries: the reason of a Disconnect packet, a malformed
+    /// value or packet, or a failure of the transport otherwise.
+    fn from(err: io::Error) -> Self {
+        if let Some(reason) = err.get_ref().and_then(|inner| inner.downcast_ref::<DisconnectReason>()) {
+            return ClientError::Disconnected(Box::new(reason.clone()));
+        }
+        if err.kind() == io::ErrorKind::InvalidData || err.get_ref().is_some_and(|inner| inner.is::<MCTypeError>()) {
+            return ClientError::Protocol(err.to_string());
+        }
+        ClientError::Io(err)
+    }
+}
+
+#[cfg(feature = "std")]
+impl From<ProtocolError> for ClientError {
+    /// Sorts `err` as per `ClientError::from` for an `io::Error`.
+    fn from(err: ProtocolError) -> Self {
+        match err {
+            ProtocolError::Disconnected(reason) => ClientError::Disconnected(reason),
+            ProtocolError::InvalidData(msg) => ClientError::Protocol(msg),
+            err => ClientError::from(io::Error::from(err))
+        }
+    }
+}
+
+/// Errors raised while decoding a `MCType` from bytes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MCTypeError {
+    /// A variable-length number spans more bytes than its type allows.
+    TooLong,
+    /// The bytes end before the value does.
+    Truncated,
+    /// The bytes of a string are not valid UTF-8.
+    InvalidUtf8,
+    /// A length prefix is negative or exceeds the maximum the type allows.
+    OversizeLength(i64),
+    /// The bytes do not represent a valid value of the type.
+    InvalidValue(String),
+}
+
+impl fmt::Display for MCTypeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MCTypeError::TooLong => write!(f, "Variable-length number exceeds its maximum size."),
+            MCTypeError::Truncated => write!(f, "Bytes end before the value does."),
+            MCTypeError::InvalidUtf8 => write!(f, "String is not valid UTF-8."),
+            MCTypeError::OversizeLength(len) => write!(f, "Length prefix {} is out of bounds.", len),
+            MCTypeError::InvalidValue(msg) => write!(f, "Invalid value: {}", msg),
+        }
+    }
+}
+
+impl Error for MCTypeError {}
+
+impl From<MCTypeError> for io::Error {
+    /// Converts the error to an `io::Error` of kind `UnexpectedEof` if the bytes were
+    /// truncated, or `InvalidData` otherwise.
+    fn from(err: MCTypeError) -> Self {
+        match err {
+            MCTypeError::Truncated => io::Error::new(io::ErrorKind::UnexpectedEof, err),
+            _ => io::Error::new(io::ErrorKind::InvalidData, err)
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl From<MCTypeError> for ProtocolError {
+    fn from(err: MCTypeError) -> Self {
+        match err {
+            MCTypeError::Truncated => ProtocolError::UnexpectedEof,
+            _ => ProtocolError::InvalidData(err.to_string())
+        }
+    }
+}
+
+/// Errors raised while authenticating a Microsoft account for online-mode servers.
+#[cfg(feature = "std")]
+#[derive(Debug)]
+pub enum AuthError {
+    /// A request to an authentication service failed, or was answered with an
+    /// unexpected status.
+    Http(String),
+    /// The user declined the sign-in on the device code page.
+    AuthorizationDeclined,
+    /// The device code expired before the user signed in.
+    DeviceCodeExpired,
+    /// The Microsoft account has no Xbox account, which must be created before it can
+    /// sign into Minecraft.
+    NoXboxAccount,
+    /// The Microsoft account belongs to a child, and must be added to a family by an adult.
+    ChildAccount,
+    /// The account does not own Minecraft, or has not yet created a profile.
+    NoMinecraftProfile,
+    /// An authentication service responded with data which could not be understood.
+    InvalidResponse(String),
+    /// A cached session could not be read or written.
+    Io(io::Error),
+    /// An encrypted credential store could not be decrypted, as the passphrase is wrong
+    /// or the store has been tampered with.
+    Decryption,
+}
+
+#[cfg(feature = "std")]
+impl fmt::Display for AuthError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AuthError::Http(msg) => write!(f, "Authentication request failed: {}", msg),
+            AuthError::AuthorizationDeclined => write!(f, "The user declined the sign-in."),
+            AuthError::DeviceCodeExpired => write!(f, "The device code expired before the user signed in."),
+            AuthError::NoXboxAccount => write!(f, "The Microsoft account has no Xbox account."),
+            AuthError::ChildAccount => write!(f, "The Microsoft account belongs to a child and must be added to a family."),
+            AuthError::NoMinecraftProfile => write!(f, "The account does not own Minecraft or has no profile."),
+            AuthError::InvalidResponse(msg) => write!(f, "Invalid authentication response: {}", msg),
+            AuthError::Io(err) => write!(f, "I/O error: {}", err),
+            AuthError::Decryption => write!(f, "The credential store could not be decrypted with the passphrase."),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl Error for AuthError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            AuthError::Io(err) => Some(err),
+            _ => None
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl From<io::Error> for AuthError {
+    fn from(err: io::Error) -> Self {
+        AuthError::Io(err)
+    }
+}
+
+#[cfg(feature = "std")]
+impl From<AuthError> for io::Error {
+    /// Converts the error to an `io::Error` of kind `PermissionDenied` if the account
+    /// cannot be signed in, or `Other` otherwise.
+    fn from(err: AuthError) -> Self {
+        match err {
+            AuthError::Io(err) => err,
+            AuthError::Http(_) | AuthError::InvalidResponse(_) => io::Error::other(err),
+            _ => io::Error::new(io::ErrorKind::PermissionDenied, err)
+        }
+    }
+}
@@ -0,0 +1,261 @@
+use std::{io, net::TcpStream};
+
+use uuid::Uuid;
+
+use super::{connection::{ConnectionState, MinecraftStream}, packet::{InboundPacket, MCPacket, PacketId}, packets::play::{Clientbound, clientbound::{BossBarAction, LegacyChatMessage, TitleAction, UpdateObjectives}}, registry::CANONICAL_VERSION, resource_pack::ResourcePackEvent, scoreboard::DisplaySlot, title, transport::Transport, version::ProtocolVersion};
+
+/// A high-level event of the game, interpreted from the packets of the Play state.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Event {
+    /// The server sent a message to the chat, or above the hotbar if `overlay` is set.
+    /// The `message` is a JSON text component, which carries its formatting. Messages sent
+    /// by players name their `sender`, whose display name is given as a JSON text
+    /// component in `sender_name`; servers older than 1.16 do not report senders.
+    ChatReceived { sender: Option<Uuid>, sender_name: Option<String>, message: String, overlay: bool },
+    /// The player's health, food or saturation changed.
+    HealthChanged { health: f32, food: i32, saturation: f32 },
+    /// A player was added to the tab list, including the player itself upon joining.
+    PlayerJoined { uuid: Uuid, name: String },
+    /// A player was removed from the tab list, such as when leaving the server.
+    PlayerLeft { uuid: Uuid },
+    /// The server processed the block actions of the player up to `sequence`, such as
+    /// digging and placing blocks, and its block changes are to be trusted over the
+    /// client's predictions.
+    BlockChangeAcknowledged { sequence: i32 },
+    /// The player died, and waits on the death screen until it respawns. The `message`
+    /// is a JSON text component.
+    Died { message: String },
+    /// The server asked the client to load a resource pack.
+    ResourcePackRequested(Box<ResourcePackEvent>),
+    /// An objective of the scoreboard was created or updated, or removed if `removed` is
+    /// set.
+    ObjectiveChanged { name: String, removed: bool },
+    /// The objective `name` is displayed in `slot`, or none is if it is `None`.
+    ObjectiveDisplayed { slot: DisplaySlot, name: Option<String> },
+    /// The score of `entity` in `objective` was set to `value`, or removed if it is
+    /// `None`. Scores removed from every objective have no `objective`.
+    ScoreChanged { entity: String, objective: Option<String>, value: Option<i32> },
+    /// A boss bar was shown or updated, or hidden if `removed` is set.
+    BossBarChanged { uuid: Uuid, removed: bool },
+    /// The server showed, hid or timed the title, or showed a message above the hotbar
+    /// with a title packet.
+    TitleChanged(Box<TitleAction>)
+}
+
+impl Event {
+    /// Interprets the events of the Play packet `packet`, if it has any, taking the packets
+    /// whose layout depends on the version to be in that of the canonical version.
+    pub fn from_packet(packet: &Clientbound) -> Vec<Event> {
+        Event::from_packet_for(packet, CANONICAL_VERSION)
+    }
+
+    /// Interprets the events of the Play packet `packet` of `version`, if it has any.
+    /// Packets which cannot be decoded in `version` have no events.
+    pub fn from_packet_for(packet: &Clientbound, version: ProtocolVersion) -> Vec<Event> {
+        match packet {
+            Clientbound::SystemChatMessage(chat) => vec![Event::ChatReceived {
+                sender: None,
+                sender_name: None,
+                message: chat.content.string().clone(),
+                overlay: chat.overlay.value()
+            }],
+            Clientbound::PlayerChatMessage(chat) => vec![Event::ChatReceived {
+                sender: Some(chat.sender),
+                sender_name: Some(chat.sender_name.string().clone()),
+                message: match &chat.unsigned_content {
+                    Some(content) => content.string().clone(),
+                    None => serde_json::json!({ "text": chat.message.string() }).to_string()
+                },
+                overlay: false
+            }],
+            Clientbound::DisguisedChatMessage(chat) => vec![Event::ChatReceived {
+                sender: None,
+                sender_name: Some(chat.chat_type_name.string().clone()),
+                message: chat.message.string().clone(),
+                overlay: false
+            }],
+            Clientbound::LegacyChatMessage(chat) => vec![Event::ChatReceived {
+                // System messages are sent by the nil UUID.
+                sender: chat.sender.filter(|sender| !sender.is_nil()),
+                sender_name: None,
+                message: chat.content.string().clone(),
+                overlay: chat.position == LegacyChatMessage::GAME_INFO
+            }],
+            Clientbound::SetHealth(health) => vec![Event::HealthChanged {
+                health: health.health.value(),
+                food: health.food,
+                saturation: health.food_saturation.value()
+            }],
+            Clientbound::PlayerInfoUpdate(update) => update.entries.iter()
+                .filter_map(|entry| entry.name.clone().map(|name| Event::PlayerJoined { uuid: entry.uuid, name }))
+                .collect(),
+            Clientbound::PlayerInfoRemove(remove) => remove.uuids.iter().map(|uuid| Event::PlayerLeft { uuid: *uuid }).collect(),
+            Clientbound::AcknowledgeBlockChange(ack) => vec![Event::BlockChangeAcknowledged { sequence: ack.sequence_id.value() }],
+            Clientbound::CombatDeath(death) => match death.death(version) {
+                Ok(Some(death)) => vec![Event::Died { message: death.message.to_json() }],
+                _ => vec![]
+            },
+            Clientbound::ResourcePack(push) => match push.pack(version) {
+                Ok(pack) => vec![Event::ResourcePackRequested(Box::new(pack))],
+                Err(_) => vec![]
+            },
+            Clientbound::UpdateObjectives(update) => match update.change(version) {
+                Ok(change) => vec![Event::ObjectiveChanged { name: change.name, removed: change.mode == UpdateObjectives::REMOVE }],
+                Err(_) => vec![]
+            },
+            Clientbound::DisplayObjective(display) => match DisplaySlot::from_id(display.position) {
+                Some(slot) => vec![Event::ObjectiveDisplayed {
+                    slot,
+                    name: Some(display.score_name.string().clone()).filter(|name| !name.is_empty())
+                }],
+                None => vec![]
+            },
+            Clientbound::UpdateScore(update) => match update.change(version) {
+                Ok(change) => vec![Event::ScoreChanged { entity: change.entity, objective: change.objective, value: change.value }],
+                Err(_) => vec![]
+            },
+            Clientbound::ResetScore(reset) => vec![Event::ScoreChanged {
+                entity: reset.entity.string().clone(),
+                objective: reset.objective.as_ref().map(|objective| objective.string().clone()),
+                value: None
+            }],
+            Clientbound::BossBar(bar) => match bar.action(version) {
+                Ok(action) => vec![Event::BossBarChanged { uuid: bar.uuid, removed: action == BossBarAction::Remove }],
+                Err(_) => vec![]
+            },
+            _ => match title::action_of(packet, version) {
+                Ok(Some(action)) => vec![Event::TitleChanged(Box::new(action))],
+                _ => vec![]
+            }
+        }
+    }
+}
+
+/// Receives the packets and events dispatched by an `EventDispatcher`. Every method has
+/// an empty default, so listeners only implement those they need.
+pub trait EventListener<T = TcpStream> {
+    /// Called with every packet received, before its events.
+    /// # Errors
+    /// An error returned stops the dispatcher, which returns it.
+    fn on_packet(&mut self, _stream: &mut MinecraftStream<T>, _packet: &MCPacket) -> io::Result<()> {
+        Ok(())
+    }
+
+    /// Called with every event received.
+    /// # Errors
+    /// An error returned stops the dispatcher, which returns it.
+    fn on_event(&mut self, _stream: &mut MinecraftStream<T>, _event: &Event) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+type PacketHandler<T> = Box<dyn FnMut(&mut MinecraftStream<T>, &MCPacket) -> io::Result<()>>;
+type EventHandler<T> = Box<dyn FnMut(&mut MinecraftStream<T>, &Event) -> io::Result<()>>;
+
+/// Dispatches the packets received on a stream to the handlers registered for them, and
+/// the events of Play packets to the event handlers. Handlers are given the stream, so
+/// they may answer the server.
+/// # Example
+/// ```no_run
+/// use mcclient::{Client, mc::{connection::Connection, events::{Event, EventDispatcher}, packet::clientbound::set_health::SetHealth}};
+///
+/// let mut client = Client::login_offline("localhost", 25565, "Makoto").expect("Could not join");
+/// let mut events: EventDispatcher = EventDispatcher::new();
+/// events.on_event(|_, event| {
+///     if let Event::ChatReceived { message, .. } = event {
+///         println!("{}", message);
+///     }
+///     Ok(())
+/// });
+/// events.on_packet(|_, health: &SetHealth| {
+///     println!("Health: {}", health.health.value());
+///     Ok(())
+/// });
+/// let err = events.run(client.connection().sock());
+/// println!("Stopped: {}", err);
+/// ```
+pub struct EventDispatcher<T = TcpStream> {
+    packet_handlers: Vec<(ConnectionState, i32, PacketHandler<T>)>,
+    event_handlers: Vec<EventHandler<T>>,
+    listeners: Vec<Box<dyn EventListener<T>>>
+}
+
+impl<T: Transport> Default for EventDispatcher<T> {
+    fn default() -> Self {
+        EventDispatcher::new()
+    }
+}
+
+impl<T: Transport> EventDispatcher<T> {
+    /// Creates a dispatcher without handlers.
+    pub fn new() -> Self {
+        EventDispatcher { packet_handlers: Vec::new(), event_handlers: Vec::new(), listeners: Vec::new() }
+    }
+
+    /// Registers `handler` for the packets of type `P`, which are decoded before they are
+    /// handed to it.
+    pub fn on_packet<P, F>(&mut self, mut handler: F)
+    where P: InboundPacket + PacketId, F: FnMut(&mut MinecraftStream<T>, &P) -> io::Result<()> + 'static {
+        self.packet_handlers.push((P::STATE, P::ID, Box::new(move |stream, packet| handler(stream, &P::from_data(packet)?))));
+    }
+
+    /// Registers `handler` for every event.
+    pub fn on_event<F>(&mut self, handler: F)
+    where F: FnMut(&mut MinecraftStream<T>, &Event) -> io::Result<()> + 'static {
+        self.event_handlers.push(Box::new(handler));
+    }
+
+    /// Registers `listener` for every packet and event.
+    pub fn add_listener<L: EventListener<T> + 'static>(&mut self, listener: L) {
+        self.listeners.push(Box::new(listener));
+    }
+
+    /// Dispatches `packet`, received on `stream`, to the handlers registered for it, and
+    /// the events it carries to the event handlers. Events are only interpreted from the
+    /// packets of the Play state.
+    /// # Errors
+    /// This function will return the first error returned by a handler, or an
+    /// `InvalidData` error if a packet which is handled cannot be parsed.
+    pub fn dispatch(&mut self, stream: &mut MinecraftStream<T>, packet: MCPacket) -> io::Result<()> {
+        let state = stream.state();
+        let id = packet.header.id.value();
+        for (_, _, handler) in self.packet_handlers.iter_mut().filter(|(handled_state, handled_id, _)| (*handled_state, *handled_id) == (state, id)) {
+            handler(stream, &packet)?;
+        }
+        for listener in &mut self.listeners {
+            listener.on_packet(stream, &packet)?;
+        }
+
+        if state != ConnectionState::Play || (self.event_handlers.is_empty() && self.listeners.is_empty()) {
+            return Ok(());
+        }
+        for event in Event::from_packet_for(&Clientbound::decode(packet)?, stream.version()) {
+            for handler in &mut self.event_handlers {
+                handler(stream, &event)?;
+            }
+            for listener in &mut self.listeners {
+                listener.on_event(stream, &event)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Reads the next packet from `stream`, and dispatches it.
+    /// # Errors
+    /// This function will return an error if the packet cannot be read, or as per `dispatch`.
+    pub fn poll(&mut self, stream: &mut MinecraftStream<T>) -> io::Result<()> {
+        let packet = stream.read()?;
+        self.dispatch(stream, packet)
+    }
+
+    /// Reads and dispatches packets from `stream` until an error occurs, such as the server
+    /// disconnecting the player or a handler returning an error, and returns the error.
+    pub fn run(&mut self, stream: &mut MinecraftStream<T>) -> io::Error {
+        loop {
+            if let Err(err) = self.poll(stream) {
+                return err;
+            }
+        }
+    }
+}
@@ -0,0 +1,162 @@
+//! The subset of `std::io` the protocol types and NBT are encoded and decoded with, for
+//! builds without the `std` feature. The names and behaviour follow `std::io`, so that the
+//! same code builds against either.
+use alloc::{string::String, vec::Vec};
+use core::fmt;
+
+/// The result of an I/O operation.
+pub type Result<T> = core::result::Result<T, Error>;
+
+/// The kinds of `Error`, as in `std::io::ErrorKind`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum ErrorKind {
+    InvalidInput,
+    InvalidData,
+    UnexpectedEof,
+    WriteZero,
+    Other
+}
+
+/// An error of an I/O operation, carrying its kind and a message.
+#[derive(Debug)]
+pub struct Error {
+    kind: ErrorKind,
+    message: String
+}
+
+impl Error {
+    /// Creates an error of `kind` described by `message`.
+    pub fn new<M: fmt::Display>(kind: ErrorKind, message: M) -> Self {
+        Error { kind, message: alloc::format!("{}", message) }
+    }
+
+    /// Gets the kind of the error.
+    pub fn kind(&self) -> ErrorKind {
+        self.kind
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+/// A sink of bytes, as in `std::io::Write`.
+pub trait Write {
+    /// Writes some of `buf`, returning how many bytes were written.
+    fn write(&mut self, buf: &[u8]) -> Result<usize>;
+
+    /// Flushes the bytes written so far out of any buffer.
+    fn flush(&mut self) -> Result<()>;
+
+    /// Writes all of `buf`.
+    fn write_all(&mut self, mut buf: &[u8]) -> Result<()> {
+        while !buf.is_empty() {
+            match self.write(buf)? {
+                0 => return Err(Error::new(ErrorKind::WriteZero, "failed to write whole buffer")),
+                written => buf = &buf[written..]
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Write for Vec<u8> {
+    fn write(&mut self, buf: &[u8]) -> Result<usize> {
+        self.extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> Result<()> {
+        Ok(())
+    }
+}
+
+impl<W: Write + ?Sized> Write for &mut W {
+    fn write(&mut self, buf: &[u8]) -> Result<usize> {
+        (**self).write(buf)
+    }
+
+    fn flush(&mut self) -> Result<()> {
+        (**self).flush()
+    }
+}
+
+/// A source of bytes, as in `std::io::Read`.
+pub trait Read {
+    /// Reads some bytes into `buf`, returning how many were read, or 0 at the end.
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize>;
+
+    /// Reads exactly enough bytes to fill `buf`.
+    fn read_exact(&mut self, mut buf: &mut [u8]) -> Result<()> {
+        while !buf.is_empty() {
+            match self.read(buf)? {
+                0 => return Err(Error::new(ErrorKind::UnexpectedEof, "failed to fill whole buffer")),
+                read => buf = &mut buf[read..]
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Read for &[u8] {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+        let read = buf.len().min(self.len());
+        let (front, rest) = self.split_at(read);
+        buf[..read].copy_from_slice(front);
+        *self = rest;
+        Ok(read)
+    }
+}
+
+impl<R: Read + ?Sized> Read for &mut R {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+        (**self).read(buf)
+    }
+}
+
+/// A position within a buffer, as in `std::io::Cursor`.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Cursor<T> {
+    inner: T,
+    position: u64
+}
+
+impl<T> Cursor<T> {
+    /// Creates a cursor at the start of `inner`.
+    pub fn new(inner: T) -> Self {
+        Cursor { inner, position: 0 }
+    }
+
+    /// Gets the position of the cursor within the buffer.
+    pub fn position(&self) -> u64 {
+        self.position
+    }
+
+    /// Moves the cursor to `position`.
+    pub fn set_position(&mut self, position: u64) {
+        self.position = position;
+    }
+
+    /// Gets the buffer.
+    pub fn get_ref(&self) -> &T {
+        &self.inner
+    }
+
+    /// Consumes the cursor, returning the buffer.
+    pub fn into_inner(self) -> T {
+        self.inner
+    }
+}
+
+impl<T: AsRef<[u8]>> Read for Cursor<T> {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+        let bytes = self.inner.as_ref();
+        let start = (self.position as usize).min(bytes.len());
+        let read = (&bytes[start..]).read(buf)?;
+        self.position += read as u64;
+        Ok(read)
+    }
+}
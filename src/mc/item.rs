@@ -0,0 +1,282 @@
+use std::io::{self, Write};
+
+use super::{chat::ChatComponent, error::MCTypeError, mctypes::{MCBoolean, MCByte, MCInt, MCShort, MCType, VarInt}, nbt::NbtTag, version::ProtocolVersion};
+
+/// The first protocol version (1.13) identifying items by a `VarInt` behind a presence flag.
+const FLATTENED_SINCE: i32 = 393;
+
+/// The first protocol version (1.20.5) describing items by structured components rather
+/// than NBT.
+pub const STRUCTURED_COMPONENTS_SINCE: i32 = 766;
+
+/// The IDs of the item components of 1.20.5 whose layout is known to this library.
+pub mod component {
+    pub const CUSTOM_DATA: i32 = 0;
+    pub const MAX_STACK_SIZE: i32 = 1;
+    pub const MAX_DAMAGE: i32 = 2;
+    pub const DAMAGE: i32 = 3;
+    pub const UNBREAKABLE: i32 = 4;
+    pub const CUSTOM_NAME: i32 = 5;
+    pub const ITEM_NAME: i32 = 6;
+    pub const LORE: i32 = 7;
+    pub const RARITY: i32 = 8;
+    pub const ENCHANTMENTS: i32 = 9;
+    pub const CUSTOM_MODEL_DATA: i32 = 13;
+    pub const HIDE_ADDITIONAL_TOOLTIP: i32 = 14;
+    pub const HIDE_TOOLTIP: i32 = 15;
+    pub const REPAIR_COST: i32 = 16;
+    pub const CREATIVE_SLOT_LOCK: i32 = 17;
+    pub const ENCHANTMENT_GLINT_OVERRIDE: i32 = 18;
+    pub const STORED_ENCHANTMENTS: i32 = 23;
+    pub const DYED_COLOR: i32 = 24;
+    pub const MAP_COLOR: i32 = 25;
+    pub const MAP_ID: i32 = 26;
+}
+
+/// A component of an item since 1.20.5, kept as its encoded `data`. The layout of the
+/// data depends on the component's type `id`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ItemComponent {
+    pub id: i32,
+    pub data: Vec<u8>
+}
+
+/// A stack of items, as held in an inventory slot.
+/// <br> <br>
+/// Before 1.20.5, the properties of an item, such as its name and enchantments, are
+/// given by its `nbt`. Since 1.20.5, they are given by its `components`, which are added
+/// to or `removed_components` from the defaults of the item.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ItemStack {
+    /// The ID of the item in the item registry of the server's version.
+    pub item_id: i32,
+    pub count: i32,
+    /// The data value of the item, which servers before 1.13 send apart from its ID,
+    /// such as the damage of a tool or the color of wool.
+    pub damage: i16,
+    pub nbt: Option<NbtTag>,
+    pub components: Vec<ItemComponent>,
+    pub removed_components: Vec<i32>
+}
+
+impl ItemStack {
+    /// Creates a stack of `count` items of `item_id`, without properties.
+    pub fn new(item_id: i32, count: i32) -> Self {
+        ItemStack { item_id, count, ..Default::default() }
+    }
+
+    /// Gets the component of type `id` added to the item, if it has one.
+    pub fn component(&self, id: i32) -> Option<&ItemComponent> {
+        self.components.iter().find(|component| component.id == id)
+    }
+
+    /// Gets the name the item was renamed to, if it was. This is read from the
+    /// `custom_name` component since 1.20.5, and from the `display.Name` NBT tag before.
+    pub fn custom_name(&self) -> Option<ChatComponent> {
+        if let Some(component) = self.component(component::CUSTOM_NAME) {
+            let (name, _) = NbtTag::parse_network(&component.data).ok()?;
+            return name?.deserialize_into().ok();
+        }
+
+        let name = self.nbt.as_ref()?.get("display")?.get("Name")?.as_str()?;
+        // Names were plain text before 1.13, which rarely parses as a component.
+        Some(ChatComponent::from_json(name).unwrap_or_else(|_| ChatComponent::text(name)))
+    }
+}
+
+/// The contents of an inventory slot, which may be empty.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Slot {
+    pub item: Option<ItemStack>
+}
+
+impl From<Option<ItemStack>> for Slot {
+    fn from(item: Option<ItemStack>) -> Self {
+        Slot { item }
+    }
+}
+
+impl From<ItemStack> for Slot {
+    fn from(item: ItemStack) -> Self {
+        Slot { item: Some(item) }
+    }
+}
+
+impl Slot {
+    /// An empty slot.
+    pub const EMPTY: Slot = Slot { item: None };
+
+    pub fn is_empty(&self) -> bool {
+        self.item.is_none()
+    }
+
+    /// Parses a slot encoded in the format of `version` from the front of `bytes`,
+    /// returning the slot along with the bytes following it.
+    /// # Errors
+    /// This function will return an `MCTypeError` if the bytes do not encode a slot. Since
+    /// 1.20.5, this includes items with components whose layout is not known to this
+    /// library, which cannot be skipped.
+    pub fn parse_for(bytes: &[u8], version: ProtocolVersion) -> Result<(Self, &[u8]), MCTypeError> {
+        if version.number() >= STRUCTURED_COMPONENTS_SINCE {
+            return Slot::parse_components(bytes, version);
+        }
+
+        let (item_id, count, damage, rest) = match version.number() >= FLATTENED_SINCE {
+            true => {
+                let (present, rest) = MCBoolean::parse(bytes)?;
+                if !present.value() {
+                    return Ok((Slot::EMPTY, rest));
+                }
+                let (item_id, rest) = VarInt::parse(rest)?;
+                let (count, rest) = MCByte::parse(rest)?;
+                (item_id.value(), count.value(), 0, rest)
+            },
+            false => {
+                let (item_id, rest) = MCShort::parse(bytes)?;
+                if item_id.value() < 0 {
+                    return Ok((Slot::EMPTY, rest));
+                }
+                let (count, rest) = MCByte::parse(rest)?;
+                let (damage, rest) = MCShort::parse(rest)?;
+                (item_id.value() as i32, count.value(), damage.value(), rest)
+            }
+        };
+
+        let (nbt, rest) = NbtTag::parse_for(rest, version)?;
+        Ok((ItemStack { item_id, count: count as i32, damage, nbt, ..Default::default() }.into(), rest))
+    }
+
+    fn parse_components(bytes: &[u8], version: ProtocolVersion) -> Result<(Self, &[u8]), MCTypeError> {
+        let (count, rest) = VarInt::parse(bytes)?;
+        if count.value() <= 0 {
+            return Ok((Slot::EMPTY, rest));
+        }
+
+        let (item_id, rest) = VarInt::parse(rest)?;
+        let (added, rest) = VarInt::parse(rest)?;
+        let (removed, mut rest) = VarInt::parse(rest)?;
+        if added.value() < 0 || removed.value() < 0 {
+            return Err(MCTypeError::OversizeLength(added.value().min(removed.value()) as i64));
+        }
+
+        let mut stack = ItemStack::new(item_id.value(), count.value());
+        for _ in 0..added.value() {
+            let (id, data) = VarInt::parse(rest)?;
+            let after = skip_component(id.value(), data, version)?;
+            stack.components.push(ItemComponent { id: id.value(), data: data[..data.len() - after.len()].to_vec() });
+            rest = after;
+        }
+        for _ in 0..removed.value() {
+            let (id, after) = VarInt::parse(rest)?;
+            stack.removed_components.push(id.value());
+            rest = after;
+        }
+
+        Ok((stack.into(), rest))
+    }
+
+    /// Encodes this slot in the format of `version`.
+    /// # Errors
+    /// This function will return an `InvalidInput` error if the item cannot be represented
+    /// in `version`, such as an ID or count out of range, or an error if `w` cannot be
+    /// written to.
+    pub fn write_for(&self, version: ProtocolVersion, w: &mut dyn Write) -> io::Result<()> {
+        let out_of_range = |field: &str| io::Error::new(io::ErrorKind::InvalidInput, format!("Item {} is out of range for the protocol version.", field));
+
+        let Some(stack) = &self.item else {
+            return match version.number() {
+                number if number >= STRUCTURED_COMPONENTS_SINCE => VarInt::from(0).encode(w),
+                number if number >= FLATTENED_SINCE => MCBoolean::from(false).encode(w),
+                _ => MCShort::from(-1).encode(w)
+            };
+        };
+
+        if version.number() >= STRUCTURED_COMPONENTS_SINCE {
+            VarInt::from(stack.count).encode(w)?;
+            VarInt::from(stack.item_id).encode(w)?;
+            VarInt::from(stack.components.len() as i32).encode(w)?;
+            VarInt::from(stack.removed_components.len() as i32).encode(w)?;
+            for component in &stack.components {
+                VarInt::from(component.id).encode(w)?;
+                w.write_all(&component.data)?;
+            }
+            for id in &stack.removed_components {
+                VarInt::from(*id).encode(w)?;
+            }
+            return Ok(());
+        }
+
+        let count = i8::try_from(stack.count).map_err(|_| out_of_range("count"))?;
+        if version.number() >= FLATTENED_SINCE {
+            MCBoolean::from(true).encode(w)?;
+            VarInt::from(stack.item_id).encode(w)?;
+            MCByte::from(count).encode(w)?;
+        } else {
+            MCShort::from(i16::try_from(stack.item_id).map_err(|_| out_of_range("ID"))?).encode(w)?;
+            MCByte::from(count).encode(w)?;
+            MCShort::from(stack.damage).encode(w)?;
+        }
+
+        NbtTag::write_optional_for(stack.nbt.as_ref(), version, w)
+    }
+
+    /// Encodes this slot in the format of `version`, as per `write_for`.
+    /// # Errors
+    /// This function will return the errors of `write_for`.
+    pub fn to_bytes_for(&self, version: ProtocolVersion) -> io::Result<Vec<u8>> {
+        let mut bytes = Vec::new();
+        self.write_for(version, &mut bytes)?;
+        Ok(bytes)
+    }
+}
+
+/// Slots are encoded in the format of the canonical version.
+impl MCType for Slot {
+    fn encode(&self, w: &mut dyn Write) -> io::Result<()> {
+        self.write_for(ProtocolVersion::from(super::PROTOCOL_VERSION), w)
+    }
+
+    fn size(&self) -> i32 {
+        self.to_bytes_for(ProtocolVersion::from(super::PROTOCOL_VERSION)).map_or(0, |bytes| bytes.len() as i32)
+    }
+
+    fn parse(bytes: &[u8]) -> Result<(Self, &[u8]), MCTypeError> {
+        Slot::parse_for(bytes, ProtocolVersion::from(super::PROTOCOL_VERSION))
+    }
+}
+
+/// Skips the data of the component of type `id` at the front of `bytes`, returning the
+/// bytes following it.
+fn skip_component(id: i32, bytes: &[u8], version: ProtocolVersion) -> Result<&[u8], MCTypeError> {
+    let nbt = |bytes| NbtTag::parse_for(bytes, version).map(|(_, rest)| rest);
+    let varint = |bytes| VarInt::parse(bytes).map(|(_, rest)| rest);
+    let boolean = |bytes| MCBoolean::parse(bytes).map(|(_, rest)| rest);
+
+    match id {
+        component::CUSTOM_DATA | component::CUSTOM_NAME | component::ITEM_NAME => nbt(bytes),
+        component::MAX_STACK_SIZE | component::MAX_DAMAGE | component::DAMAGE | component::RARITY
+        | component::CUSTOM_MODEL_DATA | component::REPAIR_COST | component::MAP_ID => varint(bytes),
+        component::UNBREAKABLE | component::ENCHANTMENT_GLINT_OVERRIDE => boolean(bytes),
+        component::HIDE_ADDITIONAL_TOOLTIP | component::HIDE_TOOLTIP | component::CREATIVE_SLOT_LOCK => Ok(bytes),
+        component::LORE => {
+            let (lines, mut rest) = VarInt::parse(bytes)?;
+            for _ in 0..lines.value() {
+                rest = nbt(rest)?;
+            }
+            Ok(rest)
+        },
+        component::ENCHANTMENTS | component::STORED_ENCHANTMENTS => {
+            let (enchantments, mut rest) = VarInt::parse(bytes)?;
+            for _ in 0..enchantments.value() {
+                rest = varint(varint(rest)?)?;
+            }
+            boolean(rest)
+        },
+        component::DYED_COLOR => boolean(MCInt::parse(bytes)?.1),
+        component::MAP_COLOR => Ok(MCInt::parse(bytes)?.1),
+        _ => Err(MCTypeError::InvalidValue(format!("Item component {} has an unknown layout.", id)))
+    }
+}
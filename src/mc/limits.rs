@@ -0,0 +1,154 @@
+//! Limits on the sizes a server may declare for what the client decodes, so that a broken
+//! or malicious server cannot make the client allocate gigabytes with a single length
+//! prefix. Lengths over a limit fail to decode, rather than being allocated for.
+#[cfg(any(feature = "std", feature = "wasm"))]
+use core::{cell::Cell, marker::PhantomData};
+
+#[cfg(any(feature = "std", feature = "wasm"))]
+use alloc::{format, string::String};
+
+use super::{error::MCTypeError, mctypes::MCString, nbt};
+
+/// The limits enforced while decoding packets, which default to the limits of the vanilla
+/// client. Each stream checks the frames it reads against its own limits, as set with
+/// `MinecraftStream::set_decode_limits` or `ClientBuilder::decode_limits`. The strings,
+/// arrays and NBT within a packet are decoded away from the stream it was read from, so
+/// they are checked against the limits `enter`ed on the decoding thread instead, which a
+/// `Client` enters with the limits of its stream while it handles a packet.
+/// # Example
+/// ```
+/// use mcclient::mc::{limits::DecodeLimits, mctypes::{MCArray, MCByte, MCTypeDecode, VarInt, MCType}};
+///
+/// let limits = DecodeLimits { max_array_length: 2, ..DecodeLimits::default() };
+/// let mut bytes = VarInt::from(3).to_bytes();
+/// bytes.extend([1, 2, 3]);
+/// {
+///     let _limits = limits.enter();
+///     assert!(MCArray::<MCByte>::parse(&bytes).is_err());
+/// }
+/// assert!(MCArray::<MCByte>::parse(&bytes).is_ok());
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DecodeLimits {
+    /// The most bytes a frame may span on the wire, after its length prefix.
+    pub max_packet_size: usize,
+    /// The most bytes a compressed packet may decompress to.
+    pub max_uncompressed_size: usize,
+    /// The most characters a string may hold, even if its field allows more.
+    pub max_string_length: usize,
+    /// The most elements an array, a list or a palette may declare.
+    pub max_array_length: usize,
+    /// The most tags which may be nested within one another in NBT.
+    pub max_nbt_depth: usize
+}
+
+impl DecodeLimits {
+    /// The largest frame the vanilla client reads, whose length fits in a 3-byte `VarInt`.
+    pub const DEFAULT_MAX_PACKET_SIZE: usize = 2_097_151;
+    /// The most bytes the vanilla server and client let a compressed packet decompress to.
+    pub const DEFAULT_MAX_UNCOMPRESSED_SIZE: usize = 8_388_608;
+    /// The most elements an array may declare by default, which no vanilla packet nears.
+    pub const DEFAULT_MAX_ARRAY_LENGTH: usize = 1 << 20;
+
+    /// Gets the limits entered on this thread, or the defaults if none are.
+    pub fn current() -> Self {
+        #[cfg(any(feature = "std", feature = "wasm"))]
+        if let Some(limits) = ENTERED.with(Cell::get) {
+            return limits;
+        }
+        DecodeLimits::default()
+    }
+
+    /// Checks what is decoded on this thread against these limits until the returned
+    /// guard is dropped, after which the limits entered before apply again.
+    #[cfg(any(feature = "std", feature = "wasm"))]
+    pub fn enter(self) -> EnteredLimits {
+        EnteredLimits { previous: ENTERED.with(|entered| entered.replace(Some(self))), thread: PhantomData }
+    }
+
+    /// Checks the length prefix of a frame against these limits, returning it as a `usize`.
+    #[cfg(any(feature = "std", feature = "wasm"))]
+    pub(crate) fn packet_size(&self, length: i32) -> Result<usize, String> {
+        match usize::try_from(length) {
+            Ok(length) if length <= self.max_packet_size => Ok(length),
+            Ok(length) => Err(format!("Packet length {} exceeds the limit of {} bytes.", length, self.max_packet_size)),
+            Err(_) => Err(format!("Negative packet length {}.", length))
+        }
+    }
+
+    /// Checks the length a compressed packet claims to decompress to against these limits.
+    #[cfg(feature = "std")]
+    pub(crate) fn uncompressed_size(&self, length: i32) -> Result<usize, String> {
+        match usize::try_from(length) {
+            Ok(length) if length <= self.max_uncompressed_size => Ok(length),
+            _ => Err(format!("Uncompressed packet length {} is out of bounds.", length))
+        }
+    }
+}
+
+impl Default for DecodeLimits {
+    fn default() -> Self {
+        DecodeLimits {
+            max_packet_size: DecodeLimits::DEFAULT_MAX_PACKET_SIZE,
+            max_uncompressed_size: DecodeLimits::DEFAULT_MAX_UNCOMPRESSED_SIZE,
+            max_string_length: MCString::MAX_LENGTH as usize,
+            max_array_length: DecodeLimits::DEFAULT_MAX_ARRAY_LENGTH,
+            max_nbt_depth: nbt::MAX_DEPTH
+        }
+    }
+}
+
+#[cfg(any(feature = "std", feature = "wasm"))]
+std::thread_local! {
+    static ENTERED: Cell<Option<DecodeLimits>> = const { Cell::new(None) };
+}
+
+/// Limits entered on a thread with `DecodeLimits::enter`, which are left when dropped.
+#[cfg(any(feature = "std", feature = "wasm"))]
+#[must_use = "The limits are left as soon as the guard is dropped."]
+pub struct EnteredLimits {
+    previous: Option<DecodeLimits>,
+    /// Keeps the guard on the thread whose limits it restores.
+    thread: PhantomData<*const ()>
+}
+
+#[cfg(any(feature = "std", feature = "wasm"))]
+impl Drop for EnteredLimits {
+    fn drop(&mut self) {
+        ENTERED.with(|entered| entered.set(self.previous));
+    }
+}
+
+/// Checks the length prefix of a frame against the current limits, returning it as a
+/// `usize`.
+#[cfg(any(feature = "std", feature = "wasm"))]
+pub(crate) fn packet_size(length: i32) -> Result<usize, String> {
+    DecodeLimits::current().packet_size(length)
+}
+
+/// Checks the length a compressed packet claims to decompress to against the current limits.
+#[cfg(feature = "std")]
+pub(crate) fn uncompressed_size(length: i32) -> Result<usize, String> {
+    DecodeLimits::current().uncompressed_size(length)
+}
+
+/// Lowers the `max_length` of a string field to the current limits.
+pub(crate) fn string_length(max_length: i32) -> i32 {
+    let limit = DecodeLimits::current().max_string_length.min(i32::MAX as usize) as i32;
+    max_length.min(limit)
+}
+
+/// Checks the element count an array declares against the current limits, returning it
+/// as a `usize`.
+pub(crate) fn array_length(count: i32) -> Result<usize, MCTypeError> {
+    match usize::try_from(count) {
+        Ok(count) if count <= DecodeLimits::current().max_array_length => Ok(count),
+        _ => Err(MCTypeError::OversizeLength(count as i64))
+    }
+}
+
+/// Gets the most tags which may be nested within one another in NBT, as per the current
+/// limits.
+pub(crate) fn nbt_depth() -> usize {
+    DecodeLimits::current().max_nbt_depth
+}
@@ -0,0 +1,105 @@
+use super::{mctypes::{Location, VarInt}, packets::play::{Clientbound, clientbound::GameEvent}};
+
+/// The health of a player which has just joined, before the server sends its own.
+const FULL_HEALTH: f32 = 20.0;
+/// The food level of a player which has just joined, before the server sends its own.
+const FULL_FOOD: i32 = 20;
+/// The game mode of creative players.
+const CREATIVE: u8 = 1;
+
+/// The state of the player the client plays as, kept up to date with the Set Health,
+/// Set Experience, Synchronize Player Position and Game Event packets sent by the server.
+#[derive(Clone, Debug, PartialEq)]
+pub struct LocalPlayer {
+    /// The ID of the player's entity, as given by Login Play.
+    pub entity_id: i32,
+    /// The health of the player, from 0 when it has died to 20 and above.
+    pub health: f32,
+    /// The food level of the player, from 0 to 20.
+    pub food: i32,
+    pub saturation: f32,
+    /// The progress towards the next experience level, from 0 to 1.
+    pub experience_bar: f32,
+    pub experience_level: i32,
+    pub total_experience: i32,
+    /// The game mode of the player: survival (0), creative (1), adventure (2) or
+    /// spectator (3). This is only known once the server changes it with a Game Event.
+    pub game_mode: Option<u8>,
+    /// The location of the player, as last set by the server or the client.
+    pub location: Location,
+    pub on_ground: bool,
+    teleport_id: Option<i32>
+}
+
+impl Default for LocalPlayer {
+    fn default() -> Self {
+        LocalPlayer {
+            entity_id: 0,
+            health: FULL_HEALTH,
+            food: FULL_FOOD,
+            saturation: 0.0,
+            experience_bar: 0.0,
+            experience_level: 0,
+            total_experience: 0,
+            game_mode: None,
+            location: Location::default(),
+            on_ground: false,
+            teleport_id: None
+        }
+    }
+}
+
+impl LocalPlayer {
+    /// Creates a player at the origin, with full health and food.
+    pub fn new() -> Self {
+        LocalPlayer::default()
+    }
+
+    /// Whether the player is in creative mode, in which it breaks blocks at once.
+    pub fn is_creative(&self) -> bool {
+        self.game_mode == Some(CREATIVE)
+    }
+
+    /// Whether the player has died, and should respawn.
+    pub fn is_dead(&self) -> bool {
+        self.health <= 0.0
+    }
+
+    /// The ID of the last teleport of the player, which the client confirms with a Confirm
+    /// Teleportation. Servers of 1.8 do not number teleports.
+    pub fn teleport_id(&self) -> Option<i32> {
+        self.teleport_id
+    }
+
+    /// Applies the Play packet `packet` to the player, if it concerns it. Returns whether
+    /// the server teleported the player, in which case the client should confirm the
+    /// teleport and send its new position back, as `Client::next_packet` does.
+    pub fn handle(&mut self, packet: &Clientbound) -> bool {
+        match packet {
+            Clientbound::LoginPlay(login) => {
+                self.entity_id = login.entity_id.value();
+            },
+            Clientbound::SetHealth(health) => {
+                self.health = health.health.value();
+                self.food = health.food;
+                self.saturation = health.food_saturation.value();
+            },
+            Clientbound::SetExperience(experience) => {
+                self.experience_bar = experience.bar.value();
+                self.experience_level = experience.level;
+                self.total_experience = experience.total;
+            },
+            Clientbound::GameEvent(event) if event.event.value() == GameEvent::CHANGE_GAME_MODE => {
+                self.game_mode = Some(event.value.value() as u8);
+            },
+            Clientbound::SynchronizePlayerPosition(teleport) => {
+                self.location = teleport.flags.apply(&self.location, &teleport.location);
+                self.on_ground = false;
+                self.teleport_id = teleport.teleport_id.as_ref().map(VarInt::value);
+                return true;
+            },
+            _ => ()
+        }
+        false
+    }
+}
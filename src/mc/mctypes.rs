@@ -1,9 +1,23 @@
-use std::io;
+use alloc::{borrow::ToOwned, format, string::{String, ToString}, vec, vec::Vec};
+use core::{fmt, str::FromStr};
+
+use uuid::Uuid;
+
+use super::{error::MCTypeError, io::{self, Cursor, Read, Write}, limits, version::ProtocolVersion};
 
 pub trait MCType {
+    /// Encodes this `MCType` according to its Minecraft protocol packet structure,
+    /// writing it directly to `w` without an intermediate buffer.
+    /// # Errors
+    /// This function will return an `io::Error` if `w` cannot be written to.
+    fn encode(&self, w: &mut dyn Write) -> io::Result<()>;
     /// Copies the data of this `MCType` and encodes it according to its
     /// Minecraft protocol packet structure.
-    fn to_bytes(&self) -> Vec<u8>;
+    fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::<u8>::with_capacity(self.size().max(0) as usize);
+        self.encode(&mut bytes).expect("Writing to a Vec cannot fail.");
+        bytes
+    }
     /// Gets the bytesize of the serialized version this `MCType`.
     /// # Examples
     /// ```
@@ -15,35 +29,166 @@ pub trait MCType {
     /// // i.e., 6 + [6].len() = 7
     /// ```
     fn size(&self) -> i32;
+    /// Parses this `MCType` from the front of `bytes`, returning the value along with
+    /// the bytes following it. Values can be parsed one after another by passing the
+    /// remainder of one parse to the next.
+    /// # Errors
+    /// This function will return an `MCTypeError` describing why the leading bytes do
+    /// not encode this `MCType`.
+    fn parse(bytes: &[u8]) -> Result<(Self, &[u8]), MCTypeError> where Self: Sized;
+    /// Decodes this `MCType` from the front of `bytes`, ignoring any bytes following it.
+    /// # Errors
+    /// This function will return an `MCTypeError` describing why the leading bytes do
+    /// not encode this `MCType`.
+    fn decode(bytes: &[u8]) -> Result<Self, MCTypeError> where Self: Sized {
+        Ok(Self::parse(bytes)?.0)
+    }
+}
+
+/// Decodes `MCType`s field-by-field from a cursor over packet bytes. Each read
+/// advances the cursor past the bytes the value consumed, so the fields of a
+/// packet body can be read in order. This is implemented for every `MCType`.
+/// # Examples
+/// ```
+/// use mcclient::mc::io::Cursor;
+/// use mcclient::mc::mctypes::{MCTypeDecode, MCString, VarInt};
+///
+/// let bytes = [0x06, b'M', b'a', b'k', b'o', b't', b'o', 0xDD, 0xC7, 0x01];
+/// let mut cursor = Cursor::new(&bytes[..]);
+/// assert_eq!(MCString::read(&mut cursor).unwrap().string(), "Makoto");
+/// assert_eq!(VarInt::read(&mut cursor).unwrap().value(), 25565);
+/// assert_eq!(cursor.position(), 10);
+/// ```
+pub trait MCTypeDecode: Sized {
+    /// Reads a value from the cursor's position, advancing the cursor past it. The
+    /// cursor is left in place if the value cannot be decoded.
+    /// # Errors
+    /// This function will return an `MCTypeError` describing why the bytes at the
+    /// cursor's position do not encode this type.
+    fn read(cursor: &mut Cursor<&[u8]>) -> Result<Self, MCTypeError>;
+}
+
+impl<T: MCType> MCTypeDecode for T {
+    fn read(cursor: &mut Cursor<&[u8]>) -> Result<Self, MCTypeError> {
+        let bytes = *cursor.get_ref();
+        let pos = (cursor.position() as usize).min(bytes.len());
+
+        let (value, rest) = T::parse(&bytes[pos..])?;
+        cursor.set_position((bytes.len() - rest.len()) as u64);
+
+        Ok(value)
+    }
 }
 
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
 #[allow(dead_code)]
 pub struct MCString {
-    size: VarInt,
     string: String,
 }
 
 #[allow(dead_code)]
 impl MCString {
+    /// The maximum length of a string in characters, unless a field specifies its own.
+    pub const MAX_LENGTH: i32 = 32767;
+
     /// Attempts to create a `MCString` from a set of bytes, which should be
-    /// lead with a `VarInt` descriptor followed by a UTF-8 string.
-    /// # Panics
-    /// This function will panic if the constituent string cannot properly be
-    /// parsed as 
+    /// lead with a `VarInt` descriptor followed by a UTF-8 string. Bytes following
+    /// the string are ignored.
     /// # Errors
     /// This function will error in the instance that the `VarInt` header cannot
-    /// be parsed.
-    pub fn from_bytes(mut bytes: &[u8]) -> Result<Self, io::Error> {
-        let size = VarInt::from_bytes(bytes)?;
-        bytes = &bytes[size.len() as usize..];
+    /// be parsed, the bytes are shorter than the header describes, or the
+    /// constituent string is not valid UTF-8.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, io::Error> {
+        Ok(MCString::decode(bytes)?)
+    }
+
+    /// Creates a `MCString` from `string`, the fallible counterpart of `MCString::from`.
+    /// # Errors
+    /// This function will return `MCTypeError::OversizeLength` if the byte length of
+    /// `string` cannot be represented by a `VarInt`.
+    pub fn new<S: Into<String>>(string: S) -> Result<Self, MCTypeError> {
+        let string = string.into();
+        i32::try_from(string.len())
+            .map_err(|_| MCTypeError::OversizeLength(string.len() as i64))?;
+        Ok(MCString { string })
+    }
+
+    /// Parses a `MCString` of at most `max_length` characters from the front of `bytes`,
+    /// returning the string along with the bytes following it. The number of bytes
+    /// consumed is the `size()` of the string returned. Fields with a smaller limit than
+    /// `MCString::MAX_LENGTH`, such as usernames, should pass their own limit.
+    /// # Errors
+    /// This function will return the errors of `parse_str`.
+    pub fn parse_bounded(bytes: &[u8], max_length: i32) -> Result<(Self, &[u8]), MCTypeError> {
+        let (string, rest) = MCString::parse_str(bytes, max_length)?;
+        Ok((MCString::from(string), rest))
+    }
+
+    /// Parses a string of at most `max_length` characters from the front of `bytes` as
+    /// per `parse_bounded`, borrowing it from `bytes` rather than copying it, so that the
+    /// fields of a packet can be read without allocating. The string is only copied once
+    /// it is converted into a `MCString` or `String`.
+    /// <br>
+    /// As in the vanilla client, characters are counted as UTF-16 code units. The length
+    /// prefix is rejected before any bytes are read if it exceeds the byte length of the
+    /// longest possible string, i.e., `max_length * 3` bytes.
+    /// # Errors
+    /// This function will return `MCTypeError::OversizeLength` if the length prefix is
+    /// negative or the string exceeds `max_length`, `MCTypeError::Truncated` if the bytes
+    /// are shorter than the prefix describes, and `MCTypeError::InvalidUtf8` if the string
+    /// is not valid UTF-8.
+    pub fn parse_str(bytes: &[u8], max_length: i32) -> Result<(&str, &[u8]), MCTypeError> {
+        let max_length = limits::string_length(max_length);
+        let (size, rest) = VarInt::parse(bytes)?;
+        if size.value() < 0 || size.value() as i64 > max_length as i64 * 3 {
+            return Err(MCTypeError::OversizeLength(size.value() as i64));
+        }
 
-        let string_res = String::from_utf8(bytes.to_vec());
-        if string_res.is_err() {
-            Err(io::Error::new(io::ErrorKind::InvalidData, string_res.err().unwrap()))
-        } else {
-            let string = string_res.unwrap();
-            Ok(MCString { size, string })
+        let len = size.value() as usize;
+        if rest.len() < len {
+            return Err(MCTypeError::Truncated);
         }
+
+        let (string_bytes, rest) = rest.split_at(len);
+        let string = core::str::from_utf8(string_bytes)
+            .map_err(|_| MCTypeError::InvalidUtf8)?;
+
+        let units = string.encode_utf16().count();
+        if units > max_length as usize {
+            return Err(MCTypeError::OversizeLength(units as i64));
+        }
+
+        Ok((string, rest))
+    }
+
+    /// Reads a string of at most `max_length` characters from the cursor's position,
+    /// borrowing it from the bytes under the cursor as per `parse_str`, and advancing the
+    /// cursor past it. The cursor is left in place if the string cannot be decoded.
+    /// # Errors
+    /// This function will return the errors of `parse_str`.
+    pub fn read_str<'a>(cursor: &mut Cursor<&'a [u8]>, max_length: i32) -> Result<&'a str, MCTypeError> {
+        let bytes: &'a [u8] = cursor.get_ref();
+        let pos = (cursor.position() as usize).min(bytes.len());
+
+        let (string, rest) = MCString::parse_str(&bytes[pos..], max_length)?;
+        cursor.set_position((bytes.len() - rest.len()) as u64);
+
+        Ok(string)
+    }
+
+    /// Reads a `MCString` of at most `max_length` characters from the cursor's position,
+    /// advancing the cursor past it, as per `parse_bounded`. The cursor is left in place
+    /// if the string cannot be decoded.
+    /// # Errors
+    /// This function will return the errors of `parse_bounded`.
+    pub fn read_bounded(cursor: &mut Cursor<&[u8]>, max_length: i32) -> Result<Self, MCTypeError> {
+        let bytes = *cursor.get_ref();
+        let pos = (cursor.position() as usize).min(bytes.len());
+
+        let (value, rest) = MCString::parse_bounded(&bytes[pos..], max_length)?;
+        cursor.set_position((bytes.len() - rest.len()) as u64);
+
+        Ok(value)
     }
 
     pub fn string(&self) -> &String {
@@ -55,13 +200,12 @@ impl From<String> for MCString {
     /// Creates a Minecraft string from a `String`.
     /// # Panics
     /// This function will panic if the size of the String cannot
-    /// be parsed to an `i32`.
+    /// be parsed to an `i32`. Use `MCString::new` to handle this case.
     fn from(value: String) -> Self {
-        let size: i32 = match value.len().try_into() {
-            Ok(num) => num,
-            Err(msg) => panic!("{}", msg)
-        };
-        MCString { size: VarInt::from(size), string: value }
+        if let Err(msg) = i32::try_from(value.len()) {
+            panic!("{}", msg);
+        }
+        MCString { string: value }
     }
 }
 
@@ -69,35 +213,40 @@ impl From<&str> for MCString {
     /// Creates a Minecraft string from a `&str`.
     /// # Panics
     /// This function will panic if the size of the String cannot
-    /// be parsed to an `i32`.
+    /// be parsed to an `i32`. Use `MCString::new` to handle this case.
     fn from(value: &str) -> Self {
-        let size: i32 = match value.len().try_into() {
-            Ok(num) => num,
-            Err(msg) => panic!("{}", msg)
-        };
-        MCString { size: VarInt::from(size), string: value.to_owned() }
+        MCString::from(value.to_owned())
     }
 }
 
 impl MCType for MCString {
-    fn to_bytes(&self) -> Vec<u8> {
-        let mut bytes = Vec::<u8>::new();
-
-        bytes.append(&mut self.size.to_bytes());
-        bytes.append(&mut self.string.as_bytes().to_vec());
-
-        bytes
+    fn encode(&self, w: &mut dyn Write) -> io::Result<()> {
+        self.length_prefix().encode(w)?;
+        w.write_all(self.string.as_bytes())
     }
 
     fn size(&self) -> i32 {
-        self.size.len() + TryInto::<i32>::try_into(self.string.len()).unwrap()
+        self.length_prefix().len() + self.string.len() as i32
+    }
+
+    /// Parses a `MCString` of at most `MCString::MAX_LENGTH` characters. See
+    /// `MCString::parse_bounded`.
+    fn parse(bytes: &[u8]) -> Result<(Self, &[u8]), MCTypeError> {
+        MCString::parse_bounded(bytes, MCString::MAX_LENGTH)
     }
 }
 
 #[allow(dead_code)]
+#[allow(clippy::len_without_is_empty)]
 impl MCString {
     pub fn len(&self) -> i32 {
-        self.size.len()
+        self.length_prefix().len()
+    }
+
+    /// The `VarInt` the string is prefixed with, holding its length in bytes. Strings
+    /// are never longer than an `i32` can hold, as checked upon their creation.
+    fn length_prefix(&self) -> VarInt {
+        VarInt::from(self.string.len() as i32)
     }
 }
 
@@ -107,17 +256,16 @@ impl MCString {
 /// <br>
 /// This structure is meant purely for data I/O and should not be used
 /// to perform any sort of arithmetic.
-#[derive(Clone)]
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
 #[allow(dead_code)]
 pub struct VarInt {
-    bytes: Vec<u8>,
     value: i32
 }
 
 impl From<i32> for VarInt {
     /// Creates a `VarInt` representation of `value`.
     fn from(value: i32) -> Self {
-        VarInt{ bytes: to_varint(value), value }
+        VarInt{ value }
     }
 }
 
@@ -133,30 +281,38 @@ impl From<&[u8]> for VarInt {
     /// The parsing of the leading bytes to a `VarInt` will panic if the number
     /// is evaluated to greater than 5 bytes in size. This can be caused by
     /// either the wrong data type being read or the bytes being badly formatted.
+    /// Use `VarInt::decode` to handle this case.
     fn from(bytes: &[u8]) -> Self {
-        let (val, slice) = match from_varint_bytes(bytes) {
+        let (val, _) = match from_varint_bytes(bytes) {
             Ok(t) => t,
             Err(msg) => panic!("{}", msg)
         };
-        VarInt{ bytes: slice.to_vec(), value: val }
+        VarInt{ value: val }
     }
 }
 
 impl MCType for VarInt {
-    fn to_bytes(&self) -> Vec<u8> {
-        let mut bytes = Vec::<u8>::new();
-
-        bytes.append(&mut self.bytes.clone());
-
-        bytes
+    fn encode(&self, w: &mut dyn Write) -> io::Result<()> {
+        w.write_all(encode_var(self.value as u32 as u64, &mut [0; 10]))
     }
 
     fn size(&self) -> i32 {
-        self.bytes.len().try_into().unwrap()
+        var_size(self.value as u32 as u64)
+    }
+
+    fn parse(bytes: &[u8]) -> Result<(Self, &[u8]), MCTypeError> {
+        let (val, slice) = from_varint_bytes(bytes)?;
+        let len = slice.len();
+        if len == 0 || slice[len - 1] & 0x80 != 0 {
+            return Err(MCTypeError::Truncated);
+        }
+
+        Ok((VarInt{ value: val }, &bytes[len..]))
     }
 }
 
 #[allow(dead_code)]
+#[allow(clippy::len_without_is_empty)]
 impl VarInt {
     /// Creats a `VarInt` from a slice of a `Vec<u8>` `vec` and consumes the
     /// front of the `Vec<u8>` that represents the constituent VarInt bytes.
@@ -164,14 +320,15 @@ impl VarInt {
     /// This function may error if the head of the vector cannot represent a
     /// `VarInt` type.
     pub fn from_vec_front(vec: &mut Vec<u8>) -> Result<Self, io::Error> {
-        let v = VarInt::from_bytes(vec.as_slice())?;
-        vec.drain(0..v.len() as usize);
-        Ok(v)
+        let (val, slice) = from_varint_bytes(vec.as_slice())?;
+        let len = slice.len();
+        vec.drain(0..len);
+        Ok(VarInt{ value: val })
     }
 
     /// Creates a `VarInt` representation of `value`.
     pub fn from_i32(value: i32) -> Self {
-        VarInt{ bytes: to_varint(value), value }
+        VarInt{ value }
     }
 
     /// Creates a `VarInt` from a slice `&[u8]` whose leading bytes represent
@@ -186,18 +343,46 @@ impl VarInt {
     /// if the number is evaluated to greater than 5 bytes in size. This can be caused 
     /// by either the wrong data type being read or the bytes being badly formatted.
     pub fn from_bytes(bytes: &[u8]) -> Result<Self, io::Error> {
-        let (val, slice) = from_varint_bytes(bytes)?;
-        Ok(VarInt{ bytes: slice.to_vec(), value: val })
+        let (val, _) = from_varint_bytes(bytes)?;
+        Ok(VarInt{ value: val })
+    }
+
+    /// Reads a `VarInt` from `reader` one byte at a time, stopping at the byte which
+    /// terminates it. Unlike `from_bytes`, the length of the `VarInt` does not need to
+    /// be known in advance, so this can be used to read a packet length directly off
+    /// of a stream. No bytes past the end of the `VarInt` are consumed.
+    /// # Errors
+    /// This function will return an `UnexpectedEof` error if `reader` ends before the
+    /// `VarInt` is terminated, an `InvalidData` error if the `VarInt` spans more than 5
+    /// bytes, and any other error returned by `reader`.
+    pub fn read_from(reader: &mut impl Read) -> Result<Self, io::Error> {
+        const MAX_VARINT_SIZE: usize = 5;
+        let mut bytes = Vec::<u8>::with_capacity(MAX_VARINT_SIZE);
+        let mut byte = [0_u8; 1];
+
+        loop {
+            if bytes.len() >= MAX_VARINT_SIZE {
+                return Err(MCTypeError::TooLong.into());
+            }
+            reader.read_exact(&mut byte)?;
+            bytes.push(byte[0]);
+
+            if byte[0] & 0x80 == 0 {
+                break;
+            }
+        }
+
+        Ok(VarInt::decode(&bytes)?)
     }
 
     /// Retrieves the byte size of the `VarInt`.
     pub fn len(&self) -> i32 {
-        self.bytes.len() as i32
+        self.size()
     }
 
-    /// Returns a slice of this `VarInt`'s byte array representation.
-    pub fn bytes(&self) -> &[u8] {
-        &self.bytes
+    /// Returns the byte array representation of this `VarInt`, encoded from its value.
+    pub fn bytes(&self) -> Vec<u8> {
+        self.to_bytes()
     }
 
     /// Returns the numerical equivalent of this `VarInt`.
@@ -210,11 +395,10 @@ impl VarInt {
     /// is not favorable.
     pub fn set(&mut self, value: i32) {
         self.value = value;
-        self.bytes = to_varint(self.value);
     }
 }
 
-fn from_varint_bytes(bytes: &[u8]) -> Result<(i32, &[u8]), io::Error> {
+fn from_varint_bytes(bytes: &[u8]) -> Result<(i32, &[u8]), MCTypeError> {
     let mut value = 0;
     let mut pos = 0;
     let mut end_idx = 0;
@@ -233,33 +417,1009 @@ fn from_varint_bytes(bytes: &[u8]) -> Result<(i32, &[u8]), io::Error> {
         pos += 7;
 
         if pos >= 32 {
-            return Err(io::Error::new(
-                io::ErrorKind::InvalidData,
-                "VarInt descriptor exceeds >5 bytes"
-            ));
+            return Err(MCTypeError::TooLong);
         }
     }
 
     Ok((value, &bytes[..end_idx]))
 }
 
-fn to_varint(mut value: i32) -> Vec<u8> {
-    let mut bytes = Vec::<u8>::new();
+/// Encodes `value` into `buffer` as a variable-length integer, seven bits at a time from
+/// the least significant, returning the bytes used. Negative `VarInt`s and `VarLong`s are
+/// passed as their unsigned counterparts, and so span the full 5 and 10 bytes.
+fn encode_var(mut value: u64, buffer: &mut [u8; 10]) -> &[u8] {
+    const SEGMENT_BITS: u64 = 0x7F;
+    const CONTINUE_BIT: u8 = 0x80;
 
-    const SEGMENT_BITS: i32 = 0x7F;
-    const CONTINUE_BIT: i32 = 0x80;
+    let mut len = 0;
+    while value & !SEGMENT_BITS != 0 {
+        buffer[len] = (value & SEGMENT_BITS) as u8 | CONTINUE_BIT;
+        value >>= 7;
+        len += 1;
+    }
+    buffer[len] = value as u8;
+    &buffer[..=len]
+}
+
+/// The number of bytes `encode_var` encodes `value` in.
+fn var_size(value: u64) -> i32 {
+    ((u64::BITS - value.leading_zeros()).max(1) as i32 + 6) / 7
+}
+
+/// A `VarLong` is a variable-length data type encoding a two's
+/// complement signed 64-bit integer. A `VarLong` can be anywhere
+/// between 1 and 10 bytes. <https://wiki.vg/Protocol#VarInt_and_VarLong>
+/// <br>
+/// Like `VarInt`, this structure is meant purely for data I/O and should
+/// not be used to perform any sort of arithmetic.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct VarLong {
+    value: i64
+}
+
+impl From<i64> for VarLong {
+    /// Creates a `VarLong` representation of `value`.
+    fn from(value: i64) -> Self {
+        VarLong{ value }
+    }
+}
+
+impl MCType for VarLong {
+    fn encode(&self, w: &mut dyn Write) -> io::Result<()> {
+        w.write_all(encode_var(self.value as u64, &mut [0; 10]))
+    }
+
+    fn size(&self) -> i32 {
+        var_size(self.value as u64)
+    }
+
+    fn parse(bytes: &[u8]) -> Result<(Self, &[u8]), MCTypeError> {
+        let (val, slice) = from_varlong_bytes(bytes)?;
+        let len = slice.len();
+        if len == 0 || slice[len - 1] & 0x80 != 0 {
+            return Err(MCTypeError::Truncated);
+        }
+
+        Ok((VarLong{ value: val }, &bytes[len..]))
+    }
+}
+
+#[allow(clippy::len_without_is_empty)]
+impl VarLong {
+    /// Creates a `VarLong` from a slice `&[u8]` whose leading bytes represent
+    /// a `VarLong` string between 1 and 10 bytes. A slice larger than the
+    /// encompassed number can be passed without unexpected error.
+    ///
+    /// # Errors
+    ///
+    /// The parsing of the leading bytes to a `VarLong` will return an `InvalidData` error
+    /// if the number is evaluated to greater than 10 bytes in size, or if it overflows an
+    /// `i64`.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, io::Error> {
+        let (val, _) = from_varlong_bytes(bytes)?;
+        Ok(VarLong{ value: val })
+    }
+
+    /// Retrieves the byte size of the `VarLong`.
+    pub fn len(&self) -> i32 {
+        self.size()
+    }
+
+    /// Returns the byte array representation of this `VarLong`, encoded from its value.
+    pub fn bytes(&self) -> Vec<u8> {
+        self.to_bytes()
+    }
+
+    /// Returns the numerical equivalent of this `VarLong`.
+    pub fn value(&self) -> i64 {
+        self.value
+    }
+
+    /// Sets the value of this `VarLong` to represent the `value` passed.
+    pub fn set(&mut self, value: i64) {
+        self.value = value;
+    }
+}
+
+fn from_varlong_bytes(bytes: &[u8]) -> Result<(i64, &[u8]), MCTypeError> {
+    let mut value = 0;
+    let mut pos = 0;
+    let mut end_idx = 0;
+
+    const SEGMENT_BITS: i64 = 0x7F;
+    const CONTINUE_BIT: i64 = 0x80;
+
+    for b in bytes.iter() {
+        // The 10th byte only carries the most significant bit of the i64.
+        if pos == 63 && (*b as i64) & SEGMENT_BITS > 1 {
+            return Err(MCTypeError::TooLong);
+        }
+
+        value |= ((*b as i64) & SEGMENT_BITS) << pos;
+        end_idx += 1;
 
-    loop {
-        if (value & !SEGMENT_BITS) == 0 {
-            bytes.push(value as u8);
+        if (*b as i64) & CONTINUE_BIT == 0 {
             break;
         }
 
-        bytes.push(((value & SEGMENT_BITS) | CONTINUE_BIT) as u8);
+        pos += 7;
+
+        if pos >= 64 {
+            return Err(MCTypeError::TooLong);
+        }
+    }
+
+    Ok((value, &bytes[..end_idx]))
+}
+
+/// The absolute position and rotation of an entity in the world.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Location {
+    pub x: f64,
+    pub y: f64,
+    pub z: f64,
+    pub yaw: f32,
+    pub pitch: f32
+}
+
+impl Location {
+    /// Gets the position of the block the location is in.
+    pub fn block(&self) -> MCPosition {
+        MCPosition::new(libm::floor(self.x) as i32, libm::floor(self.y) as i32, libm::floor(self.z) as i32)
+    }
+}
+
+/// The flags byte of a Synchronize Player Position packet, describing which of the
+/// packet's values are relative to the player's current location rather than absolute.
+/// <https://wiki.vg/Protocol#Synchronize_Player_Position>
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PositionFlags {
+    pub x_relative: bool,
+    pub y_relative: bool,
+    pub z_relative: bool,
+    pub yaw_relative: bool,
+    pub pitch_relative: bool
+}
+
+impl PositionFlags {
+    const X: u8 = 0x01;
+    const Y: u8 = 0x02;
+    const Z: u8 = 0x04;
+    const Y_ROT: u8 = 0x08;
+    const X_ROT: u8 = 0x10;
+
+    /// Decodes the flags from their bitfield. Unused bits are ignored.
+    pub fn from_byte(byte: u8) -> Self {
+        PositionFlags {
+            x_relative: byte & Self::X != 0,
+            y_relative: byte & Self::Y != 0,
+            z_relative: byte & Self::Z != 0,
+            yaw_relative: byte & Self::Y_ROT != 0,
+            pitch_relative: byte & Self::X_ROT != 0
+        }
+    }
+
+    /// Encodes the flags into their bitfield.
+    pub fn to_byte(&self) -> u8 {
+        let mut byte = 0;
+
+        if self.x_relative { byte |= Self::X; }
+        if self.y_relative { byte |= Self::Y; }
+        if self.z_relative { byte |= Self::Z; }
+        if self.yaw_relative { byte |= Self::Y_ROT; }
+        if self.pitch_relative { byte |= Self::X_ROT; }
+
+        byte
+    }
+
+    /// Computes the absolute location described by a packet's `values` given the
+    /// player's current location `base`. Relative values are offsets from `base`,
+    /// while absolute values replace it.
+    pub fn apply(&self, base: &Location, values: &Location) -> Location {
+        fn axis<T: core::ops::Add<Output = T>>(relative: bool, base: T, value: T) -> T {
+            if relative { base + value } else { value }
+        }
+
+        Location {
+            x: axis(self.x_relative, base.x, values.x),
+            y: axis(self.y_relative, base.y, values.y),
+            z: axis(self.z_relative, base.z, values.z),
+            yaw: axis(self.yaw_relative, base.yaw, values.yaw),
+            pitch: axis(self.pitch_relative, base.pitch, values.pitch)
+        }
+    }
+}
+
+impl MCType for PositionFlags {
+    fn encode(&self, w: &mut dyn Write) -> io::Result<()> {
+        w.write_all(&[self.to_byte()])
+    }
+
+    fn size(&self) -> i32 {
+        1
+    }
+
+    fn parse(bytes: &[u8]) -> Result<(Self, &[u8]), MCTypeError> {
+        match bytes.split_first() {
+            Some((byte, rest)) => Ok((PositionFlags::from_byte(*byte), rest)),
+            None => Err(MCTypeError::Truncated)
+        }
+    }
+}
+
+/// A boolean, encoded as a single byte which is either `0x00` (false) or `0x01` (true).
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), serde(transparent))]
+pub struct MCBoolean {
+    value: bool
+}
+
+impl From<bool> for MCBoolean {
+    fn from(value: bool) -> Self {
+        MCBoolean { value }
+    }
+}
+
+impl MCType for MCBoolean {
+    fn encode(&self, w: &mut dyn Write) -> io::Result<()> {
+        w.write_all(&[self.value as u8])
+    }
+
+    fn size(&self) -> i32 {
+        1
+    }
+
+    fn parse(bytes: &[u8]) -> Result<(Self, &[u8]), MCTypeError> {
+        match bytes.split_first() {
+            Some((0x00, rest)) => Ok((MCBoolean::from(false), rest)),
+            Some((0x01, rest)) => Ok((MCBoolean::from(true), rest)),
+            Some((byte, _)) => Err(MCTypeError::InvalidValue(format!("Boolean byte {:#04x} is neither 0x00 nor 0x01.", byte))),
+            None => Err(MCTypeError::Truncated)
+        }
+    }
+}
+
+impl MCBoolean {
+    /// Returns the value of this `MCBoolean`.
+    pub fn value(&self) -> bool {
+        self.value
+    }
+}
+
+/// Defines a fixed-width numeric `MCType` wrapping the primitive `$prim`, which is
+/// encoded in network (big-endian) byte order.
+macro_rules! fixed_width_type {
+    ($(#[$meta:meta])* $name:ident, $prim:ty $(, $derive:ident)*) => {
+        $(#[$meta])*
+        #[derive(Clone, Copy, Debug, Default, PartialEq $(, $derive)*)]
+        #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), serde(transparent))]
+        pub struct $name {
+            value: $prim
+        }
+
+        impl From<$prim> for $name {
+            fn from(value: $prim) -> Self {
+                $name { value }
+            }
+        }
+
+        impl MCType for $name {
+            fn encode(&self, w: &mut dyn Write) -> io::Result<()> {
+                w.write_all(&self.value.to_be_bytes())
+            }
+
+            fn size(&self) -> i32 {
+                core::mem::size_of::<$prim>() as i32
+            }
+
+            fn parse(bytes: &[u8]) -> Result<(Self, &[u8]), MCTypeError> {
+                const SIZE: usize = core::mem::size_of::<$prim>();
+                if bytes.len() < SIZE {
+                    return Err(MCTypeError::Truncated);
+                }
+
+                let (value, rest) = bytes.split_at(SIZE);
+                Ok(($name { value: <$prim>::from_be_bytes(value.try_into().unwrap()) }, rest))
+            }
+        }
+
+        impl $name {
+            /// Returns the value of this type.
+            pub fn value(&self) -> $prim {
+                self.value
+            }
+        }
+    };
+}
+
+fixed_width_type!(
+    /// A signed 8-bit integer.
+    MCByte, i8, Eq, Hash);
+fixed_width_type!(
+    /// An unsigned 8-bit integer.
+    MCUnsignedByte, u8, Eq, Hash);
+fixed_width_type!(
+    /// A signed 16-bit integer.
+    MCShort, i16, Eq, Hash);
+fixed_width_type!(
+    /// An unsigned 16-bit integer.
+    MCUnsignedShort, u16, Eq, Hash);
+fixed_width_type!(
+    /// A signed 32-bit integer.
+    MCInt, i32, Eq, Hash);
+fixed_width_type!(
+    /// A signed 64-bit integer.
+    MCLong, i64, Eq, Hash);
+fixed_width_type!(
+    /// A single-precision 32-bit IEEE 754 floating point number.
+    MCFloat, f32);
+fixed_width_type!(
+    /// A double-precision 64-bit IEEE 754 floating point number.
+    MCDouble, f64);
+
+/// A rotation angle in steps of 1/256 of a full turn, encoded as a single byte.
+/// Conversions from degrees wrap around, so 360° and -90° encode as 0 and 192.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), serde(transparent))]
+pub struct Angle {
+    value: u8
+}
+
+impl Angle {
+    /// Creates the angle nearest to `degrees`.
+    pub fn from_degrees(degrees: f32) -> Self {
+        let steps = libm::roundf(degrees / 360.0 * 256.0) % 256.0;
+        Angle { value: if steps < 0.0 { steps + 256.0 } else { steps } as u8 }
+    }
+
+    /// Returns this angle in degrees, in the range `[0, 360)`.
+    pub fn degrees(&self) -> f32 {
+        self.value as f32 * 360.0 / 256.0
+    }
+
+    /// Returns the number of 1/256 steps of this angle.
+    pub fn value(&self) -> u8 {
+        self.value
+    }
+}
+
+impl From<u8> for Angle {
+    fn from(value: u8) -> Self {
+        Angle { value }
+    }
+}
+
+impl MCType for Angle {
+    fn encode(&self, w: &mut dyn Write) -> io::Result<()> {
+        w.write_all(&[self.value])
+    }
 
-        // https://stackoverflow.com/a/70212287
-        value = ((value as u32) >> 7) as i32;
+    fn size(&self) -> i32 {
+        1
     }
 
-    bytes
-}
\ No newline at end of file
+    fn parse(bytes: &[u8]) -> Result<(Self, &[u8]), MCTypeError> {
+        let (value, rest) = MCUnsignedByte::parse(bytes)?;
+        Ok((Angle::from(value.value()), rest))
+    }
+}
+
+/// Defines a fixed-point `MCType` stored in the integer `$prim` with 5 fractional
+/// bits, as used for entity coordinates before 1.9.
+macro_rules! fixed_point_type {
+    ($(#[$meta:meta])* $name:ident, $prim:ty, $wrapper:ident) => {
+        $(#[$meta])*
+        #[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+        #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), serde(transparent))]
+        pub struct $name {
+            value: $prim
+        }
+
+        impl $name {
+            const FRACTION_BITS: u32 = 5;
+
+            /// Creates the fixed-point number nearest to `value`, saturating at the bounds
+            /// of the underlying integer.
+            pub fn from_f64(value: f64) -> Self {
+                $name { value: libm::round(value * (1 << Self::FRACTION_BITS) as f64) as $prim }
+            }
+
+            /// Returns the numerical equivalent of this fixed-point number.
+            pub fn to_f64(&self) -> f64 {
+                self.value as f64 / (1 << Self::FRACTION_BITS) as f64
+            }
+
+            /// Returns the raw integer encoding of this fixed-point number.
+            pub fn value(&self) -> $prim {
+                self.value
+            }
+        }
+
+        impl From<$prim> for $name {
+            fn from(value: $prim) -> Self {
+                $name { value }
+            }
+        }
+
+        impl MCType for $name {
+            fn encode(&self, w: &mut dyn Write) -> io::Result<()> {
+                w.write_all(&self.value.to_be_bytes())
+            }
+
+            fn size(&self) -> i32 {
+                core::mem::size_of::<$prim>() as i32
+            }
+
+            fn parse(bytes: &[u8]) -> Result<(Self, &[u8]), MCTypeError> {
+                let (value, rest) = $wrapper::parse(bytes)?;
+                Ok(($name { value: value.value() }, rest))
+            }
+        }
+    };
+}
+
+fixed_point_type!(
+    /// A fixed-point number encoded as a 32-bit integer, used for absolute entity
+    /// coordinates before 1.9.
+    FixedPointInt, i32, MCInt);
+fixed_point_type!(
+    /// A fixed-point number encoded as an 8-bit integer, used for relative entity
+    /// movement before 1.9.
+    FixedPointByte, i8, MCByte);
+
+/// A namespaced key such as `minecraft:overworld`, encoded as a `MCString`. The
+/// namespace may only contain `a-z`, `0-9`, `.`, `-` and `_`, while the path may
+/// additionally contain `/`. Identifiers without a namespace belong to `minecraft`.
+/// <https://wiki.vg/Identifier>
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Identifier {
+    namespace: String,
+    path: String
+}
+
+impl Identifier {
+    /// The namespace of identifiers which do not specify one.
+    pub const DEFAULT_NAMESPACE: &'static str = "minecraft";
+
+    /// Creates an identifier from its `namespace` and `path`.
+    /// # Errors
+    /// This function will return `MCTypeError::InvalidValue` if either part is empty or
+    /// contains a disallowed character.
+    pub fn new<N: Into<String>, P: Into<String>>(namespace: N, path: P) -> Result<Self, MCTypeError> {
+        let (namespace, path) = (namespace.into(), path.into());
+
+        let valid_namespace = |c: char| matches!(c, 'a'..='z' | '0'..='9' | '.' | '-' | '_');
+        if namespace.is_empty() || !namespace.chars().all(valid_namespace) {
+            return Err(MCTypeError::InvalidValue(format!("Invalid identifier namespace \"{}\".", namespace)));
+        }
+        if path.is_empty() || !path.chars().all(|c| valid_namespace(c) || c == '/') {
+            return Err(MCTypeError::InvalidValue(format!("Invalid identifier path \"{}\".", path)));
+        }
+
+        Ok(Identifier { namespace, path })
+    }
+
+    /// Creates an identifier in the `minecraft` namespace.
+    /// # Errors
+    /// This function will return `MCTypeError::InvalidValue` if `path` is empty or
+    /// contains a disallowed character.
+    pub fn minecraft<P: Into<String>>(path: P) -> Result<Self, MCTypeError> {
+        Identifier::new(Self::DEFAULT_NAMESPACE, path)
+    }
+
+    pub fn namespace(&self) -> &str {
+        &self.namespace
+    }
+
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+}
+
+impl FromStr for Identifier {
+    type Err = MCTypeError;
+
+    /// Parses an identifier of the form `namespace:path`, or `path` in the `minecraft`
+    /// namespace.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.split_once(':') {
+            Some((namespace, path)) => Identifier::new(namespace, path),
+            None => Identifier::minecraft(s)
+        }
+    }
+}
+
+impl fmt::Display for Identifier {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", self.namespace, self.path)
+    }
+}
+
+impl MCType for Identifier {
+    fn encode(&self, w: &mut dyn Write) -> io::Result<()> {
+        MCString::from(self.to_string()).encode(w)
+    }
+
+    fn size(&self) -> i32 {
+        MCString::from(self.to_string()).size()
+    }
+
+    fn parse(bytes: &[u8]) -> Result<(Self, &[u8]), MCTypeError> {
+        let (string, rest) = MCString::parse(bytes)?;
+        Ok((string.string().parse()?, rest))
+    }
+}
+
+/// A 128-bit UUID, encoded as two big-endian 64-bit integers, the most significant
+/// first. Converts to and from the `uuid` crate's `Uuid`, and parses from both the
+/// hyphenated and simple string forms.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), serde(transparent))]
+pub struct MCUuid {
+    value: Uuid
+}
+
+impl MCUuid {
+    /// Creates a UUID from its most and least significant 64 bits.
+    pub fn from_longs(most_significant: i64, least_significant: i64) -> Self {
+        MCUuid { value: Uuid::from_u64_pair(most_significant as u64, least_significant as u64) }
+    }
+
+    /// Returns the most and least significant 64 bits of this UUID.
+    pub fn to_longs(&self) -> (i64, i64) {
+        let (most, least) = self.value.as_u64_pair();
+        (most as i64, least as i64)
+    }
+
+    /// Returns the `Uuid` equivalent of this `MCUuid`.
+    pub fn uuid(&self) -> Uuid {
+        self.value
+    }
+}
+
+impl From<Uuid> for MCUuid {
+    fn from(value: Uuid) -> Self {
+        MCUuid { value }
+    }
+}
+
+impl From<MCUuid> for Uuid {
+    fn from(value: MCUuid) -> Self {
+        value.value
+    }
+}
+
+impl FromStr for MCUuid {
+    type Err = MCTypeError;
+
+    /// Parses a UUID from its hyphenated (`4566e69f-c907-48ee-8d71-d7ba5aa00d20`) or
+    /// simple (`4566e69fc90748ee8d71d7ba5aa00d20`) form.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Uuid::parse_str(s)
+            .map(MCUuid::from)
+            .map_err(|err| MCTypeError::InvalidValue(err.to_string()))
+    }
+}
+
+impl fmt::Display for MCUuid {
+    /// Formats the UUID in its hyphenated form.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.value.hyphenated())
+    }
+}
+
+impl MCType for MCUuid {
+    fn encode(&self, w: &mut dyn Write) -> io::Result<()> {
+        w.write_all(self.value.as_bytes())
+    }
+
+    fn size(&self) -> i32 {
+        16
+    }
+
+    fn parse(bytes: &[u8]) -> Result<(Self, &[u8]), MCTypeError> {
+        if bytes.len() < 16 {
+            return Err(MCTypeError::Truncated);
+        }
+
+        let (value, rest) = bytes.split_at(16);
+        Ok((MCUuid { value: Uuid::from_slice(value).unwrap() }, rest))
+    }
+}
+
+/// A block position, encoded as a single 64-bit integer packing a 26-bit x coordinate,
+/// a 26-bit z coordinate, and a 12-bit y coordinate, from the most significant bit.
+/// Before 1.14, the y and z coordinates were packed in the opposite order; the
+/// `_for` methods select the layout used by a given protocol version, while the
+/// `MCType` implementation uses the modern layout.
+/// <https://wiki.vg/Protocol#Position>
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MCPosition {
+    x: i32,
+    y: i32,
+    z: i32
+}
+
+impl MCPosition {
+    /// Creates a position from block coordinates. Coordinates outside of the encodable
+    /// range (26 bits for x and z, 12 bits for y) are truncated upon encoding.
+    pub fn new(x: i32, y: i32, z: i32) -> Self {
+        MCPosition { x, y, z }
+    }
+
+    /// Unpacks a position from its encoded 64-bit integer.
+    pub fn from_i64(value: i64) -> Self {
+        MCPosition {
+            x: (value >> 38) as i32,
+            y: (value << 52 >> 52) as i32,
+            z: (value << 26 >> 38) as i32
+        }
+    }
+
+    /// Packs this position into its encoded 64-bit integer.
+    pub fn to_i64(&self) -> i64 {
+        ((self.x as i64 & 0x3FFFFFF) << 38) | ((self.z as i64 & 0x3FFFFFF) << 12) | (self.y as i64 & 0xFFF)
+    }
+
+    /// The first protocol version (1.14) packing positions as x, z, y.
+    const XZY_LAYOUT_SINCE: i32 = 477;
+
+    /// Unpacks a position from its encoded 64-bit integer in the layout used by `version`.
+    pub fn from_i64_for(value: i64, version: ProtocolVersion) -> Self {
+        if version.number() >= Self::XZY_LAYOUT_SINCE {
+            return MCPosition::from_i64(value);
+        }
+
+        MCPosition {
+            x: (value >> 38) as i32,
+            y: (value << 26 >> 52) as i32,
+            z: (value << 38 >> 38) as i32
+        }
+    }
+
+    /// Packs this position into its encoded 64-bit integer in the layout used by `version`.
+    pub fn to_i64_for(&self, version: ProtocolVersion) -> i64 {
+        if version.number() >= Self::XZY_LAYOUT_SINCE {
+            return self.to_i64();
+        }
+
+        ((self.x as i64 & 0x3FFFFFF) << 38) | ((self.y as i64 & 0xFFF) << 26) | (self.z as i64 & 0x3FFFFFF)
+    }
+
+    /// Encodes this position in the layout used by `version`.
+    pub fn to_bytes_for(&self, version: ProtocolVersion) -> Vec<u8> {
+        self.to_i64_for(version).to_be_bytes().to_vec()
+    }
+
+    /// Parses a `MCPosition` encoded in the layout used by `version` from the front of
+    /// `bytes`, returning the position along with the bytes following it.
+    /// # Errors
+    /// This function will return `MCTypeError::Truncated` if `bytes` is shorter than 8 bytes.
+    pub fn parse_for(bytes: &[u8], version: ProtocolVersion) -> Result<(Self, &[u8]), MCTypeError> {
+        let (value, rest) = MCLong::parse(bytes)?;
+        Ok((MCPosition::from_i64_for(value.value(), version), rest))
+    }
+
+    pub fn x(&self) -> i32 {
+        self.x
+    }
+
+    pub fn y(&self) -> i32 {
+        self.y
+    }
+
+    pub fn z(&self) -> i32 {
+        self.z
+    }
+}
+
+impl MCType for MCPosition {
+    fn encode(&self, w: &mut dyn Write) -> io::Result<()> {
+        w.write_all(&self.to_i64().to_be_bytes())
+    }
+
+    fn size(&self) -> i32 {
+        8
+    }
+
+    fn parse(bytes: &[u8]) -> Result<(Self, &[u8]), MCTypeError> {
+        if bytes.len() < 8 {
+            return Err(MCTypeError::Truncated);
+        }
+
+        let (value, rest) = bytes.split_at(8);
+        Ok((MCPosition::from_i64(i64::from_be_bytes(value.try_into().unwrap())), rest))
+    }
+}
+
+/// An optional value, encoded as a `MCBoolean` which is followed by the value if
+/// it is present.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), serde(transparent))]
+pub struct MCOptional<T> {
+    value: Option<T>
+}
+
+impl<T> From<Option<T>> for MCOptional<T> {
+    fn from(value: Option<T>) -> Self {
+        MCOptional { value }
+    }
+}
+
+impl<T> MCOptional<T> {
+    /// Returns a reference to the value, if it is present.
+    pub fn value(&self) -> Option<&T> {
+        self.value.as_ref()
+    }
+
+    /// Consumes the `MCOptional`, returning the value if it is present.
+    pub fn into_inner(self) -> Option<T> {
+        self.value
+    }
+}
+
+impl<T: MCType> MCType for MCOptional<T> {
+    fn encode(&self, w: &mut dyn Write) -> io::Result<()> {
+        MCBoolean::from(self.value.is_some()).encode(w)?;
+
+        match &self.value {
+            Some(value) => value.encode(w),
+            None => Ok(())
+        }
+    }
+
+    fn size(&self) -> i32 {
+        1 + self.value.as_ref().map_or(0, |value| value.size())
+    }
+
+    fn parse(bytes: &[u8]) -> Result<(Self, &[u8]), MCTypeError> {
+        let (present, rest) = MCBoolean::parse(bytes)?;
+        if !present.value() {
+            return Ok((MCOptional { value: None }, rest));
+        }
+
+        let (value, rest) = T::parse(rest)?;
+        Ok((MCOptional { value: Some(value) }, rest))
+    }
+}
+
+/// An array of values, encoded as a `VarInt` element count followed by the elements.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), serde(transparent))]
+pub struct MCArray<T> {
+    elements: Vec<T>
+}
+
+impl<T> From<Vec<T>> for MCArray<T> {
+    fn from(elements: Vec<T>) -> Self {
+        MCArray { elements }
+    }
+}
+
+impl<T> MCArray<T> {
+    /// Returns the elements of the array.
+    pub fn elements(&self) -> &[T] {
+        &self.elements
+    }
+
+    /// Consumes the `MCArray`, returning its elements.
+    pub fn into_inner(self) -> Vec<T> {
+        self.elements
+    }
+}
+
+impl<T: MCType> MCType for MCArray<T> {
+    fn encode(&self, w: &mut dyn Write) -> io::Result<()> {
+        VarInt::from(self.elements.len() as i32).encode(w)?;
+
+        for element in &self.elements {
+            element.encode(w)?;
+        }
+
+        Ok(())
+    }
+
+    fn size(&self) -> i32 {
+        VarInt::from(self.elements.len() as i32).size() + self.elements.iter().map(|e| e.size()).sum::<i32>()
+    }
+
+    fn parse(bytes: &[u8]) -> Result<(Self, &[u8]), MCTypeError> {
+        let (count, mut rest) = VarInt::parse(bytes)?;
+        let count = usize::try_from(count.value())
+            .map_err(|_| MCTypeError::OversizeLength(count.value() as i64))?;
+        // Every element spans at least one byte, so a count which the bytes cannot
+        // possibly hold is known to be truncated without allocating for it.
+        if count > rest.len() {
+            return Err(MCTypeError::Truncated);
+        }
+
+        let mut elements = Vec::with_capacity(limits::array_length(count as i32)?);
+        for _ in 0..count {
+            let (element, remaining) = T::parse(rest)?;
+            elements.push(element);
+            rest = remaining;
+        }
+
+        Ok((MCArray { elements }, rest))
+    }
+}
+
+/// A set of bits, encoded as in Java's `BitSet`: a `VarInt` count of longs followed
+/// by the longs, where bit `i` is bit `i % 64` of long `i / 64`. Used by light data
+/// and chat packets since 1.17.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), serde(transparent))]
+pub struct MCBitSet {
+    longs: Vec<i64>
+}
+
+impl MCBitSet {
+    /// Creates an empty bit set.
+    pub fn new() -> Self {
+        MCBitSet::default()
+    }
+
+    /// Creates a bit set from its longs.
+    pub fn from_longs(longs: Vec<i64>) -> Self {
+        MCBitSet { longs }
+    }
+
+    /// Returns the longs backing this bit set.
+    pub fn longs(&self) -> &[i64] {
+        &self.longs
+    }
+
+    /// Gets bit `index`. Bits beyond the end of the set are unset.
+    pub fn get(&self, index: usize) -> bool {
+        self.longs.get(index / 64).is_some_and(|long| long >> (index % 64) & 1 != 0)
+    }
+
+    /// Sets bit `index` to `value`, growing the set if necessary.
+    pub fn set(&mut self, index: usize, value: bool) {
+        if index / 64 >= self.longs.len() {
+            if !value {
+                return;
+            }
+            self.longs.resize(index / 64 + 1, 0);
+        }
+
+        let bit = 1_i64 << (index % 64);
+        match value {
+            true => self.longs[index / 64] |= bit,
+            false => self.longs[index / 64] &= !bit
+        }
+    }
+}
+
+impl MCType for MCBitSet {
+    fn encode(&self, w: &mut dyn Write) -> io::Result<()> {
+        VarInt::from(self.longs.len() as i32).encode(w)?;
+
+        for long in &self.longs {
+            w.write_all(&long.to_be_bytes())?;
+        }
+
+        Ok(())
+    }
+
+    fn size(&self) -> i32 {
+        VarInt::from(self.longs.len() as i32).size() + 8 * self.longs.len() as i32
+    }
+
+    fn parse(bytes: &[u8]) -> Result<(Self, &[u8]), MCTypeError> {
+        let (longs, rest) = MCArray::<MCLong>::parse(bytes)?;
+        Ok((MCBitSet { longs: longs.elements().iter().map(|long| long.value()).collect() }, rest))
+    }
+}
+
+/// A set of exactly `N` bits, encoded without a length prefix as `ceil(N / 8)` bytes,
+/// where bit `i` is bit `i % 8` of byte `i / 8`. Used for the acknowledged messages of
+/// 1.19+ chat packets.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct MCFixedBitSet<const N: usize> {
+    bytes: Vec<u8>
+}
+
+impl<const N: usize> Default for MCFixedBitSet<N> {
+    fn default() -> Self {
+        MCFixedBitSet { bytes: vec![0; N.div_ceil(8)] }
+    }
+}
+
+impl<const N: usize> MCFixedBitSet<N> {
+    /// Creates a bit set with all `N` bits unset.
+    pub fn new() -> Self {
+        MCFixedBitSet::default()
+    }
+
+    /// Gets bit `index`. Bits beyond `N` are unset.
+    pub fn get(&self, index: usize) -> bool {
+        index < N && self.bytes[index / 8] >> (index % 8) & 1 != 0
+    }
+
+    /// Sets bit `index` to `value`.
+    /// # Panics
+    /// This function will panic if `index` is not less than `N`.
+    pub fn set(&mut self, index: usize, value: bool) {
+        assert!(index < N, "bit index {} out of range for a {}-bit set", index, N);
+
+        let bit = 1_u8 << (index % 8);
+        match value {
+            true => self.bytes[index / 8] |= bit,
+            false => self.bytes[index / 8] &= !bit
+        }
+    }
+}
+
+impl<const N: usize> MCType for MCFixedBitSet<N> {
+    fn encode(&self, w: &mut dyn Write) -> io::Result<()> {
+        w.write_all(&self.bytes)
+    }
+
+    fn size(&self) -> i32 {
+        self.bytes.len() as i32
+    }
+
+    fn parse(bytes: &[u8]) -> Result<(Self, &[u8]), MCTypeError> {
+        if bytes.len() < N.div_ceil(8) {
+            return Err(MCTypeError::Truncated);
+        }
+
+        let (set, rest) = bytes.split_at(N.div_ceil(8));
+        Ok((MCFixedBitSet { bytes: set.to_vec() }, rest))
+    }
+}
+
+// With the `serde` feature, types serialize as the values they hold rather than as their
+// encoding: a `VarInt` as a number, a `MCString` or `Identifier` as a string, a `MCUuid`
+// in its hyphenated form and a `MCPosition` as its coordinates.
+#[cfg(feature = "serde")]
+mod serde_impls {
+    use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+
+    use super::*;
+
+    /// Implements `Serialize` and `Deserialize` for `$name` through the plain value
+    /// `$repr` it holds, as converted by `$to` and `$from`.
+    macro_rules! serde_via {
+        ($name:ty, $repr:ty, $to:expr, $from:expr) => {
+            impl Serialize for $name {
+                fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                    let to: fn(&$name) -> $repr = $to;
+                    to(self).serialize(serializer)
+                }
+            }
+
+            impl<'de> Deserialize<'de> for $name {
+                fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                    let from: fn($repr) -> Result<$name, MCTypeError> = $from;
+                    from(<$repr>::deserialize(deserializer)?).map_err(de::Error::custom)
+                }
+            }
+        };
+    }
+
+    serde_via!(MCString, String, |string| string.string().to_owned(), |string| Ok(MCString::from(string)));
+    serde_via!(VarInt, i32, VarInt::value, |value| Ok(VarInt::from(value)));
+    serde_via!(VarLong, i64, VarLong::value, |value| Ok(VarLong::from(value)));
+    serde_via!(Identifier, String, Identifier::to_string, |identifier| identifier.parse());
+
+    impl<const N: usize> Serialize for MCFixedBitSet<N> {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            self.bytes.serialize(serializer)
+        }
+    }
+
+    impl<'de, const N: usize> Deserialize<'de> for MCFixedBitSet<N> {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            let bytes = Vec::<u8>::deserialize(deserializer)?;
+            if bytes.len() != N.div_ceil(8) {
+                return Err(de::Error::invalid_length(bytes.len(), &format!("{} bytes", N.div_ceil(8)).as_str()));
+            }
+            Ok(MCFixedBitSet { bytes })
+        }
+    }
+}
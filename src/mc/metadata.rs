@@ -0,0 +1,339 @@
+use std::collections::BTreeMap;
+
+use uuid::Uuid;
+
+use super::{chat::ChatComponent, error::MCTypeError, item::Slot, mctypes::{Identifier, MCBoolean, MCByte, MCFloat, MCInt, MCPosition, MCShort, MCString, MCType, MCUuid, VarInt, VarLong}, nbt::NbtTag, version::ProtocolVersion, PROTOCOL_VERSION};
+
+/// The first protocol version (1.9) giving entries an index byte and a type of their own.
+const TYPED_ENTRIES_SINCE: i32 = 107;
+
+/// The index of the entity flags, a bit field of `EntityMetadata::ON_FIRE` and the like.
+pub const FLAGS_INDEX: u8 = 0;
+/// The index of the custom name of an entity.
+pub const CUSTOM_NAME_INDEX: u8 = 2;
+/// The index of whether the custom name of an entity is always shown.
+pub const CUSTOM_NAME_VISIBLE_INDEX: u8 = 3;
+
+/// A value of entity metadata. The types a version has depend on the version; values
+/// are decoded into the same variants in every version that has them.
+#[derive(Clone, Debug, PartialEq)]
+pub enum MetadataValue {
+    Byte(i8),
+    /// A 16-bit integer, which only 1.8 has.
+    Short(i16),
+    /// A 32-bit integer, sent as a `VarInt` since 1.9.
+    Int(i32),
+    Long(i64),
+    Float(f32),
+    String(String),
+    Chat(ChatComponent),
+    OptionalChat(Option<ChatComponent>),
+    Slot(Slot),
+    Boolean(bool),
+    /// The rotation of each axis, in degrees.
+    Rotation([f32; 3]),
+    Position(MCPosition),
+    OptionalPosition(Option<MCPosition>),
+    /// A block face: down, up, north, south, west or east, from 0.
+    Direction(i32),
+    OptionalUuid(Option<Uuid>),
+    BlockState(i32),
+    /// A block state, which is absent if it is air.
+    OptionalBlockState(Option<i32>),
+    Nbt(Option<NbtTag>),
+    /// A particle, with its encoded options, such as the color of dust.
+    Particle { id: i32, data: Vec<u8> },
+    VillagerData { kind: i32, profession: i32, level: i32 },
+    OptionalVarInt(Option<i32>),
+    /// A pose, such as standing (0), sleeping (2) or sneaking (5).
+    Pose(i32),
+    CatVariant(i32),
+    FrogVariant(i32),
+    /// A position within the dimension of the given name.
+    OptionalGlobalPosition(Option<(Identifier, MCPosition)>),
+    PaintingVariant(i32),
+    SnifferState(i32),
+    Vector3([f32; 3]),
+    Quaternion([f32; 4])
+}
+
+/// The types of metadata values, in which the versions' type IDs are looked up.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Kind {
+    Byte, Short, Int, Long, Float, String, Chat, OptionalChat, Slot, Boolean, Rotation, Position,
+    OptionalPosition, Direction, OptionalUuid, BlockState, OptionalBlockState, Nbt, Particle,
+    VillagerData, OptionalVarInt, Pose, CatVariant, FrogVariant, OptionalGlobalPosition,
+    PaintingVariant, SnifferState, Vector3, Quaternion
+}
+
+/// Gets the types of metadata values in `version`, by type ID.
+fn kinds(version: ProtocolVersion) -> &'static [Kind] {
+    use Kind::*;
+
+    match version.number() {
+        number if number < TYPED_ENTRIES_SINCE => &[Byte, Short, Int, Float, String, Slot, Position, Rotation],
+        // 1.9 to 1.12.2
+        number if number < 393 => &[Byte, Int, Float, String, Chat, Slot, Boolean, Rotation, Position, OptionalPosition,
+            Direction, OptionalUuid, OptionalBlockState, Nbt],
+        // 1.13 to 1.13.2
+        number if number < 477 => &[Byte, Int, Float, String, Chat, OptionalChat, Slot, Boolean, Rotation, Position,
+            OptionalPosition, Direction, OptionalUuid, OptionalBlockState, Nbt, Particle],
+        // 1.14 to 1.18.2
+        number if number < 759 => &[Byte, Int, Float, String, Chat, OptionalChat, Slot, Boolean, Rotation, Position,
+            OptionalPosition, Direction, OptionalUuid, OptionalBlockState, Nbt, Particle, VillagerData, OptionalVarInt, Pose],
+        // 1.19 to 1.19.2
+        number if number < 761 => &[Byte, Int, Float, String, Chat, OptionalChat, Slot, Boolean, Rotation, Position,
+            OptionalPosition, Direction, OptionalUuid, OptionalBlockState, Nbt, Particle, VillagerData, OptionalVarInt, Pose,
+            CatVariant, FrogVariant, OptionalGlobalPosition, PaintingVariant],
+        761 => &[Byte, Int, Long, Float, String, Chat, OptionalChat, Slot, Boolean, Rotation, Position, OptionalPosition,
+            Direction, OptionalUuid, OptionalBlockState, Nbt, Particle, VillagerData, OptionalVarInt, Pose, CatVariant,
+            FrogVariant, OptionalGlobalPosition, PaintingVariant],
+        // 1.19.4 onwards
+        _ => &[Byte, Int, Long, Float, String, Chat, OptionalChat, Slot, Boolean, Rotation, Position, OptionalPosition,
+            Direction, OptionalUuid, BlockState, OptionalBlockState, Nbt, Particle, VillagerData, OptionalVarInt, Pose,
+            CatVariant, FrogVariant, OptionalGlobalPosition, PaintingVariant, SnifferState, Vector3, Quaternion]
+    }
+}
+
+/// The metadata of an entity, such as its flags, custom name and health, by index. The
+/// meaning of an index depends on the type of the entity and the version.
+/// <https://wiki.vg/Entity_metadata>
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct EntityMetadata {
+    entries: BTreeMap<u8, MetadataValue>
+}
+
+impl EntityMetadata {
+    /// Set in the flags of an entity which is on fire.
+    pub const ON_FIRE: i8 = 0x01;
+    pub const SNEAKING: i8 = 0x02;
+    pub const SPRINTING: i8 = 0x08;
+    pub const SWIMMING: i8 = 0x10;
+    pub const INVISIBLE: i8 = 0x20;
+    pub const GLOWING: i8 = 0x40;
+    /// Set in the flags of an entity which is flying with an elytra.
+    pub const FALL_FLYING: i8 = -0x80;
+
+    /// Parses metadata encoded in the format of `version` from the front of `bytes`,
+    /// returning the metadata along with the bytes following it.
+    /// # Errors
+    /// This function will return an `MCTypeError` if the bytes do not encode metadata, or
+    /// if a value has a type the version does not have. Particles with options are only
+    /// decoded in the canonical version, as the particles which have options differ
+    /// between versions.
+    pub fn parse_for(bytes: &[u8], version: ProtocolVersion) -> Result<(Self, &[u8]), MCTypeError> {
+        let mut reader = Reader { bytes, version };
+        let mut metadata = EntityMetadata::default();
+
+        loop {
+            let header = reader.read::<MCByte>()?.value() as u8;
+            let (index, type_id) = match version.number() >= TYPED_ENTRIES_SINCE {
+                true if header == 0xFF => break,
+                true => (header, reader.read::<VarInt>()?.value()),
+                false if header == 0x7F => break,
+                false => (header & 0x1F, (header >> 5) as i32)
+            };
+
+            let kind = usize::try_from(type_id).ok()
+                .and_then(|type_id| kinds(version).get(type_id))
+                .ok_or_else(|| MCTypeError::InvalidValue(format!("Unknown entity metadata type {}.", type_id)))?;
+            metadata.entries.insert(index, reader.value(*kind)?);
+        }
+
+        Ok((metadata, reader.bytes))
+    }
+
+    /// Gets the value at `index`, if the metadata has one.
+    pub fn get(&self, index: u8) -> Option<&MetadataValue> {
+        self.entries.get(&index)
+    }
+
+    /// Sets the value at `index`, replacing any value it had.
+    pub fn insert(&mut self, index: u8, value: MetadataValue) {
+        self.entries.insert(index, value);
+    }
+
+    /// Applies an update of metadata, replacing the values it has.
+    pub fn update(&mut self, update: EntityMetadata) {
+        self.entries.extend(update.entries);
+    }
+
+    /// Iterates over the values of the metadata, by index.
+    pub fn iter(&self) -> impl Iterator<Item = (u8, &MetadataValue)> {
+        self.entries.iter().map(|(index, value)| (*index, value))
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Gets the flags of the entity, if they are known.
+    pub fn flags(&self) -> Option<i8> {
+        match self.get(FLAGS_INDEX)? {
+            MetadataValue::Byte(flags) => Some(*flags),
+            _ => None
+        }
+    }
+
+    /// Whether the entity has every flag of `flags`.
+    pub fn has_flags(&self, flags: i8) -> bool {
+        self.flags().is_some_and(|set| set & flags == flags)
+    }
+
+    /// Gets the custom name of the entity, such as that of a mob named with a name tag.
+    pub fn custom_name(&self) -> Option<ChatComponent> {
+        match self.get(CUSTOM_NAME_INDEX)? {
+            MetadataValue::OptionalChat(name) => name.clone(),
+            // Custom names were plain strings before 1.13.
+            MetadataValue::String(name) if !name.is_empty() => Some(ChatComponent::text(name.clone())),
+            _ => None
+        }
+    }
+
+    /// Gets the health of the living entity, in the index of `version`.
+    pub fn health(&self, version: ProtocolVersion) -> Option<f32> {
+        let index = match version.number() {
+            number if number < TYPED_ENTRIES_SINCE => 6,
+            // 1.9 to 1.13.2
+            number if number < 477 => 7,
+            // 1.14 to 1.16.5
+            number if number < 755 => 8,
+            _ => 9
+        };
+
+        match self.get(index)? {
+            MetadataValue::Float(health) => Some(*health),
+            _ => None
+        }
+    }
+}
+
+/// Reads metadata values from the front of a slice, which it advances past them.
+struct Reader<'a> {
+    bytes: &'a [u8],
+    version: ProtocolVersion
+}
+
+impl Reader<'_> {
+    fn read<T: MCType>(&mut self) -> Result<T, MCTypeError> {
+        let (value, rest) = T::parse(self.bytes)?;
+        self.bytes = rest;
+        Ok(value)
+    }
+
+    fn float(&mut self) -> Result<f32, MCTypeError> {
+        Ok(self.read::<MCFloat>()?.value())
+    }
+
+    fn varint(&mut self) -> Result<i32, MCTypeError> {
+        Ok(self.read::<VarInt>()?.value())
+    }
+
+    fn boolean(&mut self) -> Result<bool, MCTypeError> {
+        Ok(self.read::<MCBoolean>()?.value())
+    }
+
+    fn position(&mut self) -> Result<MCPosition, MCTypeError> {
+        if self.version.number() < TYPED_ENTRIES_SINCE {
+            let (x, y, z) = (self.read::<MCInt>()?.value(), self.read::<MCInt>()?.value(), self.read::<MCInt>()?.value());
+            return Ok(MCPosition::new(x, y, z));
+        }
+
+        let (position, rest) = MCPosition::parse_for(self.bytes, self.version)?;
+        self.bytes = rest;
+        Ok(position)
+    }
+
+    fn chat(&mut self) -> Result<ChatComponent, MCTypeError> {
+        let (component, rest) = ChatComponent::parse_for(self.bytes, self.version)?;
+        self.bytes = rest;
+        Ok(component)
+    }
+
+    fn particle(&mut self) -> Result<MetadataValue, MCTypeError> {
+        let id = self.varint()?;
+        if ProtocolVersion::from(PROTOCOL_VERSION) != self.version {
+            return Err(MCTypeError::InvalidValue("Particles are only decoded in the canonical version.".to_owned()));
+        }
+
+        let start = self.bytes;
+        match id {
+            // block, block_marker and falling_dust
+            2 | 3 | 25 => { self.varint()?; },
+            // dust
+            14 => for _ in 0..4 { self.float()?; },
+            // dust_color_transition
+            15 => for _ in 0..7 { self.float()?; },
+            // sculk_charge
+            30 => { self.float()?; },
+            // item
+            39 => { self.read::<Slot>()?; },
+            // vibration
+            40 => return Err(MCTypeError::InvalidValue("Vibration particles cannot be decoded.".to_owned())),
+            _ => {}
+        }
+
+        Ok(MetadataValue::Particle { id, data: start[..start.len() - self.bytes.len()].to_vec() })
+    }
+
+    fn value(&mut self, kind: Kind) -> Result<MetadataValue, MCTypeError> {
+        let optional_count = |count: i32| (count > 0).then(|| count - 1);
+
+        Ok(match kind {
+            Kind::Byte => MetadataValue::Byte(self.read::<MCByte>()?.value()),
+            Kind::Short => MetadataValue::Short(self.read::<MCShort>()?.value()),
+            Kind::Int if self.version.number() < TYPED_ENTRIES_SINCE => MetadataValue::Int(self.read::<MCInt>()?.value()),
+            Kind::Int => MetadataValue::Int(self.varint()?),
+            Kind::Long => MetadataValue::Long(self.read::<VarLong>()?.value()),
+            Kind::Float => MetadataValue::Float(self.float()?),
+            Kind::String => MetadataValue::String(self.read::<MCString>()?.string().clone()),
+            Kind::Chat => MetadataValue::Chat(self.chat()?),
+            Kind::OptionalChat => MetadataValue::OptionalChat(match self.boolean()? {
+                true => Some(self.chat()?),
+                false => None
+            }),
+            Kind::Slot => {
+                let (slot, rest) = Slot::parse_for(self.bytes, self.version)?;
+                self.bytes = rest;
+                MetadataValue::Slot(slot)
+            },
+            Kind::Boolean => MetadataValue::Boolean(self.boolean()?),
+            Kind::Rotation => MetadataValue::Rotation([self.float()?, self.float()?, self.float()?]),
+            Kind::Position => MetadataValue::Position(self.position()?),
+            Kind::OptionalPosition => MetadataValue::OptionalPosition(match self.boolean()? {
+                true => Some(self.position()?),
+                false => None
+            }),
+            Kind::Direction => MetadataValue::Direction(self.varint()?),
+            Kind::OptionalUuid => MetadataValue::OptionalUuid(match self.boolean()? {
+                true => Some(self.read::<MCUuid>()?.uuid()),
+                false => None
+            }),
+            Kind::BlockState => MetadataValue::BlockState(self.varint()?),
+            Kind::OptionalBlockState => MetadataValue::OptionalBlockState(Some(self.varint()?).filter(|state| *state != 0)),
+            Kind::Nbt => {
+                let (tag, rest) = NbtTag::parse_for(self.bytes, self.version)?;
+                self.bytes = rest;
+                MetadataValue::Nbt(tag)
+            },
+            Kind::Particle => self.particle()?,
+            Kind::VillagerData => MetadataValue::VillagerData { kind: self.varint()?, profession: self.varint()?, level: self.varint()? },
+            Kind::OptionalVarInt => MetadataValue::OptionalVarInt(optional_count(self.varint()?)),
+            Kind::Pose => MetadataValue::Pose(self.varint()?),
+            Kind::CatVariant => MetadataValue::CatVariant(self.varint()?),
+            Kind::FrogVariant => MetadataValue::FrogVariant(self.varint()?),
+            Kind::OptionalGlobalPosition => MetadataValue::OptionalGlobalPosition(match self.boolean()? {
+                true => Some((self.read::<Identifier>()?, self.position()?)),
+                false => None
+            }),
+            Kind::PaintingVariant => MetadataValue::PaintingVariant(self.varint()?),
+            Kind::SnifferState => MetadataValue::SnifferState(self.varint()?),
+            Kind::Vector3 => MetadataValue::Vector3([self.float()?, self.float()?, self.float()?]),
+            Kind::Quaternion => MetadataValue::Quaternion([self.float()?, self.float()?, self.float()?, self.float()?])
+        })
+    }
+}
@@ -0,0 +1,121 @@
+//! Counters of the traffic of a connection and of the work of a client, for monitoring.
+use std::{collections::HashMap, io::{self, Read}, time::Duration};
+
+use super::{connection::ConnectionState, mctypes::{MCType, VarInt}};
+
+/// The traffic a stream has exchanged since it was opened. Packets are counted under the
+/// state they were exchanged in and their canonical ID, as they are matched with `is`.
+/// Bytes are counted as framed on the wire, before encryption, which keeps their size.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct TrafficMetrics {
+    /// The number of packets sent, by state and ID.
+    pub packets_sent: HashMap<(ConnectionState, i32), u64>,
+    /// The number of packets received, by state and ID.
+    pub packets_received: HashMap<(ConnectionState, i32), u64>,
+    pub bytes_sent: u64,
+    pub bytes_received: u64,
+    /// The number of bytes the packets sent would have spanned without compression.
+    pub uncompressed_bytes_sent: u64,
+    /// The number of bytes the packets received would have spanned without compression.
+    pub uncompressed_bytes_received: u64
+}
+
+impl TrafficMetrics {
+    /// Records a packet of `id` sent in `state`, which spans `data_length` bytes with its
+    /// ID and was framed into `wire_length` bytes.
+    pub(crate) fn record_sent(&mut self, state: ConnectionState, id: i32, data_length: i32, wire_length: usize) {
+        *self.packets_sent.entry((state, id)).or_default() += 1;
+        self.bytes_sent += wire_length as u64;
+        self.uncompressed_bytes_sent += uncompressed_length(data_length);
+    }
+
+    /// Records a packet of `id` received in `state`, as per `record_sent`.
+    pub(crate) fn record_received(&mut self, state: ConnectionState, id: i32, data_length: i32, wire_length: usize) {
+        *self.packets_received.entry((state, id)).or_default() += 1;
+        self.bytes_received += wire_length as u64;
+        self.uncompressed_bytes_received += uncompressed_length(data_length);
+    }
+
+    /// Gets the total number of packets sent.
+    pub fn total_sent(&self) -> u64 {
+        self.packets_sent.values().sum()
+    }
+
+    /// Gets the total number of packets received.
+    pub fn total_received(&self) -> u64 {
+        self.packets_received.values().sum()
+    }
+
+    /// Gets the ratio of the bytes exchanged on the wire to the bytes they would have
+    /// spanned without compression, in both directions. This is 1 or slightly above for
+    /// uncompressed connections, and `None` before anything is exchanged.
+    pub fn compression_ratio(&self) -> Option<f64> {
+        let uncompressed = self.uncompressed_bytes_sent + self.uncompressed_bytes_received;
+        match uncompressed {
+            0 => None,
+            _ => Some((self.bytes_sent + self.bytes_received) as f64 / uncompressed as f64)
+        }
+    }
+}
+
+/// The length of the uncompressed frame of a packet of `data_length` bytes, with its
+/// length prefix.
+fn uncompressed_length(data_length: i32) -> u64 {
+    (VarInt::from(data_length).size() + data_length) as u64
+}
+
+/// How long the ticks of a client take.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct TickTimings {
+    /// The number of ticks run.
+    pub ticks: u64,
+    /// The time the last tick took.
+    pub last: Duration,
+    /// The time the longest tick took.
+    pub max: Duration,
+    /// The time every tick took together.
+    pub total: Duration
+}
+
+impl TickTimings {
+    /// Records a tick which took `duration`.
+    pub(crate) fn record(&mut self, duration: Duration) {
+        self.ticks += 1;
+        self.last = duration;
+        self.max = self.max.max(duration);
+        self.total += duration;
+    }
+
+    /// Gets the average time a tick took, or zero before the first tick.
+    pub fn average(&self) -> Duration {
+        match self.ticks {
+            0 => Duration::ZERO,
+            ticks => self.total.div_f64(ticks as f64)
+        }
+    }
+}
+
+/// The metrics of a client, as gathered by `Client::metrics`.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Metrics {
+    /// The traffic of the current connection.
+    pub traffic: TrafficMetrics,
+    /// The latency of the player, as the server measures it from its Keep Alives and
+    /// reports it in the tab list, or `None` until it has.
+    pub latency: Option<Duration>,
+    pub ticks: TickTimings
+}
+
+/// Counts the bytes read through it.
+pub(crate) struct CountingReader<'a, R: ?Sized> {
+    pub reader: &'a mut R,
+    pub count: usize
+}
+
+impl<R: Read + ?Sized> Read for CountingReader<'_, R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let read = self.reader.read(buf)?;
+        self.count += read;
+        Ok(read)
+    }
+}
@@ -1,6 +1,95 @@
+#[cfg(feature = "tokio")]
+pub mod async_connection;
+#[cfg(feature = "std")]
+pub mod auth;
+#[cfg(feature = "std")]
+pub mod behaviors;
+#[cfg(feature = "std")]
+pub mod boss_bar;
+#[cfg(feature = "std")]
+pub mod builder;
+#[cfg(feature = "std")]
+pub mod capture;
+#[cfg(any(feature = "std", feature = "wasm"))]
+pub mod chat;
+#[cfg(feature = "std")]
+pub mod client;
+#[cfg(feature = "std")]
+pub mod combat;
+#[cfg(feature = "std")]
 pub mod connection;
+#[cfg(feature = "std")]
+pub mod digging;
+#[cfg(feature = "std")]
+pub mod dns;
+#[cfg(feature = "std")]
+pub mod encryption;
+#[cfg(feature = "std")]
+pub mod entities;
+pub mod error;
+#[cfg(feature = "std")]
+pub mod events;
+#[cfg(feature = "std")]
+pub mod item;
+pub mod limits;
+#[cfg(feature = "std")]
+pub mod local_player;
+#[cfg(feature = "std")]
+pub mod metadata;
+#[cfg(feature = "std")]
+pub mod metrics;
 pub mod mctypes;
+pub mod nbt;
+#[cfg(feature = "std")]
 pub mod packet;
+#[cfg(feature = "std")]
+pub mod packets;
+#[cfg(feature = "std")]
+pub mod pathfinding;
+#[cfg(feature = "std")]
+pub mod physics;
+#[cfg(feature = "std")]
+pub mod plugins;
+#[cfg(feature = "std")]
+pub mod pool;
+#[cfg(feature = "std")]
+pub mod proxy;
+#[cfg(feature = "std")]
+pub mod query;
+#[cfg(feature = "std")]
+pub mod rcon;
+#[cfg(feature = "std")]
+pub mod registry;
+#[cfg(feature = "std")]
+pub mod resource_pack;
+#[cfg(feature = "std")]
+pub mod scoreboard;
+#[cfg(feature = "std")]
+pub mod sniffer;
+#[cfg(any(feature = "std", feature = "wasm"))]
+pub mod status;
+#[cfg(feature = "tokio")]
+pub mod swarm;
+#[cfg(feature = "std")]
+pub mod tab_list;
+#[cfg(feature = "std")]
+pub mod title;
+#[cfg(any(feature = "std", feature = "wasm"))]
+pub mod translation;
+#[cfg(any(feature = "std", feature = "wasm"))]
+pub mod transport;
+pub mod version;
+#[cfg(feature = "std")]
+pub mod windows;
+#[cfg(feature = "std")]
+pub mod world;
+
+#[cfg(not(any(feature = "std", feature = "wasm")))]
+pub mod io;
+/// The I/O traits and types the protocol types are encoded and decoded with, which are
+/// those of `std` unless it is disabled.
+#[cfg(any(feature = "std", feature = "wasm"))]
+pub use std::io;
 
 #[allow(dead_code)]
 pub const PROTOCOL_VERSION: i32 = 761;
@@ -0,0 +1,553 @@
+//! Minecraft's Named Binary Tag format, which chunk data, item stacks, entity metadata
+//! and registries embed. A tag is written as its type ID followed by its payload; the
+//! root tag is also given a name, except in network NBT, which servers send since 1.20.2.
+use alloc::{borrow::ToOwned, collections::BTreeMap, format, string::{String, ToString}, vec::Vec};
+use core::fmt;
+
+use serde::{Serialize, Serializer, de::DeserializeOwned};
+use serde_json::Value;
+
+use super::{error::MCTypeError, io::{self, Write}, limits, mctypes::MCType, version::ProtocolVersion};
+
+/// The tags of a compound, by name.
+pub type NbtCompound = BTreeMap<String, NbtTag>;
+
+/// A root tag with its name.
+pub type NamedTag = (String, NbtTag);
+
+/// The most tags which may be nested within one another by default, as in the vanilla
+/// client. See `DecodeLimits::max_nbt_depth`.
+pub const MAX_DEPTH: usize = 512;
+
+/// The type ID closing a compound, or standing in for a missing root tag.
+const END_ID: u8 = 0;
+
+/// A tag of Named Binary Tag data.
+#[derive(Clone, Debug, PartialEq)]
+pub enum NbtTag {
+    Byte(i8),
+    Short(i16),
+    Int(i32),
+    Long(i64),
+    Float(f32),
+    Double(f64),
+    ByteArray(Vec<i8>),
+    String(String),
+    /// A list of tags, which must all be of the same type.
+    List(Vec<NbtTag>),
+    Compound(NbtCompound),
+    IntArray(Vec<i32>),
+    LongArray(Vec<i64>)
+}
+
+impl NbtTag {
+    /// The ID the type of this tag is written with.
+    pub fn type_id(&self) -> u8 {
+        match self {
+            NbtTag::Byte(_) => 1,
+            NbtTag::Short(_) => 2,
+            NbtTag::Int(_) => 3,
+            NbtTag::Long(_) => 4,
+            NbtTag::Float(_) => 5,
+            NbtTag::Double(_) => 6,
+            NbtTag::ByteArray(_) => 7,
+            NbtTag::String(_) => 8,
+            NbtTag::List(_) => 9,
+            NbtTag::Compound(_) => 10,
+            NbtTag::IntArray(_) => 11,
+            NbtTag::LongArray(_) => 12
+        }
+    }
+
+    /// Parses a named root tag from the front of `bytes`, as written in files and sent by
+    /// servers before 1.20.2, returning its name and the tag along with the bytes
+    /// following it. A missing root tag, written as a lone `TAG_End`, is returned as `None`.
+    /// # Errors
+    /// This function will return an `MCTypeError` if the bytes do not encode a tag, or
+    /// if its tags are nested deeper than `DecodeLimits::max_nbt_depth`.
+    pub fn parse_named(bytes: &[u8]) -> Result<(Option<NamedTag>, &[u8]), MCTypeError> {
+        let mut reader = NbtReader { bytes };
+        let type_id = reader.byte()?;
+        if type_id == END_ID {
+            return Ok((None, reader.bytes));
+        }
+
+        let name = reader.string()?;
+        let tag = reader.payload(type_id, 0)?;
+        Ok((Some((name, tag)), reader.bytes))
+    }
+
+    /// Parses an unnamed root tag from the front of `bytes`, as sent by servers since
+    /// 1.20.2, returning the tag along with the bytes following it. A missing root tag is
+    /// returned as `None`.
+    /// # Errors
+    /// This function will return the errors of `parse_named`.
+    pub fn parse_network(bytes: &[u8]) -> Result<(Option<NbtTag>, &[u8]), MCTypeError> {
+        let mut reader = NbtReader { bytes };
+        let type_id = reader.byte()?;
+        if type_id == END_ID {
+            return Ok((None, reader.bytes));
+        }
+
+        let tag = reader.payload(type_id, 0)?;
+        Ok((Some(tag), reader.bytes))
+    }
+
+    /// Parses a root tag in the form servers of `version` send it, discarding its name.
+    /// # Errors
+    /// This function will return the errors of `parse_named`.
+    pub fn parse_for(bytes: &[u8], version: ProtocolVersion) -> Result<(Option<NbtTag>, &[u8]), MCTypeError> {
+        match version >= ProtocolVersion::V1_20_2 {
+            true => NbtTag::parse_network(bytes),
+            false => NbtTag::parse_named(bytes).map(|(root, rest)| (root.map(|(_, tag)| tag), rest))
+        }
+    }
+
+    /// Writes this tag as a root tag named `name`.
+    /// # Errors
+    /// This function will return an `InvalidInput` error if a string is longer than 65535
+    /// bytes, or if a list mixes types, or an error if `w` cannot be written to.
+    pub fn write_named(&self, name: &str, w: &mut dyn Write) -> io::Result<()> {
+        w.write_all(&[self.type_id()])?;
+        write_string(name, w)?;
+        self.write_payload(w)
+    }
+
+    /// Writes this tag as an unnamed root tag, as sent to servers since 1.20.2.
+    /// # Errors
+    /// This function will return the errors of `write_named`.
+    pub fn write_network(&self, w: &mut dyn Write) -> io::Result<()> {
+        w.write_all(&[self.type_id()])?;
+        self.write_payload(w)
+    }
+
+    /// Writes `tag` as a root tag in the form servers of `version` expect, writing a lone
+    /// `TAG_End` if there is none.
+    /// # Errors
+    /// This function will return the errors of `write_named`.
+    pub fn write_optional_for(tag: Option<&NbtTag>, version: ProtocolVersion, w: &mut dyn Write) -> io::Result<()> {
+        match (tag, version >= ProtocolVersion::V1_20_2) {
+            (None, _) => w.write_all(&[END_ID]),
+            (Some(tag), true) => tag.write_network(w),
+            (Some(tag), false) => tag.write_named("", w)
+        }
+    }
+
+    /// Encodes this tag as a root tag in the form servers of `version` expect.
+    /// # Errors
+    /// This function will return the errors of `write_named`.
+    pub fn to_bytes_for(&self, version: ProtocolVersion) -> io::Result<Vec<u8>> {
+        let mut bytes = Vec::new();
+        NbtTag::write_optional_for(Some(self), version, &mut bytes)?;
+        Ok(bytes)
+    }
+
+    fn write_payload(&self, w: &mut dyn Write) -> io::Result<()> {
+        match self {
+            NbtTag::Byte(value) => w.write_all(&value.to_be_bytes()),
+            NbtTag::Short(value) => w.write_all(&value.to_be_bytes()),
+            NbtTag::Int(value) => w.write_all(&value.to_be_bytes()),
+            NbtTag::Long(value) => w.write_all(&value.to_be_bytes()),
+            NbtTag::Float(value) => w.write_all(&value.to_be_bytes()),
+            NbtTag::Double(value) => w.write_all(&value.to_be_bytes()),
+            NbtTag::ByteArray(values) => {
+                write_length(values.len(), w)?;
+                w.write_all(&values.iter().map(|value| *value as u8).collect::<Vec<_>>())
+            },
+            NbtTag::String(value) => write_string(value, w),
+            NbtTag::List(tags) => {
+                let type_id = tags.first().map_or(END_ID, NbtTag::type_id);
+                if tags.iter().any(|tag| tag.type_id() != type_id) {
+                    return Err(io::Error::new(io::ErrorKind::InvalidInput, "NBT lists cannot mix tag types."));
+                }
+
+                w.write_all(&[type_id])?;
+                write_length(tags.len(), w)?;
+                for tag in tags {
+                    tag.write_payload(w)?;
+                }
+                Ok(())
+            },
+            NbtTag::Compound(compound) => {
+                for (name, tag) in compound {
+                    tag.write_named(name, w)?;
+                }
+                w.write_all(&[END_ID])
+            },
+            NbtTag::IntArray(values) => {
+                write_length(values.len(), w)?;
+                values.iter().try_for_each(|value| w.write_all(&value.to_be_bytes()))
+            },
+            NbtTag::LongArray(values) => {
+                write_length(values.len(), w)?;
+                values.iter().try_for_each(|value| w.write_all(&value.to_be_bytes()))
+            }
+        }
+    }
+
+    /// Gets the tag named `name`, if this is a compound which has one.
+    pub fn get(&self, name: &str) -> Option<&NbtTag> {
+        self.as_compound()?.get(name)
+    }
+
+    pub fn as_compound(&self) -> Option<&NbtCompound> {
+        match self {
+            NbtTag::Compound(compound) => Some(compound),
+            _ => None
+        }
+    }
+
+    pub fn as_list(&self) -> Option<&[NbtTag]> {
+        match self {
+            NbtTag::List(tags) => Some(tags),
+            _ => None
+        }
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            NbtTag::String(value) => Some(value),
+            _ => None
+        }
+    }
+
+    /// Gets the value of an integral tag, of any width.
+    pub fn as_i64(&self) -> Option<i64> {
+        match self {
+            NbtTag::Byte(value) => Some(*value as i64),
+            NbtTag::Short(value) => Some(*value as i64),
+            NbtTag::Int(value) => Some(*value as i64),
+            NbtTag::Long(value) => Some(*value),
+            _ => None
+        }
+    }
+
+    /// Gets the value of a numeric tag, of any type.
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            NbtTag::Float(value) => Some(*value as f64),
+            NbtTag::Double(value) => Some(*value),
+            _ => self.as_i64().map(|value| value as f64)
+        }
+    }
+
+    /// Deserializes this tag into `T`, through the JSON data model: numbers become JSON
+    /// numbers, strings and compounds become strings and objects, and lists and arrays
+    /// become arrays. Bytes 0 and 1 are accepted where `T` has booleans.
+    /// # Errors
+    /// This function will return `MCTypeError::InvalidValue` if the tag does not match `T`.
+    pub fn deserialize_into<T: DeserializeOwned>(&self) -> Result<T, MCTypeError> {
+        let value = serde_json::to_value(self).map_err(|err| MCTypeError::InvalidValue(err.to_string()))?;
+        match serde_json::from_value(value.clone()) {
+            Ok(deserialized) => Ok(deserialized),
+            // Booleans are stored as bytes, which only deserialize as booleans once converted.
+            Err(err) => serde_json::from_value(bytes_to_booleans(value)).map_err(|_| MCTypeError::InvalidValue(err.to_string()))
+        }
+    }
+
+    /// Converts `value` into a tag, through the JSON data model: integers become `Int`
+    /// tags, or `Long` tags if they do not fit, other numbers become `Double` tags, and
+    /// booleans become bytes. `null` fields of objects are left out.
+    /// # Errors
+    /// This function will return `MCTypeError::InvalidValue` if `value` does not serialize
+    /// to JSON, is `null`, or has an array mixing types.
+    pub fn from_serialize<T: Serialize>(value: &T) -> Result<NbtTag, MCTypeError> {
+        let value = serde_json::to_value(value).map_err(|err| MCTypeError::InvalidValue(err.to_string()))?;
+        NbtTag::from_json(value)
+    }
+
+    fn from_json(value: Value) -> Result<NbtTag, MCTypeError> {
+        Ok(match value {
+            Value::Null => return Err(MCTypeError::InvalidValue("NBT has no null tag.".to_owned())),
+            Value::Bool(value) => NbtTag::Byte(value as i8),
+            Value::Number(number) => match (number.as_i64(), number.as_f64()) {
+                (Some(value), _) => i32::try_from(value).map_or(NbtTag::Long(value), NbtTag::Int),
+                (None, value) => NbtTag::Double(value.unwrap_or_default())
+            },
+            Value::String(value) => NbtTag::String(value),
+            Value::Array(values) => {
+                let tags = values.into_iter().map(NbtTag::from_json).collect::<Result<Vec<_>, _>>()?;
+                if tags.windows(2).any(|pair| pair[0].type_id() != pair[1].type_id()) {
+                    return Err(MCTypeError::InvalidValue("NBT lists cannot mix tag types.".to_owned()));
+                }
+                NbtTag::List(tags)
+            },
+            Value::Object(object) => NbtTag::Compound(object.into_iter()
+                .filter(|(_, value)| !value.is_null())
+                .map(|(name, value)| Ok((name, NbtTag::from_json(value)?)))
+                .collect::<Result<_, MCTypeError>>()?)
+        })
+    }
+}
+
+/// Replaces the numbers 0 and 1 within `value` by booleans.
+fn bytes_to_booleans(value: Value) -> Value {
+    match value {
+        Value::Number(number) if number.as_i64() == Some(0) => Value::Bool(false),
+        Value::Number(number) if number.as_i64() == Some(1) => Value::Bool(true),
+        Value::Array(values) => Value::Array(values.into_iter().map(bytes_to_booleans).collect()),
+        Value::Object(object) => Value::Object(object.into_iter().map(|(name, value)| (name, bytes_to_booleans(value))).collect()),
+        value => value
+    }
+}
+
+impl Serialize for NbtTag {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            NbtTag::Byte(value) => serializer.serialize_i8(*value),
+            NbtTag::Short(value) => serializer.serialize_i16(*value),
+            NbtTag::Int(value) => serializer.serialize_i32(*value),
+            NbtTag::Long(value) => serializer.serialize_i64(*value),
+            NbtTag::Float(value) => serializer.serialize_f32(*value),
+            NbtTag::Double(value) => serializer.serialize_f64(*value),
+            NbtTag::ByteArray(values) => values.serialize(serializer),
+            NbtTag::String(value) => serializer.serialize_str(value),
+            NbtTag::List(tags) => tags.serialize(serializer),
+            NbtTag::Compound(compound) => compound.serialize(serializer),
+            NbtTag::IntArray(values) => values.serialize(serializer),
+            NbtTag::LongArray(values) => values.serialize(serializer)
+        }
+    }
+}
+
+/// Tags deserialize as per `from_serialize`, so the types of numbers are inferred from
+/// their values rather than kept.
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for NbtTag {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        NbtTag::from_json(Value::deserialize(deserializer)?).map_err(serde::de::Error::custom)
+    }
+}
+
+/// Tags are displayed as SNBT, the text form commands and data packs write NBT in, such
+/// as `{Count:1b,id:"minecraft:stone"}`.
+impl fmt::Display for NbtTag {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            NbtTag::Byte(value) => write!(f, "{}b", value),
+            NbtTag::Short(value) => write!(f, "{}s", value),
+            NbtTag::Int(value) => write!(f, "{}", value),
+            NbtTag::Long(value) => write!(f, "{}L", value),
+            NbtTag::Float(value) => write!(f, "{}f", value),
+            NbtTag::Double(value) => write!(f, "{}d", value),
+            NbtTag::ByteArray(values) => write_snbt_list("[B;", values.iter().map(|value| NbtTag::Byte(*value)), f),
+            NbtTag::String(value) => write_snbt_string(value, f),
+            NbtTag::List(tags) => write_snbt_list("[", tags, f),
+            NbtTag::Compound(compound) => {
+                f.write_str("{")?;
+                for (index, (name, tag)) in compound.iter().enumerate() {
+                    if index > 0 {
+                        f.write_str(",")?;
+                    }
+                    match !name.is_empty() && name.chars().all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.' | '+')) {
+                        true => f.write_str(name)?,
+                        false => write_snbt_string(name, f)?
+                    }
+                    write!(f, ":{}", tag)?;
+                }
+                f.write_str("}")
+            },
+            NbtTag::IntArray(values) => write_snbt_list("[I;", values, f),
+            NbtTag::LongArray(values) => write_snbt_list("[L;", values.iter().map(|value| NbtTag::Long(*value)), f)
+        }
+    }
+}
+
+/// Writes `items` separated by commas, after `open` and before a closing bracket.
+fn write_snbt_list<I>(open: &str, items: I, f: &mut fmt::Formatter<'_>) -> fmt::Result
+where I: IntoIterator, I::Item: fmt::Display {
+    f.write_str(open)?;
+    for (index, item) in items.into_iter().enumerate() {
+        if index > 0 {
+            f.write_str(",")?;
+        }
+        write!(f, "{}", item)?;
+    }
+    f.write_str("]")
+}
+
+/// Writes `string` in double quotes, escaping quotes and backslashes.
+fn write_snbt_string(string: &str, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.write_str("\"")?;
+    for c in string.chars() {
+        if matches!(c, '"' | '\\') {
+            f.write_str("\\")?;
+        }
+        write!(f, "{}", c)?;
+    }
+    f.write_str("\"")
+}
+
+/// Tags are encoded as named root tags with an empty name, as in the canonical version.
+impl MCType for NbtTag {
+    fn encode(&self, w: &mut dyn Write) -> io::Result<()> {
+        self.write_named("", w)
+    }
+
+    fn size(&self) -> i32 {
+        let mut counter = ByteCounter(0);
+        self.encode(&mut counter).map_or(0, |_| counter.0 as i32)
+    }
+
+    fn parse(bytes: &[u8]) -> Result<(Self, &[u8]), MCTypeError> {
+        match NbtTag::parse_named(bytes)? {
+            (Some((_, tag)), rest) => Ok((tag, rest)),
+            (None, _) => Err(MCTypeError::InvalidValue("Missing NBT root tag.".to_owned()))
+        }
+    }
+}
+
+/// A writer which counts the bytes written to it.
+struct ByteCounter(usize);
+
+impl Write for ByteCounter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0 += buf.len();
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+fn write_length(len: usize, w: &mut dyn Write) -> io::Result<()> {
+    let len = i32::try_from(len).map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, "NBT arrays cannot exceed 2147483647 elements."))?;
+    w.write_all(&len.to_be_bytes())
+}
+
+/// Writes `string` in the modified UTF-8 of Java, which encodes the NUL character in two
+/// bytes and characters outside the Basic Multilingual Plane as surrogate pairs.
+fn write_string(string: &str, w: &mut dyn Write) -> io::Result<()> {
+    let mut encoded = Vec::with_capacity(string.len());
+    for unit in string.encode_utf16() {
+        match unit {
+            0x0001..=0x007F => encoded.push(unit as u8),
+            0x0000 | 0x0080..=0x07FF => encoded.extend([0xC0 | (unit >> 6) as u8, 0x80 | (unit & 0x3F) as u8]),
+            _ => encoded.extend([0xE0 | (unit >> 12) as u8, 0x80 | ((unit >> 6) & 0x3F) as u8, 0x80 | (unit & 0x3F) as u8])
+        }
+    }
+
+    let len = u16::try_from(encoded.len()).map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, "NBT strings cannot exceed 65535 bytes."))?;
+    w.write_all(&len.to_be_bytes())?;
+    w.write_all(&encoded)
+}
+
+/// Reads tags from the front of a slice, which it advances past them.
+struct NbtReader<'a> {
+    bytes: &'a [u8]
+}
+
+impl<'a> NbtReader<'a> {
+    fn take(&mut self, len: usize) -> Result<&'a [u8], MCTypeError> {
+        if self.bytes.len() < len {
+            return Err(MCTypeError::Truncated);
+        }
+
+        let (taken, rest) = self.bytes.split_at(len);
+        self.bytes = rest;
+        Ok(taken)
+    }
+
+    fn array<const N: usize>(&mut self) -> Result<[u8; N], MCTypeError> {
+        Ok(self.take(N)?.try_into().expect("Taken slices have the length asked for."))
+    }
+
+    fn byte(&mut self) -> Result<u8, MCTypeError> {
+        Ok(self.array::<1>()?[0])
+    }
+
+    /// Reads the length of an array or list whose elements take at least `element_size`
+    /// bytes each, which must fit in the bytes remaining.
+    fn length(&mut self, element_size: usize) -> Result<usize, MCTypeError> {
+        let len = i32::from_be_bytes(self.array()?);
+        if len < 0 {
+            return Err(MCTypeError::OversizeLength(len as i64));
+        }
+        if (len as usize).saturating_mul(element_size) > self.bytes.len() {
+            return Err(MCTypeError::Truncated);
+        }
+
+        limits::array_length(len)
+    }
+
+    fn string(&mut self) -> Result<String, MCTypeError> {
+        let len = u16::from_be_bytes(self.array()?) as usize;
+        let bytes = self.take(len)?;
+        if let Ok(string) = core::str::from_utf8(bytes) {
+            // Modified UTF-8 differs from UTF-8 only for the NUL character and supplementary
+            // characters, which are invalid UTF-8 in their modified encoding.
+            if !string.contains('\0') {
+                return Ok(string.to_owned());
+            }
+        }
+
+        let mut units = Vec::with_capacity(len);
+        let mut iter = bytes.iter().copied();
+        while let Some(first) = iter.next() {
+            let mut continuation = || iter.next().filter(|byte| byte & 0xC0 == 0x80).map(|byte| (byte & 0x3F) as u16).ok_or(MCTypeError::InvalidUtf8);
+            units.push(match first {
+                0x01..=0x7F => first as u16,
+                0xC0..=0xDF => ((first & 0x1F) as u16) << 6 | continuation()?,
+                0xE0..=0xEF => ((first & 0x0F) as u16) << 12 | continuation()? << 6 | continuation()?,
+                _ => return Err(MCTypeError::InvalidUtf8)
+            });
+        }
+
+        String::from_utf16(&units).map_err(|_| MCTypeError::InvalidUtf8)
+    }
+
+    fn payload(&mut self, type_id: u8, depth: usize) -> Result<NbtTag, MCTypeError> {
+        let max_depth = limits::nbt_depth();
+        if depth >= max_depth {
+            return Err(MCTypeError::InvalidValue(format!("NBT is nested deeper than {} tags.", max_depth)));
+        }
+
+        Ok(match type_id {
+            1 => NbtTag::Byte(i8::from_be_bytes(self.array()?)),
+            2 => NbtTag::Short(i16::from_be_bytes(self.array()?)),
+            3 => NbtTag::Int(i32::from_be_bytes(self.array()?)),
+            4 => NbtTag::Long(i64::from_be_bytes(self.array()?)),
+            5 => NbtTag::Float(f32::from_be_bytes(self.array()?)),
+            6 => NbtTag::Double(f64::from_be_bytes(self.array()?)),
+            7 => {
+                let len = self.length(1)?;
+                NbtTag::ByteArray(self.take(len)?.iter().map(|byte| *byte as i8).collect())
+            },
+            8 => NbtTag::String(self.string()?),
+            9 => {
+                let element_id = self.byte()?;
+                let len = self.length(1)?;
+                if element_id == END_ID && len > 0 {
+                    return Err(MCTypeError::InvalidValue("NBT list of TAG_End is not empty.".to_owned()));
+                }
+                let mut tags = Vec::with_capacity(len);
+                for _ in 0..len {
+                    tags.push(self.payload(element_id, depth + 1)?);
+                }
+                NbtTag::List(tags)
+            },
+            10 => {
+                let mut compound = NbtCompound::new();
+                loop {
+                    let type_id = self.byte()?;
+                    if type_id == END_ID {
+                        break;
+                    }
+                    let name = self.string()?;
+                    compound.insert(name, self.payload(type_id, depth + 1)?);
+                }
+                NbtTag::Compound(compound)
+            },
+            11 => {
+                let len = self.length(4)?;
+                NbtTag::IntArray((0..len).map(|_| self.array().map(i32::from_be_bytes)).collect::<Result<_, _>>()?)
+            },
+            12 => {
+                let len = self.length(8)?;
+                NbtTag::LongArray((0..len).map(|_| self.array().map(i64::from_be_bytes)).collect::<Result<_, _>>()?)
+            },
+            _ => return Err(MCTypeError::InvalidValue(format!("Unknown NBT tag type {}.", type_id)))
+        })
+    }
+}
@@ -0,0 +1,10 @@
+use crate::mc::{mctypes::VarInt, packet::{McDecode, PacketId}};
+
+/// Sent by the server (1.19+) to confirm every block action the client stamped
+/// with a sequence number up to and including `sequence_id`.
+#[derive(PacketId, McDecode)]
+#[packet(id = 0x05, state = Play)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AcknowledgeBlockChange {
+    pub sequence_id: VarInt
+}
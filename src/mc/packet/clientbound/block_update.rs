@@ -0,0 +1,38 @@
+use std::io;
+
+use crate::mc::{connection::ConnectionState, mctypes::{MCLong, MCPosition, MCType, VarInt}, packet::{InboundPacket, MCPacket, PacketId}, version::ProtocolVersion};
+
+/// Sent by the server when a block changes. As the layout of positions differs between
+/// versions, the position is kept in its encoded form until it is decoded for the
+/// server's version with `position`.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BlockUpdate {
+    pub location: MCLong,
+    /// The new block state. Before 1.13, this is the block ID shifted left by 4 bits,
+    /// or'd with its data value.
+    pub block_state: VarInt
+}
+
+impl BlockUpdate {
+    /// Decodes the position of the block in the layout used by `version`.
+    pub fn position(&self, version: ProtocolVersion) -> MCPosition {
+        MCPosition::from_i64_for(self.location.value(), version)
+    }
+}
+
+impl PacketId for BlockUpdate {
+    const ID: i32 = 0x09;
+    const STATE: ConnectionState = ConnectionState::Play;
+}
+
+impl InboundPacket for BlockUpdate {
+    fn from_data(packet: &MCPacket) -> Result<Self, io::Error> {
+        if !packet.is::<Self>() {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, "Invalid packet ID."));
+        }
+
+        let (location, rest) = MCLong::parse(&packet.data)?;
+        let (block_state, _) = VarInt::parse(rest)?;
+        Ok(BlockUpdate { location, block_state })
+    }
+}
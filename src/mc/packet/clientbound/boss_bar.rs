@@ -0,0 +1,76 @@
+use std::io;
+
+use bytes::Bytes;
+use uuid::Uuid;
+
+use crate::mc::{chat::ChatComponent, connection::ConnectionState, error::MCTypeError, mctypes::{MCFloat, MCType, MCUnsignedByte, MCUuid, VarInt}, packet::{InboundPacket, MCPacket, PacketId}, version::ProtocolVersion};
+
+/// What a Boss Bar packet does to its bar.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum BossBarAction {
+    /// Shows a new bar.
+    Add { title: ChatComponent, health: f32, color: i32, division: i32, flags: u8 },
+    /// Hides the bar.
+    Remove,
+    UpdateHealth(f32),
+    UpdateTitle(ChatComponent),
+    UpdateStyle { color: i32, division: i32 },
+    UpdateFlags(u8)
+}
+
+/// Sent by the server to show, update or hide a boss bar at the top of the screen, which
+/// minigames often show information in. Servers older than 1.9 have no boss bars. As the
+/// title is encoded as NBT since 1.20.3, the packet is kept in its encoded form until it
+/// is decoded for the server's version with `action`.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BossBar {
+    pub uuid: Uuid,
+    pub data: Bytes
+}
+
+impl BossBar {
+    /// Decodes the action in the format of `version`.
+    /// # Errors
+    /// This function will return an `MCTypeError` if the data does not encode an action,
+    /// or the errors of `ChatComponent::parse_for`.
+    pub fn action(&self, version: ProtocolVersion) -> Result<BossBarAction, MCTypeError> {
+        let (action, rest) = VarInt::parse(&self.data)?;
+        Ok(match action.value() {
+            0 => {
+                let (title, rest) = ChatComponent::parse_for(rest, version)?;
+                let (health, rest) = MCFloat::parse(rest)?;
+                let (color, rest) = VarInt::parse(rest)?;
+                let (division, rest) = VarInt::parse(rest)?;
+                let (flags, _) = MCUnsignedByte::parse(rest)?;
+                BossBarAction::Add { title, health: health.value(), color: color.value(), division: division.value(), flags: flags.value() }
+            },
+            1 => BossBarAction::Remove,
+            2 => BossBarAction::UpdateHealth(MCFloat::parse(rest)?.0.value()),
+            3 => BossBarAction::UpdateTitle(ChatComponent::parse_for(rest, version)?.0),
+            4 => {
+                let (color, rest) = VarInt::parse(rest)?;
+                let (division, _) = VarInt::parse(rest)?;
+                BossBarAction::UpdateStyle { color: color.value(), division: division.value() }
+            },
+            5 => BossBarAction::UpdateFlags(MCUnsignedByte::parse(rest)?.0.value()),
+            action => return Err(MCTypeError::InvalidValue(format!("Unknown boss bar action {}.", action)))
+        })
+    }
+}
+
+impl PacketId for BossBar {
+    const ID: i32 = 0x0A;
+    const STATE: ConnectionState = ConnectionState::Play;
+}
+
+impl InboundPacket for BossBar {
+    fn from_data(packet: &MCPacket) -> Result<Self, io::Error> {
+        if !packet.is::<Self>() {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, "Invalid packet ID."));
+        }
+
+        let (uuid, rest) = MCUuid::parse(&packet.data)?;
+        Ok(BossBar { uuid: uuid.uuid(), data: packet.data.slice_ref(rest) })
+    }
+}
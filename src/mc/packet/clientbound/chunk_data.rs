@@ -0,0 +1,43 @@
+use std::io;
+
+use bytes::Bytes;
+
+use crate::mc::{connection::ConnectionState, error::MCTypeError, mctypes::{MCInt, MCType}, packet::{InboundPacket, MCPacket, PacketId}, version::ProtocolVersion, world::chunk::Chunk};
+
+/// Sent by the server with the blocks, biomes and block entities of a chunk column, and
+/// since 1.18, with its light. As the layout of chunks differs between versions, the
+/// column is kept in its encoded form until it is decoded for the server's version with
+/// `chunk`.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ChunkData {
+    pub x: MCInt,
+    pub z: MCInt,
+    pub data: Bytes
+}
+
+impl ChunkData {
+    /// Decodes the chunk column in the format of `version`, in a dimension whose lowest
+    /// block is at `min_y`.
+    /// # Errors
+    /// This function will return the errors of `Chunk::parse_for`.
+    pub fn chunk(&self, version: ProtocolVersion, min_y: i32) -> Result<Chunk, MCTypeError> {
+        Chunk::parse_for(self.x.value(), self.z.value(), &self.data, version, min_y)
+    }
+}
+
+impl PacketId for ChunkData {
+    const ID: i32 = 0x20;
+    const STATE: ConnectionState = ConnectionState::Play;
+}
+
+impl InboundPacket for ChunkData {
+    fn from_data(packet: &MCPacket) -> Result<Self, io::Error> {
+        if !packet.is::<Self>() {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, "Invalid packet ID."));
+        }
+
+        let (x, rest) = MCInt::parse(&packet.data)?;
+        let (z, rest) = MCInt::parse(rest)?;
+        Ok(ChunkData { x, z, data: packet.data.slice_ref(rest) })
+    }
+}
@@ -0,0 +1,11 @@
+use crate::mc::{mctypes::MCBoolean, packet::{McDecode, PacketId}};
+
+/// Sent by the server to hide the title and subtitle, and to also reset the title times
+/// to their defaults if `reset` is set. Before 1.17, these are actions of the Title
+/// packet.
+#[derive(PacketId, McDecode)]
+#[packet(id = 0x0C, state = Play)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ClearTitles {
+    pub reset: MCBoolean
+}
@@ -0,0 +1,10 @@
+use crate::mc::{mctypes::MCUnsignedByte, packet::{McDecode, PacketId}};
+
+/// Sent by the server when it closes the window `window_id`, such as when the chest the
+/// player opened is broken.
+#[derive(PacketId, McDecode)]
+#[packet(id = 0x0F, state = Play)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CloseContainer {
+    pub window_id: MCUnsignedByte
+}
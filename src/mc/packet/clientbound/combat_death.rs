@@ -0,0 +1,72 @@
+use std::io;
+
+use bytes::Bytes;
+
+use crate::mc::{chat::ChatComponent, connection::ConnectionState, error::MCTypeError, mctypes::{MCInt, MCType, VarInt}, packet::{InboundPacket, MCPacket, PacketId}, version::ProtocolVersion};
+
+/// The decoded death of a player.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Death {
+    /// The entity ID of the player which died.
+    pub player_id: i32,
+    /// The death message shown on the death screen.
+    pub message: ChatComponent
+}
+
+/// Sent by the server when the player dies, to show the death screen until the player
+/// respawns. Before 1.17, this is a Combat Event, which also tells of the player entering
+/// and leaving combat. As the layout differs between versions, the packet is kept in its
+/// encoded form until it is decoded for the server's version with `death`.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CombatDeath {
+    pub data: Bytes
+}
+
+impl CombatDeath {
+    /// The first protocol version (1.17) sending deaths in a packet of their own.
+    const DEATH_PACKET_SINCE: i32 = 755;
+    /// The first protocol version (1.20) no longer sending the entity which killed the
+    /// player.
+    const NO_KILLER_SINCE: i32 = 763;
+    /// The event of a Combat Event telling of a death.
+    const ENTITY_DEAD_EVENT: i32 = 2;
+
+    /// Decodes the death in the format of `version`, or returns `None` if the packet is a
+    /// Combat Event of another kind.
+    /// # Errors
+    /// This function will return an `MCTypeError` if the data does not encode a death, or
+    /// the errors of `ChatComponent::parse_for`.
+    pub fn death(&self, version: ProtocolVersion) -> Result<Option<Death>, MCTypeError> {
+        let mut rest = &self.data[..];
+        if version.number() < Self::DEATH_PACKET_SINCE {
+            let (event, after) = VarInt::parse(rest)?;
+            if event.value() != Self::ENTITY_DEAD_EVENT {
+                return Ok(None);
+            }
+            rest = after;
+        }
+
+        let (player_id, mut rest) = VarInt::parse(rest)?;
+        if version.number() < Self::NO_KILLER_SINCE {
+            rest = MCInt::parse(rest)?.1;
+        }
+        let (message, _) = ChatComponent::parse_for(rest, version)?;
+        Ok(Some(Death { player_id: player_id.value(), message }))
+    }
+}
+
+impl PacketId for CombatDeath {
+    const ID: i32 = 0x34;
+    const STATE: ConnectionState = ConnectionState::Play;
+}
+
+impl InboundPacket for CombatDeath {
+    fn from_data(packet: &MCPacket) -> Result<Self, io::Error> {
+        if !packet.is::<Self>() {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, "Invalid packet ID."));
+        }
+
+        Ok(CombatDeath { data: packet.data.clone() })
+    }
+}
@@ -0,0 +1,92 @@
+use std::io;
+
+use bytes::Bytes;
+
+use crate::mc::{chat::ChatComponent, connection::ConnectionState, error::MCTypeError, mctypes::{MCBoolean, MCString, MCType, VarInt}, packet::{InboundPacket, MCPacket, PacketId, serverbound::command_suggestions_request::CommandSuggestionsRequest}, version::ProtocolVersion};
+
+/// A completion suggested by the server.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Suggestion {
+    /// The text replacing the completed range.
+    pub text: String,
+    /// The message shown when the suggestion is hovered, if any.
+    pub tooltip: Option<ChatComponent>
+}
+
+/// The decoded completions of a text the client requested.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Suggestions {
+    /// The transaction ID of the request answered, which servers older than 1.13 do not
+    /// send.
+    pub transaction_id: Option<i32>,
+    /// The index of the first character of the text which the suggestions replace.
+    /// Servers older than 1.13 do not send the range, which is then empty at the start.
+    pub start: i32,
+    /// The number of characters the suggestions replace.
+    pub length: i32,
+    pub matches: Vec<Suggestion>
+}
+
+/// Sent by the server in response to a Command Suggestions Request, with the completions
+/// of its text. Before 1.13, this is Tab-Complete, which only has the completions. As the
+/// layout differs between versions, the packet is kept in its encoded form until it is
+/// decoded for the server's version with `suggestions`.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CommandSuggestions {
+    pub data: Bytes
+}
+
+impl CommandSuggestions {
+    /// Decodes the suggestions in the format of `version`.
+    /// # Errors
+    /// This function will return an `MCTypeError` if the data does not encode suggestions,
+    /// or the errors of `ChatComponent::parse_for`.
+    pub fn suggestions(&self, version: ProtocolVersion) -> Result<Suggestions, MCTypeError> {
+        let mut rest = &self.data[..];
+        let numbered = version.number() >= CommandSuggestionsRequest::TRANSACTION_SINCE;
+        let (mut transaction_id, mut start, mut length) = (None, 0, 0);
+        if numbered {
+            let (id, after) = VarInt::parse(rest)?;
+            let (range_start, after) = VarInt::parse(after)?;
+            let (range_length, after) = VarInt::parse(after)?;
+            (transaction_id, start, length) = (Some(id.value()), range_start.value(), range_length.value());
+            rest = after;
+        }
+
+        let (count, mut rest) = VarInt::parse(rest)?;
+        let mut matches = Vec::new();
+        for _ in 0..count.value().max(0) {
+            let (text, after) = MCString::parse_bounded(rest, 32767)?;
+            rest = after;
+            let mut tooltip = None;
+            if numbered {
+                let (has_tooltip, after) = MCBoolean::parse(rest)?;
+                rest = after;
+                if has_tooltip.value() {
+                    let (component, after) = ChatComponent::parse_for(rest, version)?;
+                    (tooltip, rest) = (Some(component), after);
+                }
+            }
+            matches.push(Suggestion { text: text.string().clone(), tooltip });
+        }
+
+        Ok(Suggestions { transaction_id, start, length, matches })
+    }
+}
+
+impl PacketId for CommandSuggestions {
+    const ID: i32 = 0x0D;
+    const STATE: ConnectionState = ConnectionState::Play;
+}
+
+impl InboundPacket for CommandSuggestions {
+    fn from_data(packet: &MCPacket) -> Result<Self, io::Error> {
+        if !packet.is::<Self>() {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, "Invalid packet ID."));
+        }
+
+        Ok(CommandSuggestions { data: packet.data.clone() })
+    }
+}
@@ -0,0 +1,11 @@
+use crate::mc::{mctypes::MCLong, packet::{McDecode, PacketId}};
+
+/// Sent by the server periodically during configuration to check the client is still
+/// connected. The client must answer with a serverbound Config Keep Alive carrying the
+/// same `keep_alive_id`.
+#[derive(PacketId, McDecode)]
+#[packet(id = 0x03, state = Configuration)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ConfigKeepAlive {
+    pub keep_alive_id: MCLong
+}
@@ -0,0 +1,32 @@
+use std::io;
+
+use bytes::Bytes;
+
+use crate::mc::{connection::ConnectionState, mctypes::MCString, packet::{InboundPacket, MCPacket, PacketId}};
+
+/// Sent by the server during configuration to exchange custom data on `channel`, as its
+/// Play counterpart does.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ConfigPluginMessage {
+    pub channel: MCString,
+    pub data: Bytes
+}
+
+impl PacketId for ConfigPluginMessage {
+    const ID: i32 = 0x00;
+    const STATE: ConnectionState = ConnectionState::Configuration;
+}
+
+impl InboundPacket for ConfigPluginMessage {
+    fn from_data(packet: &MCPacket) -> Result<Self, io::Error> {
+        if !packet.is::<Self>() {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, "Invalid packet ID."));
+        }
+
+        let mut cursor = packet.cursor();
+        let channel = MCString::read_bounded(&mut cursor, 32767)?;
+        let data = packet.data.slice(cursor.position() as usize..);
+
+        Ok(ConfigPluginMessage { channel, data })
+    }
+}
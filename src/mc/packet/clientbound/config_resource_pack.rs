@@ -0,0 +1,36 @@
+use std::io;
+
+use bytes::Bytes;
+
+use crate::mc::{connection::ConnectionState, error::MCTypeError, packet::{InboundPacket, MCPacket, PacketId}, resource_pack::ResourcePackEvent, version::ProtocolVersion};
+
+/// Asks the client to load a resource pack during configuration, as its Play counterpart
+/// does.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ConfigResourcePack {
+    pub data: Bytes
+}
+
+impl ConfigResourcePack {
+    /// Decodes the pack in the format of `version`.
+    /// # Errors
+    /// This function will return the errors of `ResourcePackEvent::parse`.
+    pub fn pack(&self, version: ProtocolVersion) -> Result<ResourcePackEvent, MCTypeError> {
+        ResourcePackEvent::parse(&self.data, version)
+    }
+}
+
+impl PacketId for ConfigResourcePack {
+    const ID: i32 = 0x06;
+    const STATE: ConnectionState = ConnectionState::Configuration;
+}
+
+impl InboundPacket for ConfigResourcePack {
+    fn from_data(packet: &MCPacket) -> Result<Self, io::Error> {
+        if !packet.is::<Self>() {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, "Invalid packet ID."));
+        }
+
+        Ok(ConfigResourcePack { data: packet.data.clone() })
+    }
+}
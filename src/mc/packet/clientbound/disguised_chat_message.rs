@@ -0,0 +1,38 @@
+use std::io;
+
+use crate::mc::{connection::ConnectionState, mctypes::{MCBoolean, MCString, MCTypeDecode, VarInt}, packet::{InboundPacket, MCPacket, PacketId}};
+
+/// A message sent on behalf of a player without a signature, such as the output of `/say`
+/// from the console. The `message`, `chat_type_name` and `target_name` are JSON text
+/// components; the `chat_type` indexes the chat types of the server's registry.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DisguisedChatMessage {
+    pub message: MCString,
+    pub chat_type: VarInt,
+    pub chat_type_name: MCString,
+    pub target_name: Option<MCString>
+}
+
+impl PacketId for DisguisedChatMessage {
+    const ID: i32 = 0x18;
+    const STATE: ConnectionState = ConnectionState::Play;
+}
+
+impl InboundPacket for DisguisedChatMessage {
+    fn from_data(packet: &MCPacket) -> Result<Self, io::Error> {
+        if !packet.is::<Self>() {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, "Invalid packet ID."));
+        }
+
+        let mut cursor = packet.cursor();
+        let message = MCString::read_bounded(&mut cursor, 262144)?;
+        let chat_type = VarInt::read(&mut cursor)?;
+        let chat_type_name = MCString::read_bounded(&mut cursor, 262144)?;
+        let target_name = match MCBoolean::read(&mut cursor)?.value() {
+            true => Some(MCString::read_bounded(&mut cursor, 262144)?),
+            false => None
+        };
+
+        Ok(DisguisedChatMessage { message, chat_type, chat_type_name, target_name })
+    }
+}
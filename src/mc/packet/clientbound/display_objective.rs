@@ -0,0 +1,14 @@
+use crate::mc::{mctypes::MCString, packet::{McDecode, PacketId}};
+
+/// Sent by the server to show the objective `score_name` in the display slot `position`,
+/// or to clear the slot if the name is empty. The position is a byte before 1.20.2, which
+/// is encoded as its `VarInt` for the slots there are.
+#[derive(PacketId, McDecode)]
+#[packet(id = 0x4D, state = Play)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DisplayObjective {
+    #[varint]
+    pub position: i32,
+    #[max_len = 32767]
+    pub score_name: MCString
+}
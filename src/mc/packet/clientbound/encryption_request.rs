@@ -0,0 +1,42 @@
+use std::io;
+
+use crate::mc::{connection::ConnectionState, mctypes::{MCString, MCTypeDecode, VarInt}, packet::{InboundPacket, MCPacket, PacketId}};
+
+/// Sent by online-mode servers during login to begin encryption. The client answers
+/// with an Encryption Response carrying a shared secret and the `verify_token`, both
+/// encrypted with the server's `public_key`.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct EncryptionRequest {
+    pub server_id: MCString,
+    pub public_key: Vec<u8>,
+    pub verify_token: Vec<u8>
+}
+
+impl PacketId for EncryptionRequest {
+    const ID: i32 = 0x01;
+    const STATE: ConnectionState = ConnectionState::Login;
+}
+
+impl InboundPacket for EncryptionRequest {
+    fn from_data(packet: &MCPacket) -> Result<Self, io::Error> {
+        if !packet.is::<Self>() {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, "Invalid packet ID."));
+        }
+
+        let mut cursor = packet.cursor();
+        let server_id = MCString::read(&mut cursor)?;
+        let mut byte_array = || -> Result<Vec<u8>, io::Error> {
+            let len = VarInt::read(&mut cursor)?.value();
+            let start = cursor.position() as usize;
+            let bytes = usize::try_from(len).ok()
+                .and_then(|len| packet.data.get(start..start + len))
+                .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "Byte array length is out of bounds."))?;
+            cursor.set_position((start + bytes.len()) as u64);
+            Ok(bytes.to_vec())
+        };
+        let public_key = byte_array()?;
+        let verify_token = byte_array()?;
+
+        Ok(EncryptionRequest { server_id, public_key, verify_token })
+    }
+}
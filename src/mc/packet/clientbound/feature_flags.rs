@@ -0,0 +1,11 @@
+use crate::mc::{mctypes::{Identifier, MCArray}, packet::{McDecode, PacketId}};
+
+/// Sent by the server during configuration to announce the experimental features
+/// enabled on the server, such as `minecraft:bundle`. `minecraft:vanilla` is always
+/// enabled.
+#[derive(PacketId, McDecode)]
+#[packet(id = 0x07, state = Configuration)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FeatureFlags {
+    pub flags: MCArray<Identifier>
+}
@@ -0,0 +1,8 @@
+use crate::mc::packet::{McDecode, PacketId};
+
+/// Sent by the server once it has sent the configuration of the game. The client
+/// must answer with an Acknowledge Finish Configuration to enter the Play state.
+#[derive(PacketId, McDecode)]
+#[packet(id = 0x02, state = Configuration)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FinishConfiguration;
@@ -0,0 +1,22 @@
+use crate::mc::{mctypes::{MCFloat, MCUnsignedByte}, packet::{McDecode, PacketId}};
+
+/// Notifies the player of a change to the game, such as the weather or its game mode.
+/// The meaning of `value` depends on the `event`.
+/// <https://wiki.vg/Protocol#Game_Event>
+#[derive(PacketId, McDecode)]
+#[packet(id = 0x1C, state = Play)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GameEvent {
+    pub event: MCUnsignedByte,
+    pub value: MCFloat
+}
+
+impl GameEvent {
+    /// It starts raining.
+    pub const BEGIN_RAINING: u8 = 1;
+    /// It stops raining.
+    pub const END_RAINING: u8 = 2;
+    /// The player's game mode changes to `value`: survival (0), creative (1), adventure
+    /// (2) or spectator (3).
+    pub const CHANGE_GAME_MODE: u8 = 3;
+}
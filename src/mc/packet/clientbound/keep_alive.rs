@@ -0,0 +1,10 @@
+use crate::mc::{mctypes::MCLong, packet::{McDecode, PacketId}};
+
+/// Sent by the server periodically to check the client is still connected. The client
+/// must answer with a serverbound Keep Alive carrying the same `keep_alive_id`.
+#[derive(PacketId, McDecode)]
+#[packet(id = 0x1F, state = Play)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct KeepAlive {
+    pub keep_alive_id: MCLong
+}
@@ -0,0 +1,45 @@
+use std::io;
+
+use uuid::Uuid;
+
+use crate::mc::{connection::ConnectionState, mctypes::{MCByte, MCString, MCTypeDecode, MCUuid}, packet::{InboundPacket, MCPacket, PacketId}, registry::LEGACY_ID_BASE};
+
+/// A chat message sent by servers older than 1.19, which do not distinguish player and
+/// system messages. The `content` is a JSON text component.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LegacyChatMessage {
+    pub content: MCString,
+    /// Where the message is shown: 0 for the chat, 1 for system messages in the chat, and
+    /// 2 for above the hotbar.
+    pub position: i8,
+    /// The player who sent the message, which servers only report since 1.16.
+    pub sender: Option<Uuid>
+}
+
+impl LegacyChatMessage {
+    /// The `position` of messages shown above the hotbar.
+    pub const GAME_INFO: i8 = 2;
+}
+
+impl PacketId for LegacyChatMessage {
+    const ID: i32 = LEGACY_ID_BASE;
+    const STATE: ConnectionState = ConnectionState::Play;
+}
+
+impl InboundPacket for LegacyChatMessage {
+    fn from_data(packet: &MCPacket) -> Result<Self, io::Error> {
+        if !packet.is::<Self>() {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, "Invalid packet ID."));
+        }
+
+        let mut cursor = packet.cursor();
+        let content = MCString::read_bounded(&mut cursor, 262144)?;
+        let position = MCByte::read(&mut cursor)?.value();
+        let sender = match (cursor.position() as usize) < packet.data.len() {
+            true => Some(MCUuid::read(&mut cursor)?.uuid()),
+            false => None
+        };
+
+        Ok(LegacyChatMessage { content, position, sender })
+    }
+}
@@ -0,0 +1,78 @@
+use std::io;
+
+use bytes::Bytes;
+
+use crate::mc::{chat::ChatComponent, connection::ConnectionState, error::MCTypeError, mctypes::{MCInt, MCType, VarInt}, packet::{InboundPacket, MCPacket, PacketId}, registry::LEGACY_ID_BASE, version::ProtocolVersion};
+
+/// What a title packet does to the titles shown.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum TitleAction {
+    SetTitle(ChatComponent),
+    SetSubtitle(ChatComponent),
+    SetActionBar(ChatComponent),
+    /// Sets how long titles take to fade in, stay, and fade out, in ticks.
+    SetTimes { fade_in: i32, stay: i32, fade_out: i32 },
+    /// Hides the title and subtitle.
+    Hide,
+    /// Hides the title and subtitle, and resets the times to their defaults.
+    Reset
+}
+
+/// Sent by servers older than 1.17 to show, hide or time the title, or to show a message
+/// above the hotbar since 1.11. The packet is kept in its encoded form until it is
+/// decoded for the server's version with `action`, as the actions are numbered
+/// differently before 1.11.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LegacyTitle {
+    pub data: Bytes
+}
+
+impl LegacyTitle {
+    /// The first protocol version (1.11) with the action showing a message above the
+    /// hotbar.
+    const ACTION_BAR_SINCE: i32 = 315;
+
+    /// Decodes the action in the format of `version`.
+    /// # Errors
+    /// This function will return an `MCTypeError` if the data does not encode an action,
+    /// or the errors of `ChatComponent::parse_for`.
+    pub fn action(&self, version: ProtocolVersion) -> Result<TitleAction, MCTypeError> {
+        let (action, rest) = VarInt::parse(&self.data)?;
+        let mut action = action.value();
+        // Skip the number of the action bar action in versions without it.
+        if version.number() < Self::ACTION_BAR_SINCE && action >= 2 {
+            action += 1;
+        }
+
+        Ok(match action {
+            0 => TitleAction::SetTitle(ChatComponent::parse_for(rest, version)?.0),
+            1 => TitleAction::SetSubtitle(ChatComponent::parse_for(rest, version)?.0),
+            2 => TitleAction::SetActionBar(ChatComponent::parse_for(rest, version)?.0),
+            3 => {
+                let (fade_in, rest) = MCInt::parse(rest)?;
+                let (stay, rest) = MCInt::parse(rest)?;
+                let (fade_out, _) = MCInt::parse(rest)?;
+                TitleAction::SetTimes { fade_in: fade_in.value(), stay: stay.value(), fade_out: fade_out.value() }
+            },
+            4 => TitleAction::Hide,
+            5 => TitleAction::Reset,
+            action => return Err(MCTypeError::InvalidValue(format!("Unknown title action {}.", action)))
+        })
+    }
+}
+
+impl PacketId for LegacyTitle {
+    const ID: i32 = LEGACY_ID_BASE + 3;
+    const STATE: ConnectionState = ConnectionState::Play;
+}
+
+impl InboundPacket for LegacyTitle {
+    fn from_data(packet: &MCPacket) -> Result<Self, io::Error> {
+        if !packet.is::<Self>() {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, "Invalid packet ID."));
+        }
+
+        Ok(LegacyTitle { data: packet.data.clone() })
+    }
+}
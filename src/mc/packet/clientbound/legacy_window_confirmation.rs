@@ -0,0 +1,14 @@
+use crate::mc::{mctypes::{MCBoolean, MCByte, MCShort}, packet::{McDecode, PacketId}, registry::LEGACY_ID_BASE};
+
+/// Sent by servers older than 1.17 in answer to the click numbered `action` in the window
+/// `window_id`, telling whether the server accepted it. A rejected click must be
+/// acknowledged with a serverbound `LegacyWindowConfirmation`, after which the server
+/// sends the contents of the window again.
+#[derive(PacketId, McDecode)]
+#[packet(id = LEGACY_ID_BASE + 1, state = Play)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LegacyWindowConfirmation {
+    pub window_id: MCByte,
+    pub action: MCShort,
+    pub accepted: MCBoolean
+}
@@ -0,0 +1,31 @@
+use std::io;
+
+use crate::mc::{connection::ConnectionState, mctypes::{MCBoolean, MCInt, MCTypeDecode}, packet::{InboundPacket, MCPacket, PacketId}};
+
+/// The first packet of the Play state, sent by the server once the player has joined
+/// the game. Only the leading fields are decoded; the dimension and world fields which
+/// follow are left to later parsing.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LoginPlay {
+    pub entity_id: MCInt,
+    pub is_hardcore: MCBoolean
+}
+
+impl PacketId for LoginPlay {
+    const ID: i32 = 0x24;
+    const STATE: ConnectionState = ConnectionState::Play;
+}
+
+impl InboundPacket for LoginPlay {
+    fn from_data(packet: &MCPacket) -> Result<Self, io::Error> {
+        if !packet.is::<Self>() {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, "Invalid packet ID."));
+        }
+
+        let mut cursor = packet.cursor();
+        let entity_id = MCInt::read(&mut cursor)?;
+        let is_hardcore = MCBoolean::read(&mut cursor)?;
+
+        Ok(LoginPlay { entity_id, is_hardcore })
+    }
+}
@@ -0,0 +1,35 @@
+use std::io;
+
+use bytes::Bytes;
+
+use crate::mc::{connection::ConnectionState, mctypes::{Identifier, MCTypeDecode, VarInt}, packet::{InboundPacket, MCPacket, PacketId}};
+
+/// Sent by the server during login to exchange custom data, typically with a proxy or
+/// mod loader. The client must answer every request with a Login Plugin Response
+/// carrying the same `message_id`, even if it does not understand the `channel`.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LoginPluginRequest {
+    pub message_id: VarInt,
+    pub channel: Identifier,
+    pub data: Bytes
+}
+
+impl PacketId for LoginPluginRequest {
+    const ID: i32 = 0x04;
+    const STATE: ConnectionState = ConnectionState::Login;
+}
+
+impl InboundPacket for LoginPluginRequest {
+    fn from_data(packet: &MCPacket) -> Result<Self, io::Error> {
+        if !packet.is::<Self>() {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, "Invalid packet ID."));
+        }
+
+        let mut cursor = packet.cursor();
+        let message_id = VarInt::read(&mut cursor)?;
+        let channel = Identifier::read(&mut cursor)?;
+        let data = packet.data.slice(cursor.position() as usize..);
+
+        Ok(LoginPluginRequest { message_id, channel, data })
+    }
+}
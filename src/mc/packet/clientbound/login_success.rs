@@ -1,4 +1,66 @@
+use std::io::{self, Cursor};
+
+use uuid::Uuid;
+
+use crate::mc::{connection::ConnectionState, mctypes::{MCBoolean, MCString, MCTypeDecode, MCUuid, VarInt}, packet::{InboundPacket, MCPacket, PacketId}};
+
+/// A property of a player's profile, such as the `textures` of its skin. Online-mode
+/// servers sign properties with Mojang's key.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ProfileProperty {
+    pub name: String,
+    pub value: String,
+    pub signature: Option<String>
+}
+
+impl ProfileProperty {
+    /// Reads a property from the cursor's position, as sent in a Login Success or a
+    /// Player Info Update.
+    /// # Errors
+    /// This function will return an `InvalidData` error if the property cannot be parsed.
+    pub fn read(cursor: &mut Cursor<&[u8]>) -> Result<Self, io::Error> {
+        let name = MCString::read(cursor)?.string().clone();
+        let value = MCString::read(cursor)?.string().clone();
+        let signature = match MCBoolean::read(cursor)?.value() {
+            true => Some(MCString::read(cursor)?.string().clone()),
+            false => None
+        };
+
+        Ok(ProfileProperty { name, value, signature })
+    }
+}
+
+/// Sent by the server once the client has logged in, confirming the profile the
+/// player joins with. Receiving it moves the connection to the next state.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct LoginSuccess {
+    pub uuid: Uuid,
+    pub username: MCString,
+    pub properties: Vec<ProfileProperty>
+}
 
+impl PacketId for LoginSuccess {
+    const ID: i32 = 0x02;
+    const STATE: ConnectionState = ConnectionState::Login;
 }
 
+impl InboundPacket for LoginSuccess {
+    fn from_data(packet: &MCPacket) -> Result<Self, io::Error> {
+        if !packet.is::<Self>() {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, "Invalid packet ID."));
+        }
+
+        let mut cursor = packet.cursor();
+        let uuid = MCUuid::read(&mut cursor)?.uuid();
+        let username = MCString::read(&mut cursor)?;
+
+        let count = VarInt::read(&mut cursor)?.value();
+        let mut properties = Vec::new();
+        for _ in 0..count {
+            properties.push(ProfileProperty::read(&mut cursor)?);
+        }
+
+        Ok(LoginSuccess { uuid, username, properties })
+    }
+}
@@ -1,3 +1,60 @@
 pub mod status_response;
 pub mod login_success;
-pub mod ping_response;
\ No newline at end of file
+pub mod ping_response;
+pub mod acknowledge_block_change;
+pub mod open_sign_editor;
+pub mod set_compression;
+pub mod login_plugin_request;
+pub mod login_play;
+pub mod encryption_request;
+pub mod keep_alive;
+pub mod synchronize_player_position;
+pub mod system_chat_message;
+pub mod finish_configuration;
+pub mod config_keep_alive;
+pub mod registry_data;
+pub mod feature_flags;
+pub mod update_tags;
+pub mod set_health;
+pub mod player_info_update;
+pub mod legacy_chat_message;
+pub mod disguised_chat_message;
+pub mod player_chat_message;
+pub mod set_entity_metadata;
+pub mod chunk_data;
+pub mod block_update;
+pub mod update_section_blocks;
+pub mod unload_chunk;
+pub mod spawn_entity;
+pub mod spawn_player;
+pub mod update_entity_position;
+pub mod update_entity_position_and_rotation;
+pub mod update_entity_rotation;
+pub mod teleport_entity;
+pub mod set_entity_velocity;
+pub mod remove_entities;
+pub mod player_info_remove;
+pub mod set_experience;
+pub mod game_event;
+pub mod open_screen;
+pub mod close_container;
+pub mod set_container_content;
+pub mod set_container_slot;
+pub mod legacy_window_confirmation;
+pub mod combat_death;
+pub mod plugin_message;
+pub mod config_plugin_message;
+pub mod resource_pack;
+pub mod config_resource_pack;
+pub mod command_suggestions;
+pub mod boss_bar;
+pub mod display_objective;
+pub mod update_objectives;
+pub mod update_score;
+pub mod reset_score;
+pub mod set_title_text;
+pub mod set_subtitle_text;
+pub mod set_action_bar_text;
+pub mod set_title_animation_times;
+pub mod clear_titles;
+pub mod legacy_title;
\ No newline at end of file
@@ -0,0 +1,44 @@
+use std::io;
+
+use bytes::Bytes;
+
+use crate::mc::{chat::ChatComponent, connection::ConnectionState, error::MCTypeError, mctypes::{MCType, VarInt}, packet::{InboundPacket, MCPacket, PacketId}, version::ProtocolVersion};
+
+/// Sent by the server when it opens a window to the player, such as that of a chest. The
+/// `kind` is the ID of the window's type in the `minecraft:menu` registry. As the title
+/// is a JSON text component before 1.20.3 and NBT since, it is kept in its encoded form
+/// until it is decoded for the server's version with `title`.
+/// <br> <br>
+/// This is the layout of 1.14 and newer, so the packet is not decoded from older servers.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct OpenScreen {
+    pub window_id: VarInt,
+    pub kind: VarInt,
+    pub data: Bytes
+}
+
+impl OpenScreen {
+    /// Decodes the title of the window in the format of `version`.
+    /// # Errors
+    /// This function will return the errors of `ChatComponent::parse_for`.
+    pub fn title(&self, version: ProtocolVersion) -> Result<ChatComponent, MCTypeError> {
+        Ok(ChatComponent::parse_for(&self.data, version)?.0)
+    }
+}
+
+impl PacketId for OpenScreen {
+    const ID: i32 = 0x2C;
+    const STATE: ConnectionState = ConnectionState::Play;
+}
+
+impl InboundPacket for OpenScreen {
+    fn from_data(packet: &MCPacket) -> Result<Self, io::Error> {
+        if !packet.is::<Self>() {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, "Invalid packet ID."));
+        }
+
+        let (window_id, rest) = VarInt::parse(&packet.data)?;
+        let (kind, rest) = VarInt::parse(rest)?;
+        Ok(OpenScreen { window_id, kind, data: packet.data.slice_ref(rest) })
+    }
+}
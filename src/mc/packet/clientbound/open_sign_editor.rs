@@ -0,0 +1,34 @@
+use std::io;
+
+use crate::mc::{connection::ConnectionState, mctypes::{MCPosition, MCBoolean, MCTypeDecode}, packet::{InboundPacket, MCPacket, PacketId}};
+
+/// Sent by the server when the client places a sign or otherwise begins editing one.
+/// Since 1.20, signs have two sides and the packet specifies which side is edited;
+/// `is_front_text` is `None` for older servers.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct OpenSignEditor {
+    pub position: MCPosition,
+    pub is_front_text: Option<MCBoolean>
+}
+
+impl PacketId for OpenSignEditor {
+    const ID: i32 = 0x2D;
+    const STATE: ConnectionState = ConnectionState::Play;
+}
+
+impl InboundPacket for OpenSignEditor {
+    fn from_data(packet: &MCPacket) -> Result<Self, io::Error> {
+        if !packet.is::<Self>() {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, "Invalid packet ID."));
+        }
+
+        let mut cursor = packet.cursor();
+        let position = MCPosition::read(&mut cursor)?;
+        let is_front_text = match cursor.position() as usize == packet.data.len() {
+            true => None,
+            false => Some(MCBoolean::read(&mut cursor)?)
+        };
+
+        Ok(OpenSignEditor { position, is_front_text })
+    }
+}
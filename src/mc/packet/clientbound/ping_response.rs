@@ -1,3 +1,9 @@
-pub struct PingResponse {
+use crate::mc::{mctypes::MCLong, packet::{McDecode, PacketId}};
 
+/// Sent by the server in reply to a Ping Request, echoing its `payload`.
+#[derive(PacketId, McDecode)]
+#[packet(id = 0x01, state = Status)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PingResponse {
+    pub payload: MCLong
 }
@@ -0,0 +1,84 @@
+use std::io;
+
+use uuid::Uuid;
+
+use crate::mc::{connection::ConnectionState, mctypes::{MCBitSet, MCBoolean, MCLong, MCString, MCTypeDecode, MCUuid, VarInt}, packet::{InboundPacket, MCPacket, PacketId, serverbound::chat_message::SIGNATURE_LENGTH}};
+
+/// A chat message sent by a player, which the client may verify with the player's chat
+/// session. The `message` is plain text, which the server may have replaced for display
+/// with the JSON text component `unsigned_content`. The message is shown in the format of
+/// its `chat_type`, with `sender_name` and `target_name` as the JSON text components of
+/// the sender and target of the message.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PlayerChatMessage {
+    pub sender: Uuid,
+    pub index: VarInt,
+    pub signature: Option<Vec<u8>>,
+    pub message: MCString,
+    pub timestamp: MCLong,
+    pub salt: MCLong,
+    pub unsigned_content: Option<MCString>,
+    /// 0 if the message is shown, 1 if it is hidden, or 2 if parts of it are hidden.
+    pub filter_type: VarInt,
+    pub chat_type: VarInt,
+    pub sender_name: MCString,
+    pub target_name: Option<MCString>
+}
+
+impl PacketId for PlayerChatMessage {
+    const ID: i32 = 0x31;
+    const STATE: ConnectionState = ConnectionState::Play;
+}
+
+impl InboundPacket for PlayerChatMessage {
+    fn from_data(packet: &MCPacket) -> Result<Self, io::Error> {
+        if !packet.is::<Self>() {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, "Invalid packet ID."));
+        }
+
+        let mut cursor = packet.cursor();
+        let sender = MCUuid::read(&mut cursor)?.uuid();
+        let index = VarInt::read(&mut cursor)?;
+        let signature = match MCBoolean::read(&mut cursor)?.value() {
+            true => Some(read_signature(packet, &mut cursor)?),
+            false => None
+        };
+        let message = MCString::read_bounded(&mut cursor, 256)?;
+        let timestamp = MCLong::read(&mut cursor)?;
+        let salt = MCLong::read(&mut cursor)?;
+
+        // The signatures of the previous messages, which are only needed for verification.
+        for _ in 0..VarInt::read(&mut cursor)?.value() {
+            if VarInt::read(&mut cursor)?.value() == 0 {
+                read_signature(packet, &mut cursor)?;
+            }
+        }
+
+        let unsigned_content = match MCBoolean::read(&mut cursor)?.value() {
+            true => Some(MCString::read_bounded(&mut cursor, 262144)?),
+            false => None
+        };
+        let filter_type = VarInt::read(&mut cursor)?;
+        if filter_type.value() == 2 {
+            MCBitSet::read(&mut cursor)?;
+        }
+        let chat_type = VarInt::read(&mut cursor)?;
+        let sender_name = MCString::read_bounded(&mut cursor, 262144)?;
+        let target_name = match MCBoolean::read(&mut cursor)?.value() {
+            true => Some(MCString::read_bounded(&mut cursor, 262144)?),
+            false => None
+        };
+
+        Ok(PlayerChatMessage { sender, index, signature, message, timestamp, salt, unsigned_content, filter_type, chat_type, sender_name, target_name })
+    }
+}
+
+/// Reads a message signature from the cursor's position.
+fn read_signature(packet: &MCPacket, cursor: &mut io::Cursor<&[u8]>) -> Result<Vec<u8>, io::Error> {
+    let start = cursor.position() as usize;
+    let signature = packet.data.get(start..start + SIGNATURE_LENGTH)
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "Message signature is truncated."))?;
+    cursor.set_position((start + SIGNATURE_LENGTH) as u64);
+
+    Ok(signature.to_vec())
+}
@@ -0,0 +1,32 @@
+use std::io;
+
+use uuid::Uuid;
+
+use crate::mc::{connection::ConnectionState, mctypes::{MCTypeDecode, MCUuid, VarInt}, packet::{InboundPacket, MCPacket, PacketId}};
+
+/// Sent by the server to remove players from the tab list, such as when they leave.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PlayerInfoRemove {
+    pub uuids: Vec<Uuid>
+}
+
+impl PacketId for PlayerInfoRemove {
+    const ID: i32 = 0x35;
+    const STATE: ConnectionState = ConnectionState::Play;
+}
+
+impl InboundPacket for PlayerInfoRemove {
+    fn from_data(packet: &MCPacket) -> Result<Self, io::Error> {
+        if !packet.is::<Self>() {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, "Invalid packet ID."));
+        }
+
+        let mut cursor = packet.cursor();
+        let mut uuids = Vec::new();
+        for _ in 0..VarInt::read(&mut cursor)?.value() {
+            uuids.push(MCUuid::read(&mut cursor)?.uuid());
+        }
+
+        Ok(PlayerInfoRemove { uuids })
+    }
+}
@@ -0,0 +1,103 @@
+use std::io::{self, Cursor};
+
+use uuid::Uuid;
+
+use crate::mc::{connection::ConnectionState, mctypes::{MCArray, MCBoolean, MCLong, MCString, MCTypeDecode, MCUnsignedByte, MCUuid, VarInt}, packet::{InboundPacket, MCPacket, PacketId}};
+
+use super::login_success::ProfileProperty;
+
+/// The entry of one player in a Player Info Update. Fields are `None` unless their action
+/// is included in the update.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PlayerInfoEntry {
+    pub uuid: Uuid,
+    /// The name of a player being added.
+    pub name: Option<String>,
+    /// The profile properties of a player being added.
+    pub properties: Vec<ProfileProperty>,
+    pub game_mode: Option<i32>,
+    pub listed: Option<bool>,
+    /// The latency of the player in milliseconds.
+    pub latency: Option<i32>,
+    /// The JSON text component shown in place of the player's name in the tab list. This is
+    /// also `None` if the update removes the display name.
+    pub display_name: Option<String>
+}
+
+/// Sent by the server to add players to the tab list, or to update the players already
+/// listed. Every entry of the update carries the same `actions`.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PlayerInfoUpdate {
+    pub actions: u8,
+    pub entries: Vec<PlayerInfoEntry>
+}
+
+impl PlayerInfoUpdate {
+    pub const ADD_PLAYER: u8 = 0x01;
+    pub const INITIALIZE_CHAT: u8 = 0x02;
+    pub const UPDATE_GAME_MODE: u8 = 0x04;
+    pub const UPDATE_LISTED: u8 = 0x08;
+    pub const UPDATE_LATENCY: u8 = 0x10;
+    pub const UPDATE_DISPLAY_NAME: u8 = 0x20;
+
+    /// Whether the update includes `action`.
+    pub fn has(&self, action: u8) -> bool {
+        self.actions & action != 0
+    }
+}
+
+impl PacketId for PlayerInfoUpdate {
+    const ID: i32 = 0x36;
+    const STATE: ConnectionState = ConnectionState::Play;
+}
+
+impl InboundPacket for PlayerInfoUpdate {
+    fn from_data(packet: &MCPacket) -> Result<Self, io::Error> {
+        if !packet.is::<Self>() {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, "Invalid packet ID."));
+        }
+
+        let mut cursor = packet.cursor();
+        let actions = MCUnsignedByte::read(&mut cursor)?.value();
+        let mut entries = Vec::new();
+        for _ in 0..VarInt::read(&mut cursor)?.value() {
+            entries.push(read_entry(&mut cursor, actions)?);
+        }
+
+        Ok(PlayerInfoUpdate { actions, entries })
+    }
+}
+
+/// Reads the entry of one player, whose fields follow in the order of their action bits.
+fn read_entry(cursor: &mut Cursor<&[u8]>, actions: u8) -> Result<PlayerInfoEntry, io::Error> {
+    let mut entry = PlayerInfoEntry { uuid: MCUuid::read(cursor)?.uuid(), ..Default::default() };
+
+    if actions & PlayerInfoUpdate::ADD_PLAYER != 0 {
+        entry.name = Some(MCString::read_bounded(cursor, 16)?.string().clone());
+        for _ in 0..VarInt::read(cursor)?.value() {
+            entry.properties.push(ProfileProperty::read(cursor)?);
+        }
+    }
+    if actions & PlayerInfoUpdate::INITIALIZE_CHAT != 0 && MCBoolean::read(cursor)?.value() {
+        // The chat session of the player, which is only needed to verify signed chat.
+        MCUuid::read(cursor)?;
+        MCLong::read(cursor)?;
+        MCArray::<MCUnsignedByte>::read(cursor)?;
+        MCArray::<MCUnsignedByte>::read(cursor)?;
+    }
+    if actions & PlayerInfoUpdate::UPDATE_GAME_MODE != 0 {
+        entry.game_mode = Some(VarInt::read(cursor)?.value());
+    }
+    if actions & PlayerInfoUpdate::UPDATE_LISTED != 0 {
+        entry.listed = Some(MCBoolean::read(cursor)?.value());
+    }
+    if actions & PlayerInfoUpdate::UPDATE_LATENCY != 0 {
+        entry.latency = Some(VarInt::read(cursor)?.value());
+    }
+    if actions & PlayerInfoUpdate::UPDATE_DISPLAY_NAME != 0 && MCBoolean::read(cursor)?.value() {
+        entry.display_name = Some(MCString::read_bounded(cursor, 262144)?.string().clone());
+    }
+
+    Ok(entry)
+}
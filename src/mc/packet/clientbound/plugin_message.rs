@@ -0,0 +1,33 @@
+use std::io;
+
+use bytes::Bytes;
+
+use crate::mc::{connection::ConnectionState, mctypes::MCString, packet::{InboundPacket, MCPacket, PacketId}};
+
+/// Sent by the server to exchange custom data on `channel`, typically with mods or a
+/// proxy. Vanilla servers send their brand on `minecraft:brand` upon joining. Channels
+/// are identifiers since 1.13, and names such as `MC|Brand` before.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PluginMessage {
+    pub channel: MCString,
+    pub data: Bytes
+}
+
+impl PacketId for PluginMessage {
+    const ID: i32 = 0x15;
+    const STATE: ConnectionState = ConnectionState::Play;
+}
+
+impl InboundPacket for PluginMessage {
+    fn from_data(packet: &MCPacket) -> Result<Self, io::Error> {
+        if !packet.is::<Self>() {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, "Invalid packet ID."));
+        }
+
+        let mut cursor = packet.cursor();
+        let channel = MCString::read_bounded(&mut cursor, 32767)?;
+        let data = packet.data.slice(cursor.position() as usize..);
+
+        Ok(PluginMessage { channel, data })
+    }
+}
@@ -0,0 +1,28 @@
+use std::io;
+
+use bytes::Bytes;
+
+use crate::mc::{connection::ConnectionState, packet::{InboundPacket, MCPacket, PacketId}};
+
+/// Sent by the server during configuration with the contents of its registries, such as
+/// its dimension types, biomes and chat types. The registries are a single NBT compound,
+/// which is kept in its encoded form.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RegistryData {
+    pub data: Bytes
+}
+
+impl PacketId for RegistryData {
+    const ID: i32 = 0x05;
+    const STATE: ConnectionState = ConnectionState::Configuration;
+}
+
+impl InboundPacket for RegistryData {
+    fn from_data(packet: &MCPacket) -> Result<Self, io::Error> {
+        if !packet.is::<Self>() {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, "Invalid packet ID."));
+        }
+
+        Ok(RegistryData { data: packet.data.clone() })
+    }
+}
@@ -0,0 +1,37 @@
+use std::io;
+
+use crate::mc::{connection::ConnectionState, limits, mctypes::{MCType, VarInt}, packet::{InboundPacket, MCPacket, PacketId}};
+
+/// Sent by the server when entities leave the view of the player, or are removed from
+/// the world.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RemoveEntities {
+    pub entity_ids: Vec<i32>
+}
+
+impl PacketId for RemoveEntities {
+    const ID: i32 = 0x3A;
+    const STATE: ConnectionState = ConnectionState::Play;
+}
+
+impl InboundPacket for RemoveEntities {
+    fn from_data(packet: &MCPacket) -> Result<Self, io::Error> {
+        if !packet.is::<Self>() {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, "Invalid packet ID."));
+        }
+
+        let (count, mut rest) = VarInt::parse(&packet.data)?;
+        let count = limits::array_length(count.value())?;
+        if count > rest.len() {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, "Invalid entity count."));
+        }
+        let mut entity_ids = Vec::with_capacity(count);
+        for _ in 0..count {
+            let (entity_id, after) = VarInt::parse(rest)?;
+            entity_ids.push(entity_id.value());
+            rest = after;
+        }
+
+        Ok(RemoveEntities { entity_ids })
+    }
+}
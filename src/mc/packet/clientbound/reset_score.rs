@@ -0,0 +1,33 @@
+use std::io;
+
+use crate::mc::{connection::ConnectionState, mctypes::{MCBoolean, MCString, MCTypeDecode}, packet::{InboundPacket, MCPacket, PacketId}, registry::LEGACY_ID_BASE};
+
+/// Sent by servers running 1.20.3 or newer to remove the score of `entity` from the
+/// objective `objective`, or from every objective if it is `None`.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ResetScore {
+    pub entity: MCString,
+    pub objective: Option<MCString>
+}
+
+impl PacketId for ResetScore {
+    const ID: i32 = LEGACY_ID_BASE + 2;
+    const STATE: ConnectionState = ConnectionState::Play;
+}
+
+impl InboundPacket for ResetScore {
+    fn from_data(packet: &MCPacket) -> Result<Self, io::Error> {
+        if !packet.is::<Self>() {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, "Invalid packet ID."));
+        }
+
+        let mut cursor = packet.cursor();
+        let entity = MCString::read_bounded(&mut cursor, 32767)?;
+        let objective = match MCBoolean::read(&mut cursor)?.value() {
+            true => Some(MCString::read_bounded(&mut cursor, 32767)?),
+            false => None
+        };
+
+        Ok(ResetScore { entity, objective })
+    }
+}
@@ -0,0 +1,38 @@
+use std::io;
+
+use bytes::Bytes;
+
+use crate::mc::{connection::ConnectionState, error::MCTypeError, packet::{InboundPacket, MCPacket, PacketId}, resource_pack::ResourcePackEvent, version::ProtocolVersion};
+
+/// Sent by the server to ask the client to load a resource pack, which the client answers
+/// with Resource Pack Responses. Since 1.20.3, this is Add Resource Pack, and servers may
+/// push several packs. As the layout differs between versions, the packet is kept in its
+/// encoded form until it is decoded for the server's version with `pack`.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ResourcePack {
+    pub data: Bytes
+}
+
+impl ResourcePack {
+    /// Decodes the pack in the format of `version`.
+    /// # Errors
+    /// This function will return the errors of `ResourcePackEvent::parse`.
+    pub fn pack(&self, version: ProtocolVersion) -> Result<ResourcePackEvent, MCTypeError> {
+        ResourcePackEvent::parse(&self.data, version)
+    }
+}
+
+impl PacketId for ResourcePack {
+    const ID: i32 = 0x3C;
+    const STATE: ConnectionState = ConnectionState::Play;
+}
+
+impl InboundPacket for ResourcePack {
+    fn from_data(packet: &MCPacket) -> Result<Self, io::Error> {
+        if !packet.is::<Self>() {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, "Invalid packet ID."));
+        }
+
+        Ok(ResourcePack { data: packet.data.clone() })
+    }
+}
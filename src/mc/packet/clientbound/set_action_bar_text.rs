@@ -0,0 +1,38 @@
+use std::io;
+
+use bytes::Bytes;
+
+use crate::mc::{chat::ChatComponent, connection::ConnectionState, error::MCTypeError, packet::{InboundPacket, MCPacket, PacketId}, version::ProtocolVersion};
+
+/// Sent by the server to show a message above the hotbar for a few seconds. Before 1.17,
+/// this is an action of the Title packet, or a chat message shown above the hotbar. As
+/// the text is encoded as NBT since 1.20.3, the packet is kept in its encoded form until
+/// it is decoded for the server's version with `text`.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SetActionBarText {
+    pub data: Bytes
+}
+
+impl SetActionBarText {
+    /// Decodes the text in the format of `version`.
+    /// # Errors
+    /// This function will return the errors of `ChatComponent::parse_for`.
+    pub fn text(&self, version: ProtocolVersion) -> Result<ChatComponent, MCTypeError> {
+        Ok(ChatComponent::parse_for(&self.data, version)?.0)
+    }
+}
+
+impl PacketId for SetActionBarText {
+    const ID: i32 = 0x42;
+    const STATE: ConnectionState = ConnectionState::Play;
+}
+
+impl InboundPacket for SetActionBarText {
+    fn from_data(packet: &MCPacket) -> Result<Self, io::Error> {
+        if !packet.is::<Self>() {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, "Invalid packet ID."));
+        }
+
+        Ok(SetActionBarText { data: packet.data.clone() })
+    }
+}
@@ -0,0 +1,28 @@
+use std::io;
+
+use crate::mc::{connection::ConnectionState, mctypes::VarInt, packet::{InboundPacket, MCPacket, PacketId}};
+
+/// Sent by the server during login to enable compression. Every packet after it, in
+/// both directions, is framed in the compressed format; packets of at least `threshold`
+/// bytes are compressed. A negative threshold disables compression.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SetCompression {
+    pub threshold: VarInt
+}
+
+impl PacketId for SetCompression {
+    const ID: i32 = 0x03;
+    const STATE: ConnectionState = ConnectionState::Login;
+}
+
+impl InboundPacket for SetCompression {
+    fn from_data(packet: &MCPacket) -> Result<Self, io::Error> {
+        if !packet.is::<Self>() {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, "Invalid packet ID."));
+        }
+
+        Ok(SetCompression {
+            threshold: VarInt::from_bytes(&packet.data)?
+        })
+    }
+}
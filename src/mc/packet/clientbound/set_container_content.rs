@@ -0,0 +1,83 @@
+use std::io;
+
+use bytes::Bytes;
+
+use crate::mc::{connection::ConnectionState, error::MCTypeError, item::Slot, mctypes::{MCShort, MCType, MCUnsignedByte, VarInt}, packet::{InboundPacket, MCPacket, PacketId}, version::ProtocolVersion};
+
+/// The decoded contents of a window.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ContainerContent {
+    /// The state of the window the contents are those of, which clicks are sent with.
+    /// Servers older than 1.17.1 do not number states.
+    pub state_id: Option<i32>,
+    pub slots: Vec<Slot>,
+    /// The item carried on the cursor, which servers only send since 1.17.1.
+    pub carried: Option<Slot>
+}
+
+/// Sent by the server with the contents of every slot of the window `window_id`, such as
+/// when it is opened, with the player's inventory being window 0. As the layout of slots
+/// differs between versions, the contents are kept in their encoded form until they are
+/// decoded for the server's version with `content`.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SetContainerContent {
+    pub window_id: MCUnsignedByte,
+    pub data: Bytes
+}
+
+impl SetContainerContent {
+    /// The first protocol version (1.17.1) numbering the states of windows.
+    pub const STATE_ID_SINCE: i32 = 756;
+
+    /// Decodes the contents of the window in the format of `version`.
+    /// # Errors
+    /// This function will return an `MCTypeError` if the data does not encode the
+    /// contents of a window, or the errors of `Slot::parse_for`.
+    pub fn content(&self, version: ProtocolVersion) -> Result<ContainerContent, MCTypeError> {
+        let numbered = version.number() >= Self::STATE_ID_SINCE;
+        let (state_id, count, mut rest) = match numbered {
+            true => {
+                let (state_id, rest) = VarInt::parse(&self.data)?;
+                let (count, rest) = VarInt::parse(rest)?;
+                (Some(state_id.value()), count.value(), rest)
+            },
+            false => {
+                let (count, rest) = MCShort::parse(&self.data)?;
+                (None, count.value() as i32, rest)
+            }
+        };
+        if count < 0 {
+            return Err(MCTypeError::OversizeLength(count as i64));
+        }
+
+        let mut slots = Vec::new();
+        for _ in 0..count {
+            let (slot, after) = Slot::parse_for(rest, version)?;
+            slots.push(slot);
+            rest = after;
+        }
+        let carried = match numbered {
+            true => Some(Slot::parse_for(rest, version)?.0),
+            false => None
+        };
+
+        Ok(ContainerContent { state_id, slots, carried })
+    }
+}
+
+impl PacketId for SetContainerContent {
+    const ID: i32 = 0x10;
+    const STATE: ConnectionState = ConnectionState::Play;
+}
+
+impl InboundPacket for SetContainerContent {
+    fn from_data(packet: &MCPacket) -> Result<Self, io::Error> {
+        if !packet.is::<Self>() {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, "Invalid packet ID."));
+        }
+
+        let (window_id, data) = MCUnsignedByte::parse(&packet.data)?;
+        Ok(SetContainerContent { window_id, data: packet.data.slice_ref(data) })
+    }
+}
@@ -0,0 +1,68 @@
+use std::io;
+
+use bytes::Bytes;
+
+use crate::mc::{connection::ConnectionState, error::MCTypeError, item::Slot, mctypes::{MCByte, MCShort, MCType, VarInt}, packet::{InboundPacket, MCPacket, PacketId}, version::ProtocolVersion};
+
+use super::set_container_content::SetContainerContent;
+
+/// The decoded change of a slot.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SlotChange {
+    /// The state of the window after the change. Servers older than 1.17.1 do not
+    /// number states.
+    pub state_id: Option<i32>,
+    pub slot: i16,
+    pub item: Slot
+}
+
+/// Sent by the server when the slot of a window changes. The slot -1 of the window -1 is
+/// the item carried on the cursor, and the window -2 is the player's inventory, whichever
+/// window is open. As the layout of slots differs between versions, the change is kept
+/// in its encoded form until it is decoded for the server's version with `change`.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SetContainerSlot {
+    pub window_id: MCByte,
+    pub data: Bytes
+}
+
+impl SetContainerSlot {
+    /// The window and slot of the item carried on the cursor.
+    pub const CARRIED: i8 = -1;
+    /// The window standing for the player's inventory, whichever window is open.
+    pub const INVENTORY: i8 = -2;
+
+    /// Decodes the change in the format of `version`.
+    /// # Errors
+    /// This function will return an `MCTypeError` if the data does not encode the
+    /// change of a slot, or the errors of `Slot::parse_for`.
+    pub fn change(&self, version: ProtocolVersion) -> Result<SlotChange, MCTypeError> {
+        let (state_id, rest) = match version.number() >= SetContainerContent::STATE_ID_SINCE {
+            true => {
+                let (state_id, rest) = VarInt::parse(&self.data)?;
+                (Some(state_id.value()), rest)
+            },
+            false => (None, &self.data[..])
+        };
+        let (slot, rest) = MCShort::parse(rest)?;
+        let (item, _) = Slot::parse_for(rest, version)?;
+        Ok(SlotChange { state_id, slot: slot.value(), item })
+    }
+}
+
+impl PacketId for SetContainerSlot {
+    const ID: i32 = 0x12;
+    const STATE: ConnectionState = ConnectionState::Play;
+}
+
+impl InboundPacket for SetContainerSlot {
+    fn from_data(packet: &MCPacket) -> Result<Self, io::Error> {
+        if !packet.is::<Self>() {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, "Invalid packet ID."));
+        }
+
+        let (window_id, data) = MCByte::parse(&packet.data)?;
+        Ok(SetContainerSlot { window_id, data: packet.data.slice_ref(data) })
+    }
+}
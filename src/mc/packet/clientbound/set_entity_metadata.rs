@@ -0,0 +1,39 @@
+use std::io;
+
+use bytes::Bytes;
+
+use crate::mc::{connection::ConnectionState, error::MCTypeError, metadata::EntityMetadata, mctypes::{MCType, VarInt}, packet::{InboundPacket, MCPacket, PacketId}, version::ProtocolVersion};
+
+/// Sent by the server when the metadata of an entity changes, with the values which
+/// changed. As the layout of metadata differs between versions, the metadata is kept in
+/// its encoded form until it is decoded for the server's version with `metadata`.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SetEntityMetadata {
+    pub entity_id: VarInt,
+    pub data: Bytes
+}
+
+impl SetEntityMetadata {
+    /// Decodes the metadata in the format of `version`.
+    /// # Errors
+    /// This function will return the errors of `EntityMetadata::parse_for`.
+    pub fn metadata(&self, version: ProtocolVersion) -> Result<EntityMetadata, MCTypeError> {
+        Ok(EntityMetadata::parse_for(&self.data, version)?.0)
+    }
+}
+
+impl PacketId for SetEntityMetadata {
+    const ID: i32 = 0x4E;
+    const STATE: ConnectionState = ConnectionState::Play;
+}
+
+impl InboundPacket for SetEntityMetadata {
+    fn from_data(packet: &MCPacket) -> Result<Self, io::Error> {
+        if !packet.is::<Self>() {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, "Invalid packet ID."));
+        }
+
+        let (entity_id, data) = VarInt::parse(&packet.data)?;
+        Ok(SetEntityMetadata { entity_id, data: packet.data.slice_ref(data) })
+    }
+}
@@ -0,0 +1,14 @@
+use crate::mc::{mctypes::MCShort, packet::{McDecode, PacketId}};
+
+/// Sent by the server when the velocity of an entity changes. Velocities are in 1/8000
+/// of a block per tick.
+#[derive(PacketId, McDecode)]
+#[packet(id = 0x50, state = Play)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SetEntityVelocity {
+    #[varint]
+    pub entity_id: i32,
+    pub velocity_x: MCShort,
+    pub velocity_y: MCShort,
+    pub velocity_z: MCShort
+}
@@ -0,0 +1,14 @@
+use crate::mc::{mctypes::MCFloat, packet::{McDecode, PacketId}};
+
+/// Sent by the server whenever the player's experience changes. `bar` is the progress
+/// towards the next level, from 0 to 1.
+#[derive(PacketId, McDecode)]
+#[packet(id = 0x52, state = Play)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SetExperience {
+    pub bar: MCFloat,
+    #[varint]
+    pub level: i32,
+    #[varint]
+    pub total: i32
+}
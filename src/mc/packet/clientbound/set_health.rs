@@ -0,0 +1,13 @@
+use crate::mc::{mctypes::MCFloat, packet::{McDecode, PacketId}};
+
+/// Sent by the server whenever the player's health, food or saturation changes. The
+/// player has died once `health` reaches 0.
+#[derive(PacketId, McDecode)]
+#[packet(id = 0x53, state = Play)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SetHealth {
+    pub health: MCFloat,
+    #[varint]
+    pub food: i32,
+    pub food_saturation: MCFloat
+}
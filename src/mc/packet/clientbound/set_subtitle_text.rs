@@ -0,0 +1,38 @@
+use std::io;
+
+use bytes::Bytes;
+
+use crate::mc::{chat::ChatComponent, connection::ConnectionState, error::MCTypeError, packet::{InboundPacket, MCPacket, PacketId}, version::ProtocolVersion};
+
+/// Sent by the server to set the subtitle shown below the title, which shows along with
+/// the next title. Before 1.17, this is an action of the Title packet. As the text is
+/// encoded as NBT since 1.20.3, the packet is kept in its encoded form until it is decoded
+/// for the server's version with `text`.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SetSubtitleText {
+    pub data: Bytes
+}
+
+impl SetSubtitleText {
+    /// Decodes the text in the format of `version`.
+    /// # Errors
+    /// This function will return the errors of `ChatComponent::parse_for`.
+    pub fn text(&self, version: ProtocolVersion) -> Result<ChatComponent, MCTypeError> {
+        Ok(ChatComponent::parse_for(&self.data, version)?.0)
+    }
+}
+
+impl PacketId for SetSubtitleText {
+    const ID: i32 = 0x59;
+    const STATE: ConnectionState = ConnectionState::Play;
+}
+
+impl InboundPacket for SetSubtitleText {
+    fn from_data(packet: &MCPacket) -> Result<Self, io::Error> {
+        if !packet.is::<Self>() {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, "Invalid packet ID."));
+        }
+
+        Ok(SetSubtitleText { data: packet.data.clone() })
+    }
+}
@@ -0,0 +1,12 @@
+use crate::mc::{mctypes::MCInt, packet::{McDecode, PacketId}};
+
+/// Sent by the server to set how long titles take to fade in, stay, and fade out, in
+/// ticks. Before 1.17, this is an action of the Title packet.
+#[derive(PacketId, McDecode)]
+#[packet(id = 0x5C, state = Play)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SetTitleAnimationTimes {
+    pub fade_in: MCInt,
+    pub stay: MCInt,
+    pub fade_out: MCInt
+}
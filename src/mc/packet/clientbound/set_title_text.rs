@@ -0,0 +1,38 @@
+use std::io;
+
+use bytes::Bytes;
+
+use crate::mc::{chat::ChatComponent, connection::ConnectionState, error::MCTypeError, packet::{InboundPacket, MCPacket, PacketId}, version::ProtocolVersion};
+
+/// Sent by the server to show the title in the middle of the screen, along with the
+/// subtitle, for the times of the last Set Title Animation Times. Before 1.17, this is an
+/// action of the Title packet. As the text is encoded as NBT since 1.20.3, the packet is
+/// kept in its encoded form until it is decoded for the server's version with `text`.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SetTitleText {
+    pub data: Bytes
+}
+
+impl SetTitleText {
+    /// Decodes the text in the format of `version`.
+    /// # Errors
+    /// This function will return the errors of `ChatComponent::parse_for`.
+    pub fn text(&self, version: ProtocolVersion) -> Result<ChatComponent, MCTypeError> {
+        Ok(ChatComponent::parse_for(&self.data, version)?.0)
+    }
+}
+
+impl PacketId for SetTitleText {
+    const ID: i32 = 0x5B;
+    const STATE: ConnectionState = ConnectionState::Play;
+}
+
+impl InboundPacket for SetTitleText {
+    fn from_data(packet: &MCPacket) -> Result<Self, io::Error> {
+        if !packet.is::<Self>() {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, "Invalid packet ID."));
+        }
+
+        Ok(SetTitleText { data: packet.data.clone() })
+    }
+}
@@ -0,0 +1,27 @@
+use crate::mc::{mctypes::{Angle, MCDouble, MCShort, MCUuid}, packet::{McDecode, PacketId}};
+
+/// Sent by the server when an entity other than a player comes into view, and since
+/// 1.20.2, also when a player does. The meaning of `data` depends on the `kind`, such as
+/// the block state of a falling block. Velocities are in 1/8000 of a block per tick.
+#[derive(PacketId, McDecode)]
+#[packet(id = 0x00, state = Play)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SpawnEntity {
+    #[varint]
+    pub entity_id: i32,
+    pub uuid: MCUuid,
+    /// The ID of the entity's type in the registry of the server's version.
+    #[varint]
+    pub kind: i32,
+    pub x: MCDouble,
+    pub y: MCDouble,
+    pub z: MCDouble,
+    pub pitch: Angle,
+    pub yaw: Angle,
+    pub head_yaw: Angle,
+    #[varint]
+    pub data: i32,
+    pub velocity_x: MCShort,
+    pub velocity_y: MCShort,
+    pub velocity_z: MCShort
+}
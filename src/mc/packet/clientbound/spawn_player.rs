@@ -0,0 +1,17 @@
+use crate::mc::{mctypes::{Angle, MCDouble, MCUuid}, packet::{McDecode, PacketId}};
+
+/// Sent by the server when another player comes into view. Since 1.20.2, players are
+/// spawned with a Spawn Entity instead.
+#[derive(PacketId, McDecode)]
+#[packet(id = 0x02, state = Play)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SpawnPlayer {
+    #[varint]
+    pub entity_id: i32,
+    pub uuid: MCUuid,
+    pub x: MCDouble,
+    pub y: MCDouble,
+    pub z: MCDouble,
+    pub yaw: Angle,
+    pub pitch: Angle
+}
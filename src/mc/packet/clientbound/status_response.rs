@@ -1,30 +1,23 @@
 use std::io;
 
-use crate::mc::{mctypes::{MCString, VarInt}, packet::{InboundPacket, read_packet_header}};
+use crate::mc::{connection::ConnectionState, mctypes::MCString, packet::{InboundPacket, MCPacket, PacketId}, status};
 
-const STATUS_RES_PACKET_ID: i32 = 0x00;
+// Kept in `status`, which parses samples without this packet under the `wasm` feature.
+pub use crate::mc::status::PlayerSample;
 
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct StatusResponse {
     pub json_response: MCString
 }
 
-#[allow(unused)]
-impl InboundPacket for StatusResponse {
-    fn from_bytes(bytes: &[u8]) -> Result<Self, io::Error> {
-        let mut bytes = bytes.to_vec();
-
-        let packet_header = read_packet_header(&mut bytes)?;
-        if packet_header.id.value() != STATUS_RES_PACKET_ID {
-            return Err(io::Error::new(io::ErrorKind::InvalidData, "Invalid packet ID."));
-        }
-        
-        Ok(StatusResponse{ 
-            json_response: MCString::from_bytes(&bytes)?
-        })
-    }
+impl PacketId for StatusResponse {
+    const ID: i32 = 0x00;
+    const STATE: ConnectionState = ConnectionState::Status;
+}
 
-    fn from_data(packet: &crate::mc::packet::MCPacket) -> Result<Self, io::Error> {
-        if packet.header.id.value() != STATUS_RES_PACKET_ID {
+impl InboundPacket for StatusResponse {
+    fn from_data(packet: &MCPacket) -> Result<Self, io::Error> {
+        if !packet.is::<Self>() {
             return Err(io::Error::new(io::ErrorKind::InvalidData, "Invalid packet ID."));
         }
 
@@ -32,8 +25,39 @@ impl InboundPacket for StatusResponse {
             json_response: MCString::from_bytes(&packet.data)?
         })
     }
+}
+
+impl StatusResponse {
+    fn json(&self) -> Result<serde_json::Value, io::Error> {
+        Ok(serde_json::from_str(self.json_response.string())?)
+    }
+
+    /// Gets the protocol number the server reports in the `version.protocol`
+    /// field of its status JSON, or `-1` if the response is not valid JSON or does
+    /// not report a protocol number, which no release uses. See
+    /// `ProtocolVersion::from_status` to tell a missing number apart.
+    pub fn protocol_version(&self) -> i32 {
+        self.reported_protocol().unwrap_or(-1)
+    }
+
+    /// Gets the protocol number the server reports.
+    /// # Errors
+    /// This function will return an `InvalidData` error if the response is not
+    /// valid JSON or does not report a protocol number.
+    pub(crate) fn reported_protocol(&self) -> Result<i32, io::Error> {
+        let json = self.json()?;
+
+        json["version"]["protocol"]
+            .as_i64()
+            .and_then(|protocol| i32::try_from(protocol).ok())
+            .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "Status response has no protocol version."))
+    }
 
-    fn packet_id(&self) -> i32 {
-        STATUS_RES_PACKET_ID
+    /// Gets the `players.sample` list the server reports. Servers which do not send
+    /// a sample report an empty list, and malformed entries are skipped.
+    /// # Errors
+    /// This function will return an `InvalidData` error if the response is not valid JSON.
+    pub fn players_sample(&self) -> Result<Vec<PlayerSample>, io::Error> {
+        Ok(status::players_sample(&self.json()?))
     }
 }
@@ -0,0 +1,49 @@
+use std::io;
+
+use crate::mc::{connection::ConnectionState, mctypes::{Location, MCBoolean, MCDouble, MCFloat, MCTypeDecode, PositionFlags, VarInt}, packet::{InboundPacket, MCPacket, PacketId}};
+
+/// Teleports the player, such as upon joining or when its movement is rejected. Each of
+/// the values of `location` is relative to the player's current location if marked so in
+/// `flags`. Since 1.9, the client must confirm the teleport with a Confirm Teleportation
+/// of its `teleport_id`, which 1.8 does not send. Whether the player should dismount its
+/// vehicle is only sent from 1.17 to 1.19.3.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SynchronizePlayerPosition {
+    pub location: Location,
+    pub flags: PositionFlags,
+    pub teleport_id: Option<VarInt>,
+    pub dismount_vehicle: Option<MCBoolean>
+}
+
+impl PacketId for SynchronizePlayerPosition {
+    const ID: i32 = 0x38;
+    const STATE: ConnectionState = ConnectionState::Play;
+}
+
+impl InboundPacket for SynchronizePlayerPosition {
+    fn from_data(packet: &MCPacket) -> Result<Self, io::Error> {
+        if !packet.is::<Self>() {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, "Invalid packet ID."));
+        }
+
+        let mut cursor = packet.cursor();
+        let location = Location {
+            x: MCDouble::read(&mut cursor)?.value(),
+            y: MCDouble::read(&mut cursor)?.value(),
+            z: MCDouble::read(&mut cursor)?.value(),
+            yaw: MCFloat::read(&mut cursor)?.value(),
+            pitch: MCFloat::read(&mut cursor)?.value()
+        };
+        let flags = PositionFlags::read(&mut cursor)?;
+        let teleport_id = match (cursor.position() as usize) < packet.data.len() {
+            true => Some(VarInt::read(&mut cursor)?),
+            false => None
+        };
+        let dismount_vehicle = match (cursor.position() as usize) < packet.data.len() {
+            true => Some(MCBoolean::read(&mut cursor)?),
+            false => None
+        };
+
+        Ok(SynchronizePlayerPosition { location, flags, teleport_id, dismount_vehicle })
+    }
+}
@@ -0,0 +1,25 @@
+use std::io;
+
+use crate::mc::{chat::ChatComponent, mctypes::{MCBoolean, MCString}, packet::{McDecode, PacketId}};
+
+/// A message from the server itself, such as command feedback or a join message. The
+/// `content` is a JSON text component. Overlay messages are shown above the hotbar rather
+/// than in the chat.
+#[derive(PacketId, McDecode)]
+#[packet(id = 0x60, state = Play)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SystemChatMessage {
+    #[max_len = 262144]
+    pub content: MCString,
+    pub overlay: MCBoolean
+}
+
+
+impl SystemChatMessage {
+    /// Parses the `content` of the message.
+    /// # Errors
+    /// This function will return an `InvalidData` error if the content is not a text component.
+    pub fn component(&self) -> Result<ChatComponent, io::Error> {
+        ChatComponent::from_json(self.content.string())
+    }
+}
@@ -0,0 +1,16 @@
+use crate::mc::{mctypes::{Angle, MCBoolean, MCDouble}, packet::{McDecode, PacketId}};
+
+/// Sent by the server when an entity moves 8 blocks or more, or is teleported.
+#[derive(PacketId, McDecode)]
+#[packet(id = 0x64, state = Play)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TeleportEntity {
+    #[varint]
+    pub entity_id: i32,
+    pub x: MCDouble,
+    pub y: MCDouble,
+    pub z: MCDouble,
+    pub yaw: Angle,
+    pub pitch: Angle,
+    pub on_ground: MCBoolean
+}
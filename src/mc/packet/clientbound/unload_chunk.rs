@@ -0,0 +1,44 @@
+use std::io;
+
+use crate::mc::{connection::ConnectionState, mctypes::{MCInt, MCType}, packet::{InboundPacket, MCPacket, PacketId}, version::ProtocolVersion};
+
+/// Sent by the server when a chunk column leaves the view distance of the player, which
+/// should forget about it.
+/// <br> <br>
+/// The coordinates are read in the order of the canonical version. Since 1.20.2, the
+/// order is reversed; use `coordinates` to read them in the order of any version.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct UnloadChunk {
+    pub x: MCInt,
+    pub z: MCInt
+}
+
+impl UnloadChunk {
+    /// The first protocol version (1.20.2) sending the Z coordinate first.
+    const Z_FIRST_SINCE: i32 = 764;
+
+    /// Gets the X and Z coordinates of the column, as sent in the order of `version`.
+    pub fn coordinates(&self, version: ProtocolVersion) -> (i32, i32) {
+        match version.number() >= Self::Z_FIRST_SINCE {
+            true => (self.z.value(), self.x.value()),
+            false => (self.x.value(), self.z.value())
+        }
+    }
+}
+
+impl PacketId for UnloadChunk {
+    const ID: i32 = 0x1B;
+    const STATE: ConnectionState = ConnectionState::Play;
+}
+
+impl InboundPacket for UnloadChunk {
+    fn from_data(packet: &MCPacket) -> Result<Self, io::Error> {
+        if !packet.is::<Self>() {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, "Invalid packet ID."));
+        }
+
+        let (x, rest) = MCInt::parse(&packet.data)?;
+        let (z, _) = MCInt::parse(rest)?;
+        Ok(UnloadChunk { x, z })
+    }
+}
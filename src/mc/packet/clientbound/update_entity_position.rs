@@ -0,0 +1,15 @@
+use crate::mc::{mctypes::{MCBoolean, MCShort}, packet::{McDecode, PacketId}};
+
+/// Sent by the server when an entity moves less than 8 blocks. The deltas are in 1/4096
+/// of a block.
+#[derive(PacketId, McDecode)]
+#[packet(id = 0x27, state = Play)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct UpdateEntityPosition {
+    #[varint]
+    pub entity_id: i32,
+    pub delta_x: MCShort,
+    pub delta_y: MCShort,
+    pub delta_z: MCShort,
+    pub on_ground: MCBoolean
+}
@@ -0,0 +1,13 @@
+use crate::mc::{mctypes::{Angle, MCBoolean}, packet::{McDecode, PacketId}};
+
+/// Sent by the server when an entity turns without moving.
+#[derive(PacketId, McDecode)]
+#[packet(id = 0x29, state = Play)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct UpdateEntityRotation {
+    #[varint]
+    pub entity_id: i32,
+    pub yaw: Angle,
+    pub pitch: Angle,
+    pub on_ground: MCBoolean
+}
@@ -0,0 +1,98 @@
+use std::io;
+
+use bytes::Bytes;
+
+use crate::mc::{chat::ChatComponent, connection::ConnectionState, error::MCTypeError, mctypes::{MCByte, MCString, MCType, VarInt}, packet::{InboundPacket, MCPacket, PacketId}, version::ProtocolVersion};
+
+/// How the scores of an objective are shown.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ObjectiveKind {
+    /// As numbers.
+    #[default]
+    Integer,
+    /// As hearts, in the tab list.
+    Hearts
+}
+
+/// The decoded change of an objective.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ObjectiveChange {
+    /// The unique name of the objective.
+    pub name: String,
+    /// What the change does.
+    pub mode: i8,
+    /// The name shown for the objective, unless the objective is removed.
+    pub display_name: Option<ChatComponent>,
+    /// How the scores of the objective are shown, unless the objective is removed.
+    pub kind: Option<ObjectiveKind>
+}
+
+/// Sent by the server to create, remove or update an objective of the scoreboard. As the
+/// layout differs between versions, the packet is kept in its encoded form until it is
+/// decoded for the server's version with `change`.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct UpdateObjectives {
+    pub data: Bytes
+}
+
+impl UpdateObjectives {
+    /// The mode of changes creating an objective.
+    pub const CREATE: i8 = 0;
+    /// The mode of changes removing an objective.
+    pub const REMOVE: i8 = 1;
+    /// The mode of changes updating the display name and kind of an objective.
+    pub const UPDATE: i8 = 2;
+    /// The first protocol version (1.13) sending display names as text components, and
+    /// kinds as `VarInt`s rather than strings.
+    const COMPONENT_NAMES_SINCE: i32 = 393;
+
+    /// Decodes the change in the format of `version`. The number format objectives have
+    /// since 1.20.3 is not decoded.
+    /// # Errors
+    /// This function will return an `MCTypeError` if the data does not encode a change,
+    /// or the errors of `ChatComponent::parse_for`.
+    pub fn change(&self, version: ProtocolVersion) -> Result<ObjectiveChange, MCTypeError> {
+        let (name, rest) = MCString::parse_bounded(&self.data, 32767)?;
+        let (mode, rest) = MCByte::parse(rest)?;
+        let mode = mode.value();
+        let (mut display_name, mut kind) = (None, None);
+        if mode == Self::CREATE || mode == Self::UPDATE {
+            if version.number() >= Self::COMPONENT_NAMES_SINCE {
+                let (component, rest) = ChatComponent::parse_for(rest, version)?;
+                let (id, _) = VarInt::parse(rest)?;
+                display_name = Some(component);
+                kind = Some(match id.value() {
+                    1 => ObjectiveKind::Hearts,
+                    _ => ObjectiveKind::Integer
+                });
+            } else {
+                let (text, rest) = MCString::parse_bounded(rest, 32)?;
+                let (id, _) = MCString::parse_bounded(rest, 16)?;
+                display_name = Some(ChatComponent::text(text.string().as_str()));
+                kind = Some(match id.string().as_str() {
+                    "hearts" => ObjectiveKind::Hearts,
+                    _ => ObjectiveKind::Integer
+                });
+            }
+        }
+
+        Ok(ObjectiveChange { name: name.string().clone(), mode, display_name, kind })
+    }
+}
+
+impl PacketId for UpdateObjectives {
+    const ID: i32 = 0x54;
+    const STATE: ConnectionState = ConnectionState::Play;
+}
+
+impl InboundPacket for UpdateObjectives {
+    fn from_data(packet: &MCPacket) -> Result<Self, io::Error> {
+        if !packet.is::<Self>() {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, "Invalid packet ID."));
+        }
+
+        Ok(UpdateObjectives { data: packet.data.clone() })
+    }
+}
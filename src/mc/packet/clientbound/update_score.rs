@@ -0,0 +1,80 @@
+use std::io;
+
+use bytes::Bytes;
+
+use crate::mc::{chat::ChatComponent, connection::ConnectionState, error::MCTypeError, mctypes::{MCBoolean, MCString, MCType, VarInt}, packet::{InboundPacket, MCPacket, PacketId}, version::ProtocolVersion};
+
+/// The decoded change of a score.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ScoreChange {
+    /// The entity the score is of, which is the username of players, or the UUID of other
+    /// entities, or any text.
+    pub entity: String,
+    /// The objective the score belongs to, or `None` if the score is removed from every
+    /// objective.
+    pub objective: Option<String>,
+    /// The new value of the score, or `None` if the score is removed.
+    pub value: Option<i32>,
+    /// The name shown in place of the entity's, which servers send since 1.20.3.
+    pub display_name: Option<ChatComponent>
+}
+
+/// Sent by the server to set or remove the score of an entity. Since 1.20.3, scores are
+/// only set with this packet, and removed with Reset Score. As the layout differs between
+/// versions, the packet is kept in its encoded form until it is decoded for the server's
+/// version with `change`.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct UpdateScore {
+    pub data: Bytes
+}
+
+impl UpdateScore {
+    /// The action of updates removing a score, before 1.20.3.
+    const REMOVE: i32 = 1;
+
+    /// Decodes the change in the format of `version`. The number format scores have
+    /// since 1.20.3 is not decoded.
+    /// # Errors
+    /// This function will return an `MCTypeError` if the data does not encode a change,
+    /// or the errors of `ChatComponent::parse_for`.
+    pub fn change(&self, version: ProtocolVersion) -> Result<ScoreChange, MCTypeError> {
+        let (entity, rest) = MCString::parse_bounded(&self.data, 32767)?;
+        let entity = entity.string().clone();
+        if version >= ProtocolVersion::V1_20_4 {
+            let (objective, rest) = MCString::parse_bounded(rest, 32767)?;
+            let (value, rest) = VarInt::parse(rest)?;
+            let (has_display_name, rest) = MCBoolean::parse(rest)?;
+            let display_name = match has_display_name.value() {
+                true => Some(ChatComponent::parse_for(rest, version)?.0),
+                false => None
+            };
+            return Ok(ScoreChange { entity, objective: Some(objective.string().clone()), value: Some(value.value()), display_name });
+        }
+
+        // The action is a byte before 1.18, which is encoded as its `VarInt`.
+        let (action, rest) = VarInt::parse(rest)?;
+        let (objective, rest) = MCString::parse_bounded(rest, 32767)?;
+        let value = match action.value() == Self::REMOVE {
+            true => None,
+            false => Some(VarInt::parse(rest)?.0.value())
+        };
+        let objective = Some(objective.string().clone()).filter(|objective| !objective.is_empty());
+        Ok(ScoreChange { entity, objective, value, display_name: None })
+    }
+}
+
+impl PacketId for UpdateScore {
+    const ID: i32 = 0x57;
+    const STATE: ConnectionState = ConnectionState::Play;
+}
+
+impl InboundPacket for UpdateScore {
+    fn from_data(packet: &MCPacket) -> Result<Self, io::Error> {
+        if !packet.is::<Self>() {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, "Invalid packet ID."));
+        }
+
+        Ok(UpdateScore { data: packet.data.clone() })
+    }
+}
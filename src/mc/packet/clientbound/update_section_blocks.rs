@@ -0,0 +1,83 @@
+use std::io;
+
+use bytes::Bytes;
+
+use crate::mc::{connection::ConnectionState, error::MCTypeError, mctypes::{MCBoolean, MCInt, MCLong, MCPosition, MCType, MCUnsignedByte, VarInt, VarLong}, packet::{InboundPacket, MCPacket, PacketId}, version::ProtocolVersion};
+
+/// Sent by the server when several blocks of a chunk change at once. Before 1.16.2, the
+/// blocks are those of a chunk column; since, they are those of a section. As the layout
+/// differs between versions, the changes are kept in their encoded form until they are
+/// decoded for the server's version with `changes`.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct UpdateSectionBlocks {
+    pub data: Bytes
+}
+
+impl UpdateSectionBlocks {
+    /// The first protocol version (1.16.2) sending the blocks of a section.
+    const SECTION_POSITION_SINCE: i32 = 751;
+    /// The first protocol version (1.20) no longer sending whether light updates are
+    /// suppressed.
+    const NO_LIGHT_FLAG_SINCE: i32 = 763;
+
+    /// Decodes the changes in the format of `version`, as the positions of the blocks
+    /// paired with their new block states.
+    /// # Errors
+    /// This function will return an `MCTypeError` if the data does not encode changes.
+    pub fn changes(&self, version: ProtocolVersion) -> Result<Vec<(MCPosition, i32)>, MCTypeError> {
+        if version.number() < Self::SECTION_POSITION_SINCE {
+            let (chunk_x, rest) = MCInt::parse(&self.data)?;
+            let (chunk_z, rest) = MCInt::parse(rest)?;
+            let (count, mut rest) = VarInt::parse(rest)?;
+            let mut changes = Vec::new();
+            for _ in 0..count.value() {
+                let (horizontal, after) = MCUnsignedByte::parse(rest)?;
+                let (y, after) = MCUnsignedByte::parse(after)?;
+                let (state, after) = VarInt::parse(after)?;
+                let x = chunk_x.value() * 16 + (horizontal.value() >> 4) as i32;
+                let z = chunk_z.value() * 16 + (horizontal.value() & 15) as i32;
+                changes.push((MCPosition::new(x, y.value() as i32, z), state.value()));
+                rest = after;
+            }
+            return Ok(changes);
+        }
+
+        let (section, rest) = MCLong::parse(&self.data)?;
+        let rest = match version.number() < Self::NO_LIGHT_FLAG_SINCE {
+            true => MCBoolean::parse(rest)?.1,
+            false => rest
+        };
+        let section = section.value();
+        let (section_x, section_y, section_z) = ((section >> 42) as i32, (section << 44 >> 44) as i32, (section << 22 >> 42) as i32);
+
+        let (count, mut rest) = VarInt::parse(rest)?;
+        let mut changes = Vec::new();
+        for _ in 0..count.value() {
+            let (record, after) = VarLong::parse(rest)?;
+            let record = record.value();
+            let position = MCPosition::new(
+                section_x * 16 + (record >> 8 & 15) as i32,
+                section_y * 16 + (record & 15) as i32,
+                section_z * 16 + (record >> 4 & 15) as i32
+            );
+            changes.push((position, (record >> 12) as i32));
+            rest = after;
+        }
+        Ok(changes)
+    }
+}
+
+impl PacketId for UpdateSectionBlocks {
+    const ID: i32 = 0x3F;
+    const STATE: ConnectionState = ConnectionState::Play;
+}
+
+impl InboundPacket for UpdateSectionBlocks {
+    fn from_data(packet: &MCPacket) -> Result<Self, io::Error> {
+        if !packet.is::<Self>() {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, "Invalid packet ID."));
+        }
+
+        Ok(UpdateSectionBlocks { data: packet.data.clone() })
+    }
+}
@@ -0,0 +1,58 @@
+use std::io;
+
+use crate::mc::{connection::ConnectionState, mctypes::{Identifier, MCTypeDecode, VarInt}, packet::{InboundPacket, MCPacket, PacketId}};
+
+/// A tag of a registry, naming a group of its entries such as `minecraft:logs`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Tag {
+    pub name: Identifier,
+    /// The numeric IDs of the registry entries in the tag.
+    pub entries: Vec<i32>
+}
+
+/// The tags of one registry, such as `minecraft:block`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RegistryTags {
+    pub registry: Identifier,
+    pub tags: Vec<Tag>
+}
+
+/// Sent by the server during configuration with the tags of its registries.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct UpdateTags {
+    pub registries: Vec<RegistryTags>
+}
+
+impl PacketId for UpdateTags {
+    const ID: i32 = 0x08;
+    const STATE: ConnectionState = ConnectionState::Configuration;
+}
+
+impl InboundPacket for UpdateTags {
+    fn from_data(packet: &MCPacket) -> Result<Self, io::Error> {
+        if !packet.is::<Self>() {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, "Invalid packet ID."));
+        }
+
+        let mut cursor = packet.cursor();
+        let mut registries = Vec::new();
+        for _ in 0..VarInt::read(&mut cursor)?.value() {
+            let registry = Identifier::read(&mut cursor)?;
+
+            let mut tags = Vec::new();
+            for _ in 0..VarInt::read(&mut cursor)?.value() {
+                let name = Identifier::read(&mut cursor)?;
+                let mut entries = Vec::new();
+                for _ in 0..VarInt::read(&mut cursor)?.value() {
+                    entries.push(VarInt::read(&mut cursor)?.value());
+                }
+                tags.push(Tag { name, entries });
+            }
+            registries.push(RegistryTags { registry, tags });
+        }
+
+        Ok(UpdateTags { registries })
+    }
+}
@@ -1,27 +1,92 @@
-use std::io;
+use std::io::{self, Read, Write, Cursor};
 
-use super::mctypes::{VarInt, MCType};
+use bytes::Bytes;
+use flate2::{read::ZlibDecoder, write::ZlibEncoder, Compression};
+
+use super::{mctypes::{VarInt, MCType}, error::{DisconnectReason, ProtocolError}, connection::ConnectionState, limits::{self, DecodeLimits}, registry::{self, Direction}, version::ProtocolVersion};
+
+pub use mcclient_derive::{McDecode, McEncode, PacketId};
 
 pub mod serverbound;
 pub mod clientbound;
 
+/// Associates a packet structure with its packet ID at compile time. Every packet
+/// implementing `PacketId` reports `Self::ID` and `Self::STATE` through
+/// `PacketIdentity`, so a packet body can never be sent or parsed under the wrong ID.
+pub trait PacketId {
+    /// The ID of this packet in the protocol state it belongs to.
+    const ID: i32;
+    /// The protocol state this packet belongs to.
+    const STATE: ConnectionState;
+}
+
+/// The ID a packet is sent or read under, and the protocol state it belongs to. Typed
+/// packets get this from their `PacketId`, and cannot implement it themselves; only
+/// packets whose ID is decided at runtime, such as a raw `MCPacket`, do.
+pub trait PacketIdentity {
+    /// Retrieves the ID of this packet.
+    fn packet_id(&self) -> i32;
+    /// Retrieves the protocol state this packet may be sent in. Packets without a known
+    /// state, such as a raw `MCPacket`, return `None` and may be sent in any state.
+    fn state(&self) -> Option<ConnectionState>;
+}
+
+impl<P: PacketId> PacketIdentity for P {
+    fn packet_id(&self) -> i32 {
+        P::ID
+    }
+
+    fn state(&self) -> Option<ConnectionState> {
+        Some(P::STATE)
+    }
+}
+
 /// Interfaces serverbound packets. Structs implementing this trait are
 /// expected to be mcproto-compliant packets; transfering malformatted
 /// packets will result in undefined behavior.
-pub trait OutboundPacket {
+pub trait OutboundPacket: PacketIdentity {
+    /// Serializes the internal packet data directly into `w`, excluding the length
+    /// and Packet ID.
+    /// # Errors
+    /// This function will return an `io::Error` if `w` cannot be written to.
+    fn encode(&self, w: &mut dyn Write) -> io::Result<()>;
     /// Serializes the internal packet data into an array of bytes.
-    fn to_bytes(&self) -> Vec<u8>;
-    /// Retrieves the ID of this packet. This can be a compile-time constant.
-    /// Will eventually refactor.
-    fn packet_id(&self) -> i32;
+    fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::<u8>::with_capacity(self.len().max(0) as usize);
+        self.encode(&mut bytes).expect("Writing to a Vec cannot fail.");
+        bytes
+    }
     /// Get length of packet (excluding length of Packet ID)
     fn len(&self) -> i32;
+    /// Serializes the packet data into `w` as per `encode`, laid out as in protocol
+    /// `version`. Streams frame packets with this, so that packets whose layout differs
+    /// between versions are sent as the negotiated version expects.
+    /// # Errors
+    /// This function will return an `io::Error` if `w` cannot be written to.
+    fn encode_for(&self, version: ProtocolVersion, w: &mut dyn Write) -> io::Result<()> {
+        let _ = version;
+        self.encode(w)
+    }
+    /// Get length of packet as per `len`, laid out as in protocol `version`.
+    fn len_for(&self, version: ProtocolVersion) -> i32 {
+        let _ = version;
+        self.len()
+    }
+    /// Retrieves the protocol state the connection switches to once this packet has
+    /// been sent, if sending it switches state.
+    fn next_state(&self) -> Option<ConnectionState> {
+        None
+    }
+    /// Whether the packet carries no data beyond its Packet ID.
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
 }
 
 /// Interfaced clientbound packets. Struct implementing this trait
 /// are expected to be mcproto-compliant packets which are parsed
 /// from an array of bytes retrieved from a server.
-pub trait InboundPacket: Sized {
+pub trait InboundPacket: PacketId + Sized {
     /// Attempts to deserialize the given bytes into the implied packet
     /// type. Note: Passing bytes that are not formatted as per the
     /// Minecraft protocol is undefined.
@@ -35,28 +100,29 @@ pub trait InboundPacket: Sized {
     /// If the inbound packet data is well-formatted and can be parsed
     /// by the implementing structure, a `Box<Self>` is returned.
     #[deprecated]
-    fn from_bytes(bytes: &[u8]) -> Result<Self, io::Error>;
+    fn from_bytes(bytes: &[u8]) -> Result<Self, io::Error> {
+        Self::from_data(&MCPacket::from_bytes(&mut bytes.to_vec())?)
+    }
 
     fn from_data(packet: &MCPacket) -> Result<Self, io::Error>;
 
-    /// Retrieves the ID of this inbound packet.
-    fn packet_id(&self) -> i32;
+    /// Deserializes `packet` as per `from_data`, laid out as in protocol `version`, such
+    /// as the `version` of the stream it was read from.
+    fn from_data_for(packet: &MCPacket, version: ProtocolVersion) -> Result<Self, io::Error> {
+        let _ = version;
+        Self::from_data(packet)
+    }
 }
 
 /// Serialize a serverbound packet to be sent to a server.
 #[allow(dead_code)]
 pub fn serialize_packet(data: &dyn OutboundPacket) -> Vec<u8> {
     let mut serialized_packet_bytes = Vec::<u8>::new();
-
-    let packet_id = VarInt::from_i32(data.packet_id());
-    let size = data.len() + packet_id.size();
-    serialized_packet_bytes.append(&mut VarInt::from(size).to_bytes());
-    serialized_packet_bytes.append(&mut packet_id.to_bytes());
-    serialized_packet_bytes.append(&mut data.to_bytes());
-
+    write_packet(&mut serialized_packet_bytes, data).expect("Writing to a Vec cannot fail.");
     serialized_packet_bytes
 }
 
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct MCPacketHeader{ pub size: VarInt, pub id: VarInt }
 
 /// Attemps to parse a packet header from bytes, consuming the `VarInt` elements of
@@ -71,17 +137,255 @@ pub fn read_packet_header(bytes: &mut Vec<u8>) -> Result<MCPacketHeader, io::Err
     Ok(MCPacketHeader{ size: packet_size, id: packet_id })
 }
 
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct MCPacket {
     pub header: MCPacketHeader,
-    pub data: Vec<u8>,
+    /// The fields of the packet, sharing the buffer the packet was received in. Typed
+    /// packets decoded from it keep slices of it for their opaque fields rather than
+    /// copies, and `Bytes::to_vec` copies it out if it needs to be modified.
+    pub data: Bytes,
 }
 
 impl MCPacket {
+    /// Constructs a Minecraft packet from its ID and serialized fields, computing the
+    /// length of the packet.
+    pub fn new<D: Into<Bytes>>(id: i32, data: D) -> MCPacket {
+        let data = data.into();
+        let id = VarInt::from(id);
+        let size = VarInt::from(id.len() + data.len() as i32);
+        MCPacket{ header: MCPacketHeader{ size, id }, data }
+    }
+
     /// Constructs a Minecraft packet object from a set of bytes, consuming the `bytes` passed.
     /// # Errors
     /// This function will return `io::Error` if the bytes cannot be properly parsed.
     pub fn from_bytes(bytes: &mut Vec<u8>) -> Result<MCPacket, io::Error> {
         let header = read_packet_header(bytes)?;
-        Ok(MCPacket{ header, data: std::mem::take(bytes) })
+        Ok(MCPacket{ header, data: Bytes::from(std::mem::take(bytes)) })
+    }
+
+    /// Constructs a packet from the body of its frame, its Packet ID followed by its
+    /// fields, which the packet keeps sharing.
+    /// # Errors
+    /// This function will return `ProtocolError::InvalidData` if the Packet ID is malformed.
+    pub fn from_frame(frame: Bytes) -> Result<MCPacket, ProtocolError> {
+        let (id, data) = split_packet_id(frame)?;
+        Ok(MCPacket::new(id.value(), data))
     }
+
+    /// Creates a cursor over the packet data, for decoding its fields in order
+    /// with `MCTypeDecode`.
+    pub fn cursor(&self) -> Cursor<&[u8]> {
+        Cursor::new(&self.data[..])
+    }
+
+    /// Whether this packet has the ID of packet type `P`.
+    pub fn is<P: PacketId>(&self) -> bool {
+        self.header.id.value() == P::ID
+    }
+}
+
+impl PacketIdentity for MCPacket {
+    fn packet_id(&self) -> i32 {
+        self.header.id.value()
+    }
+
+    fn state(&self) -> Option<ConnectionState> {
+        None
+    }
+}
+
+impl OutboundPacket for MCPacket {
+    fn encode(&self, w: &mut dyn Write) -> io::Result<()> {
+        w.write_all(&self.data)
+    }
+
+    fn len(&self) -> i32 {
+        self.data.len() as i32
+    }
+}
+
+/// Assembles a `MCPacket` field-by-field from `MCType`s, in the order the fields
+/// appear in the packet.
+/// # Example
+/// ```
+/// use mcclient::mc::{mctypes::{MCString, MCBoolean}, packet::{PacketBuilder, serverbound::login_start::LoginStart}};
+///
+/// let packet = PacketBuilder::of::<LoginStart>()
+///     .field(&MCString::from("Makoto"))
+///     .field(&MCBoolean::from(false))
+///     .build();
+/// assert_eq!(packet.header.size.value(), 9);
+/// ```
+pub struct PacketBuilder {
+    id: i32,
+    data: Vec<u8>,
+}
+
+impl PacketBuilder {
+    /// Begins a packet with the ID `id`. Packets are otherwise begun with `of`, which
+    /// takes the ID of a typed packet, so that their IDs cannot be mistyped.
+    pub(crate) fn new(id: i32) -> Self {
+        PacketBuilder { id, data: Vec::new() }
+    }
+
+    /// Begins a packet with the ID of packet type `P`.
+    pub fn of<P: PacketId>() -> Self {
+        PacketBuilder::new(P::ID)
+    }
+
+    /// Appends a field to the packet.
+    pub fn field<T: MCType>(mut self, value: &T) -> Self {
+        self.data.extend(value.to_bytes());
+        self
+    }
+
+    /// Appends raw bytes to the packet, such as a trailing byte array.
+    pub fn bytes(mut self, bytes: &[u8]) -> Self {
+        self.data.extend_from_slice(bytes);
+        self
+    }
+
+    /// Completes the packet, computing its length.
+    pub fn build(self) -> MCPacket {
+        MCPacket::new(self.id, self.data)
+    }
+}
+
+/// Writes a single packet to `writer`, prefixed with its length. The packet is
+/// encoded directly into `writer` without being buffered first, so `writer` should
+/// usually be buffered itself. The writer is not flushed.
+/// # Errors
+/// This function will return an `io::Error` if the packet cannot be written.
+pub fn write_packet<W: Write>(writer: &mut W, packet: &dyn OutboundPacket) -> Result<(), io::Error> {
+    let packet_id = VarInt::from(packet.packet_id());
+    VarInt::from(packet.len() + packet_id.size()).encode(writer)?;
+    packet_id.encode(writer)?;
+    packet.encode(writer)
+}
+
+/// The largest uncompressed size of a compressed packet, as enforced by the vanilla
+/// server and client, which `DecodeLimits::max_uncompressed_size` defaults to.
+pub const MAX_UNCOMPRESSED_SIZE: i32 = DecodeLimits::DEFAULT_MAX_UNCOMPRESSED_SIZE as i32;
+
+/// Writes a single packet to `writer` in the compressed format used once the server has
+/// sent Set Compression. The Packet ID and data are zlib-compressed if they span at
+/// least `threshold` bytes, and are otherwise sent as-is. The writer is not flushed.
+/// # Errors
+/// This function will return an `io::Error` if the packet cannot be written.
+pub fn write_compressed_packet<W: Write>(writer: &mut W, packet: &dyn OutboundPacket, threshold: i32) -> Result<(), io::Error> {
+    write_compressed_packet_at(writer, packet, threshold, Compression::default().level())
+}
+
+/// Writes a single packet to `writer` as per `write_compressed_packet`, compressing it
+/// at the zlib `level`, from 0 (stored) to 9 (smallest).
+/// # Errors
+/// This function will return an `io::Error` if the packet cannot be written.
+pub fn write_compressed_packet_at<W: Write>(writer: &mut W, packet: &dyn OutboundPacket, threshold: i32, level: u32) -> Result<(), io::Error> {
+    let packet_id = VarInt::from(packet.packet_id());
+    let data_length = packet.len() + packet_id.size();
+
+    if data_length < threshold {
+        VarInt::from(data_length + 1).encode(writer)?;
+        VarInt::from(0).encode(writer)?;
+        packet_id.encode(writer)?;
+        return packet.encode(writer);
+    }
+
+    let mut encoder = ZlibEncoder::new(Vec::new(), Compression::new(level.min(9)));
+    packet_id.encode(&mut encoder)?;
+    packet.encode(&mut encoder)?;
+    let compressed = encoder.finish()?;
+
+    let data_length = VarInt::from(data_length);
+    VarInt::from(data_length.size() + compressed.len() as i32).encode(writer)?;
+    data_length.encode(writer)?;
+    writer.write_all(&compressed)
+}
+
+/// Reads a single packet from `reader`, blocking until the entire packet has been
+/// received. The length prefix is read one byte at a time, after which the packet
+/// body is read in full; short reads from slow or fragmented connections are
+/// retried until the promised number of bytes has arrived.
+/// # Errors
+/// This function will return `ProtocolError::UnexpectedEof` if the stream closes
+/// before the full packet is received, `ProtocolError::InvalidData` if the length
+/// prefix or packet ID is malformed, and `ProtocolError::Io` if the read otherwise
+/// fails.
+pub fn read_packet<R: Read>(reader: &mut R) -> Result<MCPacket, ProtocolError> {
+    let size = VarInt::read_from(reader).map_err(|err| match err.kind() {
+        io::ErrorKind::InvalidData => ProtocolError::InvalidData(err.to_string()),
+        _ => ProtocolError::from(err)
+    })?;
+    let length = limits::packet_size(size.value()).map_err(ProtocolError::InvalidData)?;
+
+    let mut data = vec![0; length];
+    reader.read_exact(&mut data)?;
+
+    let (id, data) = split_packet_id(Bytes::from(data))?;
+    Ok(MCPacket{ header: MCPacketHeader{ size, id }, data })
+}
+
+/// Reads a single packet in the compressed format from `reader`, as per `read_packet`,
+/// decompressing it if needed.
+/// # Errors
+/// In addition to the errors returned by `read_packet`, this function will return
+/// `ProtocolError::InvalidData` if the packet cannot be decompressed, or if it does not
+/// decompress to the size it claims.
+pub fn read_compressed_packet<R: Read>(reader: &mut R) -> Result<MCPacket, ProtocolError> {
+    let packet = read_packet(reader)?;
+    // The header read as the Packet ID is the length of the uncompressed data.
+    let data_length = packet.header.id.value();
+    if data_length == 0 {
+        let (id, data) = split_packet_id(packet.data)?;
+        return Ok(MCPacket::new(id.value(), data));
+    }
+    limits::uncompressed_size(data_length).map_err(ProtocolError::InvalidData)?;
+
+    let mut data = Vec::with_capacity(data_length as usize);
+    ZlibDecoder::new(&packet.data[..])
+        .take(data_length as u64 + 1)
+        .read_to_end(&mut data)
+        .map_err(|err| ProtocolError::InvalidData(format!("Could not decompress packet: {}", err)))?;
+    if data.len() != data_length as usize {
+        return Err(ProtocolError::InvalidData(format!("Packet decompressed to {} bytes rather than {}.", data.len(), data_length)));
+    }
+
+    let (id, data) = split_packet_id(Bytes::from(data))?;
+    Ok(MCPacket::new(id.value(), data))
+}
+
+/// Splits the Packet ID off of the front of the bytes of a packet, leaving its fields in
+/// the same buffer.
+fn split_packet_id(data: Bytes) -> Result<(VarInt, Bytes), ProtocolError> {
+    let (id, rest) = VarInt::parse(&data).map_err(|err| ProtocolError::InvalidData(err.to_string()))?;
+    let offset = data.len() - rest.len();
+    Ok((id, data.slice(offset..)))
+}
+
+/// Reads a single packet from `reader` as per `read_packet`, additionally recognizing
+/// the Disconnect packet of the connection's current `state` in the protocol `version`.
+/// # Errors
+/// In addition to the errors returned by `read_packet`, this function will return
+/// `ProtocolError::Disconnected` carrying the server's reason if the packet read is
+/// a Disconnect packet.
+pub fn read_state_packet<R: Read>(reader: &mut R, state: ConnectionState, version: ProtocolVersion) -> Result<MCPacket, ProtocolError> {
+    surface_disconnect(read_packet(reader)?, state, version)
+}
+
+/// Passes `packet`, as received in the protocol `version`, through, unless it is the
+/// Disconnect packet of `state`. Its ID is translated to its canonical ID as per
+/// `registry` to be recognized, while the packet passed through keeps its own.
+/// # Errors
+/// This function will return `ProtocolError::Disconnected` carrying the server's reason,
+/// parsed as per `DisconnectReason::decode`, if `packet` is a Disconnect packet.
+pub fn surface_disconnect(packet: MCPacket, state: ConnectionState, version: ProtocolVersion) -> Result<MCPacket, ProtocolError> {
+    let canonical = registry::canonical_id(version, state, Direction::Clientbound, packet.header.id.value());
+    if canonical.is_some() && state.disconnect_packet_id() == canonical {
+        let reason = DisconnectReason::decode(state, version, &packet.data)
+            .map_err(|err| ProtocolError::InvalidData(err.to_string()))?;
+        return Err(ProtocolError::Disconnected(Box::new(reason)));
+    }
+
+    Ok(packet)
 }
@@ -0,0 +1,7 @@
+use crate::mc::packet::{McEncode, PacketId};
+
+/// Acknowledges a Finish Configuration, moving the connection to the Play state.
+#[derive(PacketId, McEncode)]
+#[packet(id = 0x02, state = Configuration, next_state = Play)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AcknowledgeFinishConfiguration;
@@ -0,0 +1,76 @@
+use std::io::{self, Write};
+
+use crate::mc::{connection::ConnectionState, mctypes::{MCFixedBitSet, MCLong, MCString, MCType, VarInt}, packet::{OutboundPacket, PacketId}};
+
+use super::chat_message::SIGNATURE_LENGTH;
+
+/// The signature of one argument of a signed command, such as the message of `/msg`.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ArgumentSignature {
+    pub name: MCString,
+    #[cfg_attr(feature = "serde", serde(with = "super::chat_message::serde_signature"))]
+    pub signature: [u8; SIGNATURE_LENGTH]
+}
+
+/// Runs a command, given without its leading slash. Commands may be at most 256 characters.
+/// Since 1.19, players run commands with this packet rather than a chat message; the
+/// message arguments of commands can be signed as chat messages are, and unsigned commands
+/// are only accepted by servers which do not enforce secure chat.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ChatCommand {
+    pub command: MCString,
+    pub timestamp: MCLong,
+    pub salt: MCLong,
+    pub argument_signatures: Vec<ArgumentSignature>,
+    pub message_count: VarInt,
+    pub acknowledged: MCFixedBitSet<20>
+}
+
+impl ChatCommand {
+    /// Creates an unsigned command run at `timestamp`, in milliseconds since the epoch,
+    /// acknowledging no messages.
+    pub fn unsigned(command: MCString, timestamp: i64) -> Self {
+        ChatCommand {
+            command,
+            timestamp: timestamp.into(),
+            salt: 0.into(),
+            argument_signatures: Vec::new(),
+            message_count: 0.into(),
+            acknowledged: MCFixedBitSet::new()
+        }
+    }
+}
+
+impl PacketId for ChatCommand {
+    const ID: i32 = 0x04;
+    const STATE: ConnectionState = ConnectionState::Play;
+}
+
+impl OutboundPacket for ChatCommand {
+    fn encode(&self, w: &mut dyn Write) -> io::Result<()> {
+        self.command.encode(w)?;
+        self.timestamp.encode(w)?;
+        self.salt.encode(w)?;
+        VarInt::from(self.argument_signatures.len() as i32).encode(w)?;
+        for argument in &self.argument_signatures {
+            argument.name.encode(w)?;
+            w.write_all(&argument.signature)?;
+        }
+        self.message_count.encode(w)?;
+        self.acknowledged.encode(w)
+    }
+
+    fn len(&self) -> i32 {
+        let mut size: i32 = 0;
+
+        size += self.command.size();
+        size += self.timestamp.size() + self.salt.size();
+        size += VarInt::from(self.argument_signatures.len() as i32).size();
+        for argument in &self.argument_signatures {
+            size += argument.name.size() + SIGNATURE_LENGTH as i32;
+        }
+        size += self.message_count.size() + self.acknowledged.size();
+
+        size
+    }
+}
@@ -0,0 +1,110 @@
+use std::io::{self, Write};
+
+use crate::mc::{connection::ConnectionState, mctypes::{MCBoolean, MCFixedBitSet, MCLong, MCString, MCType, VarInt}, packet::{OutboundPacket, PacketId}};
+
+/// The length of a chat message signature.
+pub const SIGNATURE_LENGTH: usize = 256;
+
+/// Serializes signatures as sequences of bytes, as serde only implements arrays of up to
+/// 32 elements.
+#[cfg(feature = "serde")]
+pub(crate) mod serde_signature {
+    use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+
+    use super::SIGNATURE_LENGTH;
+
+    pub fn serialize<S: Serializer>(signature: &[u8; SIGNATURE_LENGTH], serializer: S) -> Result<S::Ok, S::Error> {
+        signature.as_slice().serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<[u8; SIGNATURE_LENGTH], D::Error> {
+        Vec::<u8>::deserialize(deserializer)?
+            .try_into()
+            .map_err(|bytes: Vec<u8>| de::Error::invalid_length(bytes.len(), &"256 bytes"))
+    }
+
+    /// Serializes optional signatures, as `null` if they are absent.
+    pub mod option {
+        use serde::{Deserialize, Deserializer, Serializer};
+
+        use super::SIGNATURE_LENGTH;
+
+        pub fn serialize<S: Serializer>(signature: &Option<[u8; SIGNATURE_LENGTH]>, serializer: S) -> Result<S::Ok, S::Error> {
+            match signature {
+                Some(signature) => super::serialize(signature, serializer),
+                None => serializer.serialize_none()
+            }
+        }
+
+        pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<[u8; SIGNATURE_LENGTH]>, D::Error> {
+            Option::<Vec<u8>>::deserialize(deserializer)?
+                .map(|bytes| bytes.try_into().map_err(|bytes: Vec<u8>| serde::de::Error::invalid_length(bytes.len(), &"256 bytes")))
+                .transpose()
+        }
+    }
+}
+
+/// Sends a chat message. Messages may be at most 256 characters. Since 1.19, messages
+/// can be signed with the player's chat key; unsigned messages have no `signature`, and
+/// are only accepted by servers which do not enforce secure chat.
+/// <br> <br>
+/// `acknowledged` marks which of the last 20 chat messages received the client has seen,
+/// and `message_count` is the number of messages seen since the last acknowledgement.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ChatMessage {
+    pub message: MCString,
+    pub timestamp: MCLong,
+    pub salt: MCLong,
+    #[cfg_attr(feature = "serde", serde(with = "serde_signature::option"))]
+    pub signature: Option<[u8; SIGNATURE_LENGTH]>,
+    pub message_count: VarInt,
+    pub acknowledged: MCFixedBitSet<20>
+}
+
+impl ChatMessage {
+    /// Creates an unsigned message sent at `timestamp`, in milliseconds since the epoch,
+    /// acknowledging no messages.
+    pub fn unsigned(message: MCString, timestamp: i64) -> Self {
+        ChatMessage {
+            message,
+            timestamp: timestamp.into(),
+            salt: 0.into(),
+            signature: None,
+            message_count: 0.into(),
+            acknowledged: MCFixedBitSet::new()
+        }
+    }
+}
+
+impl PacketId for ChatMessage {
+    const ID: i32 = 0x05;
+    const STATE: ConnectionState = ConnectionState::Play;
+}
+
+impl OutboundPacket for ChatMessage {
+    fn encode(&self, w: &mut dyn Write) -> io::Result<()> {
+        self.message.encode(w)?;
+        self.timestamp.encode(w)?;
+        self.salt.encode(w)?;
+        MCBoolean::from(self.signature.is_some()).encode(w)?;
+        if let Some(signature) = &self.signature {
+            w.write_all(signature)?;
+        }
+        self.message_count.encode(w)?;
+        self.acknowledged.encode(w)
+    }
+
+    fn len(&self) -> i32 {
+        let mut size: i32 = 0;
+
+        size += self.message.size();
+        size += self.timestamp.size() + self.salt.size();
+        size += 1;
+        if self.signature.is_some() {
+            size += SIGNATURE_LENGTH as i32;
+        }
+        size += self.message_count.size() + self.acknowledged.size();
+
+        size
+    }
+}
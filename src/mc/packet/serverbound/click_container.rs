@@ -0,0 +1,117 @@
+use std::io::{self, Write};
+
+use crate::mc::{connection::ConnectionState, item::Slot, mctypes::{MCByte, MCShort, MCType, MCUnsignedByte, VarInt}, packet::{OutboundPacket, PacketId, clientbound::set_container_content::SetContainerContent}, version::ProtocolVersion};
+
+/// The ways of clicking a slot, which give the `button` of a click its meaning.
+/// <https://wiki.vg/Protocol#Click_Container>
+#[repr(i32)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ClickMode {
+    /// Picks up or puts down items with the left (0) or right (1) button.
+    Pickup = 0,
+    /// Moves the items to the other part of the window, as with a shift-click.
+    QuickMove = 1,
+    /// Swaps the items with the hotbar slot `button`, or with the off hand (40).
+    Swap = 2,
+    /// Picks up a full stack of the items, in creative mode.
+    Clone = 3,
+    /// Drops one item (0) or the whole stack (1).
+    Throw = 4,
+    /// Spreads the carried items over several slots, as when dragging.
+    QuickCraft = 5,
+    /// Picks up every item like the carried one, as with a double click.
+    PickupAll = 6
+}
+
+/// Clicks the `slot` of the window `window_id` with the `button` of `mode`.
+/// <br> <br>
+/// Since 1.17.1, the click carries the state ID of the window it was made in, along with
+/// the slots it changed and the item then carried on the cursor as the client predicts
+/// them, which the server corrects if it disagrees. Before, the click carries a number the
+/// server confirms it with, and `item` is the item of the clicked slot before the click.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ClickContainer {
+    pub window_id: MCUnsignedByte,
+    pub state_id: Option<VarInt>,
+    pub slot: MCShort,
+    pub button: MCByte,
+    pub action: Option<MCShort>,
+    pub mode: ClickMode,
+    pub changed_slots: Vec<(i16, Slot)>,
+    pub item: Slot,
+    version: ProtocolVersion
+}
+
+impl ClickContainer {
+    /// Creates a click in the format of `version`, without predicted changes. The
+    /// `number` is the state ID of the window if `version` is 1.17.1 or newer, and the
+    /// number of the click otherwise.
+    pub fn new(version: ProtocolVersion, window_id: u8, slot: i16, button: i8, mode: ClickMode, number: i32) -> Self {
+        let numbered = version.number() >= SetContainerContent::STATE_ID_SINCE;
+        ClickContainer {
+            window_id: MCUnsignedByte::from(window_id),
+            state_id: numbered.then(|| VarInt::from(number)),
+            slot: MCShort::from(slot),
+            button: MCByte::from(button),
+            action: (!numbered).then(|| MCShort::from(number as i16)),
+            mode,
+            changed_slots: Vec::new(),
+            item: Slot::EMPTY,
+            version
+        }
+    }
+
+    fn slot_bytes(&self) -> io::Result<Vec<u8>> {
+        let mut bytes = Vec::new();
+        if self.state_id.is_some() {
+            VarInt::from(self.changed_slots.len() as i32).encode(&mut bytes)?;
+            for (slot, item) in &self.changed_slots {
+                MCShort::from(*slot).encode(&mut bytes)?;
+                item.write_for(self.version, &mut bytes)?;
+            }
+        }
+        self.item.write_for(self.version, &mut bytes)?;
+        Ok(bytes)
+    }
+}
+
+impl PacketId for ClickContainer {
+    const ID: i32 = 0x0A;
+    const STATE: ConnectionState = ConnectionState::Play;
+}
+
+impl OutboundPacket for ClickContainer {
+    fn encode(&self, w: &mut dyn Write) -> io::Result<()> {
+        self.window_id.encode(w)?;
+        if let Some(state_id) = &self.state_id {
+            state_id.encode(w)?;
+        }
+        self.slot.encode(w)?;
+        self.button.encode(w)?;
+        if let Some(action) = &self.action {
+            action.encode(w)?;
+        }
+        // The mode is a byte in 1.8, which encodes its values as a VarInt does.
+        VarInt::from(self.mode as i32).encode(w)?;
+        w.write_all(&self.slot_bytes()?)
+    }
+
+    fn len(&self) -> i32 {
+        let mut size: i32 = 0;
+
+        size += self.window_id.size();
+        if let Some(state_id) = &self.state_id {
+            size += state_id.size();
+        }
+        size += self.slot.size();
+        size += self.button.size();
+        if let Some(action) = &self.action {
+            size += action.size();
+        }
+        size += VarInt::from(self.mode as i32).size();
+        size += self.slot_bytes().map_or(0, |bytes| bytes.len() as i32);
+
+        size
+    }
+}
@@ -0,0 +1,35 @@
+use std::io::{self, Write};
+
+use crate::mc::{connection::ConnectionState, mctypes::{MCType, VarInt}, packet::{OutboundPacket, PacketId}};
+
+/// Actions which can be performed with a Client Command.
+#[repr(i32)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ClientCommandAction {
+    /// Respawns the player once it has died.
+    PerformRespawn = 0,
+    /// Requests the player's statistics.
+    RequestStats = 1
+}
+
+/// Asks the server to respawn the player, or to send its statistics.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ClientCommand {
+    pub action: ClientCommandAction
+}
+
+impl PacketId for ClientCommand {
+    const ID: i32 = 0x06;
+    const STATE: ConnectionState = ConnectionState::Play;
+}
+
+impl OutboundPacket for ClientCommand {
+    fn encode(&self, w: &mut dyn Write) -> io::Result<()> {
+        VarInt::from(self.action as i32).encode(w)
+    }
+
+    fn len(&self) -> i32 {
+        VarInt::from(self.action as i32).size()
+    }
+}
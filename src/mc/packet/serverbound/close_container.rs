@@ -0,0 +1,9 @@
+use crate::mc::{mctypes::MCUnsignedByte, packet::{McEncode, PacketId}};
+
+/// Closes the window `window_id`, which is 0 for the player's inventory.
+#[derive(PacketId, McEncode)]
+#[packet(id = 0x0B, state = Play)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CloseContainer {
+    pub window_id: MCUnsignedByte
+}
@@ -0,0 +1,72 @@
+use std::io::{self, Write};
+
+use crate::mc::{connection::ConnectionState, mctypes::{MCBoolean, MCString, MCType, VarInt}, packet::{OutboundPacket, PacketId}, version::ProtocolVersion};
+
+/// Asks the server to complete the chat or command `text`, as when the player presses
+/// tab, which the server answers with Command Suggestions. Since 1.13, requests carry a
+/// `transaction_id` the response repeats. Before 1.13, this is Tab-Complete, which
+/// tells whether the text is to be taken as a command without its slash since 1.9, and
+/// never names the block the player looks at here.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CommandSuggestionsRequest {
+    pub transaction_id: Option<VarInt>,
+    pub text: MCString,
+    pub assume_command: Option<MCBoolean>,
+    pub has_position: Option<MCBoolean>
+}
+
+impl CommandSuggestionsRequest {
+    /// The first protocol version (1.9) telling whether to assume the text is a command.
+    const ASSUME_COMMAND_SINCE: i32 = 107;
+    /// The first protocol version (1.13) numbering requests.
+    pub const TRANSACTION_SINCE: i32 = 393;
+
+    /// Creates the request to complete `text` in the format of `version`, numbered
+    /// `transaction_id` if the version numbers requests.
+    pub fn new(version: ProtocolVersion, transaction_id: i32, text: &str) -> Self {
+        let numbered = version.number() >= Self::TRANSACTION_SINCE;
+        CommandSuggestionsRequest {
+            transaction_id: numbered.then(|| VarInt::from(transaction_id)),
+            text: text.into(),
+            assume_command: (!numbered && version.number() >= Self::ASSUME_COMMAND_SINCE).then(|| MCBoolean::from(false)),
+            has_position: (!numbered).then(|| MCBoolean::from(false))
+        }
+    }
+}
+
+impl PacketId for CommandSuggestionsRequest {
+    const ID: i32 = 0x08;
+    const STATE: ConnectionState = ConnectionState::Play;
+}
+
+impl OutboundPacket for CommandSuggestionsRequest {
+    fn encode(&self, w: &mut dyn Write) -> io::Result<()> {
+        if let Some(transaction_id) = &self.transaction_id {
+            transaction_id.encode(w)?;
+        }
+        self.text.encode(w)?;
+        if let Some(assume_command) = &self.assume_command {
+            assume_command.encode(w)?;
+        }
+        if let Some(has_position) = &self.has_position {
+            has_position.encode(w)?;
+        }
+        Ok(())
+    }
+
+    fn len(&self) -> i32 {
+        let mut size = self.text.size();
+
+        if let Some(transaction_id) = &self.transaction_id {
+            size += transaction_id.size();
+        }
+        if let Some(assume_command) = &self.assume_command {
+            size += assume_command.size();
+        }
+        if let Some(has_position) = &self.has_position {
+            size += has_position.size();
+        }
+
+        size
+    }
+}
@@ -0,0 +1,10 @@
+use crate::mc::{mctypes::MCLong, packet::{McEncode, PacketId}};
+
+/// Answers a clientbound Keep Alive of the Configuration state with the same
+/// `keep_alive_id`, as its Play counterpart does.
+#[derive(PacketId, McEncode)]
+#[packet(id = 0x03, state = Configuration)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ConfigKeepAlive {
+    pub keep_alive_id: MCLong
+}
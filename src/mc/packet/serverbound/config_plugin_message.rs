@@ -0,0 +1,36 @@
+use std::io::{self, Write};
+
+use crate::mc::{connection::ConnectionState, mctypes::{MCString, MCType}, packet::{OutboundPacket, PacketId}};
+
+use super::plugin_message::CLIENT_BRAND;
+
+/// Sends custom data on `channel` during configuration, as its Play counterpart does.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ConfigPluginMessage {
+    pub channel: MCString,
+    pub data: Vec<u8>
+}
+
+impl ConfigPluginMessage {
+    /// Creates the message reporting the `CLIENT_BRAND`, which vanilla clients send once
+    /// configuration starts.
+    pub fn brand() -> Self {
+        ConfigPluginMessage { channel: "minecraft:brand".into(), data: MCString::from(CLIENT_BRAND).to_bytes() }
+    }
+}
+
+impl PacketId for ConfigPluginMessage {
+    const ID: i32 = 0x01;
+    const STATE: ConnectionState = ConnectionState::Configuration;
+}
+
+impl OutboundPacket for ConfigPluginMessage {
+    fn encode(&self, w: &mut dyn Write) -> io::Result<()> {
+        self.channel.encode(w)?;
+        w.write_all(&self.data)
+    }
+
+    fn len(&self) -> i32 {
+        self.channel.size() + self.data.len() as i32
+    }
+}
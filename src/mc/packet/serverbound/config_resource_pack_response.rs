@@ -0,0 +1,51 @@
+use std::io::{self, Write};
+
+use uuid::Uuid;
+
+use crate::mc::{connection::ConnectionState, mctypes::{VarInt, MCType, MCUuid}, packet::{OutboundPacket, PacketId}, version::ProtocolVersion};
+
+use super::resource_pack_response::ResourcePackResult;
+
+/// Responds to a resource pack pushed by the server during configuration, as its Play
+/// counterpart does.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ConfigResourcePackResponse {
+    pub uuid: Option<Uuid>,
+    pub result: ResourcePackResult
+}
+
+impl ConfigResourcePackResponse {
+    /// Creates a response for the pack `uuid`, which is only included in the packet
+    /// if `version` is 1.20.3 or newer.
+    pub fn new(version: ProtocolVersion, uuid: Uuid, result: ResourcePackResult) -> Self {
+        ConfigResourcePackResponse {
+            uuid: (version >= ProtocolVersion::V1_20_4).then_some(uuid),
+            result
+        }
+    }
+}
+
+impl PacketId for ConfigResourcePackResponse {
+    const ID: i32 = 0x05;
+    const STATE: ConnectionState = ConnectionState::Configuration;
+}
+
+impl OutboundPacket for ConfigResourcePackResponse {
+    fn encode(&self, w: &mut dyn Write) -> io::Result<()> {
+        if let Some(uuid) = self.uuid {
+            MCUuid::from(uuid).encode(w)?;
+        }
+        VarInt::from(self.result as i32).encode(w)
+    }
+
+    fn len(&self) -> i32 {
+        let mut size: i32 = 0;
+
+        if self.uuid.is_some() {
+            size += 16;
+        }
+        size += VarInt::from(self.result as i32).len();
+
+        size
+    }
+}
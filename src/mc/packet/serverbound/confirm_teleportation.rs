@@ -0,0 +1,10 @@
+use crate::mc::{mctypes::VarInt, packet::{McEncode, PacketId}};
+
+/// Confirms a Synchronize Player Position with its `teleport_id`. The server ignores the
+/// player's movement until the teleport has been confirmed.
+#[derive(PacketId, McEncode)]
+#[packet(id = 0x00, state = Play)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ConfirmTeleportation {
+    pub teleport_id: VarInt
+}
@@ -0,0 +1,34 @@
+use std::io::{self, Write};
+
+use crate::mc::{connection::ConnectionState, mctypes::{MCType, VarInt}, packet::{OutboundPacket, PacketId}};
+
+/// Answers an Encryption Request. Both fields are encrypted with the server's public
+/// key; the connection is encrypted with the shared secret once this packet is sent.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct EncryptionResponse {
+    pub shared_secret: Vec<u8>,
+    pub verify_token: Vec<u8>
+}
+
+impl PacketId for EncryptionResponse {
+    const ID: i32 = 0x01;
+    const STATE: ConnectionState = ConnectionState::Login;
+}
+
+impl OutboundPacket for EncryptionResponse {
+    fn encode(&self, w: &mut dyn Write) -> io::Result<()> {
+        VarInt::from(self.shared_secret.len() as i32).encode(w)?;
+        w.write_all(&self.shared_secret)?;
+        VarInt::from(self.verify_token.len() as i32).encode(w)?;
+        w.write_all(&self.verify_token)
+    }
+
+    fn len(&self) -> i32 {
+        let mut size: i32 = 0;
+
+        size += VarInt::from(self.shared_secret.len() as i32).size() + self.shared_secret.len() as i32;
+        size += VarInt::from(self.verify_token.len() as i32).size() + self.verify_token.len() as i32;
+
+        size
+    }
+}
@@ -1,15 +1,17 @@
-use std::mem::size_of;
+use std::{io::{self, Write}, mem::size_of};
 
-use crate::mc::{packet::OutboundPacket, mctypes::{VarInt, MCType, MCString}};
+use crate::mc::{connection::ConnectionState, packet::{OutboundPacket, PacketId}, mctypes::{VarInt, MCType, MCString}};
 
 #[repr(i32)]
 #[derive(Clone, Copy)]
 #[allow(dead_code)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum NextState {
     STATUS = 1,
     LOGIN = 2
 }
 
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Handshake {
     pub protocol_version: VarInt,
     pub server_addr: MCString,
@@ -17,20 +19,24 @@ pub struct Handshake {
     pub next_state: NextState
 }
 
-impl OutboundPacket for Handshake {
-    fn to_bytes(&self) -> Vec<u8> {
-        let mut bytes = Vec::<u8>::new();
-
-        bytes.append(&mut self.protocol_version.to_bytes());
-        bytes.append(&mut self.server_addr.to_bytes());
-        bytes.append(&mut self.port.to_be_bytes().to_vec());
-        bytes.append(&mut VarInt::from_i32(self.next_state as i32).to_bytes());
+impl PacketId for Handshake {
+    const ID: i32 = 0x00;
+    const STATE: ConnectionState = ConnectionState::Handshaking;
+}
 
-        bytes
+impl OutboundPacket for Handshake {
+    fn encode(&self, w: &mut dyn Write) -> io::Result<()> {
+        self.protocol_version.encode(w)?;
+        self.server_addr.encode(w)?;
+        w.write_all(&self.port.to_be_bytes())?;
+        VarInt::from_i32(self.next_state as i32).encode(w)
     }
 
-    fn packet_id(&self) -> i32 {
-        0x00
+    fn next_state(&self) -> Option<ConnectionState> {
+        match self.next_state {
+            NextState::STATUS => Some(ConnectionState::Status),
+            NextState::LOGIN => Some(ConnectionState::Login)
+        }
     }
 
     fn len(&self) -> i32 {
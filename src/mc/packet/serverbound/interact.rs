@@ -0,0 +1,110 @@
+use std::io::{self, Write};
+
+use crate::mc::{connection::ConnectionState, mctypes::{MCBoolean, MCFloat, MCType, VarInt}, packet::{OutboundPacket, PacketId}, version::ProtocolVersion};
+
+use super::use_item_on::Hand;
+
+/// The ways of interacting with an entity.
+#[repr(i32)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum InteractAction {
+    /// Uses the held item on the entity, or interacts with it, such as to trade with a
+    /// villager.
+    Interact = 0,
+    Attack = 1,
+    /// Interacts with the entity at the point `target` of its bounding box, such as to
+    /// place an item on an armor stand.
+    InteractAt = 2
+}
+
+/// Attacks or interacts with the entity `entity_id`. The `hand` is sent since 1.9 for
+/// interactions, and whether the player is `sneaking` since 1.16.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Interact {
+    pub entity_id: VarInt,
+    pub action: InteractAction,
+    pub target: Option<[MCFloat; 3]>,
+    pub hand: Option<Hand>,
+    pub sneaking: Option<MCBoolean>
+}
+
+impl Interact {
+    /// The first protocol version (1.9) telling the hand of interactions.
+    const HAND_SINCE: i32 = 107;
+    /// The first protocol version (1.16) telling whether the player is sneaking.
+    const SNEAKING_SINCE: i32 = 735;
+
+    fn new(version: ProtocolVersion, entity_id: i32, action: InteractAction, hand: Option<Hand>, sneaking: bool) -> Self {
+        Interact {
+            entity_id: VarInt::from(entity_id),
+            action,
+            target: None,
+            hand: hand.filter(|_| version.number() >= Self::HAND_SINCE),
+            sneaking: (version.number() >= Self::SNEAKING_SINCE).then(|| MCBoolean::from(sneaking))
+        }
+    }
+
+    /// Creates an attack of the entity `entity_id`, in the format of `version`.
+    pub fn attack(version: ProtocolVersion, entity_id: i32, sneaking: bool) -> Self {
+        Interact::new(version, entity_id, InteractAction::Attack, None, sneaking)
+    }
+
+    /// Creates an interaction with the entity `entity_id` using `hand`, in the format of
+    /// `version`.
+    pub fn using(version: ProtocolVersion, entity_id: i32, hand: Hand, sneaking: bool) -> Self {
+        Interact::new(version, entity_id, InteractAction::Interact, Some(hand), sneaking)
+    }
+
+    /// Creates an interaction with the entity `entity_id` at the point `target` of its
+    /// bounding box, relative to its position, using `hand`, in the format of `version`.
+    pub fn interact_at(version: ProtocolVersion, entity_id: i32, target: [f32; 3], hand: Hand, sneaking: bool) -> Self {
+        Interact {
+            target: Some(target.map(MCFloat::from)),
+            ..Interact::new(version, entity_id, InteractAction::InteractAt, Some(hand), sneaking)
+        }
+    }
+}
+
+impl PacketId for Interact {
+    const ID: i32 = 0x0F;
+    const STATE: ConnectionState = ConnectionState::Play;
+}
+
+impl OutboundPacket for Interact {
+    fn encode(&self, w: &mut dyn Write) -> io::Result<()> {
+        self.entity_id.encode(w)?;
+        VarInt::from(self.action as i32).encode(w)?;
+        if let Some(target) = &self.target {
+            for axis in target {
+                axis.encode(w)?;
+            }
+        }
+        if let Some(hand) = self.hand {
+            VarInt::from(hand as i32).encode(w)?;
+        }
+        if let Some(sneaking) = &self.sneaking {
+            sneaking.encode(w)?;
+        }
+
+        Ok(())
+    }
+
+    fn len(&self) -> i32 {
+        let mut size: i32 = 0;
+
+        size += self.entity_id.size();
+        size += VarInt::from(self.action as i32).size();
+        if let Some(target) = &self.target {
+            size += target.iter().map(|axis| axis.size()).sum::<i32>();
+        }
+        if let Some(hand) = self.hand {
+            size += VarInt::from(hand as i32).size();
+        }
+        if let Some(sneaking) = &self.sneaking {
+            size += sneaking.size();
+        }
+
+        size
+    }
+}
@@ -0,0 +1,10 @@
+use crate::mc::{mctypes::MCLong, packet::{McEncode, PacketId}};
+
+/// Answers a clientbound Keep Alive with the same `keep_alive_id`. Servers disconnect
+/// clients which do not answer within 15 seconds.
+#[derive(PacketId, McEncode)]
+#[packet(id = 0x11, state = Play)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct KeepAlive {
+    pub keep_alive_id: MCLong
+}
@@ -0,0 +1,11 @@
+use crate::mc::{mctypes::MCString, packet::{McEncode, PacketId}, registry::LEGACY_ID_BASE};
+
+/// Sends a chat message, or runs a command if it starts with a slash, to servers older than
+/// 1.19. Messages may be at most 256 characters, or 100 before 1.11.
+#[derive(PacketId, McEncode)]
+#[packet(id = LEGACY_ID_BASE, state = Play)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LegacyChatMessage {
+    #[max_len = 256]
+    pub message: MCString
+}
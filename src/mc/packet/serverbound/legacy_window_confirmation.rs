@@ -0,0 +1,12 @@
+use crate::mc::{mctypes::{MCBoolean, MCByte, MCShort}, packet::{McEncode, PacketId}, registry::LEGACY_ID_BASE};
+
+/// Acknowledges to servers older than 1.17 that they rejected the click numbered `action`
+/// in the window `window_id`, by echoing their confirmation as `accepted`.
+#[derive(PacketId, McEncode)]
+#[packet(id = LEGACY_ID_BASE + 1, state = Play)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LegacyWindowConfirmation {
+    pub window_id: MCByte,
+    pub action: MCShort,
+    pub accepted: MCBoolean
+}
@@ -0,0 +1,8 @@
+use crate::mc::packet::{McEncode, PacketId};
+
+/// Acknowledges a Login Success, moving the connection to the Configuration state.
+/// Only sent to servers running 1.20.2 or newer, which enter Play once configured.
+#[derive(PacketId, McEncode)]
+#[packet(id = 0x03, state = Login, next_state = Configuration)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LoginAcknowledged;
@@ -0,0 +1,32 @@
+use std::io::{self, Write};
+
+use crate::mc::{connection::ConnectionState, mctypes::{MCBoolean, MCType, VarInt}, packet::{OutboundPacket, PacketId}};
+
+/// Answers the Login Plugin Request with the same `message_id`. `data` is `None` if
+/// the client does not understand the request, which is the expected answer for
+/// channels the client does not implement.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LoginPluginResponse {
+    pub message_id: VarInt,
+    pub data: Option<Vec<u8>>
+}
+
+impl PacketId for LoginPluginResponse {
+    const ID: i32 = 0x02;
+    const STATE: ConnectionState = ConnectionState::Login;
+}
+
+impl OutboundPacket for LoginPluginResponse {
+    fn encode(&self, w: &mut dyn Write) -> io::Result<()> {
+        self.message_id.encode(w)?;
+        MCBoolean::from(self.data.is_some()).encode(w)?;
+        match &self.data {
+            Some(data) => w.write_all(data),
+            None => Ok(())
+        }
+    }
+
+    fn len(&self) -> i32 {
+        self.message_id.size() + 1 + self.data.as_ref().map_or(0, |data| data.len() as i32)
+    }
+}
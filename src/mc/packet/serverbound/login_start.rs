@@ -1,40 +1,59 @@
-use std::mem::size_of;
+use std::{io::{self, Write}, mem::size_of};
 
 use uuid::Uuid;
 
-use crate::mc::{mctypes::{MCString, MCType}, packet::OutboundPacket};
+use crate::mc::{connection::ConnectionState, mctypes::{MCString, MCType, MCUuid}, packet::{OutboundPacket, PacketId}, version::ProtocolVersion};
 
+/// Starts the login as `username`. Since 1.19.3 the client may send the UUID of
+/// the player, which is prefixed with `has_uuid` until 1.20.2, where it became
+/// mandatory.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct LoginStart {
     pub username: MCString,
     pub has_uuid: bool,
-    pub uuid: Uuid
+    pub uuid: Uuid,
+    /// Whether `has_uuid` is sent ahead of the UUID.
+    pub prefixed: bool
 }
 
-impl OutboundPacket for LoginStart {
-    fn to_bytes(&self) -> Vec<u8> {
-        let mut bytes: Vec<u8> = vec![];
+impl LoginStart {
+    /// Creates a login start as `username` with `uuid`, in the layout of `version`. The
+    /// UUID is only included if `version` is 1.19.3 or newer.
+    pub fn new<U: Into<MCString>>(version: ProtocolVersion, username: U, uuid: Uuid) -> Self {
+        LoginStart {
+            username: username.into(),
+            has_uuid: version >= ProtocolVersion::V1_19_3,
+            uuid,
+            prefixed: (ProtocolVersion::V1_19_3..ProtocolVersion::V1_20_2).contains(&version)
+        }
+    }
+}
+
+impl PacketId for LoginStart {
+    const ID: i32 = 0x00;
+    const STATE: ConnectionState = ConnectionState::Login;
+}
 
-        bytes.extend(self.username.to_bytes());
-        bytes.push(self.has_uuid as u8);
+impl OutboundPacket for LoginStart {
+    fn encode(&self, w: &mut dyn Write) -> io::Result<()> {
+        self.username.encode(w)?;
+        if self.prefixed {
+            w.write_all(&[self.has_uuid as u8])?;
+        }
         if self.has_uuid {
-            let uid = self.uuid.as_u64_pair();
-            bytes.extend_from_slice(&uid.1.to_be_bytes());
-            bytes.extend_from_slice(&uid.0.to_be_bytes());
+            MCUuid::from(self.uuid).encode(w)?;
         }
 
-        bytes
-    }
-
-    fn packet_id(&self) -> i32 {
-        0x00
+        Ok(())
     }
 
     fn len(&self) -> i32 {
         let mut size: i32 = 0;
 
         size += self.username.size();
-        size += size_of::<bool>() as i32;
-        //size += 1; // NO idea why this is needed. Won't work without it.
+        if self.prefixed {
+            size += size_of::<bool>() as i32;
+        }
         if self.has_uuid {
             size += size_of::<Uuid>() as i32;
         }
@@ -1,3 +1,31 @@
 pub mod handshake;
 pub mod status_request;
-pub mod login_start;
\ No newline at end of file
+pub mod login_start;
+pub mod update_sign;
+pub mod resource_pack_response;
+pub mod player_command;
+pub mod ping_request;
+pub mod login_plugin_response;
+pub mod encryption_response;
+pub mod keep_alive;
+pub mod confirm_teleportation;
+pub mod set_player_position;
+pub mod chat_message;
+pub mod login_acknowledged;
+pub mod acknowledge_finish_configuration;
+pub mod config_keep_alive;
+pub mod chat_command;
+pub mod legacy_chat_message;
+pub mod set_player_position_and_rotation;
+pub mod player_action;
+pub mod use_item_on;
+pub mod swing_arm;
+pub mod click_container;
+pub mod close_container;
+pub mod legacy_window_confirmation;
+pub mod interact;
+pub mod client_command;
+pub mod plugin_message;
+pub mod config_plugin_message;
+pub mod config_resource_pack_response;
+pub mod command_suggestions_request;
\ No newline at end of file
@@ -0,0 +1,11 @@
+use crate::mc::{mctypes::MCLong, packet::{McEncode, PacketId}};
+
+/// Pings the server at the end of a status exchange. The server echoes `payload`
+/// back in a Ping Response, and closes the connection. Clients conventionally send
+/// the current time in milliseconds so the round-trip latency can be measured.
+#[derive(PacketId, McEncode)]
+#[packet(id = 0x01, state = Status)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PingRequest {
+    pub payload: MCLong
+}
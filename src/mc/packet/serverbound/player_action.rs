@@ -0,0 +1,117 @@
+use std::io::{self, Write};
+
+use crate::mc::{connection::{BlockSequence, ConnectionState}, mctypes::{MCByte, MCLong, MCPosition, MCType, VarInt}, packet::{OutboundPacket, PacketId}, version::ProtocolVersion};
+
+/// Actions which can be performed with a Player Action.
+#[repr(i32)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum PlayerActionStatus {
+    StartedDigging = 0,
+    CancelledDigging = 1,
+    FinishedDigging = 2,
+    DropItemStack = 3,
+    DropItem = 4,
+    /// Finishes eating, drinking or drawing a bow, among others.
+    FinishUsingItem = 5,
+    SwapItemInHand = 6
+}
+
+/// A face of a block.
+#[repr(i8)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum BlockFace {
+    Bottom = 0,
+    Top = 1,
+    North = 2,
+    South = 3,
+    West = 4,
+    East = 5
+}
+
+impl BlockFace {
+    /// Gets the direction the face looks towards, as a step along each axis.
+    pub fn direction(&self) -> (i32, i32, i32) {
+        match self {
+            BlockFace::Bottom => (0, -1, 0),
+            BlockFace::Top => (0, 1, 0),
+            BlockFace::North => (0, 0, -1),
+            BlockFace::South => (0, 0, 1),
+            BlockFace::West => (-1, 0, 0),
+            BlockFace::East => (1, 0, 0)
+        }
+    }
+
+    /// Gets the face of the block at `position` which faces the point at `x`, `y` and `z`.
+    pub fn towards(position: MCPosition, x: f64, y: f64, z: f64) -> Self {
+        let (delta_x, delta_y, delta_z) = (x - position.x() as f64 - 0.5, y - position.y() as f64 - 0.5, z - position.z() as f64 - 0.5);
+        if delta_y.abs() >= delta_x.abs() && delta_y.abs() >= delta_z.abs() {
+            if delta_y < 0.0 { BlockFace::Bottom } else { BlockFace::Top }
+        } else if delta_x.abs() >= delta_z.abs() {
+            if delta_x < 0.0 { BlockFace::West } else { BlockFace::East }
+        } else if delta_z < 0.0 {
+            BlockFace::North
+        } else {
+            BlockFace::South
+        }
+    }
+}
+
+/// Digs the block at `location`, or drops or swaps the held item. Digging a block
+/// which does not break at once is started, then finished once the time it takes has
+/// passed, and is cancelled if the player stops. Since 1.19, actions carry the
+/// `sequence` number the server acknowledges them with.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PlayerAction {
+    pub status: PlayerActionStatus,
+    /// The position of the block, packed in the layout of the server's version.
+    pub location: MCLong,
+    pub face: BlockFace,
+    pub sequence: Option<VarInt>
+}
+
+impl PlayerAction {
+    /// Creates an action on the block at `position`, in the format of `version`. The
+    /// `sequence` is only included in the packet if `version` is 1.19 or newer.
+    pub fn new(version: ProtocolVersion, status: PlayerActionStatus, position: MCPosition, face: BlockFace, sequence: VarInt) -> Self {
+        PlayerAction {
+            status,
+            location: MCLong::from(position.to_i64_for(version)),
+            face,
+            sequence: (version.number() >= BlockSequence::SEQUENCED_SINCE).then_some(sequence)
+        }
+    }
+}
+
+impl PacketId for PlayerAction {
+    const ID: i32 = 0x1C;
+    const STATE: ConnectionState = ConnectionState::Play;
+}
+
+impl OutboundPacket for PlayerAction {
+    fn encode(&self, w: &mut dyn Write) -> io::Result<()> {
+        // Before 1.9, the status is a byte, which its values encode alike as a VarInt.
+        VarInt::from(self.status as i32).encode(w)?;
+        self.location.encode(w)?;
+        MCByte::from(self.face as i8).encode(w)?;
+        if let Some(sequence) = &self.sequence {
+            sequence.encode(w)?;
+        }
+
+        Ok(())
+    }
+
+    fn len(&self) -> i32 {
+        let mut size: i32 = 0;
+
+        size += VarInt::from(self.status as i32).size();
+        size += self.location.size();
+        size += 1;
+        if let Some(sequence) = &self.sequence {
+            size += sequence.size();
+        }
+
+        size
+    }
+}
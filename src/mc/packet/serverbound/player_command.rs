@@ -0,0 +1,53 @@
+use std::io::{self, Write};
+
+use crate::mc::{connection::ConnectionState, mctypes::{VarInt, MCType}, packet::{OutboundPacket, PacketId}};
+
+/// Actions which can be performed with a Player Command.
+#[repr(i32)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum PlayerCommandAction {
+    StartSneaking = 0,
+    StopSneaking = 1,
+    LeaveBed = 2,
+    StartSprinting = 3,
+    StopSprinting = 4,
+    StartJumpWithHorse = 5,
+    StopJumpWithHorse = 6,
+    OpenVehicleInventory = 7,
+    StartFlyingWithElytra = 8
+}
+
+/// Informs the server of a change in the player's stance or movement mode, such as
+/// sneaking or sprinting. `jump_boost` ranges from 0 to 100 and is only meaningful
+/// for `StartJumpWithHorse`; it should be 0 otherwise.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PlayerCommand {
+    pub entity_id: VarInt,
+    pub action: PlayerCommandAction,
+    pub jump_boost: VarInt
+}
+
+impl PlayerCommand {
+    /// Creates a command performing `action` for the player `entity_id`, with no jump boost.
+    pub fn new(entity_id: i32, action: PlayerCommandAction) -> Self {
+        PlayerCommand { entity_id: entity_id.into(), action, jump_boost: 0.into() }
+    }
+}
+
+impl PacketId for PlayerCommand {
+    const ID: i32 = 0x1D;
+    const STATE: ConnectionState = ConnectionState::Play;
+}
+
+impl OutboundPacket for PlayerCommand {
+    fn encode(&self, w: &mut dyn Write) -> io::Result<()> {
+        self.entity_id.encode(w)?;
+        VarInt::from(self.action as i32).encode(w)?;
+        self.jump_boost.encode(w)
+    }
+
+    fn len(&self) -> i32 {
+        self.entity_id.size() + VarInt::from(self.action as i32).size() + self.jump_boost.size()
+    }
+}
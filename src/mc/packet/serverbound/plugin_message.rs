@@ -0,0 +1,49 @@
+use std::io::{self, Write};
+
+use crate::mc::{connection::ConnectionState, mctypes::{MCString, MCType}, packet::{OutboundPacket, PacketId}, version::ProtocolVersion};
+
+/// The brand the client reports to servers, that of vanilla clients, since servers may
+/// treat other brands with suspicion.
+pub const CLIENT_BRAND: &str = "vanilla";
+/// The first protocol version (1.13) naming channels with identifiers.
+const IDENTIFIER_CHANNELS_SINCE: i32 = 393;
+
+/// Gets the channel brands are sent on in `version`.
+pub fn brand_channel(version: ProtocolVersion) -> &'static str {
+    match version.number() >= IDENTIFIER_CHANNELS_SINCE {
+        true => "minecraft:brand",
+        false => "MC|Brand"
+    }
+}
+
+/// Sends custom data on `channel`, typically to plugins or a proxy. Channels are
+/// identifiers since 1.13, and names such as `MC|Brand` before.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PluginMessage {
+    pub channel: MCString,
+    pub data: Vec<u8>
+}
+
+impl PluginMessage {
+    /// Creates the message reporting the `CLIENT_BRAND` in the format of `version`, which
+    /// vanilla clients send upon joining.
+    pub fn brand(version: ProtocolVersion) -> Self {
+        PluginMessage { channel: brand_channel(version).into(), data: MCString::from(CLIENT_BRAND).to_bytes() }
+    }
+}
+
+impl PacketId for PluginMessage {
+    const ID: i32 = 0x0C;
+    const STATE: ConnectionState = ConnectionState::Play;
+}
+
+impl OutboundPacket for PluginMessage {
+    fn encode(&self, w: &mut dyn Write) -> io::Result<()> {
+        self.channel.encode(w)?;
+        w.write_all(&self.data)
+    }
+
+    fn len(&self) -> i32 {
+        self.channel.size() + self.data.len() as i32
+    }
+}
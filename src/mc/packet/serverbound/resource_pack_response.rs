@@ -0,0 +1,86 @@
+use std::io::{self, Write};
+
+use uuid::Uuid;
+
+use crate::mc::{connection::ConnectionState, mctypes::{VarInt, MCString, MCType, MCUuid}, packet::{OutboundPacket, PacketId}, resource_pack::ResourcePackEvent, version::ProtocolVersion};
+
+/// The outcome of a resource pack push reported back to the server. Servers
+/// before 1.20.3 only understand `SuccessfullyLoaded` through `Accepted`.
+#[repr(i32)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ResourcePackResult {
+    SuccessfullyLoaded = 0,
+    Declined = 1,
+    FailedDownload = 2,
+    Accepted = 3,
+    Downloaded = 4,
+    InvalidUrl = 5,
+    FailedReload = 6,
+    Discarded = 7
+}
+
+/// Responds to a resource pack pushed by the server. Servers which require a
+/// resource pack may kick clients which never respond. Since 1.20.3, servers can
+/// push several packs, and each response carries the `uuid` of the pack it is for.
+/// Before 1.10, responses carry the `hash` of the pack instead.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ResourcePackResponse {
+    pub uuid: Option<Uuid>,
+    pub hash: Option<MCString>,
+    pub result: ResourcePackResult
+}
+
+impl ResourcePackResponse {
+    /// The last protocol version (1.9.4) whose responses carry the hash of the pack.
+    const HASH_UNTIL: i32 = 110;
+
+    /// Creates a response for the pack `uuid`, which is only included in the packet
+    /// if `version` is 1.20.3 or newer.
+    pub fn new(version: ProtocolVersion, uuid: Uuid, result: ResourcePackResult) -> Self {
+        ResourcePackResponse {
+            uuid: (version >= ProtocolVersion::V1_20_4).then_some(uuid),
+            hash: None,
+            result
+        }
+    }
+
+    /// Creates a response for `pack`, carrying its UUID or hash as `version` expects.
+    pub fn to(version: ProtocolVersion, pack: &ResourcePackEvent, result: ResourcePackResult) -> Self {
+        ResourcePackResponse {
+            hash: (version.number() <= Self::HASH_UNTIL).then(|| pack.hash.as_str().into()),
+            ..ResourcePackResponse::new(version, pack.uuid.unwrap_or_default(), result)
+        }
+    }
+}
+
+impl PacketId for ResourcePackResponse {
+    const ID: i32 = 0x24;
+    const STATE: ConnectionState = ConnectionState::Play;
+}
+
+impl OutboundPacket for ResourcePackResponse {
+    fn encode(&self, w: &mut dyn Write) -> io::Result<()> {
+        if let Some(uuid) = self.uuid {
+            MCUuid::from(uuid).encode(w)?;
+        }
+        if let Some(hash) = &self.hash {
+            hash.encode(w)?;
+        }
+        VarInt::from(self.result as i32).encode(w)
+    }
+
+    fn len(&self) -> i32 {
+        let mut size: i32 = 0;
+
+        if self.uuid.is_some() {
+            size += 16;
+        }
+        if let Some(hash) = &self.hash {
+            size += hash.size();
+        }
+        size += VarInt::from(self.result as i32).len();
+
+        size
+    }
+}
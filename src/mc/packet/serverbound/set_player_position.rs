@@ -0,0 +1,13 @@
+use crate::mc::{mctypes::{MCBoolean, MCDouble}, packet::{McEncode, PacketId}};
+
+/// Moves the player to an absolute position, without changing its rotation. `feet_y` is
+/// the height of the bottom of the player's bounding box.
+#[derive(PacketId, McEncode)]
+#[packet(id = 0x13, state = Play)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SetPlayerPosition {
+    pub x: MCDouble,
+    pub feet_y: MCDouble,
+    pub z: MCDouble,
+    pub on_ground: MCBoolean
+}
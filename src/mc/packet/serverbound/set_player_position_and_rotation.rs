@@ -0,0 +1,15 @@
+use crate::mc::{mctypes::{MCBoolean, MCDouble, MCFloat}, packet::{McEncode, PacketId}};
+
+/// Moves the player to an absolute position and rotation, as per `SetPlayerPosition`.
+/// `yaw` and `pitch` are in degrees.
+#[derive(PacketId, McEncode)]
+#[packet(id = 0x14, state = Play)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SetPlayerPositionAndRotation {
+    pub x: MCDouble,
+    pub feet_y: MCDouble,
+    pub z: MCDouble,
+    pub yaw: MCFloat,
+    pub pitch: MCFloat,
+    pub on_ground: MCBoolean
+}
@@ -1,17 +1,21 @@
-use crate::mc::packet::OutboundPacket;
+use std::io::{self, Write};
 
+use crate::mc::{connection::ConnectionState, packet::{OutboundPacket, PacketId}};
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct StatusRequest;
 
+impl PacketId for StatusRequest {
+    const ID: i32 = 0x00;
+    const STATE: ConnectionState = ConnectionState::Status;
+}
+
 impl OutboundPacket for StatusRequest {
-    fn to_bytes(&self) -> Vec<u8> {
-        vec![]
+    fn encode(&self, _w: &mut dyn Write) -> io::Result<()> {
+        Ok(())
     }
 
     fn len(&self) -> i32 {
         0
     }
-
-    fn packet_id(&self) -> i32 {
-        0x00
-    }
 }
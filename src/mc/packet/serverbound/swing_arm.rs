@@ -0,0 +1,19 @@
+use crate::mc::{mctypes::VarInt, packet::{McEncode, PacketId}};
+
+use super::use_item_on::Hand;
+
+/// Swings the arm of `hand`, as when digging, placing a block or attacking. Servers
+/// expect a swing along with these actions. The arm of 1.8 carries no hand, so the
+/// packet is not sent to 1.8 servers.
+#[derive(PacketId, McEncode)]
+#[packet(id = 0x2F, state = Play)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SwingArm {
+    pub hand: VarInt
+}
+
+impl SwingArm {
+    pub fn new(hand: Hand) -> Self {
+        SwingArm { hand: VarInt::from(hand as i32) }
+    }
+}
@@ -0,0 +1,44 @@
+use std::io::{self, Write};
+
+use crate::mc::{connection::ConnectionState, mctypes::{MCPosition, MCBoolean, MCString, MCType}, packet::{OutboundPacket, PacketId}};
+
+/// Sets the text of a sign the client is editing. Each line may be at most 384
+/// characters. Since 1.20, `is_front_text` selects the side of the sign being
+/// written, and must be `None` for older servers.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct UpdateSign {
+    pub position: MCPosition,
+    pub is_front_text: Option<MCBoolean>,
+    pub lines: [MCString; 4]
+}
+
+impl PacketId for UpdateSign {
+    const ID: i32 = 0x2E;
+    const STATE: ConnectionState = ConnectionState::Play;
+}
+
+impl OutboundPacket for UpdateSign {
+    fn encode(&self, w: &mut dyn Write) -> io::Result<()> {
+        self.position.encode(w)?;
+        if let Some(is_front_text) = &self.is_front_text {
+            is_front_text.encode(w)?;
+        }
+        for line in &self.lines {
+            line.encode(w)?;
+        }
+
+        Ok(())
+    }
+
+    fn len(&self) -> i32 {
+        let mut size: i32 = 0;
+
+        size += self.position.size();
+        if let Some(is_front_text) = &self.is_front_text {
+            size += is_front_text.size();
+        }
+        size += self.lines.iter().map(|line| line.size()).sum::<i32>();
+
+        size
+    }
+}
@@ -0,0 +1,86 @@
+use std::io::{self, Write};
+
+use crate::mc::{connection::{BlockSequence, ConnectionState}, mctypes::{MCBoolean, MCFloat, MCPosition, MCType, VarInt}, packet::{OutboundPacket, PacketId}, version::ProtocolVersion};
+
+use super::player_action::BlockFace;
+
+/// A hand of the player.
+#[repr(i32)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Hand {
+    MainHand = 0,
+    OffHand = 1
+}
+
+/// Uses the item held in `hand` on the `face` of the block at `location`, such as to
+/// place a block against it, or interacts with the block, such as to open a door. The
+/// `cursor` is the point of the face which was clicked, from 0 to 1 along each axis of
+/// the block. Since 1.19, the use carries the `sequence` number the server acknowledges
+/// it with.
+/// <br> <br>
+/// This is the layout of 1.14 and newer, so the packet is not sent to older servers.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct UseItemOn {
+    pub hand: Hand,
+    pub location: MCPosition,
+    pub face: BlockFace,
+    pub cursor: [MCFloat; 3],
+    /// Whether the player's head is inside the block.
+    pub inside_block: MCBoolean,
+    pub sequence: Option<VarInt>
+}
+
+impl UseItemOn {
+    /// Creates a use of the item in `hand` on the center of the `face` of the block at
+    /// `position`, in the format of `version`. The `sequence` is only included in the
+    /// packet if `version` is 1.19 or newer.
+    pub fn new(version: ProtocolVersion, hand: Hand, position: MCPosition, face: BlockFace, sequence: VarInt) -> Self {
+        let (x, y, z) = face.direction();
+        UseItemOn {
+            hand,
+            location: position,
+            face,
+            cursor: [x, y, z].map(|axis| MCFloat::from(0.5 + axis as f32 * 0.5)),
+            inside_block: MCBoolean::from(false),
+            sequence: (version.number() >= BlockSequence::SEQUENCED_SINCE).then_some(sequence)
+        }
+    }
+}
+
+impl PacketId for UseItemOn {
+    const ID: i32 = 0x31;
+    const STATE: ConnectionState = ConnectionState::Play;
+}
+
+impl OutboundPacket for UseItemOn {
+    fn encode(&self, w: &mut dyn Write) -> io::Result<()> {
+        VarInt::from(self.hand as i32).encode(w)?;
+        self.location.encode(w)?;
+        VarInt::from(self.face as i32).encode(w)?;
+        for axis in &self.cursor {
+            axis.encode(w)?;
+        }
+        self.inside_block.encode(w)?;
+        if let Some(sequence) = &self.sequence {
+            sequence.encode(w)?;
+        }
+
+        Ok(())
+    }
+
+    fn len(&self) -> i32 {
+        let mut size: i32 = 0;
+
+        size += VarInt::from(self.hand as i32).size();
+        size += self.location.size();
+        size += VarInt::from(self.face as i32).size();
+        size += self.cursor.iter().map(|axis| axis.size()).sum::<i32>();
+        size += self.inside_block.size();
+        if let Some(sequence) = &self.sequence {
+            size += sequence.size();
+        }
+
+        size
+    }
+}
@@ -0,0 +1,61 @@
+use std::io;
+
+use crate::mc::packet::{InboundPacket, MCPacket, PacketId};
+
+use self::clientbound::*;
+
+pub mod serverbound {
+    pub use crate::mc::packet::serverbound::{
+        acknowledge_finish_configuration::AcknowledgeFinishConfiguration,
+        config_keep_alive::ConfigKeepAlive,
+        config_plugin_message::ConfigPluginMessage,
+        config_resource_pack_response::ConfigResourcePackResponse
+    };
+}
+
+pub mod clientbound {
+    pub use crate::mc::packet::clientbound::{
+        finish_configuration::FinishConfiguration,
+        config_keep_alive::ConfigKeepAlive,
+        registry_data::RegistryData,
+        feature_flags::FeatureFlags,
+        update_tags::{UpdateTags, RegistryTags, Tag},
+        config_plugin_message::ConfigPluginMessage,
+        config_resource_pack::ConfigResourcePack
+    };
+}
+
+/// The packets a server sends in the Configuration state, which servers running 1.20.2
+/// or newer enter after login. Disconnect packets are surfaced as errors by
+/// `MinecraftStream::read`, and are not decoded here.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Clientbound {
+    FinishConfiguration(FinishConfiguration),
+    ConfigKeepAlive(ConfigKeepAlive),
+    RegistryData(RegistryData),
+    FeatureFlags(FeatureFlags),
+    UpdateTags(UpdateTags),
+    ConfigPluginMessage(ConfigPluginMessage),
+    ConfigResourcePack(ConfigResourcePack),
+    /// A packet which has no typed definition.
+    Unknown(MCPacket)
+}
+
+impl Clientbound {
+    /// Decodes `packet` into its typed packet, or `Unknown` if it has none.
+    /// # Errors
+    /// This function will return an `InvalidData` error if the packet has the ID of a
+    /// typed packet but cannot be parsed as one.
+    pub fn decode(packet: MCPacket) -> Result<Self, io::Error> {
+        Ok(match packet.header.id.value() {
+            FinishConfiguration::ID => Clientbound::FinishConfiguration(FinishConfiguration::from_data(&packet)?),
+            ConfigKeepAlive::ID => Clientbound::ConfigKeepAlive(ConfigKeepAlive::from_data(&packet)?),
+            RegistryData::ID => Clientbound::RegistryData(RegistryData::from_data(&packet)?),
+            FeatureFlags::ID => Clientbound::FeatureFlags(FeatureFlags::from_data(&packet)?),
+            UpdateTags::ID => Clientbound::UpdateTags(UpdateTags::from_data(&packet)?),
+            ConfigPluginMessage::ID => Clientbound::ConfigPluginMessage(ConfigPluginMessage::from_data(&packet)?),
+            ConfigResourcePack::ID => Clientbound::ConfigResourcePack(ConfigResourcePack::from_data(&packet)?),
+            _ => Clientbound::Unknown(packet)
+        })
+    }
+}
@@ -0,0 +1,3 @@
+pub mod serverbound {
+    pub use crate::mc::packet::serverbound::handshake::{Handshake, NextState};
+}
@@ -0,0 +1,51 @@
+use std::io;
+
+use crate::mc::packet::{InboundPacket, MCPacket, PacketId};
+
+use self::clientbound::*;
+
+pub mod serverbound {
+    pub use crate::mc::packet::serverbound::{
+        login_start::LoginStart,
+        encryption_response::EncryptionResponse,
+        login_plugin_response::LoginPluginResponse,
+        login_acknowledged::LoginAcknowledged
+    };
+}
+
+pub mod clientbound {
+    pub use crate::mc::packet::clientbound::{
+        encryption_request::EncryptionRequest,
+        login_success::{LoginSuccess, ProfileProperty},
+        set_compression::SetCompression,
+        login_plugin_request::LoginPluginRequest
+    };
+}
+
+/// The packets a server sends in the Login state. Disconnect packets are surfaced as
+/// errors by `MinecraftStream::read`, and are not decoded here.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Clientbound {
+    EncryptionRequest(EncryptionRequest),
+    LoginSuccess(LoginSuccess),
+    SetCompression(SetCompression),
+    LoginPluginRequest(LoginPluginRequest),
+    /// A packet which has no typed definition.
+    Unknown(MCPacket)
+}
+
+impl Clientbound {
+    /// Decodes `packet` into its typed packet, or `Unknown` if it has none.
+    /// # Errors
+    /// This function will return an `InvalidData` error if the packet has the ID of a
+    /// typed packet but cannot be parsed as one.
+    pub fn decode(packet: MCPacket) -> Result<Self, io::Error> {
+        Ok(match packet.header.id.value() {
+            EncryptionRequest::ID => Clientbound::EncryptionRequest(EncryptionRequest::from_data(&packet)?),
+            LoginSuccess::ID => Clientbound::LoginSuccess(LoginSuccess::from_data(&packet)?),
+            SetCompression::ID => Clientbound::SetCompression(SetCompression::from_data(&packet)?),
+            LoginPluginRequest::ID => Clientbound::LoginPluginRequest(LoginPluginRequest::from_data(&packet)?),
+            _ => Clientbound::Unknown(packet)
+        })
+    }
+}
@@ -0,0 +1,17 @@
+//! Typed packets organized by the protocol state they belong to. Each state module
+//! re-exports the packet structures of the state, and defines a `Clientbound` enum which
+//! decodes a received `MCPacket` into its typed packet, to be matched on:
+//! ```
+//! use mcclient::mc::{packet::MCPacket, packets::status::Clientbound};
+//!
+//! let packet = MCPacket::new(0x01, 42_i64.to_be_bytes().to_vec());
+//! match Clientbound::decode(packet).unwrap() {
+//!     Clientbound::PingResponse(pong) => assert_eq!(pong.payload.value(), 42),
+//!     _ => panic!("Expected a Ping Response")
+//! }
+//! ```
+pub mod handshake;
+pub mod status;
+pub mod login;
+pub mod configuration;
+pub mod play;
@@ -0,0 +1,196 @@
+use std::io;
+
+use crate::mc::packet::{InboundPacket, MCPacket, PacketId};
+
+use self::clientbound::*;
+
+pub mod serverbound {
+    pub use crate::mc::packet::serverbound::{
+        confirm_teleportation::ConfirmTeleportation,
+        chat_message::ChatMessage,
+        chat_command::{ChatCommand, ArgumentSignature},
+        legacy_chat_message::LegacyChatMessage,
+        keep_alive::KeepAlive,
+        set_player_position::SetPlayerPosition,
+        set_player_position_and_rotation::SetPlayerPositionAndRotation,
+        player_command::{PlayerCommand, PlayerCommandAction},
+        resource_pack_response::{ResourcePackResponse, ResourcePackResult},
+        update_sign::UpdateSign,
+        player_action::{PlayerAction, PlayerActionStatus, BlockFace},
+        use_item_on::{UseItemOn, Hand},
+        swing_arm::SwingArm,
+        click_container::{ClickContainer, ClickMode},
+        close_container::CloseContainer,
+        legacy_window_confirmation::LegacyWindowConfirmation,
+        interact::{Interact, InteractAction},
+        client_command::{ClientCommand, ClientCommandAction},
+        plugin_message::PluginMessage,
+        command_suggestions_request::CommandSuggestionsRequest
+    };
+}
+
+pub mod clientbound {
+    pub use crate::mc::packet::clientbound::{
+        acknowledge_block_change::AcknowledgeBlockChange,
+        keep_alive::KeepAlive,
+        login_play::LoginPlay,
+        open_sign_editor::OpenSignEditor,
+        synchronize_player_position::SynchronizePlayerPosition,
+        system_chat_message::SystemChatMessage,
+        set_health::SetHealth,
+        set_experience::SetExperience,
+        game_event::GameEvent,
+        player_info_update::{PlayerInfoUpdate, PlayerInfoEntry},
+        player_info_remove::PlayerInfoRemove,
+        player_chat_message::PlayerChatMessage,
+        disguised_chat_message::DisguisedChatMessage,
+        legacy_chat_message::LegacyChatMessage,
+        set_entity_metadata::SetEntityMetadata,
+        chunk_data::ChunkData,
+        block_update::BlockUpdate,
+        update_section_blocks::UpdateSectionBlocks,
+        unload_chunk::UnloadChunk,
+        spawn_entity::SpawnEntity,
+        spawn_player::SpawnPlayer,
+        update_entity_position::UpdateEntityPosition,
+        update_entity_position_and_rotation::UpdateEntityPositionAndRotation,
+        update_entity_rotation::UpdateEntityRotation,
+        teleport_entity::TeleportEntity,
+        set_entity_velocity::SetEntityVelocity,
+        remove_entities::RemoveEntities,
+        open_screen::OpenScreen,
+        close_container::CloseContainer,
+        set_container_content::{SetContainerContent, ContainerContent},
+        set_container_slot::{SetContainerSlot, SlotChange},
+        legacy_window_confirmation::LegacyWindowConfirmation,
+        combat_death::{CombatDeath, Death},
+        plugin_message::PluginMessage,
+        resource_pack::ResourcePack,
+        command_suggestions::{CommandSuggestions, Suggestions, Suggestion},
+        boss_bar::{BossBar, BossBarAction},
+        display_objective::DisplayObjective,
+        update_objectives::{UpdateObjectives, ObjectiveChange, ObjectiveKind},
+        update_score::{UpdateScore, ScoreChange},
+        reset_score::ResetScore,
+        set_title_text::SetTitleText,
+        set_subtitle_text::SetSubtitleText,
+        set_action_bar_text::SetActionBarText,
+        set_title_animation_times::SetTitleAnimationTimes,
+        clear_titles::ClearTitles,
+        legacy_title::{LegacyTitle, TitleAction}
+    };
+}
+
+/// The packets a server sends in the Play state. Disconnect packets are surfaced as
+/// errors by `MinecraftStream::read`, and are not decoded here.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Clientbound {
+    AcknowledgeBlockChange(AcknowledgeBlockChange),
+    KeepAlive(KeepAlive),
+    LoginPlay(LoginPlay),
+    OpenSignEditor(OpenSignEditor),
+    SynchronizePlayerPosition(SynchronizePlayerPosition),
+    SystemChatMessage(SystemChatMessage),
+    SetHealth(SetHealth),
+    SetExperience(SetExperience),
+    GameEvent(GameEvent),
+    PlayerInfoUpdate(PlayerInfoUpdate),
+    PlayerInfoRemove(PlayerInfoRemove),
+    PlayerChatMessage(PlayerChatMessage),
+    DisguisedChatMessage(DisguisedChatMessage),
+    LegacyChatMessage(LegacyChatMessage),
+    SetEntityMetadata(SetEntityMetadata),
+    ChunkData(ChunkData),
+    BlockUpdate(BlockUpdate),
+    UpdateSectionBlocks(UpdateSectionBlocks),
+    UnloadChunk(UnloadChunk),
+    SpawnEntity(SpawnEntity),
+    SpawnPlayer(SpawnPlayer),
+    UpdateEntityPosition(UpdateEntityPosition),
+    UpdateEntityPositionAndRotation(UpdateEntityPositionAndRotation),
+    UpdateEntityRotation(UpdateEntityRotation),
+    TeleportEntity(TeleportEntity),
+    SetEntityVelocity(SetEntityVelocity),
+    RemoveEntities(RemoveEntities),
+    OpenScreen(OpenScreen),
+    CloseContainer(CloseContainer),
+    SetContainerContent(SetContainerContent),
+    SetContainerSlot(SetContainerSlot),
+    LegacyWindowConfirmation(LegacyWindowConfirmation),
+    CombatDeath(CombatDeath),
+    PluginMessage(PluginMessage),
+    ResourcePack(ResourcePack),
+    CommandSuggestions(CommandSuggestions),
+    BossBar(BossBar),
+    DisplayObjective(DisplayObjective),
+    UpdateObjectives(UpdateObjectives),
+    UpdateScore(UpdateScore),
+    ResetScore(ResetScore),
+    SetTitleText(SetTitleText),
+    SetSubtitleText(SetSubtitleText),
+    SetActionBarText(SetActionBarText),
+    SetTitleAnimationTimes(SetTitleAnimationTimes),
+    ClearTitles(ClearTitles),
+    LegacyTitle(LegacyTitle),
+    /// A packet which has no typed definition.
+    Unknown(MCPacket)
+}
+
+impl Clientbound {
+    /// Decodes `packet` into its typed packet, or `Unknown` if it has none.
+    /// # Errors
+    /// This function will return an `InvalidData` error if the packet has the ID of a
+    /// typed packet but cannot be parsed as one.
+    pub fn decode(packet: MCPacket) -> Result<Self, io::Error> {
+        Ok(match packet.header.id.value() {
+            AcknowledgeBlockChange::ID => Clientbound::AcknowledgeBlockChange(AcknowledgeBlockChange::from_data(&packet)?),
+            KeepAlive::ID => Clientbound::KeepAlive(KeepAlive::from_data(&packet)?),
+            LoginPlay::ID => Clientbound::LoginPlay(LoginPlay::from_data(&packet)?),
+            OpenSignEditor::ID => Clientbound::OpenSignEditor(OpenSignEditor::from_data(&packet)?),
+            SynchronizePlayerPosition::ID => Clientbound::SynchronizePlayerPosition(SynchronizePlayerPosition::from_data(&packet)?),
+            SystemChatMessage::ID => Clientbound::SystemChatMessage(SystemChatMessage::from_data(&packet)?),
+            SetHealth::ID => Clientbound::SetHealth(SetHealth::from_data(&packet)?),
+            SetExperience::ID => Clientbound::SetExperience(SetExperience::from_data(&packet)?),
+            GameEvent::ID => Clientbound::GameEvent(GameEvent::from_data(&packet)?),
+            PlayerInfoUpdate::ID => Clientbound::PlayerInfoUpdate(PlayerInfoUpdate::from_data(&packet)?),
+            PlayerInfoRemove::ID => Clientbound::PlayerInfoRemove(PlayerInfoRemove::from_data(&packet)?),
+            PlayerChatMessage::ID => Clientbound::PlayerChatMessage(PlayerChatMessage::from_data(&packet)?),
+            DisguisedChatMessage::ID => Clientbound::DisguisedChatMessage(DisguisedChatMessage::from_data(&packet)?),
+            LegacyChatMessage::ID => Clientbound::LegacyChatMessage(LegacyChatMessage::from_data(&packet)?),
+            SetEntityMetadata::ID => Clientbound::SetEntityMetadata(SetEntityMetadata::from_data(&packet)?),
+            ChunkData::ID => Clientbound::ChunkData(ChunkData::from_data(&packet)?),
+            BlockUpdate::ID => Clientbound::BlockUpdate(BlockUpdate::from_data(&packet)?),
+            UpdateSectionBlocks::ID => Clientbound::UpdateSectionBlocks(UpdateSectionBlocks::from_data(&packet)?),
+            UnloadChunk::ID => Clientbound::UnloadChunk(UnloadChunk::from_data(&packet)?),
+            SpawnEntity::ID => Clientbound::SpawnEntity(SpawnEntity::from_data(&packet)?),
+            SpawnPlayer::ID => Clientbound::SpawnPlayer(SpawnPlayer::from_data(&packet)?),
+            UpdateEntityPosition::ID => Clientbound::UpdateEntityPosition(UpdateEntityPosition::from_data(&packet)?),
+            UpdateEntityPositionAndRotation::ID => Clientbound::UpdateEntityPositionAndRotation(UpdateEntityPositionAndRotation::from_data(&packet)?),
+            UpdateEntityRotation::ID => Clientbound::UpdateEntityRotation(UpdateEntityRotation::from_data(&packet)?),
+            TeleportEntity::ID => Clientbound::TeleportEntity(TeleportEntity::from_data(&packet)?),
+            SetEntityVelocity::ID => Clientbound::SetEntityVelocity(SetEntityVelocity::from_data(&packet)?),
+            RemoveEntities::ID => Clientbound::RemoveEntities(RemoveEntities::from_data(&packet)?),
+            OpenScreen::ID => Clientbound::OpenScreen(OpenScreen::from_data(&packet)?),
+            CloseContainer::ID => Clientbound::CloseContainer(CloseContainer::from_data(&packet)?),
+            SetContainerContent::ID => Clientbound::SetContainerContent(SetContainerContent::from_data(&packet)?),
+            SetContainerSlot::ID => Clientbound::SetContainerSlot(SetContainerSlot::from_data(&packet)?),
+            LegacyWindowConfirmation::ID => Clientbound::LegacyWindowConfirmation(LegacyWindowConfirmation::from_data(&packet)?),
+            CombatDeath::ID => Clientbound::CombatDeath(CombatDeath::from_data(&packet)?),
+            PluginMessage::ID => Clientbound::PluginMessage(PluginMessage::from_data(&packet)?),
+            ResourcePack::ID => Clientbound::ResourcePack(ResourcePack::from_data(&packet)?),
+            CommandSuggestions::ID => Clientbound::CommandSuggestions(CommandSuggestions::from_data(&packet)?),
+            BossBar::ID => Clientbound::BossBar(BossBar::from_data(&packet)?),
+            DisplayObjective::ID => Clientbound::DisplayObjective(DisplayObjective::from_data(&packet)?),
+            UpdateObjectives::ID => Clientbound::UpdateObjectives(UpdateObjectives::from_data(&packet)?),
+            UpdateScore::ID => Clientbound::UpdateScore(UpdateScore::from_data(&packet)?),
+            ResetScore::ID => Clientbound::ResetScore(ResetScore::from_data(&packet)?),
+            SetTitleText::ID => Clientbound::SetTitleText(SetTitleText::from_data(&packet)?),
+            SetSubtitleText::ID => Clientbound::SetSubtitleText(SetSubtitleText::from_data(&packet)?),
+            SetActionBarText::ID => Clientbound::SetActionBarText(SetActionBarText::from_data(&packet)?),
+            SetTitleAnimationTimes::ID => Clientbound::SetTitleAnimationTimes(SetTitleAnimationTimes::from_data(&packet)?),
+            ClearTitles::ID => Clientbound::ClearTitles(ClearTitles::from_data(&packet)?),
+            LegacyTitle::ID => Clientbound::LegacyTitle(LegacyTitle::from_data(&packet)?),
+            _ => Clientbound::Unknown(packet)
+        })
+    }
+}
@@ -0,0 +1,36 @@
+use std::io;
+
+use crate::mc::packet::{InboundPacket, MCPacket, PacketId};
+
+use self::clientbound::*;
+
+pub mod serverbound {
+    pub use crate::mc::packet::serverbound::{status_request::StatusRequest, ping_request::PingRequest};
+}
+
+pub mod clientbound {
+    pub use crate::mc::packet::clientbound::{status_response::StatusResponse, ping_response::PingResponse};
+}
+
+/// The packets a server sends in the Status state.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Clientbound {
+    StatusResponse(StatusResponse),
+    PingResponse(PingResponse),
+    /// A packet which has no typed definition.
+    Unknown(MCPacket)
+}
+
+impl Clientbound {
+    /// Decodes `packet` into its typed packet, or `Unknown` if it has none.
+    /// # Errors
+    /// This function will return an `InvalidData` error if the packet has the ID of a
+    /// typed packet but cannot be parsed as one.
+    pub fn decode(packet: MCPacket) -> Result<Self, io::Error> {
+        Ok(match packet.header.id.value() {
+            StatusResponse::ID => Clientbound::StatusResponse(StatusResponse::from_data(&packet)?),
+            PingResponse::ID => Clientbound::PingResponse(PingResponse::from_data(&packet)?),
+            _ => Clientbound::Unknown(packet)
+        })
+    }
+}
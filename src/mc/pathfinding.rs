@@ -0,0 +1,256 @@
+//! Finding and following paths through the world, for the player to walk along.
+use std::{cmp::Ordering, collections::{BinaryHeap, HashMap, VecDeque}};
+
+use super::{local_player::LocalPlayer, mctypes::MCPosition, packets::play::Clientbound, physics::{self, Physics}, world::World};
+
+/// The largest number of positions a search visits before giving up, which bounds the
+/// time spent looking for a path to an unreachable goal.
+pub const MAX_VISITED: usize = 20_000;
+/// The furthest the player drops down along a path, since longer falls hurt it.
+const MAX_DROP: i32 = 3;
+/// The extra cost of jumping up a block over walking.
+const JUMP_COST: f64 = 1.0;
+/// The extra cost of each block dropped, over walking.
+const DROP_COST: f64 = 0.5;
+/// The horizontal distance from the center of a position of the path under which the
+/// player has reached it.
+const REACH_DISTANCE: f64 = 0.3;
+/// The number of ticks without reaching the next position of the path after which the
+/// player is stuck, and the path is planned again.
+const STUCK_TICKS: u32 = 40;
+
+/// The eight horizontal directions the player may walk in, the first four being the
+/// cardinal ones.
+const DIRECTIONS: [(i32, i32); 8] = [(1, 0), (-1, 0), (0, 1), (0, -1), (1, 1), (1, -1), (-1, 1), (-1, -1)];
+
+fn offset(position: MCPosition, x: i32, y: i32, z: i32) -> MCPosition {
+    MCPosition::new(position.x() + x, position.y() + y, position.z() + z)
+}
+
+/// Whether the player fits with its feet in the block at `position`.
+fn is_clear(world: &World, position: MCPosition) -> bool {
+    !physics::is_solid(world, position) && !physics::is_solid(world, offset(position, 0, 1, 0))
+}
+
+/// Whether the player can stand with its feet in the block at `position`, on top of the
+/// block below it. Stairs and slabs are stood on like full blocks.
+pub fn is_standable(world: &World, position: MCPosition) -> bool {
+    is_clear(world, position) && physics::is_solid(world, offset(position, 0, -1, 0))
+}
+
+/// The positions the player can move to from `position` in a step, along with the
+/// cost of each step.
+fn neighbors(world: &World, position: MCPosition) -> Vec<(MCPosition, f64)> {
+    let mut neighbors = Vec::new();
+    for (index, (x, z)) in DIRECTIONS.into_iter().enumerate() {
+        let next = offset(position, x, 0, z);
+        if index >= 4 {
+            // The player only walks diagonally on level ground, without cutting corners.
+            if is_standable(world, next) && is_clear(world, offset(position, x, 0, 0)) && is_clear(world, offset(position, 0, 0, z)) {
+                neighbors.push((next, std::f64::consts::SQRT_2));
+            }
+            continue;
+        }
+
+        if is_standable(world, next) {
+            neighbors.push((next, 1.0));
+        } else if !is_clear(world, next) {
+            let above = offset(next, 0, 1, 0);
+            if is_standable(world, above) && !physics::is_solid(world, offset(position, 0, 2, 0)) {
+                neighbors.push((above, 1.0 + JUMP_COST));
+            }
+        } else {
+            for drop in 1..=MAX_DROP {
+                let below = offset(next, 0, -drop, 0);
+                if physics::is_solid(world, below) {
+                    break;
+                }
+                if is_standable(world, below) {
+                    neighbors.push((below, 1.0 + drop as f64 * DROP_COST));
+                    break;
+                }
+            }
+        }
+    }
+    neighbors
+}
+
+fn distance(a: MCPosition, b: MCPosition) -> f64 {
+    let (x, y, z) = ((a.x() - b.x()) as f64, (a.y() - b.y()) as f64, (a.z() - b.z()) as f64);
+    (x * x + y * y + z * z).sqrt()
+}
+
+/// A position to visit, ordered so that the `BinaryHeap` pops the cheapest first.
+struct Candidate {
+    estimate: f64,
+    position: MCPosition
+}
+
+impl PartialEq for Candidate {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Candidate {}
+
+impl PartialOrd for Candidate {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Candidate {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.estimate.total_cmp(&self.estimate)
+    }
+}
+
+/// Finds the shortest path for the player to walk from `start` to `goal`, the blocks its
+/// feet are in, with A*. The player walks over the blocks of `world`, jumps up single
+/// blocks, and drops down up to 3 blocks. The path holds each position the player
+/// passes through after `start`, up to `goal`.
+/// <br> <br>
+/// Returns `None` if the player cannot stand at `goal`, or no path was found within
+/// `MAX_VISITED` positions, such as when it leads through columns which are not loaded.
+pub fn find_path(world: &World, start: MCPosition, goal: MCPosition) -> Option<Vec<MCPosition>> {
+    if !is_standable(world, goal) {
+        return None;
+    }
+
+    let mut costs: HashMap<MCPosition, (f64, Option<MCPosition>)> = HashMap::from([(start, (0.0, None))]);
+    let mut open = BinaryHeap::from([Candidate { estimate: distance(start, goal), position: start }]);
+    let mut visited = 0;
+    while let Some(Candidate { estimate, position }) = open.pop() {
+        if position == goal {
+            let mut path = vec![goal];
+            while let Some((_, Some(previous))) = costs.get(path.last()?) {
+                path.push(*previous);
+            }
+            path.pop();
+            path.reverse();
+            return Some(path);
+        }
+
+        let cost = costs[&position].0;
+        if estimate > cost + distance(position, goal) {
+            // A cheaper way to this position was found since it was queued.
+            continue;
+        }
+        visited += 1;
+        if visited > MAX_VISITED {
+            return None;
+        }
+
+        for (next, step) in neighbors(world, position) {
+            let next_cost = cost + step;
+            if costs.get(&next).is_none_or(|(known, _)| next_cost < *known) {
+                costs.insert(next, (next_cost, Some(position)));
+                open.push(Candidate { estimate: next_cost + distance(next, goal), position: next });
+            }
+        }
+    }
+    None
+}
+
+/// Leads the player to a goal along a path found with `find_path`, by walking from one
+/// position of the path to the next with its `Physics`.
+/// <br> <br>
+/// The path is planned again when the world changes such that the player can no longer
+/// walk it, or when the player does not reach the next position of the path for 2
+/// seconds, such as when something blocks it. While no path is found, the navigator
+/// waits for the world to change, such as when the columns on the way are loaded.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Navigator {
+    goal: Option<MCPosition>,
+    path: VecDeque<MCPosition>,
+    plan: bool,
+    world_changed: bool,
+    stuck_ticks: u32
+}
+
+impl Navigator {
+    /// Creates a navigator without a goal.
+    pub fn new() -> Self {
+        Navigator::default()
+    }
+
+    /// The position the player is navigating to, if any.
+    pub fn goal(&self) -> Option<MCPosition> {
+        self.goal
+    }
+
+    /// Whether the player is navigating to a goal.
+    pub fn is_navigating(&self) -> bool {
+        self.goal.is_some()
+    }
+
+    /// The positions of the path left to walk, up to the goal. This is empty while no
+    /// path is found.
+    pub fn path(&self) -> impl Iterator<Item = &MCPosition> {
+        self.path.iter()
+    }
+
+    /// Starts navigating to `goal`, the block the player's feet should end in. The path is
+    /// planned on the next tick.
+    pub fn navigate_to(&mut self, goal: MCPosition) {
+        *self = Navigator { goal: Some(goal), plan: true, ..Navigator::default() };
+    }
+
+    /// Stops navigating, leaving the player where it is.
+    pub fn stop(&mut self, physics: &mut Physics) {
+        *self = Navigator::default();
+        physics.stop();
+    }
+
+    /// Takes note of the Play packet `packet`, if it changes the world or moves the player,
+    /// in which case the path is checked or planned again on the next tick.
+    pub fn handle(&mut self, packet: &Clientbound) {
+        match packet {
+            Clientbound::ChunkData(_) | Clientbound::BlockUpdate(_) | Clientbound::UpdateSectionBlocks(_) | Clientbound::UnloadChunk(_) => {
+                self.world_changed = true;
+            },
+            Clientbound::SynchronizePlayerPosition(_) => {
+                self.plan = true;
+            },
+            _ => ()
+        }
+    }
+
+    /// Steers `physics` towards the next position of the path of `player` in `world`,
+    /// planning the path first if needed. This should be called before each tick of the
+    /// physics.
+    pub fn tick(&mut self, physics: &mut Physics, player: &LocalPlayer, world: &World) {
+        let Some(goal) = self.goal else {
+            return;
+        };
+        let lost = self.path.is_empty() || !self.path.iter().all(|position| is_standable(world, *position));
+        if std::mem::take(&mut self.world_changed) && lost || self.stuck_ticks >= STUCK_TICKS {
+            self.plan = true;
+        }
+        let feet = player.location.block();
+        if std::mem::take(&mut self.plan) {
+            self.stuck_ticks = 0;
+            self.path = find_path(world, feet, goal).unwrap_or_default().into();
+        }
+
+        let location = &player.location;
+        while let Some(next) = self.path.front() {
+            let (x, z) = (next.x() as f64 + 0.5 - location.x, next.z() as f64 + 0.5 - location.z);
+            if feet != *next || x.hypot(z) > REACH_DISTANCE {
+                break;
+            }
+            self.path.pop_front();
+            self.stuck_ticks = 0;
+        }
+
+        match self.path.front() {
+            Some(next) => {
+                physics.walk_to(next.x() as f64 + 0.5, next.y() as f64, next.z() as f64 + 0.5);
+                self.stuck_ticks += 1;
+            },
+            None if feet == goal => self.stop(physics),
+            None => ()
+        }
+    }
+}
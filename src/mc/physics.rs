@@ -0,0 +1,217 @@
+//! A simulation of the movement of the player, enough for servers to accept it.
+use std::time::Duration;
+
+use super::{local_player::LocalPlayer, mctypes::{Location, MCPosition}, world::World};
+
+/// The time between two ticks of the game, at which the client sends the player's movement.
+pub const TICK: Duration = Duration::from_millis(50);
+/// The width of the player's bounding box, as well as its depth.
+pub const PLAYER_WIDTH: f64 = 0.6;
+/// The height of the player's bounding box, while standing.
+pub const PLAYER_HEIGHT: f64 = 1.8;
+/// The height of the player's eyes above its feet, while standing.
+pub const EYE_HEIGHT: f64 = 1.62;
+/// The distance the player walks in a tick.
+pub const WALK_SPEED: f64 = 0.21585;
+/// The vertical velocity lost to gravity each tick, in blocks per tick.
+pub const GRAVITY: f64 = 0.08;
+/// The factor vertical velocity is multiplied by each tick.
+const AIR_DRAG: f64 = 0.98;
+/// The vertical velocity of a jump, which clears a block.
+const JUMP_VELOCITY: f64 = 0.42;
+/// The horizontal distance from its target under which the player has arrived.
+const ARRIVAL_DISTANCE: f64 = 1e-3;
+
+/// An axis-aligned bounding box, from its lowest to its highest corner.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Aabb {
+    pub min: [f64; 3],
+    pub max: [f64; 3]
+}
+
+impl Aabb {
+    /// Gets the box of a player standing at `location`.
+    pub fn of_player(location: &Location) -> Self {
+        let half = PLAYER_WIDTH / 2.0;
+        Aabb {
+            min: [location.x - half, location.y, location.z - half],
+            max: [location.x + half, location.y + PLAYER_HEIGHT, location.z + half]
+        }
+    }
+
+    /// Gets the box of the full block at `position`.
+    pub fn of_block(position: MCPosition) -> Self {
+        let min = [position.x() as f64, position.y() as f64, position.z() as f64];
+        Aabb { min, max: min.map(|bound| bound + 1.0) }
+    }
+
+    /// Whether the box overlaps `other`. Boxes which only touch do not overlap.
+    pub fn intersects(&self, other: &Aabb) -> bool {
+        (0..3).all(|axis| self.min[axis] < other.max[axis] && other.min[axis] < self.max[axis])
+    }
+
+    /// Gets the box moved by `offset`.
+    pub fn offset(&self, offset: [f64; 3]) -> Self {
+        Aabb { min: [0, 1, 2].map(|axis| self.min[axis] + offset[axis]), max: [0, 1, 2].map(|axis| self.max[axis] + offset[axis]) }
+    }
+
+    /// Gets the box stretched to cover its movement by `motion`.
+    fn stretch(&self, motion: [f64; 3]) -> Self {
+        Aabb {
+            min: [0, 1, 2].map(|axis| self.min[axis] + motion[axis].min(0.0)),
+            max: [0, 1, 2].map(|axis| self.max[axis] + motion[axis].max(0.0))
+        }
+    }
+
+    /// Shortens the movement of `other` by `distance` along `axis`, so that it stops
+    /// against this box if it would have run into it.
+    fn clip(&self, other: &Aabb, axis: usize, distance: f64) -> f64 {
+        let overlaps = (0..3).filter(|other_axis| *other_axis != axis)
+            .all(|other_axis| self.min[other_axis] < other.max[other_axis] && other.min[other_axis] < self.max[other_axis]);
+        if !overlaps {
+            distance
+        } else if distance > 0.0 && other.max[axis] <= self.min[axis] {
+            distance.min(self.min[axis] - other.max[axis])
+        } else if distance < 0.0 && other.min[axis] >= self.max[axis] {
+            distance.max(self.max[axis] - other.min[axis])
+        } else {
+            distance
+        }
+    }
+}
+
+/// Whether the player cannot move through the block at `position`. Blocks other than air
+/// are treated as full cubes, as are the blocks of columns which are not loaded, so that
+/// the player does not fall out of the world before it is sent.
+pub fn is_solid(world: &World, position: MCPosition) -> bool {
+    if world.chunk_at(position).is_none() {
+        return true;
+    }
+    match world.block_state_at(position) {
+        Some(0) | None => false,
+        Some(state) => !world.block_registry().get(state).is_some_and(|block| block.is_air())
+    }
+}
+
+/// Gets the boxes of the solid blocks overlapping `area`.
+fn solid_boxes(world: &World, area: &Aabb) -> Vec<Aabb> {
+    let [min_x, min_y, min_z] = area.min.map(|bound| bound.floor() as i32);
+    let [max_x, max_y, max_z] = area.max.map(|bound| bound.ceil() as i32);
+    let mut boxes = Vec::new();
+    for x in min_x..max_x {
+        for y in min_y..max_y {
+            for z in min_z..max_z {
+                let position = MCPosition::new(x, y, z);
+                if is_solid(world, position) {
+                    boxes.push(Aabb::of_block(position));
+                }
+            }
+        }
+    }
+    boxes
+}
+
+/// Moves `aabb` by `motion`, stopping against the solid blocks of `world` along the
+/// vertical axis first, then along each horizontal axis. Returns the movement made.
+pub fn collide(world: &World, aabb: &Aabb, motion: [f64; 3]) -> [f64; 3] {
+    let boxes = solid_boxes(world, &aabb.stretch(motion));
+    let mut aabb = *aabb;
+    let mut moved = [0.0; 3];
+    for axis in [1, 0, 2] {
+        let distance = boxes.iter().fold(motion[axis], |distance, block| block.clip(&aabb, axis, distance));
+        moved[axis] = distance;
+        let mut offset = [0.0; 3];
+        offset[axis] = distance;
+        aabb = aabb.offset(offset);
+    }
+    moved
+}
+
+/// The movement of the player, simulated tick by tick against the tracked world: the
+/// player falls, is stopped by blocks, and walks towards its target, jumping onto the
+/// blocks in its way.
+/// <br> <br>
+/// The simulation is basic: every block other than air is a full cube, and the player
+/// neither swims nor flies. The player stays in place while its column is not loaded.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Physics {
+    /// The velocity of the player along each axis, in blocks per tick.
+    pub velocity: [f64; 3],
+    target: Option<[f64; 3]>,
+    blocked: bool
+}
+
+impl Physics {
+    /// Creates a simulation of a player standing still.
+    pub fn new() -> Self {
+        Physics::default()
+    }
+
+    /// The point the player is walking to, if any.
+    pub fn target(&self) -> Option<[f64; 3]> {
+        self.target
+    }
+
+    /// Whether the player is walking to a target.
+    pub fn is_walking(&self) -> bool {
+        self.target.is_some()
+    }
+
+    /// Starts walking towards `x`, `y` and `z`, in a straight line. The player only
+    /// reaches the height of its target by falling or jumping onto blocks.
+    pub fn walk_to(&mut self, x: f64, y: f64, z: f64) {
+        self.target = Some([x, y, z]);
+    }
+
+    /// Makes `player` jump at the next tick, if it stands on the ground.
+    pub fn jump(&mut self, player: &LocalPlayer) {
+        if player.on_ground {
+            self.velocity[1] = JUMP_VELOCITY;
+        }
+    }
+
+    /// Stops the player, as when it is teleported.
+    pub fn stop(&mut self) {
+        self.velocity = [0.0; 3];
+        self.target = None;
+        self.blocked = false;
+    }
+
+    /// Simulates a tick of the movement of `player` in `world`, updating its location,
+    /// rotation and whether it stands on the ground.
+    pub fn tick(&mut self, player: &mut LocalPlayer, world: &World) {
+        let location = &mut player.location;
+        if world.chunk_at(location.block()).is_none() {
+            return;
+        }
+
+        let (mut velocity_x, mut velocity_z) = (0.0, 0.0);
+        if let Some([x, _, z]) = self.target {
+            let (delta_x, delta_z) = (x - location.x, z - location.z);
+            let distance = delta_x.hypot(delta_z);
+            if distance < ARRIVAL_DISTANCE {
+                self.target = None;
+            } else {
+                let speed = WALK_SPEED.min(distance);
+                (velocity_x, velocity_z) = (delta_x / distance * speed, delta_z / distance * speed);
+                location.yaw = (-delta_x.atan2(delta_z).to_degrees()) as f32;
+                if self.blocked && player.on_ground {
+                    self.velocity[1] = JUMP_VELOCITY;
+                }
+            }
+        }
+        self.velocity[0] = velocity_x;
+        self.velocity[2] = velocity_z;
+
+        let moved = collide(world, &Aabb::of_player(location), self.velocity);
+        location.x += moved[0];
+        location.y += moved[1];
+        location.z += moved[2];
+        player.on_ground = self.velocity[1] < 0.0 && moved[1] != self.velocity[1];
+        self.blocked = moved[0] != self.velocity[0] || moved[2] != self.velocity[2];
+        if moved[1] != self.velocity[1] {
+            self.velocity[1] = 0.0;
+        }
+        self.velocity[1] = (self.velocity[1] - GRAVITY) * AIR_DRAG;
+    }
+}
@@ -0,0 +1,83 @@
+//! Plugins, which hook into a client as it reads packets and ticks, so that the behaviours
+//! of bots can be written once and composed, such as from other crates.
+use std::{io, net::TcpStream};
+
+use uuid::Uuid;
+
+use super::{client::Client, error::MCTypeError, packets::play::Clientbound};
+
+/// A set of hooks a client calls while it runs, once added with `Client::add_plugin`.
+/// Every hook is given the client, whose world, entities, inventory and connection it
+/// may read and act on, and does nothing by default. Plugins are called in the order they
+/// were added, and are kept when the client reconnects.
+/// <br> <br>
+/// An error returned by a hook is returned by the call of the client which ran it.
+/// # Example
+/// ```no_run
+/// use std::io;
+/// use uuid::Uuid;
+/// use mcclient::{Client, mc::{chat::ChatComponent, plugins::Plugin}};
+///
+/// /// Greets every player who says hello.
+/// struct Greeter;
+///
+/// impl Plugin for Greeter {
+///     fn on_chat(&mut self, client: &mut Client, sender: Option<Uuid>, message: &str) -> io::Result<()> {
+///         let text = ChatComponent::from_json(message)?.to_plain_text();
+///         match sender.is_some() && text.contains("hello") {
+///             true => client.send_chat("Hello!"),
+///             false => Ok(())
+///         }
+///     }
+/// }
+///
+/// let mut client = Client::login_offline("localhost", 25565, "Makoto").expect("Could not join");
+/// client.add_plugin(Greeter).expect("Could not start the plugin");
+/// loop {
+///     client.next_packet().expect("Disconnected");
+/// }
+/// ```
+pub trait Plugin<T = TcpStream> {
+    /// Called once the plugin is added, and each time the client has joined again after
+    /// reconnecting.
+    /// # Errors
+    /// An error returned here is returned by `Client::add_plugin` or `Client::next_packet`.
+    fn on_connect(&mut self, _client: &mut Client<T>) -> io::Result<()> {
+        Ok(())
+    }
+
+    /// Called with each packet read with `Client::next_packet`, once the client has
+    /// applied it to the state of the game.
+    /// # Errors
+    /// An error returned here is returned by `Client::next_packet`.
+    fn on_packet(&mut self, _client: &mut Client<T>, _packet: &Clientbound) -> io::Result<()> {
+        Ok(())
+    }
+
+    /// Called with each failure to update a part of the state of the game from `packet`
+    /// in `Client::next_packet`, before `on_packet`. That part of the state is left as it
+    /// was before the packet.
+    /// # Errors
+    /// An error returned here is returned by `Client::next_packet`.
+    fn on_tracking_error(&mut self, _client: &mut Client<T>, _packet: &Clientbound, _err: &MCTypeError) -> io::Result<()> {
+        Ok(())
+    }
+
+    /// Called at the start of each `Client::tick`, after the behaviors of the client, so
+    /// that movement and rotation are sent with the position of that tick.
+    /// # Errors
+    /// An error returned here is returned by `Client::tick`.
+    fn on_tick(&mut self, _client: &mut Client<T>) -> io::Result<()> {
+        Ok(())
+    }
+
+    /// Called with each message the server sends to the chat, after `on_packet`. The
+    /// `message` is a JSON text component, as in `Event::ChatReceived`, and the `sender`
+    /// is the player who sent it, if the server tells. Messages shown above the hotbar are
+    /// left out.
+    /// # Errors
+    /// An error returned here is returned by `Client::next_packet`.
+    fn on_chat(&mut self, _client: &mut Client<T>, _sender: Option<Uuid>, _message: &str) -> io::Result<()> {
+        Ok(())
+    }
+}
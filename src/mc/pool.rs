@@ -0,0 +1,134 @@
+//! Buffers reused across the packets of a stream, so that a busy connection reads,
+//! decompresses and compresses packets without allocating for each of them.
+use bytes::Bytes;
+
+/// How a `BufferPool` has been used since it was created.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct PoolStats {
+    /// The number of buffers handed out which had been used before.
+    pub reused: u64,
+    /// The number of buffers handed out which had to be allocated, as the pool was empty.
+    pub allocated: u64,
+    /// The number of buffers given back, and kept for reuse.
+    pub returned: u64,
+    /// The number of buffers given back, and dropped as the pool was full or the buffer
+    /// was larger than the pool keeps.
+    pub discarded: u64,
+    /// The number of buffers currently kept.
+    pub pooled: usize,
+    /// The capacity of the buffers currently kept, in bytes.
+    pub pooled_bytes: usize
+}
+
+impl PoolStats {
+    /// Gets the share of the buffers handed out which were reused, from 0 to 1, or `None`
+    /// before any was handed out.
+    pub fn reuse_ratio(&self) -> Option<f64> {
+        match self.reused + self.allocated {
+            0 => None,
+            taken => Some(self.reused as f64 / taken as f64)
+        }
+    }
+}
+
+/// A pool of byte buffers, taken to hold a packet and given back once the packet is done
+/// with. Streams frame, decompress and compress packets in the buffers of their pool; the
+/// packets read off of a stream own their buffer until they are handed back with
+/// `MinecraftStream::recycle`.
+/// <br> <br>
+/// The pool keeps at most `max_buffers` buffers of at most `max_capacity` bytes each, so
+/// that a burst of large packets, such as chunks, does not keep their memory forever.
+#[derive(Debug)]
+pub struct BufferPool {
+    buffers: Vec<Vec<u8>>,
+    max_buffers: usize,
+    max_capacity: usize,
+    stats: PoolStats
+}
+
+impl BufferPool {
+    /// The number of buffers pools keep by default.
+    pub const DEFAULT_MAX_BUFFERS: usize = 8;
+    /// The largest buffer pools keep by default, which fits any chunk sent by vanilla.
+    pub const DEFAULT_MAX_CAPACITY: usize = 1 << 20;
+
+    /// Creates an empty pool with the default limits.
+    pub fn new() -> Self {
+        BufferPool::with_limits(BufferPool::DEFAULT_MAX_BUFFERS, BufferPool::DEFAULT_MAX_CAPACITY)
+    }
+
+    /// Creates an empty pool keeping at most `max_buffers` buffers of at most
+    /// `max_capacity` bytes each. A pool keeping no buffers allocates for every packet.
+    pub fn with_limits(max_buffers: usize, max_capacity: usize) -> Self {
+        BufferPool { buffers: Vec::new(), max_buffers, max_capacity, stats: PoolStats::default() }
+    }
+
+    /// Changes the limits of the pool, as per `with_limits`, dropping the buffers which
+    /// exceed them.
+    pub fn set_limits(&mut self, max_buffers: usize, max_capacity: usize) {
+        self.max_buffers = max_buffers;
+        self.max_capacity = max_capacity;
+        self.buffers.retain(|buffer| buffer.capacity() <= max_capacity);
+        self.buffers.truncate(max_buffers);
+        self.update_pooled();
+    }
+
+    /// Takes an empty buffer able to hold at least `capacity` bytes, reusing the largest
+    /// buffer kept if there is any.
+    pub fn take(&mut self, capacity: usize) -> Vec<u8> {
+        // The buffers are kept from the smallest to the largest.
+        let mut buffer = match self.buffers.pop() {
+            Some(buffer) => {
+                self.stats.reused += 1;
+                buffer
+            },
+            None => {
+                self.stats.allocated += 1;
+                Vec::new()
+            }
+        };
+        buffer.reserve(capacity);
+        self.update_pooled();
+        buffer
+    }
+
+    /// Gives `buffer` back to the pool, which keeps it unless the pool is full or the
+    /// buffer is larger than the pool keeps.
+    pub fn give(&mut self, mut buffer: Vec<u8>) {
+        if self.buffers.len() >= self.max_buffers || buffer.capacity() > self.max_capacity || buffer.capacity() == 0 {
+            self.stats.discarded += 1;
+            return;
+        }
+
+        buffer.clear();
+        let position = self.buffers.partition_point(|kept| kept.capacity() < buffer.capacity());
+        self.buffers.insert(position, buffer);
+        self.stats.returned += 1;
+        self.update_pooled();
+    }
+
+    /// Gives the buffer of `bytes` back to the pool as per `give`, if nothing else refers
+    /// to it, such as a typed packet decoded from the packet the bytes belong to.
+    pub fn recycle(&mut self, bytes: Bytes) {
+        match bytes.try_into_mut() {
+            Ok(bytes) => self.give(bytes.into()),
+            Err(_) => self.stats.discarded += 1
+        }
+    }
+
+    /// Gets the statistics of the pool, to tune its limits.
+    pub fn stats(&self) -> PoolStats {
+        self.stats
+    }
+
+    fn update_pooled(&mut self) {
+        self.stats.pooled = self.buffers.len();
+        self.stats.pooled_bytes = self.buffers.iter().map(Vec::capacity).sum();
+    }
+}
+
+impl Default for BufferPool {
+    fn default() -> Self {
+        BufferPool::new()
+    }
+}
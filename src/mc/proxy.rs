@@ -0,0 +1,226 @@
+//! Connections to servers tunnelled through a SOCKS5 or HTTP proxy.
+use std::{io::{self, Read, Write}, net::{IpAddr, TcpStream}};
+
+/// The version byte of SOCKS5 messages.
+const SOCKS_VERSION: u8 = 5;
+/// The version byte of the SOCKS5 username and password exchange.
+const SOCKS_AUTH_VERSION: u8 = 1;
+/// The SOCKS5 method of connecting without authenticating.
+const SOCKS_NO_AUTH: u8 = 0;
+/// The SOCKS5 method of authenticating with a username and password.
+const SOCKS_PASSWORD_AUTH: u8 = 2;
+/// The SOCKS5 reply of a proxy accepting none of the methods offered.
+const SOCKS_NO_METHOD: u8 = 0xFF;
+/// The SOCKS5 command opening a TCP connection.
+const SOCKS_CONNECT: u8 = 1;
+/// The SOCKS5 address types.
+const SOCKS_IPV4: u8 = 1;
+const SOCKS_DOMAIN: u8 = 3;
+const SOCKS_IPV6: u8 = 4;
+/// The longest header of an HTTP response read before giving up on the proxy.
+const MAX_HTTP_HEADER: usize = 8192;
+
+/// A proxy to connect to servers through, for both status pings and logins.
+/// # Example
+/// ```no_run
+/// use mcclient::mc::{connection::OfflineConnection, proxy::ProxyConfig};
+///
+/// let proxy = ProxyConfig::socks5("127.0.0.1:1080").with_credentials("makoto", "hunter2");
+/// let connection = OfflineConnection::connect_via("mc.example.com", 25565, &proxy).expect("Could not connect");
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ProxyConfig {
+    /// A SOCKS5 proxy at `address`, given as `host:port`. The proxy resolves the host of
+    /// the server itself.
+    Socks5 { address: String, credentials: Option<(String, String)> },
+    /// An HTTP proxy at `address`, which tunnels the connection with a `CONNECT` request.
+    /// Credentials are sent with Basic authentication.
+    Http { address: String, credentials: Option<(String, String)> }
+}
+
+impl ProxyConfig {
+    /// Creates the config of the SOCKS5 proxy at `address`, which requires no
+    /// authentication.
+    pub fn socks5<A: Into<String>>(address: A) -> Self {
+        ProxyConfig::Socks5 { address: address.into(), credentials: None }
+    }
+
+    /// Creates the config of the HTTP proxy at `address`, which requires no
+    /// authentication.
+    pub fn http<A: Into<String>>(address: A) -> Self {
+        ProxyConfig::Http { address: address.into(), credentials: None }
+    }
+
+    /// Authenticates to the proxy as `username` with `password`.
+    pub fn with_credentials<U: Into<String>, P: Into<String>>(mut self, username: U, password: P) -> Self {
+        match &mut self {
+            ProxyConfig::Socks5 { credentials, .. } | ProxyConfig::Http { credentials, .. } => *credentials = Some((username.into(), password.into()))
+        }
+        self
+    }
+
+    /// Gets the address of the proxy.
+    pub fn address(&self) -> &str {
+        match self {
+            ProxyConfig::Socks5 { address, .. } | ProxyConfig::Http { address, .. } => address
+        }
+    }
+
+    /// Connects to `host` and `port` through the proxy.
+    /// # Errors
+    /// This function will return an error if the proxy cannot be reached, or if it fails
+    /// or refuses to open the tunnel, as per `tunnel`.
+    pub fn connect(&self, host: &str, port: u16) -> Result<TcpStream, io::Error> {
+        let mut stream = TcpStream::connect(self.address())?;
+        self.tunnel(&mut stream, host, port)?;
+        Ok(stream)
+    }
+
+    /// Asks the proxy at the other end of `stream` to open a tunnel to `host` and `port`.
+    /// Once this returns, the stream carries the bytes exchanged with the server.
+    /// # Errors
+    /// This function will return a `PermissionDenied` error if the proxy rejects the
+    /// credentials, or requires some it was not given, a `ConnectionRefused` error if it
+    /// fails to reach the server, or an `InvalidData` error if its replies are malformed.
+    pub fn tunnel<S: Read + Write>(&self, stream: &mut S, host: &str, port: u16) -> Result<(), io::Error> {
+        match self {
+            ProxyConfig::Socks5 { credentials, .. } => socks5_tunnel(stream, credentials.as_ref(), host, port),
+            ProxyConfig::Http { credentials, .. } => http_tunnel(stream, credentials.as_ref(), host, port)
+        }
+    }
+}
+
+fn socks5_tunnel<S: Read + Write>(stream: &mut S, credentials: Option<&(String, String)>, host: &str, port: u16) -> Result<(), io::Error> {
+    let invalid = |message: &str| io::Error::new(io::ErrorKind::InvalidData, message.to_owned());
+    let denied = |message: &str| io::Error::new(io::ErrorKind::PermissionDenied, message.to_owned());
+
+    match credentials {
+        Some(_) => stream.write_all(&[SOCKS_VERSION, 2, SOCKS_NO_AUTH, SOCKS_PASSWORD_AUTH])?,
+        None => stream.write_all(&[SOCKS_VERSION, 1, SOCKS_NO_AUTH])?
+    }
+    let mut choice = [0; 2];
+    stream.read_exact(&mut choice)?;
+    if choice[0] != SOCKS_VERSION {
+        return Err(invalid("The proxy does not speak SOCKS5."));
+    }
+    match (choice[1], credentials) {
+        (SOCKS_NO_AUTH, _) => (),
+        (SOCKS_PASSWORD_AUTH, Some((username, password))) => {
+            if username.len() > 255 || password.len() > 255 {
+                return Err(io::Error::new(io::ErrorKind::InvalidInput, "SOCKS5 credentials are limited to 255 bytes."));
+            }
+            let mut request = vec![SOCKS_AUTH_VERSION, username.len() as u8];
+            request.extend(username.as_bytes());
+            request.push(password.len() as u8);
+            request.extend(password.as_bytes());
+            stream.write_all(&request)?;
+
+            let mut status = [0; 2];
+            stream.read_exact(&mut status)?;
+            if status[1] != 0 {
+                return Err(denied("The proxy rejected the credentials."));
+            }
+        },
+        (SOCKS_NO_METHOD, _) | (SOCKS_PASSWORD_AUTH, None) => return Err(denied("The proxy requires authentication.")),
+        (method, _) => return Err(invalid(&format!("The proxy chose the unknown method {}.", method)))
+    }
+
+    let mut request = vec![SOCKS_VERSION, SOCKS_CONNECT, 0];
+    match host.parse::<IpAddr>() {
+        Ok(IpAddr::V4(address)) => {
+            request.push(SOCKS_IPV4);
+            request.extend(address.octets());
+        },
+        Ok(IpAddr::V6(address)) => {
+            request.push(SOCKS_IPV6);
+            request.extend(address.octets());
+        },
+        Err(_) if host.len() <= 255 => {
+            request.extend([SOCKS_DOMAIN, host.len() as u8]);
+            request.extend(host.as_bytes());
+        },
+        Err(_) => return Err(io::Error::new(io::ErrorKind::InvalidInput, format!("The host {} is too long.", host)))
+    }
+    request.extend(port.to_be_bytes());
+    stream.write_all(&request)?;
+    stream.flush()?;
+
+    let mut reply = [0; 4];
+    stream.read_exact(&mut reply)?;
+    if reply[1] != 0 {
+        let reason = match reply[1] {
+            2 => "the connection is not allowed",
+            3 => "the network is unreachable",
+            4 => "the host is unreachable",
+            5 => "the connection was refused",
+            6 => "the connection timed out",
+            _ => "the proxy failed"
+        };
+        return Err(io::Error::new(io::ErrorKind::ConnectionRefused, format!("The proxy could not connect to {}:{}: {}.", host, port, reason)));
+    }
+    // Skip the address the proxy bound, and its port.
+    let bound = match reply[3] {
+        SOCKS_IPV4 => 4,
+        SOCKS_IPV6 => 16,
+        SOCKS_DOMAIN => {
+            let mut length = [0];
+            stream.read_exact(&mut length)?;
+            length[0] as usize
+        },
+        _ => return Err(invalid("The proxy replied with an unknown address type."))
+    };
+    stream.read_exact(&mut vec![0; bound + 2])?;
+    Ok(())
+}
+
+fn http_tunnel<S: Read + Write>(stream: &mut S, credentials: Option<&(String, String)>, host: &str, port: u16) -> Result<(), io::Error> {
+    let authority = match host.contains(':') {
+        true => format!("[{}]:{}", host, port),
+        false => format!("{}:{}", host, port)
+    };
+    let mut request = format!("CONNECT {0} HTTP/1.1\r\nHost: {0}\r\n", authority);
+    if let Some((username, password)) = credentials {
+        request += &format!("Proxy-Authorization: Basic {}\r\n", base64(format!("{}:{}", username, password).as_bytes()));
+    }
+    request += "\r\n";
+    stream.write_all(request.as_bytes())?;
+    stream.flush()?;
+
+    // The header is read a byte at a time, so that none of the server's bytes are taken.
+    let mut header = Vec::new();
+    let mut byte = [0];
+    while !header.ends_with(b"\r\n\r\n") {
+        if header.len() >= MAX_HTTP_HEADER {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, "The proxy's response is too long."));
+        }
+        stream.read_exact(&mut byte)?;
+        header.push(byte[0]);
+    }
+
+    let header = String::from_utf8_lossy(&header);
+    let status_line = header.lines().next().unwrap_or_default();
+    let status = status_line.split(' ').nth(1).and_then(|status| status.parse::<u16>().ok());
+    match status {
+        Some(200..=299) => Ok(()),
+        Some(407) => Err(io::Error::new(io::ErrorKind::PermissionDenied, "The proxy requires authentication.")),
+        Some(_) => Err(io::Error::new(io::ErrorKind::ConnectionRefused, format!("The proxy could not connect to {}: {}.", authority, status_line))),
+        None => Err(io::Error::new(io::ErrorKind::InvalidData, "The proxy did not answer with HTTP."))
+    }
+}
+
+/// Encodes `bytes` in padded base64, as HTTP Basic authentication expects.
+fn base64(bytes: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+    let mut encoded = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let triple = u32::from_be_bytes([0, chunk[0], *chunk.get(1).unwrap_or(&0), *chunk.get(2).unwrap_or(&0)]);
+        for index in 0..4 {
+            match index <= chunk.len() {
+                true => encoded.push(ALPHABET[(triple >> (18 - 6 * index) & 0x3F) as usize] as char),
+                false => encoded.push('=')
+            }
+        }
+    }
+    encoded
+}
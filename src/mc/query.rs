@@ -0,0 +1,268 @@
+//! A client of the GameSpy4 UDP Query protocol, which servers with `enable-query` answer
+//! with their player list, plugins and map, beyond what the status ping tells.
+use std::{collections::HashMap, io, net::UdpSocket, time::Duration};
+
+use super::status::split_address;
+
+/// The magic bytes every query request starts with.
+const MAGIC: [u8; 2] = [0xFE, 0xFD];
+/// The type of the request for a challenge token.
+const HANDSHAKE: u8 = 9;
+/// The type of the requests for a stat.
+const STAT: u8 = 0;
+/// The bits of the session ID servers keep.
+const SESSION_MASK: i32 = 0x0F0F0F0F;
+/// The padding following the session ID of a full stat response.
+const FULL_STAT_PADDING: usize = 11;
+/// The padding preceding the players of a full stat response.
+const PLAYERS_PADDING: usize = 10;
+/// The time a server is given to answer each request.
+const QUERY_TIMEOUT: Duration = Duration::from_secs(3);
+/// The longest response read.
+const MAX_RESPONSE: usize = 65535;
+
+/// The status of a server as told by a basic stat query.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BasicStat {
+    /// The message of the day, with legacy formatting codes kept.
+    pub motd: String,
+    /// The type of game, which is always `SMP`.
+    pub game_type: String,
+    /// The name of the main world.
+    pub map: String,
+    pub players_online: i32,
+    pub players_max: i32,
+    /// The port the server listens for players on.
+    pub host_port: u16,
+    /// The address the server listens for players on.
+    pub host_ip: String
+}
+
+impl BasicStat {
+    /// Decodes a basic stat from the `response` to a basic stat request, as per
+    /// `parse_response`.
+    /// # Errors
+    /// This function will return an `InvalidData` error if the response is malformed.
+    pub fn parse(response: &[u8], session_id: i32) -> Result<Self, io::Error> {
+        let mut fields = Fields(parse_response(response, STAT, session_id)?);
+        let motd = fields.string()?;
+        let game_type = fields.string()?;
+        let map = fields.string()?;
+        let players_online = fields.number()?;
+        let players_max = fields.number()?;
+        let port = fields.take(2)?;
+        let host_port = u16::from_le_bytes([port[0], port[1]]);
+        let host_ip = fields.string()?;
+
+        Ok(BasicStat { motd, game_type, map, players_online, players_max, host_port, host_ip })
+    }
+}
+
+/// The status of a server as told by a full stat query.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FullStat {
+    /// Every value the server reported, by key, such as `hostname` and `version`.
+    pub values: HashMap<String, String>,
+    /// The names of the players online.
+    pub players: Vec<String>
+}
+
+impl FullStat {
+    /// Decodes a full stat from the `response` to a full stat request, as per
+    /// `parse_response`.
+    /// # Errors
+    /// This function will return an `InvalidData` error if the response is malformed.
+    pub fn parse(response: &[u8], session_id: i32) -> Result<Self, io::Error> {
+        let mut fields = Fields(parse_response(response, STAT, session_id)?);
+        fields.take(FULL_STAT_PADDING)?;
+        let mut values = HashMap::new();
+        loop {
+            let key = fields.string()?;
+            if key.is_empty() {
+                break;
+            }
+            values.insert(key, fields.string()?);
+        }
+
+        fields.take(PLAYERS_PADDING)?;
+        let mut players = Vec::new();
+        while !fields.0.is_empty() {
+            let player = fields.string()?;
+            if player.is_empty() {
+                break;
+            }
+            players.push(player);
+        }
+        Ok(FullStat { values, players })
+    }
+
+    /// Gets the value the server reported for `key`, if any.
+    pub fn value(&self, key: &str) -> Option<&str> {
+        self.values.get(key).map(String::as_str)
+    }
+
+    /// Gets the message of the day, with legacy formatting codes kept.
+    pub fn motd(&self) -> &str {
+        self.value("hostname").unwrap_or_default()
+    }
+
+    /// Gets the name of the version the server runs, such as `1.19.3`.
+    pub fn version(&self) -> &str {
+        self.value("version").unwrap_or_default()
+    }
+
+    /// Gets the name of the main world.
+    pub fn map(&self) -> &str {
+        self.value("map").unwrap_or_default()
+    }
+
+    /// Gets the number of players online.
+    pub fn players_online(&self) -> i32 {
+        self.value("numplayers").and_then(|players| players.parse().ok()).unwrap_or_default()
+    }
+
+    /// Gets the most players the server lets join.
+    pub fn players_max(&self) -> i32 {
+        self.value("maxplayers").and_then(|players| players.parse().ok()).unwrap_or_default()
+    }
+
+    /// Gets the software the server runs, such as `CraftBukkit on Bukkit 1.19.3`, and the
+    /// plugins it has loaded. Vanilla servers report neither.
+    pub fn plugins(&self) -> (Option<&str>, Vec<&str>) {
+        let plugins = self.value("plugins").unwrap_or_default();
+        match plugins.split_once(": ") {
+            Some((server_mod, plugins)) => (Some(server_mod), plugins.split("; ").filter(|plugin| !plugin.is_empty()).collect()),
+            None if plugins.is_empty() => (None, Vec::new()),
+            None => (Some(plugins), Vec::new())
+        }
+    }
+}
+
+/// The fields of a response, read from the front.
+struct Fields<'a>(&'a [u8]);
+
+impl<'a> Fields<'a> {
+    fn take(&mut self, length: usize) -> Result<&'a [u8], io::Error> {
+        if self.0.len() < length {
+            return Err(truncated());
+        }
+        let (taken, rest) = self.0.split_at(length);
+        self.0 = rest;
+        Ok(taken)
+    }
+
+    /// Reads a null-terminated string.
+    fn string(&mut self) -> Result<String, io::Error> {
+        let end = self.0.iter().position(|byte| *byte == 0).ok_or_else(truncated)?;
+        let string = String::from_utf8_lossy(&self.0[..end]).into_owned();
+        self.0 = &self.0[end + 1..];
+        Ok(string)
+    }
+
+    /// Reads a number written as a null-terminated string.
+    fn number(&mut self) -> Result<i32, io::Error> {
+        let number = self.string()?;
+        number.parse().map_err(|_| io::Error::new(io::ErrorKind::InvalidData, format!("Invalid number {} in query response.", number)))
+    }
+}
+
+fn truncated() -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, "Truncated query response.")
+}
+
+/// Encodes a request of `kind` for the session `session_id`, followed by `payload`.
+pub fn request(kind: u8, session_id: i32, payload: &[u8]) -> Vec<u8> {
+    let mut request = MAGIC.to_vec();
+    request.push(kind);
+    request.extend((session_id & SESSION_MASK).to_be_bytes());
+    request.extend(payload);
+    request
+}
+
+/// Checks that `response` answers a request of `kind` for the session `session_id`,
+/// returning the bytes following its header.
+/// # Errors
+/// This function will return an `InvalidData` error if the response answers another
+/// request.
+pub fn parse_response(response: &[u8], kind: u8, session_id: i32) -> Result<&[u8], io::Error> {
+    match response.split_first() {
+        Some((answered, rest)) if *answered == kind && rest.len() >= 4 && rest[..4] == (session_id & SESSION_MASK).to_be_bytes() => Ok(&rest[4..]),
+        _ => Err(io::Error::new(io::ErrorKind::InvalidData, "The query response does not answer the request."))
+    }
+}
+
+/// Decodes the challenge token out of the `response` to a handshake.
+/// # Errors
+/// This function will return an `InvalidData` error if the response is malformed.
+pub fn parse_challenge(response: &[u8], session_id: i32) -> Result<i32, io::Error> {
+    Fields(parse_response(response, HANDSHAKE, session_id)?).number()
+}
+
+/// A query session with a server, whose challenge token is renewed with each stat.
+/// # Example
+/// ```no_run
+/// use mcclient::mc::query::QueryClient;
+///
+/// let query = QueryClient::connect("localhost:25565").expect("Could not reach the server");
+/// let stat = query.full_stat().expect("The server did not answer");
+/// println!("{} on {}: {:?}", stat.motd(), stat.map(), stat.players);
+/// ```
+pub struct QueryClient {
+    socket: UdpSocket,
+    session_id: i32
+}
+
+impl QueryClient {
+    /// Opens a session with the query port of the server at `address`, given as `host` or
+    /// `host:port`, which is the port players join on unless the server sets another.
+    /// # Errors
+    /// This function will return an error if the address is malformed or cannot be
+    /// resolved.
+    pub fn connect(address: &str) -> Result<Self, io::Error> {
+        let (host, port) = split_address(address)?;
+        let socket = UdpSocket::bind(match host.contains(':') {
+            true => "[::]:0",
+            false => "0.0.0.0:0"
+        })?;
+        socket.connect((host, port))?;
+        socket.set_read_timeout(Some(QUERY_TIMEOUT))?;
+
+        Ok(QueryClient { socket, session_id: rand::random::<i32>() & SESSION_MASK })
+    }
+
+    /// Requests the basic stat of the server.
+    /// # Errors
+    /// This function will return an error if the server does not answer in time, or an
+    /// `InvalidData` error if its answer is malformed.
+    pub fn basic_stat(&self) -> Result<BasicStat, io::Error> {
+        let challenge = self.challenge()?;
+        let response = self.exchange(&request(STAT, self.session_id, &challenge.to_be_bytes()))?;
+        BasicStat::parse(&response, self.session_id)
+    }
+
+    /// Requests the full stat of the server, which lists its players and plugins.
+    /// # Errors
+    /// This function will return an error if the server does not answer in time, or an
+    /// `InvalidData` error if its answer is malformed.
+    pub fn full_stat(&self) -> Result<FullStat, io::Error> {
+        let challenge = self.challenge()?;
+        let mut payload = challenge.to_be_bytes().to_vec();
+        // The padding asks for the full stat.
+        payload.extend([0; 4]);
+        let response = self.exchange(&request(STAT, self.session_id, &payload))?;
+        FullStat::parse(&response, self.session_id)
+    }
+
+    fn challenge(&self) -> Result<i32, io::Error> {
+        let response = self.exchange(&request(HANDSHAKE, self.session_id, &[]))?;
+        parse_challenge(&response, self.session_id)
+    }
+
+    fn exchange(&self, request: &[u8]) -> Result<Vec<u8>, io::Error> {
+        self.socket.send(request)?;
+        let mut response = vec![0; MAX_RESPONSE];
+        let length = self.socket.recv(&mut response)?;
+        response.truncate(length);
+        Ok(response)
+    }
+}
@@ -0,0 +1,151 @@
+//! A client of the Source RCON protocol, which servers with `enable-rcon` speak to let
+//! admins run commands remotely.
+use std::{io::{self, Read, Write}, net::TcpStream};
+
+use super::status::parse_address;
+
+/// The port servers listen for RCON on unless another is configured.
+pub const DEFAULT_RCON_PORT: u16 = 25575;
+/// The type of the packet logging in with the password.
+const LOGIN: i32 = 3;
+/// The type of the packet running a command.
+const COMMAND: i32 = 2;
+/// The type of the packets carrying a response.
+const RESPONSE: i32 = 0;
+/// A type servers do not know, and answer with a single packet, which marks the end of
+/// the response to the command sent before it.
+const END_MARKER: i32 = 200;
+/// The request ID servers answer a login with if the password is wrong.
+const AUTH_FAILED: i32 = -1;
+/// The most bytes of a command servers accept.
+pub const MAX_COMMAND_LENGTH: usize = 1446;
+/// The most bytes of a packet, beyond which the length is taken to be malformed.
+const MAX_PACKET_LENGTH: i32 = 4096 + 10;
+
+/// A packet of the RCON protocol.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RconPacket {
+    /// The ID the client gave the request, which the server answers with.
+    pub request_id: i32,
+    pub kind: i32,
+    pub payload: String
+}
+
+impl RconPacket {
+    /// Encodes the packet, with its little-endian length prefix.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let length = 4 + 4 + self.payload.len() as i32 + 2;
+        let mut bytes = Vec::with_capacity(4 + length as usize);
+        bytes.extend(length.to_le_bytes());
+        bytes.extend(self.request_id.to_le_bytes());
+        bytes.extend(self.kind.to_le_bytes());
+        bytes.extend(self.payload.as_bytes());
+        bytes.extend([0, 0]);
+        bytes
+    }
+
+    /// Reads a packet from `reader`, blocking until it has been received in full.
+    /// # Errors
+    /// This function will return an `InvalidData` error if the length of the packet is out
+    /// of bounds, or an error if it cannot be read.
+    pub fn read<R: Read>(reader: &mut R) -> Result<Self, io::Error> {
+        let mut int = [0; 4];
+        reader.read_exact(&mut int)?;
+        let length = i32::from_le_bytes(int);
+        if !(10..=MAX_PACKET_LENGTH).contains(&length) {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, format!("RCON packet length {} is out of bounds.", length)));
+        }
+
+        let mut body = vec![0; length as usize];
+        reader.read_exact(&mut body)?;
+        let request_id = i32::from_le_bytes([body[0], body[1], body[2], body[3]]);
+        let kind = i32::from_le_bytes([body[4], body[5], body[6], body[7]]);
+        let payload = &body[8..body.len() - 2];
+        Ok(RconPacket { request_id, kind, payload: String::from_utf8_lossy(payload).into_owned() })
+    }
+}
+
+/// A logged-in RCON session, over TCP unless another transport is given.
+/// # Example
+/// ```no_run
+/// use mcclient::mc::rcon::RconClient;
+///
+/// let mut rcon = RconClient::connect("localhost", "hunter2").expect("Could not log in");
+/// println!("{}", rcon.exec("list").expect("Could not run the command"));
+/// ```
+pub struct RconClient<T = TcpStream> {
+    stream: T,
+    next_id: i32
+}
+
+impl RconClient<TcpStream> {
+    /// Connects to the RCON port of the server at `address`, given as `host` or
+    /// `host:port` with a default port of `DEFAULT_RCON_PORT`, and logs in with `password`.
+    /// # Errors
+    /// This function will return an error if the server cannot be reached, or as per
+    /// `login`.
+    pub fn connect(address: &str, password: &str) -> Result<Self, io::Error> {
+        let (host, port) = parse_address(address)?;
+        RconClient::login(TcpStream::connect((host, port.unwrap_or(DEFAULT_RCON_PORT)))?, password)
+    }
+}
+
+impl<T: Read + Write> RconClient<T> {
+    /// Logs in with `password` over an established `stream`.
+    /// # Errors
+    /// This function will return a `PermissionDenied` error if the password is wrong, an
+    /// `InvalidData` error if the server's answer is malformed, or an error if the stream
+    /// fails.
+    pub fn login(stream: T, password: &str) -> Result<Self, io::Error> {
+        let mut client = RconClient { stream, next_id: 1 };
+        let id = client.send(LOGIN, password)?;
+        loop {
+            let answer = RconPacket::read(&mut client.stream)?;
+            match answer.request_id {
+                AUTH_FAILED => return Err(io::Error::new(io::ErrorKind::PermissionDenied, "The RCON password is wrong.")),
+                // Some servers send an empty response ahead of the answer to the login.
+                answered if answered == id && answer.kind == COMMAND => return Ok(client),
+                answered if answered == id => (),
+                answered => return Err(io::Error::new(io::ErrorKind::InvalidData, format!("Unexpected RCON request ID {} in the answer to the login.", answered)))
+            }
+        }
+    }
+
+    /// Runs `command`, without its leading slash, and returns the server's response.
+    /// Responses too long for a packet are split by the server, and joined back together.
+    /// # Errors
+    /// This function will return an `InvalidInput` error if the command is longer than
+    /// `MAX_COMMAND_LENGTH` bytes, or an error if the stream fails.
+    pub fn exec(&mut self, command: &str) -> Result<String, io::Error> {
+        if command.len() > MAX_COMMAND_LENGTH {
+            return Err(io::Error::new(io::ErrorKind::InvalidInput, format!("RCON commands are limited to {} bytes.", MAX_COMMAND_LENGTH)));
+        }
+
+        let id = self.send(COMMAND, command)?;
+        let end = self.send(END_MARKER, "")?;
+        let mut response = String::new();
+        loop {
+            let packet = RconPacket::read(&mut self.stream)?;
+            match packet.request_id {
+                answered if answered == end => return Ok(response),
+                answered if answered == id && packet.kind == RESPONSE => response += &packet.payload,
+                _ => ()
+            }
+        }
+    }
+
+    /// Gets the stream the session runs over.
+    pub fn stream(&mut self) -> &mut T {
+        &mut self.stream
+    }
+
+    /// Sends a packet of `kind` carrying `payload`, under the next request ID, which is
+    /// returned.
+    fn send(&mut self, kind: i32, payload: &str) -> Result<i32, io::Error> {
+        let request_id = self.next_id;
+        self.next_id = self.next_id.wrapping_add(1).max(1);
+        self.stream.write_all(&RconPacket { request_id, kind, payload: payload.to_owned() }.to_bytes())?;
+        self.stream.flush()?;
+        Ok(request_id)
+    }
+}
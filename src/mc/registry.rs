@@ -0,0 +1,212 @@
+use std::io::{self, Write};
+
+use super::{connection::ConnectionState, packet::{OutboundPacket, PacketId, PacketIdentity}, version::ProtocolVersion};
+
+/// The version whose packet IDs the typed packets of this library are declared with.
+/// Packet IDs of other versions are translated to and from these canonical IDs.
+pub const CANONICAL_VERSION: ProtocolVersion = ProtocolVersion::V1_19_3;
+
+/// Inbound packets of a non-canonical version which have no canonical counterpart are
+/// given their raw ID offset by this base, so that they are never mistaken for the
+/// canonical packet which happens to share their raw ID.
+pub const UNMAPPED_ID_BASE: i32 = 0x1000;
+
+/// Packets of older versions which were removed before the canonical version, as well as
+/// the few packets of newer versions added after it, are given canonical IDs from this
+/// base, so that they can be typed and translated like the rest.
+pub const LEGACY_ID_BASE: i32 = 0x800;
+
+/// The direction a packet travels in.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Direction {
+    Serverbound,
+    Clientbound
+}
+
+/// Pairs of the canonical ID of a packet and its ID in one version.
+type IdTable = &'static [(i32, i32)];
+
+const V1_8_SERVERBOUND_PLAY: IdTable = &[
+    (0x06, 0x16), (0x08, 0x14), (0x0A, 0x0E), (0x0B, 0x0D), (0x0C, 0x17), (0x0F, 0x02), (0x11, 0x00), (0x13, 0x04),
+    (0x14, 0x06), (0x1C, 0x07), (0x1D, 0x0B), (0x24, 0x19), (0x2E, 0x12), (0x800, 0x01), (0x801, 0x0F)
+];
+const V1_8_CLIENTBOUND_PLAY: IdTable = &[
+    (0x09, 0x23), (0x0D, 0x3A), (0x0F, 0x2E), (0x10, 0x30), (0x12, 0x2F), (0x15, 0x3F), (0x17, 0x40), (0x1C, 0x2B),
+    (0x1F, 0x00), (0x20, 0x21), (0x24, 0x01), (0x2D, 0x36), (0x34, 0x42), (0x38, 0x08), (0x3C, 0x48), (0x3F, 0x22),
+    (0x4D, 0x3D), (0x4E, 0x1C), (0x52, 0x1F), (0x53, 0x06), (0x54, 0x3B), (0x57, 0x3C), (0x800, 0x02), (0x801, 0x32),
+    (0x803, 0x45)
+];
+
+const V1_12_2_SERVERBOUND_PLAY: IdTable = &[
+    (0x00, 0x00), (0x06, 0x03), (0x08, 0x01), (0x0A, 0x07), (0x0B, 0x08), (0x0C, 0x09), (0x0F, 0x0A), (0x11, 0x0B),
+    (0x13, 0x0D), (0x14, 0x0E), (0x1C, 0x14), (0x1D, 0x15), (0x24, 0x18), (0x2E, 0x1C), (0x2F, 0x1D), (0x800, 0x02),
+    (0x801, 0x05)
+];
+const V1_12_2_CLIENTBOUND_PLAY: IdTable = &[
+    (0x02, 0x05), (0x09, 0x0B), (0x0A, 0x0C), (0x0D, 0x0E), (0x0F, 0x12), (0x10, 0x14), (0x12, 0x16), (0x15, 0x18),
+    (0x17, 0x1A), (0x1B, 0x1D), (0x1C, 0x1E), (0x1F, 0x1F), (0x20, 0x20), (0x24, 0x23), (0x27, 0x26), (0x28, 0x27),
+    (0x29, 0x28), (0x2D, 0x2A), (0x34, 0x2D), (0x38, 0x2F), (0x3A, 0x32), (0x3C, 0x34), (0x3F, 0x10), (0x4D, 0x3B),
+    (0x4E, 0x3C), (0x50, 0x3E), (0x52, 0x40), (0x53, 0x41), (0x54, 0x42), (0x57, 0x45), (0x64, 0x4C), (0x800, 0x0F),
+    (0x801, 0x11), (0x803, 0x48)
+];
+
+const V1_16_5_SERVERBOUND_PLAY: IdTable = &[
+    (0x00, 0x00), (0x06, 0x04), (0x08, 0x06), (0x0A, 0x09), (0x0B, 0x0A), (0x0C, 0x0B), (0x0F, 0x0E), (0x11, 0x10),
+    (0x13, 0x12), (0x14, 0x13), (0x1C, 0x1B), (0x1D, 0x1C), (0x24, 0x21), (0x2E, 0x2B), (0x2F, 0x2C), (0x31, 0x2E),
+    (0x800, 0x03), (0x801, 0x07)
+];
+const V1_16_5_CLIENTBOUND_PLAY: IdTable = &[
+    (0x02, 0x04), (0x09, 0x0B), (0x0A, 0x0C), (0x0D, 0x0F), (0x0F, 0x12), (0x10, 0x13), (0x12, 0x15), (0x15, 0x17),
+    (0x17, 0x19), (0x1B, 0x1C), (0x1C, 0x1D), (0x1F, 0x1F), (0x20, 0x20), (0x24, 0x24), (0x27, 0x27), (0x28, 0x28),
+    (0x29, 0x29), (0x2C, 0x2D), (0x2D, 0x2E), (0x34, 0x31), (0x38, 0x34), (0x3A, 0x36), (0x3C, 0x38), (0x3F, 0x3B),
+    (0x4D, 0x43), (0x4E, 0x44), (0x50, 0x46), (0x52, 0x48), (0x53, 0x49), (0x54, 0x4A), (0x57, 0x4D), (0x64, 0x56),
+    (0x800, 0x0E), (0x801, 0x11), (0x803, 0x4F)
+];
+
+const V1_20_1_SERVERBOUND_PLAY: IdTable = &[
+    (0x00, 0x00), (0x04, 0x04), (0x05, 0x05), (0x06, 0x07), (0x08, 0x09), (0x0A, 0x0B), (0x0B, 0x0C), (0x0C, 0x0D),
+    (0x0F, 0x10), (0x11, 0x12), (0x13, 0x14), (0x14, 0x15), (0x1C, 0x1D), (0x1D, 0x1E), (0x24, 0x24), (0x2E, 0x2E),
+    (0x2F, 0x2F), (0x31, 0x31)
+];
+const V1_20_1_CLIENTBOUND_PLAY: IdTable = &[
+    (0x00, 0x01), (0x02, 0x03), (0x05, 0x06), (0x09, 0x0A), (0x0A, 0x0B), (0x0C, 0x0E), (0x0D, 0x0F), (0x0F, 0x11),
+    (0x10, 0x12), (0x12, 0x14), (0x15, 0x17), (0x17, 0x1A), (0x18, 0x1B), (0x1B, 0x1E), (0x1C, 0x1F), (0x1F, 0x23),
+    (0x20, 0x24), (0x24, 0x28), (0x27, 0x2B), (0x28, 0x2C), (0x29, 0x2D), (0x2C, 0x30), (0x2D, 0x31), (0x31, 0x35),
+    (0x34, 0x38), (0x35, 0x39), (0x36, 0x3A), (0x38, 0x3C), (0x3A, 0x3E), (0x3C, 0x40), (0x3F, 0x43), (0x42, 0x46),
+    (0x4D, 0x51), (0x4E, 0x52), (0x50, 0x54), (0x52, 0x56), (0x53, 0x57), (0x54, 0x58), (0x57, 0x5B), (0x59, 0x5D),
+    (0x5B, 0x5F), (0x5C, 0x60), (0x60, 0x64), (0x64, 0x68)
+];
+
+const V1_20_2_SERVERBOUND_PLAY: IdTable = &[
+    (0x00, 0x00), (0x04, 0x04), (0x05, 0x05), (0x06, 0x08), (0x08, 0x0A), (0x0A, 0x0D), (0x0B, 0x0E), (0x0C, 0x0F),
+    (0x0F, 0x12), (0x11, 0x14), (0x13, 0x16), (0x14, 0x17), (0x1C, 0x20), (0x1D, 0x21), (0x24, 0x27), (0x2E, 0x31),
+    (0x2F, 0x32), (0x31, 0x34)
+];
+const V1_20_2_CLIENTBOUND_PLAY: IdTable = &[
+    (0x00, 0x01), (0x05, 0x05), (0x09, 0x09), (0x0A, 0x0A), (0x0C, 0x0F), (0x0D, 0x10), (0x0F, 0x12), (0x10, 0x13),
+    (0x12, 0x15), (0x15, 0x18), (0x17, 0x1B), (0x18, 0x1C), (0x1B, 0x1F), (0x1C, 0x20), (0x1F, 0x24), (0x20, 0x25),
+    (0x24, 0x29), (0x27, 0x2C), (0x28, 0x2D), (0x29, 0x2E), (0x2C, 0x31), (0x2D, 0x32), (0x31, 0x37), (0x34, 0x3A),
+    (0x35, 0x3B), (0x36, 0x3C), (0x38, 0x3E), (0x3A, 0x40), (0x3C, 0x42), (0x3F, 0x45), (0x42, 0x48), (0x4D, 0x53),
+    (0x4E, 0x54), (0x50, 0x56), (0x52, 0x58), (0x53, 0x59), (0x54, 0x5A), (0x57, 0x5D), (0x59, 0x5F), (0x5B, 0x61),
+    (0x5C, 0x62), (0x60, 0x67), (0x64, 0x6B)
+];
+
+const V1_20_4_SERVERBOUND_PLAY: IdTable = &[
+    (0x00, 0x00), (0x04, 0x04), (0x05, 0x05), (0x06, 0x08), (0x08, 0x0A), (0x0A, 0x0D), (0x0B, 0x0E), (0x0C, 0x10),
+    (0x0F, 0x13), (0x11, 0x15), (0x13, 0x17), (0x14, 0x18), (0x1C, 0x21), (0x1D, 0x22), (0x24, 0x28), (0x2E, 0x32),
+    (0x2F, 0x33), (0x31, 0x35)
+];
+const V1_20_4_CLIENTBOUND_PLAY: IdTable = &[
+    (0x00, 0x01), (0x05, 0x05), (0x09, 0x09), (0x0A, 0x0A), (0x0C, 0x0F), (0x0D, 0x10), (0x0F, 0x12), (0x10, 0x13),
+    (0x12, 0x15), (0x15, 0x18), (0x17, 0x1B), (0x18, 0x1C), (0x1B, 0x1F), (0x1C, 0x20), (0x1F, 0x24), (0x20, 0x25),
+    (0x24, 0x29), (0x27, 0x2C), (0x28, 0x2D), (0x29, 0x2E), (0x2C, 0x31), (0x2D, 0x32), (0x31, 0x37), (0x34, 0x3A),
+    (0x35, 0x3B), (0x36, 0x3C), (0x38, 0x3E), (0x3A, 0x40), (0x3C, 0x44), (0x3F, 0x47), (0x42, 0x4A), (0x4D, 0x55),
+    (0x4E, 0x56), (0x50, 0x58), (0x52, 0x5A), (0x53, 0x5B), (0x54, 0x5C), (0x57, 0x5F), (0x59, 0x61), (0x5B, 0x63),
+    (0x5C, 0x64), (0x60, 0x69), (0x64, 0x6D), (0x802, 0x42)
+];
+
+const V1_20_4_SERVERBOUND_CONFIGURATION: IdTable = &[
+    (0x00, 0x00), (0x01, 0x01), (0x02, 0x02), (0x03, 0x03), (0x04, 0x04), (0x05, 0x05)
+];
+const V1_20_4_CLIENTBOUND_CONFIGURATION: IdTable = &[
+    (0x00, 0x00), (0x01, 0x01), (0x02, 0x02), (0x03, 0x03), (0x04, 0x04), (0x05, 0x05),
+    (0x06, 0x07), (0x07, 0x08), (0x08, 0x09)
+];
+
+/// Gets the table translating the IDs of `state` in `version`, or `None` if the IDs are
+/// those of the canonical version. Handshaking, Status and Login IDs have not changed
+/// between the supported versions, and the canonical Configuration IDs are those of its
+/// introduction in 1.20.2.
+fn table(version: ProtocolVersion, state: ConnectionState, direction: Direction) -> Option<IdTable> {
+    let tables = match (state, version) {
+        (ConnectionState::Play, _) => play_tables(version)?,
+        (ConnectionState::Configuration, ProtocolVersion::V1_20_4) => (V1_20_4_SERVERBOUND_CONFIGURATION, V1_20_4_CLIENTBOUND_CONFIGURATION),
+        _ => return None
+    };
+
+    Some(match direction {
+        Direction::Serverbound => tables.0,
+        Direction::Clientbound => tables.1
+    })
+}
+
+/// Gets the serverbound and clientbound tables of the Play state in `version`.
+fn play_tables(version: ProtocolVersion) -> Option<(IdTable, IdTable)> {
+    Some(match version {
+        ProtocolVersion::V1_8 => (V1_8_SERVERBOUND_PLAY, V1_8_CLIENTBOUND_PLAY),
+        ProtocolVersion::V1_12_2 => (V1_12_2_SERVERBOUND_PLAY, V1_12_2_CLIENTBOUND_PLAY),
+        ProtocolVersion::V1_16_5 => (V1_16_5_SERVERBOUND_PLAY, V1_16_5_CLIENTBOUND_PLAY),
+        ProtocolVersion::V1_20_1 => (V1_20_1_SERVERBOUND_PLAY, V1_20_1_CLIENTBOUND_PLAY),
+        ProtocolVersion::V1_20_2 => (V1_20_2_SERVERBOUND_PLAY, V1_20_2_CLIENTBOUND_PLAY),
+        ProtocolVersion::V1_20_4 => (V1_20_4_SERVERBOUND_PLAY, V1_20_4_CLIENTBOUND_PLAY),
+        ProtocolVersion::V1_19_3 | ProtocolVersion::Unknown(_) => return None
+    })
+}
+
+/// Translates the canonical ID `canonical` of a packet of `state` to its ID in `version`.
+/// Versions without a table of their own, such as unknown versions, use canonical IDs.
+/// <br>
+/// Returns `None` if the packet does not exist in `version`, or has a layout too different
+/// from the canonical packet to be sent under the same structure.
+pub fn packet_id(version: ProtocolVersion, state: ConnectionState, direction: Direction, canonical: i32) -> Option<i32> {
+    match table(version, state, direction) {
+        Some(table) => table.iter().find(|(from, _)| *from == canonical).map(|(_, to)| *to),
+        None => Some(canonical)
+    }
+}
+
+/// Translates the ID `id` of a packet of `state` in `version` to its canonical ID, the
+/// inverse of `packet_id`. Returns `None` if the packet has no canonical counterpart.
+pub fn canonical_id(version: ProtocolVersion, state: ConnectionState, direction: Direction, id: i32) -> Option<i32> {
+    match table(version, state, direction) {
+        Some(table) => table.iter().find(|(_, to)| *to == id).map(|(from, _)| *from),
+        None => Some(id)
+    }
+}
+
+/// Gets the ID of the typed packet `P` in `version`, or `None` if it does not exist there.
+pub fn id_of<P: PacketId>(version: ProtocolVersion, direction: Direction) -> Option<i32> {
+    packet_id(version, P::STATE, direction, P::ID)
+}
+
+/// Recovers the ID an inbound packet was received under from the ID it was given by
+/// `canonical_id` translation, undoing the `UNMAPPED_ID_BASE` offset of packets without
+/// a canonical counterpart.
+pub fn raw_id(version: ProtocolVersion, state: ConnectionState, id: i32) -> i32 {
+    match id >= UNMAPPED_ID_BASE {
+        true => id - UNMAPPED_ID_BASE,
+        false => packet_id(version, state, Direction::Clientbound, id).unwrap_or(id)
+    }
+}
+
+/// An outbound packet sent under the ID and layout it has in a protocol version.
+pub(crate) struct Versioned<'a> {
+    pub packet: &'a dyn OutboundPacket,
+    pub id: i32,
+    pub version: ProtocolVersion
+}
+
+impl PacketIdentity for Versioned<'_> {
+    fn packet_id(&self) -> i32 {
+        self.id
+    }
+
+    fn state(&self) -> Option<ConnectionState> {
+        self.packet.state()
+    }
+}
+
+impl OutboundPacket for Versioned<'_> {
+    fn encode(&self, w: &mut dyn Write) -> io::Result<()> {
+        self.packet.encode_for(self.version, w)
+    }
+
+    fn len(&self) -> i32 {
+        self.packet.len_for(self.version)
+    }
+
+    fn next_state(&self) -> Option<ConnectionState> {
+        self.packet.next_state()
+    }
+}
@@ -0,0 +1,84 @@
+//! The resource packs servers push to the client, and the policy they are answered with.
+use uuid::Uuid;
+
+use super::{chat::ChatComponent, error::MCTypeError, mctypes::{MCBoolean, MCString, MCType, MCUuid}, packet::serverbound::resource_pack_response::ResourcePackResult, version::ProtocolVersion};
+
+/// The first protocol version (1.17) telling whether a pack is forced, and prompting for it.
+const FORCED_SINCE: i32 = 755;
+
+/// A resource pack the server asks the client to load, as decoded from a Resource Pack
+/// packet of the Play or Configuration state.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ResourcePackEvent {
+    /// The UUID of the pack, which servers running 1.20.3 or newer send, as they may push
+    /// several packs.
+    pub uuid: Option<Uuid>,
+    /// The URL the pack is downloaded from.
+    pub url: String,
+    /// The SHA-1 hash of the pack, as a hexadecimal string, which may be empty.
+    pub hash: String,
+    /// Whether the server kicks clients which decline the pack. Servers older than 1.17
+    /// do not tell.
+    pub forced: bool,
+    /// The message shown to the player when asking to load the pack, if any.
+    pub prompt: Option<ChatComponent>
+}
+
+impl ResourcePackEvent {
+    /// Decodes the pack from the data of a Resource Pack packet in the format of `version`.
+    /// # Errors
+    /// This function will return an `MCTypeError` if the data does not encode a pack, or
+    /// the errors of `ChatComponent::parse_for`.
+    pub fn parse(bytes: &[u8], version: ProtocolVersion) -> Result<Self, MCTypeError> {
+        let mut rest = bytes;
+        let mut uuid = None;
+        if version >= ProtocolVersion::V1_20_4 {
+            let (parsed, after) = MCUuid::parse(rest)?;
+            uuid = Some(parsed.uuid());
+            rest = after;
+        }
+        let (url, rest) = MCString::parse_bounded(rest, 32767)?;
+        let (hash, rest) = MCString::parse_bounded(rest, 40)?;
+
+        let (mut forced, mut prompt) = (false, None);
+        if version.number() >= FORCED_SINCE {
+            let (is_forced, rest) = MCBoolean::parse(rest)?;
+            let (has_prompt, rest) = MCBoolean::parse(rest)?;
+            forced = is_forced.value();
+            if has_prompt.value() {
+                prompt = Some(ChatComponent::parse_for(rest, version)?.0);
+            }
+        }
+
+        Ok(ResourcePackEvent { uuid, url: url.string().clone(), hash: hash.string().clone(), forced, prompt })
+    }
+}
+
+/// How the client answers the resource packs the server pushes. The client never
+/// downloads packs, so accepting a pack only reports it as loaded.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum ResourcePackPolicy {
+    /// Accepts the pack and reports it loaded, which satisfies servers requiring it.
+    #[default]
+    Accept,
+    /// Declines the pack, for which servers forcing it kick the player.
+    Decline,
+    /// Accepts the pack, then reports it discarded rather than loaded. Servers older than
+    /// 1.20.3 have no status for discarded packs, and are only told the pack was accepted.
+    AcceptAndDiscard
+}
+
+impl ResourcePackPolicy {
+    /// Gets the statuses the client responds to a pack with, in order, in `version`.
+    /// Servers running 1.20.3 or newer are also told once the pack has been downloaded.
+    pub fn results(&self, version: ProtocolVersion) -> Vec<ResourcePackResult> {
+        let reports_download = version >= ProtocolVersion::V1_20_4;
+        match (self, reports_download) {
+            (ResourcePackPolicy::Accept, true) => vec![ResourcePackResult::Accepted, ResourcePackResult::Downloaded, ResourcePackResult::SuccessfullyLoaded],
+            (ResourcePackPolicy::Accept, false) => vec![ResourcePackResult::Accepted, ResourcePackResult::SuccessfullyLoaded],
+            (ResourcePackPolicy::Decline, _) => vec![ResourcePackResult::Declined],
+            (ResourcePackPolicy::AcceptAndDiscard, true) => vec![ResourcePackResult::Accepted, ResourcePackResult::Downloaded, ResourcePackResult::Discarded],
+            (ResourcePackPolicy::AcceptAndDiscard, false) => vec![ResourcePackResult::Accepted]
+        }
+    }
+}
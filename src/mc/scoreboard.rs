@@ -0,0 +1,181 @@
+//! The scoreboard, whose objectives minigames often show their information in.
+use std::collections::HashMap;
+
+use super::{chat::ChatComponent, error::MCTypeError, packets::play::{Clientbound, clientbound::{ObjectiveKind, ScoreChange, UpdateObjectives}}, version::ProtocolVersion};
+
+/// The places an objective can be displayed in.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum DisplaySlot {
+    /// The tab list, next to the name of each player.
+    List,
+    /// The sidebar, on the right of the screen.
+    Sidebar,
+    /// Below the name of each player.
+    BelowName,
+    /// The sidebar of the players of a team of the color with this index, from black (0)
+    /// to white (15).
+    TeamSidebar(u8)
+}
+
+impl DisplaySlot {
+    /// The number of team colors, which each have a sidebar.
+    const TEAM_COLORS: i32 = 16;
+
+    /// Gets the slot of its ID in the protocol, or `None` if there is no such slot.
+    pub fn from_id(id: i32) -> Option<Self> {
+        Some(match id {
+            0 => DisplaySlot::List,
+            1 => DisplaySlot::Sidebar,
+            2 => DisplaySlot::BelowName,
+            3.. if id < 3 + Self::TEAM_COLORS => DisplaySlot::TeamSidebar((id - 3) as u8),
+            _ => return None
+        })
+    }
+
+    /// Gets the ID of the slot in the protocol.
+    pub fn id(&self) -> i32 {
+        match self {
+            DisplaySlot::List => 0,
+            DisplaySlot::Sidebar => 1,
+            DisplaySlot::BelowName => 2,
+            DisplaySlot::TeamSidebar(color) => 3 + *color as i32
+        }
+    }
+}
+
+/// The score of an entity in an objective.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Score {
+    pub value: i32,
+    /// The name shown in place of the entity's, which servers send since 1.20.3.
+    pub display_name: Option<ChatComponent>
+}
+
+/// An objective of the scoreboard, with the scores of the entities it counts.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Objective {
+    /// The unique name of the objective.
+    pub name: String,
+    /// The name shown above the objective's scores.
+    pub display_name: ChatComponent,
+    pub kind: ObjectiveKind,
+    scores: HashMap<String, Score>
+}
+
+impl Objective {
+    /// Gets the scores of the objective, by the entity they are of.
+    pub fn scores(&self) -> &HashMap<String, Score> {
+        &self.scores
+    }
+
+    /// Gets the score of `entity`, such as the username of a player, if it has one.
+    pub fn score(&self, entity: &str) -> Option<i32> {
+        self.scores.get(entity).map(|score| score.value)
+    }
+
+    /// Gets the scores in the order the sidebar shows them: from the highest to the
+    /// lowest, and by entity for equal scores.
+    pub fn ranking(&self) -> Vec<(&str, &Score)> {
+        let mut ranking: Vec<_> = self.scores.iter().map(|(entity, score)| (entity.as_str(), score)).collect();
+        ranking.sort_by(|(entity, score), (other_entity, other)| other.value.cmp(&score.value).then_with(|| entity.cmp(other_entity)));
+        ranking
+    }
+}
+
+/// The objectives of the scoreboard and where they are displayed, kept up to date with the
+/// Update Objectives, Display Objective, Update Score and Reset Score packets sent by the
+/// server.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Scoreboard {
+    objectives: HashMap<String, Objective>,
+    displayed: HashMap<DisplaySlot, String>
+}
+
+impl Scoreboard {
+    /// Creates a scoreboard without objectives.
+    pub fn new() -> Self {
+        Scoreboard::default()
+    }
+
+    /// Gets the objective named `name`, if it exists.
+    pub fn objective(&self, name: &str) -> Option<&Objective> {
+        self.objectives.get(name)
+    }
+
+    /// Gets every objective of the scoreboard, in no particular order.
+    pub fn objectives(&self) -> impl Iterator<Item = &Objective> {
+        self.objectives.values()
+    }
+
+    /// Gets the objective displayed in `slot`, if any.
+    pub fn displayed(&self, slot: DisplaySlot) -> Option<&Objective> {
+        self.displayed.get(&slot).and_then(|name| self.objectives.get(name))
+    }
+
+    /// Applies the Play packet `packet` of `version` to the scoreboard, if it concerns it.
+    /// Scores of objectives which do not exist are ignored.
+    /// # Errors
+    /// This function will return an `MCTypeError` if an objective or score cannot be
+    /// decoded.
+    pub fn handle(&mut self, packet: &Clientbound, version: ProtocolVersion) -> Result<(), MCTypeError> {
+        match packet {
+            Clientbound::UpdateObjectives(update) => {
+                let change = update.change(version)?;
+                match change.mode {
+                    UpdateObjectives::REMOVE => {
+                        self.objectives.remove(&change.name);
+                        self.displayed.retain(|_, name| *name != change.name);
+                    },
+                    UpdateObjectives::CREATE => {
+                        self.objectives.insert(change.name.clone(), Objective {
+                            name: change.name,
+                            display_name: change.display_name.unwrap_or_default(),
+                            kind: change.kind.unwrap_or_default(),
+                            scores: HashMap::new()
+                        });
+                    },
+                    _ => if let Some(objective) = self.objectives.get_mut(&change.name) {
+                        if let Some(display_name) = change.display_name {
+                            objective.display_name = display_name;
+                        }
+                        if let Some(kind) = change.kind {
+                            objective.kind = kind;
+                        }
+                    }
+                }
+            },
+            Clientbound::DisplayObjective(display) => {
+                let Some(slot) = DisplaySlot::from_id(display.position) else {
+                    return Ok(());
+                };
+                match display.score_name.string().is_empty() {
+                    true => self.displayed.remove(&slot),
+                    false => self.displayed.insert(slot, display.score_name.string().clone())
+                };
+            },
+            Clientbound::UpdateScore(update) => self.apply(update.change(version)?),
+            Clientbound::ResetScore(reset) => self.apply(ScoreChange {
+                entity: reset.entity.string().clone(),
+                objective: reset.objective.as_ref().map(|objective| objective.string().clone()),
+                value: None,
+                display_name: None
+            }),
+            _ => ()
+        }
+        Ok(())
+    }
+
+    fn apply(&mut self, change: ScoreChange) {
+        match (change.value, change.objective) {
+            (Some(value), Some(objective)) => if let Some(objective) = self.objectives.get_mut(&objective) {
+                objective.scores.insert(change.entity, Score { value, display_name: change.display_name });
+            },
+            (None, Some(objective)) => if let Some(objective) = self.objectives.get_mut(&objective) {
+                objective.scores.remove(&change.entity);
+            },
+            (_, None) => for objective in self.objectives.values_mut() {
+                objective.scores.remove(&change.entity);
+            }
+        }
+    }
+}
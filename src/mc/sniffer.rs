@@ -0,0 +1,266 @@
+//! A man-in-the-middle proxy which relays the connections of clients to a server, decoding
+//! every packet on the way so that it can be logged, altered or dropped.
+use std::{fmt, io::{self, BufReader, Cursor, Read, Write}, net::{Shutdown, TcpListener, TcpStream, ToSocketAddrs}, sync::{Arc, Mutex}, thread};
+
+use super::{connection::ConnectionState, dns, limits, mctypes::{MCString, MCType, VarInt}, packet::{read_compressed_packet, read_packet, write_compressed_packet, write_packet, MCPacket, PacketId, InboundPacket, clientbound::{encryption_request::EncryptionRequest, login_success::LoginSuccess, set_compression::SetCompression, finish_configuration::FinishConfiguration}, serverbound::{handshake::Handshake, login_acknowledged::LoginAcknowledged, acknowledge_finish_configuration::AcknowledgeFinishConfiguration}}, registry::{self, Direction, UNMAPPED_ID_BASE}, version::ProtocolVersion, PROTOCOL_VERSION};
+
+type PacketHook = Box<dyn FnMut(&mut SniffedPacket) -> Verdict + Send>;
+
+/// What becomes of a packet once a hook has seen it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Verdict {
+    /// The packet is handed to the next hook, and forwarded once every hook has seen it.
+    Forward,
+    /// The packet is never forwarded, nor seen by the hooks after this one.
+    Drop
+}
+
+/// A packet relayed by a `Sniffer`, as handed to its hooks, or replayed from a capture.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SniffedPacket {
+    pub direction: Direction,
+    /// The protocol state of the connection in the direction of the packet.
+    pub state: ConnectionState,
+    /// The protocol version of the connection, as the client announced in its handshake.
+    pub version: ProtocolVersion,
+    /// The packet under its canonical ID, as per `MinecraftStream::read`, so that it can
+    /// be matched against the typed packets with `is`. A hook may change or replace it,
+    /// and what it leaves is forwarded in its place.
+    pub packet: MCPacket
+}
+
+impl fmt::Display for SniffedPacket {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let direction = match self.direction {
+            Direction::Serverbound => "C -> S",
+            Direction::Clientbound => "S -> C"
+        };
+        write!(f, "{} {:?} 0x{:02X} ({} bytes)", direction, self.state, self.packet.header.id.value(), self.packet.data.len())
+    }
+}
+
+/// The protocol state of a relayed connection, which each direction tracks on its own
+/// since the client and the server switch state at different packets.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct Session {
+    version: ProtocolVersion,
+    serverbound: ConnectionState,
+    clientbound: ConnectionState,
+    compression_threshold: Option<i32>,
+    encrypted: bool
+}
+
+impl Session {
+    /// Creates the session of a connection which is in `state` in both directions, and
+    /// exchanges packets in `version`.
+    pub(crate) fn starting(version: ProtocolVersion, state: ConnectionState) -> Self {
+        Session { version, serverbound: state, clientbound: state, compression_threshold: None, encrypted: false }
+    }
+
+    pub(crate) fn state(&self, direction: Direction) -> ConnectionState {
+        match direction {
+            Direction::Serverbound => self.serverbound,
+            Direction::Clientbound => self.clientbound
+        }
+    }
+
+    /// Interprets the `packet` received in `direction` under its raw ID, translating it to
+    /// its canonical ID and moving the session on as it calls for.
+    /// # Errors
+    /// This function will return an `InvalidData` error if a packet which switches the
+    /// session's state is malformed.
+    pub(crate) fn decode(&mut self, direction: Direction, packet: MCPacket) -> Result<SniffedPacket, io::Error> {
+        let (state, version) = (self.state(direction), self.version);
+        let id = packet.header.id.value();
+        let packet = match registry::canonical_id(version, state, direction, id) {
+            Some(canonical) if canonical == id => packet,
+            Some(canonical) => MCPacket::new(canonical, packet.data),
+            None => MCPacket::new(UNMAPPED_ID_BASE + id, packet.data)
+        };
+        self.observe(direction, &packet)?;
+        Ok(SniffedPacket { direction, state, version, packet })
+    }
+
+    /// Moves the session on as the `packet` received in `direction` calls for, before it is
+    /// forwarded to an endpoint which may answer it at once.
+    fn observe(&mut self, direction: Direction, packet: &MCPacket) -> Result<(), io::Error> {
+        match (direction, self.state(direction)) {
+            (Direction::Serverbound, Handshake::STATE) if packet.is::<Handshake>() => {
+                let (version, rest) = VarInt::parse(&packet.data)?;
+                let (_, rest) = MCString::parse(rest)?;
+                let (next_state, _) = VarInt::parse(rest.get(2..).unwrap_or_default())?;
+                let state = match next_state.value() {
+                    1 => ConnectionState::Status,
+                    _ => ConnectionState::Login
+                };
+                self.version = ProtocolVersion::from(version.value());
+                (self.serverbound, self.clientbound) = (state, state);
+            },
+            (Direction::Serverbound, LoginAcknowledged::STATE) if packet.is::<LoginAcknowledged>() => self.serverbound = ConnectionState::Configuration,
+            (Direction::Serverbound, AcknowledgeFinishConfiguration::STATE) if packet.is::<AcknowledgeFinishConfiguration>() => self.serverbound = ConnectionState::Play,
+            (Direction::Clientbound, SetCompression::STATE) if packet.is::<SetCompression>() => {
+                let threshold = SetCompression::from_data(packet)?.threshold.value();
+                self.compression_threshold = Some(threshold).filter(|threshold| *threshold >= 0);
+            },
+            (Direction::Clientbound, EncryptionRequest::STATE) if packet.is::<EncryptionRequest>() => self.encrypted = true,
+            (Direction::Clientbound, LoginSuccess::STATE) if packet.is::<LoginSuccess>() => match self.version < ProtocolVersion::V1_20_2 {
+                true => (self.serverbound, self.clientbound) = (ConnectionState::Play, ConnectionState::Play),
+                false => self.clientbound = ConnectionState::Configuration
+            },
+            (Direction::Clientbound, FinishConfiguration::STATE) if packet.is::<FinishConfiguration>() => self.clientbound = ConnectionState::Play,
+            _ => ()
+        }
+        Ok(())
+    }
+}
+
+/// A proxy which listens for clients, connects each of them to a target server and
+/// relays their packets in both directions through its hooks, handling compression. This
+/// is meant for debugging the protocol against real clients and servers.
+/// <br> <br>
+/// The sniffer holds no keys, so it can only decode the connections of offline-mode
+/// servers. Once a server asks for encryption, the rest of the connection is relayed
+/// as opaque bytes, without being seen by the hooks.
+/// # Example
+/// ```no_run
+/// use mcclient::mc::sniffer::{Sniffer, Verdict};
+///
+/// let mut sniffer = Sniffer::new("localhost:25565");
+/// sniffer.on_packet(|sniffed| {
+///     println!("{}", sniffed);
+///     Verdict::Forward
+/// });
+/// sniffer.listen("127.0.0.1:25566").expect("Could not listen");
+/// ```
+#[derive(Clone)]
+pub struct Sniffer {
+    target: String,
+    hooks: Arc<Mutex<Vec<PacketHook>>>
+}
+
+impl Sniffer {
+    /// Creates a sniffer relaying clients to the server at `target`, given as `host` or
+    /// `host:port` and resolved as per `dns::resolve` for every client.
+    pub fn new<A: Into<String>>(target: A) -> Self {
+        Sniffer { target: target.into(), hooks: Arc::new(Mutex::new(Vec::new())) }
+    }
+
+    /// Registers `hook` for every packet relayed, in either direction. Hooks are called
+    /// in the order they were registered, one packet at a time across every connection.
+    pub fn on_packet<F>(&mut self, hook: F)
+    where F: FnMut(&mut SniffedPacket) -> Verdict + Send + 'static {
+        self.hooks.lock().unwrap_or_else(|poisoned| poisoned.into_inner()).push(Box::new(hook));
+    }
+
+    /// Listens for clients on `address`, relaying each of them on threads of its own as
+    /// per `relay`. This blocks for as long as the listener accepts connections.
+    /// # Errors
+    /// This function will return an error if the address cannot be listened on, or if
+    /// accepting a client fails. Errors of the relayed connections are dropped.
+    pub fn listen<A: ToSocketAddrs>(&self, address: A) -> Result<(), io::Error> {
+        let listener = TcpListener::bind(address)?;
+        for client in listener.incoming() {
+            let client = client?;
+            let sniffer = self.clone();
+            thread::spawn(move || sniffer.relay(client));
+        }
+        Ok(())
+    }
+
+    /// Connects `client` to the target server, and relays their packets until either of
+    /// them closes the connection.
+    /// # Errors
+    /// This function will return an error if the server cannot be reached, or an
+    /// `InvalidData` error if either endpoint sends a malformed packet, after which the
+    /// connection is closed.
+    pub fn relay(&self, client: TcpStream) -> Result<(), io::Error> {
+        let (host, port) = dns::resolve(&self.target)?;
+        let server = TcpStream::connect((host.as_str(), port))?;
+        let session = Arc::new(Mutex::new(Session::starting(PROTOCOL_VERSION.into(), ConnectionState::Handshaking)));
+
+        let serverbound = {
+            let (client, server, session, hooks) = (client.try_clone()?, server.try_clone()?, session.clone(), self.hooks.clone());
+            thread::spawn(move || relay_direction(Direction::Serverbound, client, server, &session, &hooks))
+        };
+        let clientbound = relay_direction(Direction::Clientbound, server, client, &session, &self.hooks);
+        let serverbound = serverbound.join().map_err(|_| io::Error::other("The serverbound relay panicked."))?;
+        clientbound.and(serverbound)
+    }
+}
+
+/// Relays the packets `from` sends to `to` until either closes, then closes both.
+fn relay_direction(direction: Direction, from: TcpStream, mut to: TcpStream, session: &Mutex<Session>, hooks: &Mutex<Vec<PacketHook>>) -> Result<(), io::Error> {
+    let mut reader = BufReader::new(from);
+    let result = loop {
+        match relay_packet(direction, &mut reader, &mut to, session, hooks) {
+            Ok(true) => (),
+            Ok(false) => break io::copy(&mut reader, &mut to).map(|_| ()),
+            Err(err) if err.kind() == io::ErrorKind::UnexpectedEof => break Ok(()),
+            Err(err) => break Err(err)
+        }
+    };
+
+    let _ = reader.get_ref().shutdown(Shutdown::Both);
+    let _ = to.shutdown(Shutdown::Both);
+    result
+}
+
+/// Relays the next packet `reader` receives to `to`, through the hooks. Returns whether
+/// the connection can still be decoded, which it cannot once encrypted.
+fn relay_packet<R: Read>(direction: Direction, reader: &mut R, to: &mut TcpStream, session: &Mutex<Session>, hooks: &Mutex<Vec<PacketHook>>) -> Result<bool, io::Error> {
+    // The frame is read whole before being decoded, as the framing may change while
+    // waiting for it.
+    let frame = read_frame(reader)?;
+    // The session is only locked while the packet updates it, so that neither the hooks
+    // nor a slow peer hold up the other direction.
+    let (mut sniffed, threshold, encrypted) = {
+        let mut session = session.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        if session.encrypted {
+            drop(session);
+            to.write_all(&frame)?;
+            return Ok(false);
+        }
+
+        let threshold = session.compression_threshold;
+        let packet = match threshold {
+            Some(_) => read_compressed_packet(&mut Cursor::new(&frame))?,
+            None => read_packet(&mut Cursor::new(&frame))?
+        };
+        (session.decode(direction, packet)?, threshold, session.encrypted)
+    };
+    let (state, version) = (sniffed.state, sniffed.version);
+    let dropped = hooks.lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .iter_mut()
+        .any(|hook| hook(&mut sniffed) == Verdict::Drop);
+    if dropped {
+        return Ok(!encrypted);
+    }
+
+    let id = sniffed.packet.header.id.value();
+    let raw_id = match id >= UNMAPPED_ID_BASE {
+        true => id - UNMAPPED_ID_BASE,
+        false => registry::packet_id(version, state, direction, id).unwrap_or(id)
+    };
+    let packet = MCPacket::new(raw_id, sniffed.packet.data);
+    let mut outbound = Vec::new();
+    match threshold {
+        Some(threshold) => write_compressed_packet(&mut outbound, &packet, threshold)?,
+        None => write_packet(&mut outbound, &packet)?
+    }
+    to.write_all(&outbound)?;
+    Ok(!encrypted)
+}
+
+/// Reads the next frame from `reader`, with its length prefix, without interpreting it.
+fn read_frame<R: Read>(reader: &mut R) -> Result<Vec<u8>, io::Error> {
+    let length = VarInt::read_from(reader)?;
+    let size = limits::packet_size(length.value())
+        .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+
+    let mut frame = length.to_bytes();
+    let prefix = frame.len();
+    frame.resize(prefix + size, 0);
+    reader.read_exact(&mut frame[prefix..])?;
+    Ok(frame)
+}
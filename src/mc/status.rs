@@ -0,0 +1,380 @@
+use std::time::Duration;
+#[cfg(feature = "std")]
+use std::{io::{Read, Write}, net::TcpStream, time::Instant};
+
+use serde::Deserialize;
+use serde_json::Value;
+
+use super::{chat::{strip_ansi_codes, strip_section_codes, ChatComponent}, error::MCTypeError, io, limits, mctypes::{MCLong, MCString, MCType, MCUnsignedShort, VarInt}, transport::AsyncTransport, PROTOCOL_VERSION};
+#[cfg(feature = "std")]
+use super::{chat::SECTION_SIGN, connection::{Connection, MinecraftStream, OfflineConnection}, dns, packet::clientbound::status_response::StatusResponse, proxy::ProxyConfig};
+
+/// The port Minecraft servers listen on unless another is given.
+pub const DEFAULT_PORT: u16 = 25565;
+
+/// The status of a server as shown in the multiplayer server list.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ServerStatus {
+    /// The message of the day, flattened to plain text. Legacy formatting codes are kept.
+    pub motd: String,
+    /// The name of the version the server runs, such as `1.19.3` or the name of a proxy.
+    pub version_name: String,
+    /// The protocol number the server speaks.
+    pub protocol: i32,
+    pub players_online: i32,
+    pub players_max: i32,
+    /// The players (or decorative lines) listed on hover, if the server sends any.
+    pub sample: Vec<PlayerSample>,
+    /// The server icon as a `data:image/png;base64,` URL, if the server has one.
+    pub favicon: Option<String>,
+    /// The measured round-trip time of a ping to the server.
+    pub latency: Duration
+}
+
+impl ServerStatus {
+    /// Builds a `ServerStatus` out of a status response and the latency measured for it.
+    /// Fields which the server leaves out are reported as empty or 0.
+    /// # Errors
+    /// This function will return an `InvalidData` error if the response is not valid JSON.
+    #[cfg(feature = "std")]
+    pub fn from_response(response: &StatusResponse, latency: Duration) -> Result<Self, io::Error> {
+        ServerStatus::from_json(response.json_response.string(), latency)
+    }
+
+    /// Builds a `ServerStatus` out of the JSON of a status response, as per `from_response`.
+    /// # Errors
+    /// This function will return an `InvalidData` error if `json` is not valid JSON.
+    pub fn from_json(json: &str, latency: Duration) -> Result<Self, io::Error> {
+        let json: Value = serde_json::from_str(json)?;
+        let number = |value: &Value| value.as_i64().and_then(|n| i32::try_from(n).ok()).unwrap_or_default();
+
+        Ok(ServerStatus {
+            motd: ChatComponent::deserialize(&json["description"]).map(|motd| motd.to_plain_text()).unwrap_or_default(),
+            version_name: json["version"]["name"].as_str().unwrap_or_default().to_owned(),
+            protocol: number(&json["version"]["protocol"]),
+            players_online: number(&json["players"]["online"]),
+            players_max: number(&json["players"]["max"]),
+            sample: players_sample(&json),
+            favicon: json["favicon"].as_str().map(str::to_owned),
+            latency
+        })
+    }
+
+    /// Gets the message of the day with legacy formatting codes removed.
+    pub fn clean_motd(&self) -> String {
+        strip_section_codes(&self.motd)
+    }
+}
+
+/// An entry of the `players.sample` list of a status response, which servers
+/// commonly use to list some online players on hover in the server list.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PlayerSample {
+    name: String,
+    id: String
+}
+
+impl PlayerSample {
+    /// Gets the name of the player as sent by the server, including any formatting codes.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Gets the name of the player with legacy formatting codes and ANSI escape
+    /// sequences removed.
+    pub fn clean_name(&self) -> String {
+        strip_ansi_codes(&strip_section_codes(&self.name))
+    }
+
+    /// Gets the UUID of the player as a hyphenated string. Servers listing decorative
+    /// text rather than players often send a nil UUID.
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+}
+
+/// Gets the `players.sample` list of the status JSON `json`. Servers which do not send a
+/// sample report an empty list, and malformed entries are skipped.
+pub(crate) fn players_sample(json: &Value) -> Vec<PlayerSample> {
+    let sample = match json["players"]["sample"].as_array() {
+        Some(sample) => sample,
+        None => return vec![]
+    };
+
+    sample.iter()
+        .filter_map(|entry| Some(PlayerSample {
+            name: entry["name"].as_str()?.to_owned(),
+            id: entry["id"].as_str()?.to_owned()
+        }))
+        .collect()
+}
+
+/// Splits `address` into a host and a port, defaulting to `DEFAULT_PORT`. IPv6
+/// addresses with a port must be enclosed in brackets, as in `[::1]:25565`.
+/// # Errors
+/// This function will return an `InvalidInput` error if the port is not a valid number.
+pub fn split_address(address: &str) -> Result<(&str, u16), io::Error> {
+    let (host, port) = parse_address(address)?;
+    Ok((host, port.unwrap_or(DEFAULT_PORT)))
+}
+
+/// Splits `address` into a host and its port, if it has one, as per `split_address`.
+/// # Errors
+/// This function will return an `InvalidInput` error if the port is not a valid number.
+pub fn parse_address(address: &str) -> Result<(&str, Option<u16>), io::Error> {
+    let invalid_port = || io::Error::new(io::ErrorKind::InvalidInput, format!("Invalid port in address {}.", address));
+
+    if let Some(rest) = address.strip_prefix('[') {
+        return match rest.split_once(']') {
+            Some((host, "")) => Ok((host, None)),
+            Some((host, port)) => Ok((host, Some(port.strip_prefix(':').and_then(|port| port.parse().ok()).ok_or_else(invalid_port)?))),
+            None => Err(io::Error::new(io::ErrorKind::InvalidInput, format!("Unclosed bracket in address {}.", address)))
+        };
+    }
+
+    match address.split_once(':') {
+        Some((host, port)) if !port.contains(':') => Ok((host, Some(port.parse().map_err(|_| invalid_port())?))),
+        _ => Ok((address, None))
+    }
+}
+
+/// Fetches the status of the server at `address`, given as `host` or `host:port`, as
+/// shown in the multiplayer server list. The latency is measured with a ping following
+/// the status request. Servers which do not answer the status request are retried
+/// with a legacy ping, as per `legacy_status`. Addresses without a port are resolved
+/// through their SRV record, as per `dns::resolve`.
+/// # Example
+/// ```no_run
+/// let status = mcclient::status("localhost:25565").expect("Could not get status");
+/// println!("{} ({}/{}) in {:?}", status.clean_motd(), status.players_online, status.players_max, status.latency);
+/// ```
+/// # Errors
+/// This function will return an error if the server cannot be reached, or if it does not
+/// respond with a well-formed status.
+#[cfg(feature = "std")]
+pub fn status(address: &str) -> Result<ServerStatus, io::Error> {
+    let (host, port) = dns::resolve(address)?;
+    status_at(&host, port, None)
+}
+
+/// Fetches the status of the server at `address` through `proxy`, as per `status`. The
+/// SRV record of the address is looked up directly rather than through the proxy.
+/// # Errors
+/// This function will return an error if the proxy or the server cannot be reached, or
+/// if the server does not respond with a well-formed status.
+#[cfg(feature = "std")]
+pub fn status_via(address: &str, proxy: &ProxyConfig) -> Result<ServerStatus, io::Error> {
+    let (host, port) = dns::resolve(address)?;
+    status_at(&host, port, Some(proxy))
+}
+
+#[cfg(feature = "std")]
+fn status_at(host: &str, port: u16, proxy: Option<&ProxyConfig>) -> Result<ServerStatus, io::Error> {
+    let connect = || match proxy {
+        Some(proxy) => proxy.connect(host, port),
+        None => TcpStream::connect((host, port))
+    };
+    let mut connection = OfflineConnection::with_stream(MinecraftStream::new(connect()?), host, port);
+
+    let modern = connection.status().and_then(|response| {
+        let start = Instant::now();
+        connection.ping()?;
+        ServerStatus::from_response(&response, start.elapsed())
+    });
+
+    match modern {
+        Ok(status) => Ok(status),
+        Err(err) => connect()
+            .and_then(|mut stream| legacy_ping(&mut stream, host, port))
+            .map_err(|_| err)
+    }
+}
+
+/// Fetches the status of the server at `host` and `port` over `transport`, an established
+/// connection to it, as per `status` but without falling back to the legacy ping. This
+/// needs neither sockets nor a clock of its own, so it runs wherever the transport does,
+/// such as in a browser: `clock` gives the time elapsed since any fixed instant, such as
+/// `performance.now()`, and the latency is measured with it.
+/// <br>
+/// Built with the `wasm` feature rather than `std`, only the parts of this module which
+/// need no sockets, such as this and `ServerStatus`, are built, and the crate builds for
+/// `wasm32-unknown-unknown`.
+/// # Errors
+/// This function will return an error if the transport fails or closes, or if the server
+/// does not respond with a well-formed status.
+pub async fn status_over<T, C>(transport: &mut T, host: &str, port: u16, mut clock: C) -> Result<ServerStatus, io::Error>
+where T: AsyncTransport, C: FnMut() -> Duration {
+    let mut request = frame(STATUS_ID, &[&VarInt::from(PROTOCOL_VERSION), &MCString::from(host), &MCUnsignedShort::from(port), &VarInt::from(NEXT_STATE_STATUS)]);
+    request.extend(frame(STATUS_ID, &[]));
+    transport.send(&request).await?;
+
+    let mut inbound = Vec::new();
+    let response = receive_packet(transport, &mut inbound, STATUS_ID).await?;
+    let (json, _) = MCString::parse(&response)?;
+
+    let start = clock();
+    let payload = start.as_millis() as i64;
+    transport.send(&frame(PING_ID, &[&MCLong::from(payload)])).await?;
+    let (pong, _) = MCLong::parse(&receive_packet(transport, &mut inbound, PING_ID).await?)?;
+    if pong.value() != payload {
+        return Err(io::Error::new(io::ErrorKind::InvalidData, "Ping response does not echo the ping payload."));
+    }
+
+    ServerStatus::from_json(json.string(), clock().saturating_sub(start))
+}
+
+/// The ID of the Handshake, Status Request and Status Response packets.
+const STATUS_ID: i32 = 0x00;
+/// The ID of the Ping Request and Ping Response packets.
+const PING_ID: i32 = 0x01;
+/// The state a Handshake asks to move to for a status ping.
+const NEXT_STATE_STATUS: i32 = 1;
+
+/// Encodes the uncompressed packet of ID `id` made of `fields`, prefixed with its length,
+/// as `packet::write_packet` does. The status ping is encoded with this rather than with
+/// its packet structures, which need the `std` feature.
+fn frame(id: i32, fields: &[&dyn MCType]) -> Vec<u8> {
+    let mut body = VarInt::from(id).to_bytes();
+    for field in fields {
+        body.extend(field.to_bytes());
+    }
+
+    let mut frame = VarInt::from(body.len() as i32).to_bytes();
+    frame.extend(body);
+    frame
+}
+
+/// Receives the next uncompressed packet from `transport`, which must have the ID `id`,
+/// returning its fields. The bytes received past its end are kept in `inbound` for the
+/// next one.
+async fn receive_packet<T: AsyncTransport>(transport: &mut T, inbound: &mut Vec<u8>, id: i32) -> Result<Vec<u8>, io::Error> {
+    let mut chunk = [0; 4096];
+    loop {
+        match VarInt::parse(inbound) {
+            Ok((length, rest)) => {
+                let length = limits::packet_size(length.value())
+                    .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+                if length <= rest.len() {
+                    let (packet_id, fields) = VarInt::parse(&rest[..length])?;
+                    if packet_id.value() != id {
+                        return Err(io::Error::new(io::ErrorKind::InvalidData, "Invalid packet ID."));
+                    }
+                    let fields = fields.to_vec();
+                    let consumed = inbound.len() - rest.len() + length;
+                    inbound.drain(..consumed);
+                    return Ok(fields);
+                }
+            },
+            Err(MCTypeError::Truncated) => {},
+            Err(err) => return Err(err.into())
+        }
+
+        match transport.receive(&mut chunk).await? {
+            0 => return Err(io::Error::new(io::ErrorKind::UnexpectedEof, "The server closed the connection before its response arrived.")),
+            received => inbound.extend_from_slice(&chunk[..received])
+        }
+    }
+}
+
+/// The packet ID of the legacy (pre-1.7) Server List Ping.
+#[cfg(feature = "std")]
+const LEGACY_PING_ID: u8 = 0xFE;
+/// The packet ID of the legacy Kick packet the server responds to a legacy ping with.
+#[cfg(feature = "std")]
+const LEGACY_KICK_ID: u8 = 0xFF;
+/// The protocol number sent in the 1.6 extension of the legacy ping.
+#[cfg(feature = "std")]
+const LEGACY_PROTOCOL_VERSION: u8 = 74;
+
+/// Fetches the status of the server at `address` with the legacy Server List Ping
+/// spoken by servers before 1.7, which some older or modified servers still answer.
+/// Legacy responses carry no player sample or favicon. The latency is measured as the
+/// time taken for the response to arrive. The address is resolved as per `status`.
+/// # Errors
+/// This function will return an error if the server cannot be reached, or if it does not
+/// respond with a well-formed legacy status.
+#[cfg(feature = "std")]
+pub fn legacy_status(address: &str) -> Result<ServerStatus, io::Error> {
+    let (host, port) = dns::resolve(address)?;
+    let mut stream = TcpStream::connect((host.as_str(), port))?;
+
+    legacy_ping(&mut stream, &host, port)
+}
+
+/// Performs a legacy Server List Ping over `transport`, describing the server as
+/// `host` and `port` for servers which support the 1.6 extension of the ping.
+/// # Errors
+/// This function will return an error if the ping cannot be sent, or if the response
+/// is not a well-formed legacy status.
+#[cfg(feature = "std")]
+pub fn legacy_ping<T: Read + Write>(transport: &mut T, host: &str, port: u16) -> Result<ServerStatus, io::Error> {
+    let mut ping = vec![LEGACY_PING_ID, 0x01, 0xFA];
+    ping.extend(ucs2_string("MC|PingHost"));
+    let host = ucs2_string(host);
+    ping.extend((1 + host.len() as u16 + 4).to_be_bytes());
+    ping.push(LEGACY_PROTOCOL_VERSION);
+    ping.extend(host);
+    ping.extend((port as i32).to_be_bytes());
+
+    let start = Instant::now();
+    transport.write_all(&ping)?;
+    transport.flush()?;
+
+    let mut header = [0_u8; 3];
+    transport.read_exact(&mut header)?;
+    if header[0] != LEGACY_KICK_ID {
+        return Err(io::Error::new(io::ErrorKind::InvalidData, format!("Legacy ping answered with packet {:#04x}.", header[0])));
+    }
+
+    let mut response = vec![0_u8; u16::from_be_bytes([header[1], header[2]]) as usize * 2];
+    transport.read_exact(&mut response)?;
+    let latency = start.elapsed();
+
+    let units: Vec<u16> = response.chunks_exact(2).map(|unit| u16::from_be_bytes([unit[0], unit[1]])).collect();
+    let response = String::from_utf16(&units)
+        .map_err(|_| io::Error::new(io::ErrorKind::InvalidData, "Legacy ping response is not valid UCS-2."))?;
+
+    parse_legacy_response(&response, latency)
+}
+
+/// Parses the string of a legacy Kick packet. Servers since 1.4 send `§1`, followed by
+/// the protocol, version name, MOTD, and player counts separated by NUL characters;
+/// older servers send the MOTD and player counts separated by `§`.
+#[cfg(feature = "std")]
+fn parse_legacy_response(response: &str, latency: Duration) -> Result<ServerStatus, io::Error> {
+    let malformed = || io::Error::new(io::ErrorKind::InvalidData, "Malformed legacy ping response.");
+    let number = |field: &str| field.parse::<i32>().map_err(|_| malformed());
+
+    let (protocol, version_name, motd, online, max) = match response.strip_prefix(&format!("{}1\0", SECTION_SIGN)) {
+        Some(fields) => match fields.split('\0').collect::<Vec<_>>()[..] {
+            [protocol, version_name, motd, online, max] => (number(protocol)?, version_name, motd, online, max),
+            _ => return Err(malformed())
+        },
+        None => match response.rsplitn(3, SECTION_SIGN).collect::<Vec<_>>()[..] {
+            [max, online, motd] => (0, "", motd, online, max),
+            _ => return Err(malformed())
+        }
+    };
+
+    Ok(ServerStatus {
+        motd: motd.to_owned(),
+        version_name: version_name.to_owned(),
+        protocol,
+        players_online: number(online)?,
+        players_max: number(max)?,
+        sample: vec![],
+        favicon: None,
+        latency
+    })
+}
+
+/// Encodes `string` as a legacy string: its length in UTF-16 code units as a big-endian
+/// short, followed by its big-endian UCS-2 code units.
+#[cfg(feature = "std")]
+fn ucs2_string(string: &str) -> Vec<u8> {
+    let units: Vec<u16> = string.encode_utf16().collect();
+    let mut bytes = (units.len() as u16).to_be_bytes().to_vec();
+    bytes.extend(units.iter().flat_map(|unit| unit.to_be_bytes()));
+    bytes
+}
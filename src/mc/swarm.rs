@@ -0,0 +1,224 @@
+//! Swarms of bots joining a server at once, for load testing, available with the `tokio`
+//! feature.
+use std::{io, sync::{atomic::{AtomicU64, Ordering}, Arc, Mutex}, time::{Duration, Instant}};
+
+use tokio::task::JoinSet;
+
+use super::{async_connection::{resolve, AsyncOfflineConnection}, connection::ConnectionState, events::Event, packets::play::Clientbound, version::ProtocolVersion};
+
+type SwarmHandler = Arc<dyn Fn(usize, &Event) + Send + Sync>;
+
+/// The counters of a swarm, shared by its bots.
+#[derive(Default)]
+struct Counters {
+    connected: AtomicU64,
+    joined: AtomicU64,
+    failed: AtomicU64,
+    disconnected: AtomicU64,
+    packets: AtomicU64,
+    decode_failures: AtomicU64
+}
+
+/// The aggregate statistics of a swarm at one point in time.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SwarmStats {
+    /// The number of bots currently in the game.
+    pub connected: u64,
+    /// The number of bots which have joined, including those since disconnected.
+    pub joined: u64,
+    /// The number of bots which could not join.
+    pub failed: u64,
+    /// The number of bots which were disconnected after joining.
+    pub disconnected: u64,
+    /// The number of packets the bots have received in the game.
+    pub packets_received: u64,
+    /// The number of packets received in the game which could not be decoded, and whose
+    /// events were skipped.
+    pub decode_failures: u64,
+    /// The time since the swarm started running.
+    pub elapsed: Duration
+}
+
+impl SwarmStats {
+    /// Gets the average number of packets the bots have received per second.
+    pub fn packets_per_second(&self) -> f64 {
+        match self.elapsed.is_zero() {
+            true => 0.0,
+            false => self.packets_received as f64 / self.elapsed.as_secs_f64()
+        }
+    }
+}
+
+/// A number of bots joining an offline-mode server concurrently, one after another, and
+/// reading on until they are disconnected. Every bot hands the events it receives to the
+/// same handlers. This is meant for stress testing servers.
+/// # Example
+/// ```no_run
+/// # async fn run() {
+/// use std::time::Duration;
+/// use mcclient::mc::{events::Event, swarm::Swarm};
+///
+/// let mut swarm = Swarm::new("localhost:25565", 50).stagger(Duration::from_millis(200));
+/// swarm.on_event(|bot, event| {
+///     if let Event::ChatReceived { message, .. } = event {
+///         println!("Bot {} read: {}", bot, message);
+///     }
+/// });
+/// let stats = swarm.run().await;
+/// println!("{} joined, {} failed, {:.1} packets/s", stats.joined, stats.failed, stats.packets_per_second());
+/// # }
+/// ```
+pub struct Swarm {
+    address: String,
+    bots: usize,
+    username_prefix: String,
+    stagger: Duration,
+    version: Option<ProtocolVersion>,
+    handlers: Vec<SwarmHandler>,
+    counters: Arc<Counters>,
+    started: Mutex<Option<Instant>>
+}
+
+impl Swarm {
+    /// Creates a swarm of `bots` bots joining the server at `address`, given as `host` or
+    /// `host:port` and resolved as per `dns::resolve`. Bots are named `Bot0`, `Bot1` and
+    /// so on, and join 100 milliseconds apart.
+    pub fn new<A: Into<String>>(address: A, bots: usize) -> Self {
+        Swarm {
+            address: address.into(),
+            bots,
+            username_prefix: "Bot".to_owned(),
+            stagger: Duration::from_millis(100),
+            version: None,
+            handlers: Vec::new(),
+            counters: Arc::new(Counters::default()),
+            started: Mutex::new(None)
+        }
+    }
+
+    /// Names the bots `prefix` followed by their index. Names are limited to 16
+    /// characters by the server.
+    pub fn username_prefix<P: Into<String>>(mut self, prefix: P) -> Self {
+        self.username_prefix = prefix.into();
+        self
+    }
+
+    /// Starts each bot `stagger` after the one before it, so that the server is not asked
+    /// to log them all in at once.
+    pub fn stagger(mut self, stagger: Duration) -> Self {
+        self.stagger = stagger;
+        self
+    }
+
+    /// Joins in the protocol version `version`, rather than in the version the server
+    /// reports, which every bot asks for otherwise.
+    pub fn version(mut self, version: ProtocolVersion) -> Self {
+        self.version = Some(version);
+        self
+    }
+
+    /// Registers `handler` for every event any bot receives in the game, which is given
+    /// the index of the bot, from 0.
+    pub fn on_event<F>(&mut self, handler: F)
+    where F: Fn(usize, &Event) + Send + Sync + 'static {
+        self.handlers.push(Arc::new(handler));
+    }
+
+    /// Runs the swarm until every bot has failed to join or has been disconnected, and
+    /// returns the final statistics. `stats` can be read meanwhile.
+    pub async fn run(&self) -> SwarmStats {
+        *self.started.lock().unwrap_or_else(|poisoned| poisoned.into_inner()) = Some(Instant::now());
+        let handlers = Arc::new(self.handlers.clone());
+        let mut bots = JoinSet::new();
+        for index in 0..self.bots {
+            let bot = Bot {
+                index,
+                address: self.address.clone(),
+                username: format!("{}{}", self.username_prefix, index),
+                version: self.version,
+                handlers: handlers.clone(),
+                counters: self.counters.clone()
+            };
+            let delay = self.stagger.saturating_mul(index as u32);
+            bots.spawn(async move {
+                tokio::time::sleep(delay).await;
+                bot.run().await
+            });
+        }
+        while bots.join_next().await.is_some() {}
+        self.stats()
+    }
+
+    /// Gets the statistics of the swarm so far.
+    pub fn stats(&self) -> SwarmStats {
+        let count = |counter: &AtomicU64| counter.load(Ordering::Relaxed);
+        let started = *self.started.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        SwarmStats {
+            connected: count(&self.counters.connected),
+            joined: count(&self.counters.joined),
+            failed: count(&self.counters.failed),
+            disconnected: count(&self.counters.disconnected),
+            packets_received: count(&self.counters.packets),
+            decode_failures: count(&self.counters.decode_failures),
+            elapsed: started.map(|started| started.elapsed()).unwrap_or_default()
+        }
+    }
+}
+
+/// One bot of a swarm.
+struct Bot {
+    index: usize,
+    address: String,
+    username: String,
+    version: Option<ProtocolVersion>,
+    handlers: Arc<Vec<SwarmHandler>>,
+    counters: Arc<Counters>
+}
+
+impl Bot {
+    async fn run(self) {
+        let mut connection = match self.join().await {
+            Ok(connection) => connection,
+            Err(_) => {
+                self.counters.failed.fetch_add(1, Ordering::Relaxed);
+                return;
+            }
+        };
+        self.counters.joined.fetch_add(1, Ordering::Relaxed);
+        self.counters.connected.fetch_add(1, Ordering::Relaxed);
+
+        while self.receive(&mut connection).await.is_ok() {}
+        drop(connection);
+        self.counters.connected.fetch_sub(1, Ordering::Relaxed);
+        self.counters.disconnected.fetch_add(1, Ordering::Relaxed);
+    }
+
+    async fn join(&self) -> Result<AsyncOfflineConnection, io::Error> {
+        let (host, port) = resolve(&self.address).await?;
+        let mut connection = AsyncOfflineConnection::negotiate(host, port, self.version).await?;
+        connection.login(&self.username).await?;
+        Ok(connection)
+    }
+
+    /// Reads the next packet, handing the events it carries to the handlers. Only failing
+    /// to read ends the bot; packets which cannot be decoded are counted and skipped.
+    async fn receive(&self, connection: &mut AsyncOfflineConnection) -> Result<(), io::Error> {
+        let packet = connection.sock().read().await?;
+        self.counters.packets.fetch_add(1, Ordering::Relaxed);
+        if self.handlers.is_empty() || connection.sock().state() != ConnectionState::Play {
+            return Ok(());
+        }
+
+        let version = connection.sock().version();
+        let Ok(packet) = Clientbound::decode(packet) else {
+            self.counters.decode_failures.fetch_add(1, Ordering::Relaxed);
+            return Ok(());
+        };
+        for event in Event::from_packet_for(&packet, version) {
+            for handler in self.handlers.iter() {
+                handler(self.index, &event);
+            }
+        }
+        Ok(())
+    }
+}
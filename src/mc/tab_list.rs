@@ -0,0 +1,118 @@
+use std::collections::HashMap;
+
+use uuid::Uuid;
+
+use super::{chat::ChatComponent, packet::clientbound::login_success::ProfileProperty, packets::play::{Clientbound, clientbound::PlayerInfoUpdate}};
+
+/// A player listed in the tab list.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct TabListEntry {
+    pub uuid: Uuid,
+    pub name: String,
+    /// The profile properties of the player, such as its skin.
+    pub properties: Vec<ProfileProperty>,
+    /// The game mode of the player: survival (0), creative (1), adventure (2) or
+    /// spectator (3).
+    pub game_mode: i32,
+    /// Whether the player is shown in the tab list. Unlisted players are still known to
+    /// the client, such as for their skins.
+    pub listed: bool,
+    /// The latency of the player in milliseconds.
+    pub latency: i32,
+    /// The JSON text component shown in place of the player's name, if any.
+    pub display_name: Option<String>
+}
+
+impl TabListEntry {
+    /// The display name of the player as a component, or its name if it has none.
+    pub fn display_component(&self) -> ChatComponent {
+        self.display_name.as_deref()
+            .and_then(|display_name| ChatComponent::from_json(display_name).ok())
+            .unwrap_or_else(|| ChatComponent::text(&self.name))
+    }
+}
+
+/// The players of the tab list, kept up to date with the Player Info Update and Player
+/// Info Remove packets sent by the server.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct TabList {
+    players: HashMap<Uuid, TabListEntry>
+}
+
+impl TabList {
+    /// Creates an empty tab list.
+    pub fn new() -> Self {
+        TabList::default()
+    }
+
+    /// Applies the Play packet `packet` to the tab list, if it concerns it. Updates to
+    /// players which were never added are ignored.
+    pub fn handle(&mut self, packet: &Clientbound) {
+        match packet {
+            Clientbound::PlayerInfoUpdate(update) => {
+                for entry in &update.entries {
+                    if let Some(name) = &entry.name {
+                        self.players.insert(entry.uuid, TabListEntry {
+                            uuid: entry.uuid,
+                            name: name.clone(),
+                            properties: entry.properties.clone(),
+                            ..Default::default()
+                        });
+                    }
+                    let Some(player) = self.players.get_mut(&entry.uuid) else {
+                        continue;
+                    };
+                    if let Some(game_mode) = entry.game_mode {
+                        player.game_mode = game_mode;
+                    }
+                    if let Some(listed) = entry.listed {
+                        player.listed = listed;
+                    }
+                    if let Some(latency) = entry.latency {
+                        player.latency = latency;
+                    }
+                    if update.has(PlayerInfoUpdate::UPDATE_DISPLAY_NAME) {
+                        player.display_name = entry.display_name.clone();
+                    }
+                }
+            },
+            Clientbound::PlayerInfoRemove(remove) => {
+                for uuid in &remove.uuids {
+                    self.players.remove(uuid);
+                }
+            },
+            _ => ()
+        }
+    }
+
+    /// Gets the player `uuid`, if it is known.
+    pub fn get(&self, uuid: &Uuid) -> Option<&TabListEntry> {
+        self.players.get(uuid)
+    }
+
+    /// Gets the player named `name`, ignoring case as the server does.
+    pub fn find_by_name(&self, name: &str) -> Option<&TabListEntry> {
+        self.iter().find(|player| player.name.eq_ignore_ascii_case(name))
+    }
+
+    /// Iterates over the known players, in no particular order.
+    pub fn iter(&self) -> impl Iterator<Item = &TabListEntry> {
+        self.players.values()
+    }
+
+    /// Gets the players shown in the tab list, sorted by name.
+    pub fn listed(&self) -> Vec<&TabListEntry> {
+        let mut players: Vec<&TabListEntry> = self.iter().filter(|player| player.listed).collect();
+        players.sort_by_key(|player| player.name.to_lowercase());
+        players
+    }
+
+    /// The number of known players.
+    pub fn len(&self) -> usize {
+        self.players.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.players.is_empty()
+    }
+}
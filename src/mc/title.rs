@@ -0,0 +1,154 @@
+//! The titles shown in the middle of the screen, and the message shown above the hotbar,
+//! which minigames often prompt players with.
+use super::{chat::ChatComponent, error::MCTypeError, packets::play::{Clientbound, clientbound::{LegacyChatMessage, TitleAction}}, version::ProtocolVersion};
+
+/// The number of ticks messages are shown above the hotbar.
+const ACTION_BAR_TICKS: u32 = 60;
+
+/// How long titles take to fade in, stay, and fade out, in ticks.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TitleTimes {
+    pub fade_in: i32,
+    pub stay: i32,
+    pub fade_out: i32
+}
+
+impl TitleTimes {
+    /// The number of ticks titles are shown for, fading included.
+    pub fn total(&self) -> u32 {
+        (self.fade_in.max(0) + self.stay.max(0) + self.fade_out.max(0)) as u32
+    }
+}
+
+impl Default for TitleTimes {
+    fn default() -> Self {
+        TitleTimes { fade_in: 10, stay: 70, fade_out: 20 }
+    }
+}
+
+/// Gets the title action of the Play packet `packet` of `version`, if it is a title
+/// packet.
+/// # Errors
+/// This function will return an `MCTypeError` if the title packet cannot be decoded.
+pub fn action_of(packet: &Clientbound, version: ProtocolVersion) -> Result<Option<TitleAction>, MCTypeError> {
+    Ok(Some(match packet {
+        Clientbound::SetTitleText(title) => TitleAction::SetTitle(title.text(version)?),
+        Clientbound::SetSubtitleText(subtitle) => TitleAction::SetSubtitle(subtitle.text(version)?),
+        Clientbound::SetActionBarText(action_bar) => TitleAction::SetActionBar(action_bar.text(version)?),
+        Clientbound::SetTitleAnimationTimes(times) => TitleAction::SetTimes {
+            fade_in: times.fade_in.value(),
+            stay: times.stay.value(),
+            fade_out: times.fade_out.value()
+        },
+        Clientbound::ClearTitles(clear) if clear.reset.value() => TitleAction::Reset,
+        Clientbound::ClearTitles(_) => TitleAction::Hide,
+        Clientbound::LegacyTitle(title) => title.action(version)?,
+        _ => return Ok(None)
+    }))
+}
+
+/// The title, subtitle and message above the hotbar shown to the player, kept up to date
+/// with the title packets and the chat messages shown above the hotbar sent by the
+/// server. Titles and messages disappear after their time, which passes as `tick` is
+/// called, as `Client::tick` does.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Titles {
+    title: Option<ChatComponent>,
+    subtitle: Option<ChatComponent>,
+    action_bar: Option<ChatComponent>,
+    times: TitleTimes,
+    title_ticks: u32,
+    action_bar_ticks: u32
+}
+
+impl Titles {
+    /// Creates titles showing nothing, with the default times.
+    pub fn new() -> Self {
+        Titles::default()
+    }
+
+    /// Gets the title shown, if any.
+    pub fn title(&self) -> Option<&ChatComponent> {
+        self.title.as_ref()
+    }
+
+    /// Gets the subtitle, which is shown below the title while there is one.
+    pub fn subtitle(&self) -> Option<&ChatComponent> {
+        self.subtitle.as_ref()
+    }
+
+    /// Gets the message shown above the hotbar, if any.
+    pub fn action_bar(&self) -> Option<&ChatComponent> {
+        self.action_bar.as_ref()
+    }
+
+    /// Gets how long titles are shown for.
+    pub fn times(&self) -> TitleTimes {
+        self.times
+    }
+
+    /// Applies the Play packet `packet` of `version` to the titles, if it concerns them.
+    /// # Errors
+    /// This function will return an `MCTypeError` if a title packet cannot be decoded.
+    pub fn handle(&mut self, packet: &Clientbound, version: ProtocolVersion) -> Result<(), MCTypeError> {
+        let overlay = match packet {
+            Clientbound::SystemChatMessage(chat) if chat.overlay.value() => chat.component().ok(),
+            Clientbound::LegacyChatMessage(chat) if chat.position == LegacyChatMessage::GAME_INFO => ChatComponent::from_json(chat.content.string()).ok(),
+            _ => None
+        };
+        if let Some(text) = overlay {
+            self.show_action_bar(text);
+        }
+
+        match action_of(packet, version)? {
+            Some(TitleAction::SetTitle(title)) => {
+                self.title = Some(title);
+                self.title_ticks = self.times.total();
+            },
+            Some(TitleAction::SetSubtitle(subtitle)) => self.subtitle = Some(subtitle),
+            Some(TitleAction::SetActionBar(text)) => self.show_action_bar(text),
+            Some(TitleAction::SetTimes { fade_in, stay, fade_out }) => {
+                self.times = TitleTimes { fade_in, stay, fade_out };
+                if self.title.is_some() {
+                    self.title_ticks = self.times.total();
+                }
+            },
+            Some(action @ (TitleAction::Hide | TitleAction::Reset)) => {
+                self.hide_title();
+                if action == TitleAction::Reset {
+                    self.times = TitleTimes::default();
+                }
+            },
+            None => ()
+        }
+        Ok(())
+    }
+
+    /// Counts a tick towards the time the title and the message above the hotbar are
+    /// shown for, hiding them once it has passed.
+    pub fn tick(&mut self) {
+        if self.title_ticks > 0 {
+            self.title_ticks -= 1;
+            if self.title_ticks == 0 {
+                self.hide_title();
+            }
+        }
+        if self.action_bar_ticks > 0 {
+            self.action_bar_ticks -= 1;
+            if self.action_bar_ticks == 0 {
+                self.action_bar = None;
+            }
+        }
+    }
+
+    fn show_action_bar(&mut self, text: ChatComponent) {
+        self.action_bar = Some(text);
+        self.action_bar_ticks = ACTION_BAR_TICKS;
+    }
+
+    fn hide_title(&mut self) {
+        self.title = None;
+        self.subtitle = None;
+        self.title_ticks = 0;
+    }
+}
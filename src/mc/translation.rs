@@ -0,0 +1,149 @@
+use std::{collections::HashMap, fs, io, path::Path, sync::OnceLock};
+
+/// The English messages of the translation keys servers send most often, used when no
+/// other translations are given.
+const BUILTIN: &[(&str, &str)] = &[
+    ("chat.type.text", "<%s> %s"),
+    ("chat.type.text.narrate", "%s says %s"),
+    ("chat.type.announcement", "[%s] %s"),
+    ("chat.type.emote", "* %s %s"),
+    ("chat.type.admin", "[%s: %s]"),
+    ("chat.type.team.text", "%s <%s> %s"),
+    ("chat.type.team.sent", "-> %s <%s> %s"),
+    ("commands.message.display.incoming", "%s whispers to you: %s"),
+    ("commands.message.display.outgoing", "You whisper to %s: %s"),
+    ("multiplayer.player.joined", "%s joined the game"),
+    ("multiplayer.player.joined.renamed", "%s (formerly known as %s) joined the game"),
+    ("multiplayer.player.left", "%s left the game"),
+    ("multiplayer.disconnect.kicked", "Kicked by an operator"),
+    ("multiplayer.disconnect.banned", "You are banned from this server."),
+    ("multiplayer.disconnect.not_whitelisted", "You are not white-listed on this server!"),
+    ("multiplayer.disconnect.server_shutdown", "Server closed"),
+    ("multiplayer.disconnect.server_full", "Server is full!"),
+    ("death.attack.generic", "%1$s died"),
+    ("death.attack.player", "%1$s was slain by %2$s"),
+    ("death.attack.mob", "%1$s was slain by %2$s"),
+    ("death.attack.arrow", "%1$s was shot by %2$s"),
+    ("death.attack.fall", "%1$s hit the ground too hard"),
+    ("death.attack.lava", "%1$s tried to swim in lava"),
+    ("death.attack.inFire", "%1$s went up in flames"),
+    ("death.attack.onFire", "%1$s burned to death"),
+    ("death.attack.drown", "%1$s drowned"),
+    ("death.attack.starve", "%1$s starved to death"),
+    ("death.attack.explosion", "%1$s blew up"),
+    ("death.attack.outOfWorld", "%1$s fell out of the world"),
+    ("death.fell.accident.generic", "%1$s fell from a high place")
+];
+
+/// A table of the messages of translation keys, such as `chat.type.text`, which text
+/// components refer to instead of giving their text. Messages have `%s` placeholders,
+/// filled with the component's arguments in order, or `%1$s` placeholders, filled with
+/// the argument of that position. `%%` is a literal percent sign.
+/// # Example
+/// ```no_run
+/// use mcclient::mc::{chat::ChatComponent, translation::Translations};
+///
+/// // The language file is found in the game's assets, under `minecraft/lang/en_us.json`.
+/// let translations = Translations::load("en_us.json").expect("Could not load translations");
+/// let message = ChatComponent::from_json(r#"{"translate":"chat.type.text","with":["Makoto","Hi"]}"#).unwrap();
+/// assert_eq!(message.to_plain_text_with(&translations), "<Makoto> Hi");
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Translations {
+    messages: HashMap<String, String>
+}
+
+impl Translations {
+    /// Creates an empty table.
+    pub fn new() -> Self {
+        Translations::default()
+    }
+
+    /// Gets the table of the translation keys servers send most often, which components
+    /// are resolved with unless another table is given.
+    pub fn builtin() -> &'static Translations {
+        static BUILTIN_TRANSLATIONS: OnceLock<Translations> = OnceLock::new();
+        BUILTIN_TRANSLATIONS.get_or_init(|| BUILTIN.iter().copied().collect())
+    }
+
+    /// Parses a language file, a JSON object of translation keys and their messages, such
+    /// as the game's `en_us.json`.
+    /// # Errors
+    /// This function will return an `InvalidData` error if `json` is not an object of strings.
+    pub fn from_json(json: &str) -> Result<Self, io::Error> {
+        Ok(Translations { messages: serde_json::from_str(json)? })
+    }
+
+    /// Loads the language file at `path`, as per `from_json`.
+    /// # Errors
+    /// This function will return an error if the file cannot be read, or as per `from_json`.
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self, io::Error> {
+        Translations::from_json(&fs::read_to_string(path)?)
+    }
+
+    /// Gets the message of `key`, if the table has one.
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.messages.get(key).map(String::as_str)
+    }
+
+    /// Sets the message of `key`, replacing any message it had.
+    pub fn insert<K: Into<String>, M: Into<String>>(&mut self, key: K, message: M) {
+        self.messages.insert(key.into(), message.into());
+    }
+}
+
+impl<K: Into<String>, M: Into<String>> FromIterator<(K, M)> for Translations {
+    fn from_iter<I: IntoIterator<Item = (K, M)>>(iter: I) -> Self {
+        Translations { messages: iter.into_iter().map(|(key, message)| (key.into(), message.into())).collect() }
+    }
+}
+
+/// A piece of a translated message: either literal text, or the placeholder of the
+/// argument at an index.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum FormatPiece<'a> {
+    Literal(&'a str),
+    Argument(usize)
+}
+
+/// Splits the translated message `format` into its literal text and placeholders.
+/// Malformed placeholders are kept as literal text.
+pub(crate) fn parse_format(format: &str) -> Vec<FormatPiece<'_>> {
+    let mut pieces = Vec::new();
+    let mut next_argument = 0;
+    let mut rest = format;
+
+    while let Some(start) = rest.find('%') {
+        if start > 0 {
+            pieces.push(FormatPiece::Literal(&rest[..start]));
+        }
+        let placeholder = &rest[start + 1..];
+
+        if let Some(after) = placeholder.strip_prefix('%') {
+            pieces.push(FormatPiece::Literal("%"));
+            rest = after;
+        } else if let Some(after) = placeholder.strip_prefix('s') {
+            pieces.push(FormatPiece::Argument(next_argument));
+            next_argument += 1;
+            rest = after;
+        } else {
+            let digits = placeholder.find(|c: char| !c.is_ascii_digit()).unwrap_or(placeholder.len());
+            match (placeholder[..digits].parse::<usize>(), placeholder[digits..].starts_with("$s")) {
+                (Ok(position), true) if position > 0 => {
+                    pieces.push(FormatPiece::Argument(position - 1));
+                    rest = &placeholder[digits + 2..];
+                },
+                _ => {
+                    pieces.push(FormatPiece::Literal("%"));
+                    rest = placeholder;
+                }
+            }
+        }
+    }
+
+    if !rest.is_empty() {
+        pieces.push(FormatPiece::Literal(rest));
+    }
+
+    pieces
+}
@@ -0,0 +1,132 @@
+use core::cmp::Ordering;
+
+#[cfg(feature = "std")]
+use super::{io, packet::clientbound::status_response::StatusResponse};
+
+/// Minecraft protocol versions known to this library. Each release maps to the
+/// protocol number reported by servers in their status response and sent by
+/// clients in the handshake. <https://wiki.vg/Protocol_version_numbers>
+/// <br>
+/// Protocol numbers that do not correspond to a known release are preserved
+/// as `Unknown`, so a server's reported version is never lost.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ProtocolVersion {
+    V1_8,
+    V1_12_2,
+    V1_16_5,
+    V1_19_3,
+    V1_20_1,
+    V1_20_2,
+    V1_20_4,
+    Unknown(i32)
+}
+
+/// Versions serialize as their protocol number with the `serde` feature.
+#[cfg(feature = "serde")]
+impl serde::Serialize for ProtocolVersion {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_i32(self.number())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for ProtocolVersion {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Ok(ProtocolVersion::from(<i32 as serde::Deserialize>::deserialize(deserializer)?))
+    }
+}
+
+impl ProtocolVersion {
+    /// The releases known to this library, from oldest to newest.
+    pub const KNOWN: [ProtocolVersion; 7] = [
+        ProtocolVersion::V1_8,
+        ProtocolVersion::V1_12_2,
+        ProtocolVersion::V1_16_5,
+        ProtocolVersion::V1_19_3,
+        ProtocolVersion::V1_20_1,
+        ProtocolVersion::V1_20_2,
+        ProtocolVersion::V1_20_4
+    ];
+
+    /// Gets the protocol number of this version.
+    pub fn number(&self) -> i32 {
+        match self {
+            ProtocolVersion::V1_8 => 47,
+            ProtocolVersion::V1_12_2 => 340,
+            ProtocolVersion::V1_16_5 => 754,
+            ProtocolVersion::V1_19_3 => 761,
+            ProtocolVersion::V1_20_1 => 763,
+            ProtocolVersion::V1_20_2 => 764,
+            ProtocolVersion::V1_20_4 => 765,
+            ProtocolVersion::Unknown(number) => *number,
+        }
+    }
+
+    /// Gets the data version of this release, which the worlds and structures it saves are
+    /// stamped with, or `None` for 1.8, which predates data versions, and unknown versions.
+    pub fn data_version(&self) -> Option<i32> {
+        match self {
+            ProtocolVersion::V1_12_2 => Some(1343),
+            ProtocolVersion::V1_16_5 => Some(2586),
+            ProtocolVersion::V1_19_3 => Some(3218),
+            ProtocolVersion::V1_20_1 => Some(3465),
+            ProtocolVersion::V1_20_2 => Some(3578),
+            ProtocolVersion::V1_20_4 => Some(3700),
+            ProtocolVersion::V1_8 | ProtocolVersion::Unknown(_) => None
+        }
+    }
+
+    /// Whether this version is one of the releases known to this library.
+    pub fn is_known(&self) -> bool {
+        !matches!(self, ProtocolVersion::Unknown(_))
+    }
+
+    /// Gets the newest known release which is not newer than this version, which is the
+    /// version itself if it is known. Servers reporting a newer, unknown version commonly
+    /// accept older clients, so this is the best version to join them with.
+    /// <br>
+    /// Returns `None` if this version predates every known release.
+    pub fn nearest_known(&self) -> Option<ProtocolVersion> {
+        ProtocolVersion::KNOWN.into_iter().rev().find(|known| known <= self)
+    }
+
+    /// Maps the protocol reported by a server's status response to a `ProtocolVersion`.
+    /// If the reported number is not a known release, `Unknown` carrying the raw number
+    /// is returned.
+    /// # Errors
+    /// This function will return an `InvalidData` error if the status response is not
+    /// valid JSON or does not report a protocol number.
+    #[cfg(feature = "std")]
+    pub fn from_status(status: &StatusResponse) -> Result<Self, io::Error> {
+        Ok(ProtocolVersion::from(status.reported_protocol()?))
+    }
+}
+
+impl From<i32> for ProtocolVersion {
+    /// Maps a raw protocol number to its `ProtocolVersion`.
+    fn from(number: i32) -> Self {
+        match number {
+            47 => ProtocolVersion::V1_8,
+            340 => ProtocolVersion::V1_12_2,
+            754 => ProtocolVersion::V1_16_5,
+            761 => ProtocolVersion::V1_19_3,
+            763 => ProtocolVersion::V1_20_1,
+            764 => ProtocolVersion::V1_20_2,
+            765 => ProtocolVersion::V1_20_4,
+            _ => ProtocolVersion::Unknown(number)
+        }
+    }
+}
+
+impl PartialOrd for ProtocolVersion {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for ProtocolVersion {
+    /// Versions are ordered by their protocol number.
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.number().cmp(&other.number())
+    }
+}
@@ -0,0 +1,411 @@
+//! The windows the player interacts with: its own inventory, and the containers the
+//! server opens to it, such as chests.
+use std::slice::Chunks;
+
+use super::{chat::ChatComponent, error::MCTypeError, item::{ItemStack, Slot}, packets::play::{Clientbound, clientbound::{ContainerContent, SetContainerContent, SetContainerSlot}, serverbound::{ClickContainer, ClickMode}}, version::ProtocolVersion};
+
+/// The number of slots of the player's main inventory and hotbar, which every window
+/// ends with.
+pub const PLAYER_SLOTS: usize = 36;
+/// The number of slots of the hotbar, which ends the player's slots.
+pub const HOTBAR_SLOTS: usize = 9;
+/// The slot standing for the outside of windows, clicking which drops the carried items.
+pub const OUTSIDE: i16 = -999;
+/// The number of items a slot is taken to hold at most when predicting clicks. Items
+/// which stack less are corrected by the server.
+const MAX_STACK: i32 = 64;
+/// The number of slots of a row of containers such as chests.
+const ROW_SLOTS: usize = 9;
+/// The slot of the player's inventory its main inventory starts at, after the crafting
+/// grid and armor.
+const INVENTORY_PLAYER_START: usize = 9;
+/// The first protocol version (1.9) giving the player's inventory an off hand slot.
+const OFF_HAND_SINCE: i32 = 107;
+
+/// A window of slots: the player's inventory, or a container opened by the server. The
+/// slots of a container are followed by the player's main inventory and hotbar.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Window {
+    pub id: u8,
+    /// The ID of the window's type in the `minecraft:menu` registry. This is `None` for
+    /// the player's inventory, and for the windows opened by servers older than 1.14.
+    pub kind: Option<i32>,
+    /// The title of the window, known as `kind` is.
+    pub title: Option<ChatComponent>,
+    pub slots: Vec<Slot>
+}
+
+impl Window {
+    /// The ID of the player's inventory.
+    pub const INVENTORY_ID: u8 = 0;
+
+    /// The slot the player's main inventory starts at.
+    fn player_start(&self) -> usize {
+        match self.id {
+            Window::INVENTORY_ID => INVENTORY_PLAYER_START.min(self.slots.len()),
+            _ => self.slots.len().saturating_sub(PLAYER_SLOTS)
+        }
+    }
+
+    /// Gets the item in `slot`, or `None` if the window has no such slot.
+    pub fn get(&self, slot: i16) -> Option<&Slot> {
+        self.slots.get(usize::try_from(slot).ok()?)
+    }
+
+    /// Gets the slots of the container, before the player's main inventory. Those of the
+    /// player's inventory are its crafting grid and armor.
+    pub fn container_slots(&self) -> &[Slot] {
+        &self.slots[..self.player_start()]
+    }
+
+    /// Gets the slots of the player's main inventory followed by its hotbar.
+    pub fn player_slots(&self) -> &[Slot] {
+        let start = self.player_start();
+        &self.slots[start..(start + PLAYER_SLOTS).min(self.slots.len())]
+    }
+
+    /// Gets the slots of the player's hotbar, from left to right.
+    pub fn hotbar(&self) -> &[Slot] {
+        let player_slots = self.player_slots();
+        &player_slots[player_slots.len().saturating_sub(HOTBAR_SLOTS)..]
+    }
+
+    /// Gets the slot of the window holding the hotbar slot `index`, or `None` if it has
+    /// none, such as before its contents are sent.
+    pub fn hotbar_slot(&self, index: u8) -> Option<i16> {
+        let slot = self.player_start() + PLAYER_SLOTS - HOTBAR_SLOTS + index as usize;
+        (usize::from(index) < HOTBAR_SLOTS && slot < self.slots.len()).then_some(slot as i16)
+    }
+
+    /// Gets the slots of the container as rows of 9, as laid out by chests and the like.
+    pub fn rows(&self) -> Chunks<'_, Slot> {
+        self.container_slots().chunks(ROW_SLOTS)
+    }
+}
+
+/// The ways the player clicks slots.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ClickAction {
+    /// Picks up the items of the slot, puts the carried items down, or swaps them. The
+    /// carried items are dropped when clicking `OUTSIDE`.
+    LeftClick,
+    /// Picks up half of the items of the slot, or puts a carried item down. A carried
+    /// item is dropped when clicking `OUTSIDE`.
+    RightClick,
+    /// Moves the items of the slot to the other part of the window, such as from a chest
+    /// to the player's inventory.
+    ShiftClick,
+    /// Swaps the items of the slot with those of the hotbar slot from 0 to 8.
+    Swap(u8),
+    /// Drops an item of the slot.
+    Drop,
+    /// Drops every item of the slot.
+    DropStack
+}
+
+impl ClickAction {
+    fn mode_and_button(&self) -> (ClickMode, i8) {
+        match self {
+            ClickAction::LeftClick => (ClickMode::Pickup, 0),
+            ClickAction::RightClick => (ClickMode::Pickup, 1),
+            ClickAction::ShiftClick => (ClickMode::QuickMove, 0),
+            ClickAction::Swap(index) => (ClickMode::Swap, *index as i8),
+            ClickAction::Drop => (ClickMode::Throw, 0),
+            ClickAction::DropStack => (ClickMode::Throw, 1)
+        }
+    }
+}
+
+/// Whether `a` and `b` are items of the same kind, which stack together.
+fn stacks_with(a: &ItemStack, b: &ItemStack) -> bool {
+    ItemStack { count: 0, ..a.clone() } == ItemStack { count: 0, ..b.clone() }
+}
+
+/// Gets `count` items of the kind of `stack`, or an empty slot if there are none.
+fn with_count(stack: &ItemStack, count: i32) -> Slot {
+    match count > 0 {
+        true => ItemStack { count, ..stack.clone() }.into(),
+        false => Slot::EMPTY
+    }
+}
+
+/// Predicts the contents of a slot and the cursor after picking up or putting down items,
+/// with the left button or the `right` one.
+fn pickup(clicked: &Slot, carried: &Slot, right: bool) -> (Slot, Slot) {
+    match (&clicked.item, &carried.item) {
+        (None, None) => (Slot::EMPTY, Slot::EMPTY),
+        (Some(stack), None) => match right {
+            true => {
+                let taken = (stack.count + 1) / 2;
+                (with_count(stack, stack.count - taken), with_count(stack, taken))
+            },
+            false => (Slot::EMPTY, clicked.clone())
+        },
+        (None, Some(held)) => match right {
+            true => (with_count(held, 1), with_count(held, held.count - 1)),
+            false => (carried.clone(), Slot::EMPTY)
+        },
+        (Some(stack), Some(held)) if stacks_with(stack, held) => {
+            let moved = match right {
+                true => 1,
+                false => held.count
+            }.min(MAX_STACK - stack.count).max(0);
+            (with_count(stack, stack.count + moved), with_count(held, held.count - moved))
+        },
+        (Some(_), Some(_)) => (carried.clone(), clicked.clone())
+    }
+}
+
+/// The player's inventory and the window open on top of it, kept up to date with the
+/// Open Screen, Set Container Content, Set Container Slot and Close Container packets
+/// sent by the server.
+/// <br> <br>
+/// Clicks are predicted locally, as the vanilla client does, and sent along with their
+/// prediction since 1.17.1, so that the server only sends back the slots it disagrees
+/// with. Shift-clicks are not predicted, and their outcome is sent by the server. Before
+/// 1.17.1, the server confirms or rejects each click, and sends the window's contents
+/// again after rejecting one.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Windows {
+    version: ProtocolVersion,
+    inventory: Window,
+    open: Option<Window>,
+    carried: Slot,
+    state_id: i32,
+    action: i16
+}
+
+impl Windows {
+    /// Creates the empty inventory of a player in `version`.
+    pub fn new(version: ProtocolVersion) -> Self {
+        let size = match version.number() >= OFF_HAND_SINCE {
+            true => 46,
+            false => 45
+        };
+        Windows {
+            version,
+            inventory: Window { id: Window::INVENTORY_ID, slots: vec![Slot::EMPTY; size], ..Window::default() },
+            open: None,
+            carried: Slot::EMPTY,
+            state_id: 0,
+            action: 0
+        }
+    }
+
+    /// Gets the player's inventory. The player's slots of the open window are mirrored
+    /// into it.
+    pub fn inventory(&self) -> &Window {
+        &self.inventory
+    }
+
+    /// Gets the window the server opened to the player, if any.
+    pub fn open_window(&self) -> Option<&Window> {
+        self.open.as_ref()
+    }
+
+    /// Gets the window clicks are made in: the open window if any, or else the player's
+    /// inventory.
+    pub fn current(&self) -> &Window {
+        self.open.as_ref().unwrap_or(&self.inventory)
+    }
+
+    /// Gets the items carried on the cursor.
+    pub fn carried(&self) -> &Slot {
+        &self.carried
+    }
+
+    /// The state ID of the last contents sent by the server, which clicks are sent with.
+    pub fn state_id(&self) -> i32 {
+        self.state_id
+    }
+
+    /// Applies the Play packet `packet` to the windows, if it concerns them.
+    /// # Errors
+    /// This function will return an `MCTypeError` if the title or contents of a window
+    /// cannot be decoded.
+    pub fn handle(&mut self, packet: &Clientbound) -> Result<(), MCTypeError> {
+        match packet {
+            Clientbound::OpenScreen(screen) => {
+                self.open = Some(Window {
+                    id: screen.window_id.value() as u8,
+                    kind: Some(screen.kind.value()),
+                    title: Some(screen.title(self.version)?),
+                    slots: Vec::new()
+                });
+            },
+            Clientbound::CloseContainer(close) if self.open.as_ref().is_some_and(|open| open.id == close.window_id.value()) => {
+                self.open = None;
+                self.carried = Slot::EMPTY;
+            },
+            Clientbound::SetContainerContent(content) => {
+                let ContainerContent { state_id, slots, carried } = content.content(self.version)?;
+                let id = content.window_id.value();
+                if id == Window::INVENTORY_ID {
+                    self.inventory.slots = slots;
+                } else {
+                    // Servers older than 1.14 open windows in a layout which is not decoded.
+                    match &mut self.open {
+                        Some(open) if open.id == id => open.slots = slots,
+                        _ => self.open = Some(Window { id, slots, ..Window::default() })
+                    }
+                }
+                for slot in 0..self.window(id).map_or(0, |window| window.slots.len()) {
+                    self.mirror(id, slot);
+                }
+                if let Some(state_id) = state_id {
+                    self.state_id = state_id;
+                }
+                if let Some(carried) = carried {
+                    self.carried = carried;
+                }
+            },
+            Clientbound::SetContainerSlot(update) => {
+                let change = update.change(self.version)?;
+                if let Some(state_id) = change.state_id {
+                    self.state_id = state_id;
+                }
+                match update.window_id.value() {
+                    SetContainerSlot::CARRIED if change.slot == SetContainerSlot::CARRIED as i16 => self.carried = change.item,
+                    SetContainerSlot::INVENTORY => self.set_slot(Window::INVENTORY_ID, change.slot, change.item),
+                    id if id >= 0 => self.set_slot(id as u8, change.slot, change.item),
+                    _ => ()
+                }
+            },
+            _ => ()
+        }
+        Ok(())
+    }
+
+    fn window(&self, id: u8) -> Option<&Window> {
+        match id {
+            Window::INVENTORY_ID => Some(&self.inventory),
+            _ => self.open.as_ref().filter(|open| open.id == id)
+        }
+    }
+
+    fn window_mut(&mut self, id: u8) -> Option<&mut Window> {
+        match id {
+            Window::INVENTORY_ID => Some(&mut self.inventory),
+            _ => self.open.as_mut().filter(|open| open.id == id)
+        }
+    }
+
+    /// Sets the item of `slot` in the window `id`, if it has the slot, and mirrors it
+    /// between the player's inventory and the open window.
+    fn set_slot(&mut self, id: u8, slot: i16, item: Slot) {
+        let Some(target) = self.window_mut(id).and_then(|window| window.slots.get_mut(usize::try_from(slot).ok()?)) else {
+            return;
+        };
+        *target = item;
+        self.mirror(id, slot as usize);
+    }
+
+    /// Copies the item of `slot` in the window `id` to the slot holding it in the other
+    /// window, if it is one of the player's slots.
+    fn mirror(&mut self, id: u8, slot: usize) {
+        let Some(open) = &mut self.open else {
+            return;
+        };
+        let (inventory_slot, open_slot) = match id {
+            Window::INVENTORY_ID => {
+                let Some(index) = slot.checked_sub(INVENTORY_PLAYER_START).filter(|index| *index < PLAYER_SLOTS) else {
+                    return;
+                };
+                (slot, open.player_start() + index)
+            },
+            _ => {
+                let Some(index) = slot.checked_sub(open.player_start()).filter(|index| *index < PLAYER_SLOTS) else {
+                    return;
+                };
+                (INVENTORY_PLAYER_START + index, slot)
+            }
+        };
+        let (Some(inventory_item), Some(open_item)) = (self.inventory.slots.get_mut(inventory_slot), open.slots.get_mut(open_slot)) else {
+            return;
+        };
+        match id {
+            Window::INVENTORY_ID => *open_item = inventory_item.clone(),
+            _ => *inventory_item = open_item.clone()
+        }
+    }
+
+    /// Predicts the slots changed by clicking `slot` of the current window with `action`,
+    /// along with the items then carried, or returns `None` if the window has no such
+    /// slot.
+    fn predict(&self, slot: i16, action: ClickAction) -> Option<(Vec<(i16, Slot)>, Slot)> {
+        let window = self.current();
+        if slot == OUTSIDE {
+            let carried = match (action, &self.carried.item) {
+                (ClickAction::RightClick, Some(held)) => with_count(held, held.count - 1),
+                (ClickAction::LeftClick, _) => Slot::EMPTY,
+                _ => self.carried.clone()
+            };
+            return Some((Vec::new(), carried));
+        }
+
+        let clicked = window.get(slot)?;
+        let changed = match action {
+            ClickAction::LeftClick | ClickAction::RightClick => {
+                let (clicked, carried) = pickup(clicked, &self.carried, action == ClickAction::RightClick);
+                return Some((vec![(slot, clicked)], carried));
+            },
+            ClickAction::ShiftClick => Vec::new(),
+            ClickAction::Swap(index) => {
+                let target = window.hotbar_slot(index)?;
+                match target != slot && self.carried.is_empty() {
+                    true => vec![(slot, window.get(target)?.clone()), (target, clicked.clone())],
+                    false => Vec::new()
+                }
+            },
+            ClickAction::Drop | ClickAction::DropStack => match (&clicked.item, self.carried.is_empty()) {
+                (Some(stack), true) => {
+                    let dropped = match action {
+                        ClickAction::Drop => 1,
+                        _ => stack.count
+                    };
+                    vec![(slot, with_count(stack, stack.count - dropped))]
+                },
+                _ => Vec::new()
+            }
+        };
+        Some((changed, self.carried.clone()))
+    }
+
+    /// Clicks `slot` of the current window with `action`, applying the predicted outcome
+    /// of the click to the windows, and returns the click to send to the server. Returns
+    /// `None` if the window has no such slot, other than `OUTSIDE`.
+    pub fn click(&mut self, slot: i16, action: ClickAction) -> Option<ClickContainer> {
+        let (changed, carried) = self.predict(slot, action)?;
+        let (mode, button) = action.mode_and_button();
+        let id = self.current().id;
+        let modern = self.version.number() >= SetContainerContent::STATE_ID_SINCE;
+        let number = match modern {
+            true => self.state_id,
+            false => {
+                self.action = self.action.wrapping_add(1);
+                self.action as i32
+            }
+        };
+
+        let mut click = ClickContainer::new(self.version, id, slot, button, mode, number);
+        click.item = match modern {
+            true => carried.clone(),
+            false => self.current().get(slot).cloned().unwrap_or_default()
+        };
+        for (changed_slot, item) in changed {
+            self.set_slot(id, changed_slot, item.clone());
+            if modern {
+                click.changed_slots.push((changed_slot, item));
+            }
+        }
+        self.carried = carried;
+        Some(click)
+    }
+
+    /// Closes the current window, returning its ID for the server to be told of it. The
+    /// server takes back the carried items.
+    pub fn close(&mut self) -> u8 {
+        self.carried = Slot::EMPTY;
+        self.open.take().map_or(Window::INVENTORY_ID, |open| open.id)
+    }
+}
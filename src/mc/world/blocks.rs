@@ -0,0 +1,190 @@
+use std::{collections::{BTreeMap, HashMap}, fmt, fs, io, path::Path, sync::OnceLock};
+
+use serde::Deserialize;
+
+/// The first block states of the report, which are numbered alike by every version since
+/// 1.13, used when no other report is given.
+const BUILTIN_REPORT: &str = r#"{
+    "minecraft:air": { "states": [{ "id": 0, "default": true }] },
+    "minecraft:stone": { "states": [{ "id": 1, "default": true }] },
+    "minecraft:granite": { "states": [{ "id": 2, "default": true }] },
+    "minecraft:polished_granite": { "states": [{ "id": 3, "default": true }] },
+    "minecraft:diorite": { "states": [{ "id": 4, "default": true }] },
+    "minecraft:polished_diorite": { "states": [{ "id": 5, "default": true }] },
+    "minecraft:andesite": { "states": [{ "id": 6, "default": true }] },
+    "minecraft:polished_andesite": { "states": [{ "id": 7, "default": true }] },
+    "minecraft:grass_block": { "states": [
+        { "id": 8, "properties": { "snowy": "true" } },
+        { "id": 9, "default": true, "properties": { "snowy": "false" } }
+    ] },
+    "minecraft:dirt": { "states": [{ "id": 10, "default": true }] },
+    "minecraft:coarse_dirt": { "states": [{ "id": 11, "default": true }] },
+    "minecraft:podzol": { "states": [
+        { "id": 12, "properties": { "snowy": "true" } },
+        { "id": 13, "default": true, "properties": { "snowy": "false" } }
+    ] },
+    "minecraft:cobblestone": { "states": [{ "id": 14, "default": true }] }
+}"#;
+
+/// The largest block state ID a report may give, well above those of any version, which
+/// bounds the memory taken by a registry.
+const MAX_STATE_ID: i32 = 1 << 20;
+
+/// A block state: a block, such as `minecraft:oak_stairs`, with a value for each of its
+/// properties, such as `facing=north`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BlockState {
+    pub id: i32,
+    pub name: String,
+    pub properties: BTreeMap<String, String>,
+    /// Whether this is the state a block is placed in by default.
+    pub default: bool
+}
+
+impl BlockState {
+    /// Gets the value of the property `name`, if the block has it.
+    pub fn property(&self, name: &str) -> Option<&str> {
+        self.properties.get(name).map(String::as_str)
+    }
+
+    /// Whether the block is one of the kinds of air.
+    pub fn is_air(&self) -> bool {
+        matches!(self.name.as_str(), "minecraft:air" | "minecraft:cave_air" | "minecraft:void_air")
+    }
+}
+
+/// Block states are displayed as commands name them, such as
+/// `minecraft:grass_block[snowy=false]`.
+impl fmt::Display for BlockState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name)?;
+        if self.properties.is_empty() {
+            return Ok(());
+        }
+        let properties: Vec<String> = self.properties.iter().map(|(name, value)| format!("{}={}", name, value)).collect();
+        write!(f, "[{}]", properties.join(","))
+    }
+}
+
+/// A block of the report, with the states it can be in.
+#[derive(Deserialize)]
+struct ReportBlock {
+    states: Vec<ReportState>
+}
+
+#[derive(Deserialize)]
+struct ReportState {
+    id: i32,
+    #[serde(default)]
+    default: bool,
+    #[serde(default)]
+    properties: BTreeMap<String, String>
+}
+
+/// The block states of a version, mapping the IDs servers send in chunks and block
+/// changes to the blocks they stand for. Since 1.13, servers number the states of every
+/// block in order, and the numbering changes whenever blocks are added.
+/// <br> <br>
+/// The states of a version are listed by the block report of its data generator, which
+/// the server jar writes to `generated/reports/blocks.json` when run with
+/// `java -DbundlerMainClass=net.minecraft.data.Main -jar server.jar --reports`.
+/// # Example
+/// ```no_run
+/// use mcclient::mc::world::blocks::BlockRegistry;
+///
+/// let blocks = BlockRegistry::load("generated/reports/blocks.json").expect("Could not load the block report");
+/// let stone = blocks.default_state("minecraft:stone").unwrap();
+/// assert_eq!(blocks.get(stone.id), Some(stone));
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct BlockRegistry {
+    states: Vec<Option<BlockState>>,
+    defaults: HashMap<String, i32>
+}
+
+impl BlockRegistry {
+    /// Creates a registry without block states.
+    pub fn new() -> Self {
+        BlockRegistry::default()
+    }
+
+    /// Gets the registry of the first block states, from air to cobblestone, which every
+    /// version since 1.13 numbers alike. It is used unless another registry is given.
+    pub fn builtin() -> &'static BlockRegistry {
+        static BUILTIN_BLOCKS: OnceLock<BlockRegistry> = OnceLock::new();
+        BUILTIN_BLOCKS.get_or_init(|| BlockRegistry::from_report(BUILTIN_REPORT).expect("The builtin block report is valid."))
+    }
+
+    /// Parses a block report, a JSON object of block names and their states, as written
+    /// by the data generator of a version.
+    /// # Errors
+    /// This function will return an `InvalidData` error if `json` is not a block report,
+    /// or gives a state a negative or implausibly large ID.
+    pub fn from_report(json: &str) -> Result<Self, io::Error> {
+        let report: HashMap<String, ReportBlock> = serde_json::from_str(json)?;
+        let mut registry = BlockRegistry::new();
+        for (name, block) in report {
+            for state in block.states {
+                registry.insert(BlockState { id: state.id, name: name.clone(), properties: state.properties, default: state.default })?;
+            }
+        }
+        Ok(registry)
+    }
+
+    /// Loads the block report at `path`, as per `from_report`.
+    /// # Errors
+    /// This function will return an error if the file cannot be read, or as per `from_report`.
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self, io::Error> {
+        BlockRegistry::from_report(&fs::read_to_string(path)?)
+    }
+
+    /// Adds `state`, replacing any state with its ID.
+    /// # Errors
+    /// This function will return an `InvalidData` error if the ID of the state is negative
+    /// or implausibly large.
+    pub fn insert(&mut self, state: BlockState) -> Result<(), io::Error> {
+        if !(0..MAX_STATE_ID).contains(&state.id) {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, format!("Block state ID {} is out of range.", state.id)));
+        }
+
+        let index = state.id as usize;
+        if self.states.len() <= index {
+            self.states.resize(index + 1, None);
+        }
+        if state.default {
+            self.defaults.insert(state.name.clone(), state.id);
+        }
+        self.states[index] = Some(state);
+        Ok(())
+    }
+
+    /// Gets the block state of `id`, if the registry has it.
+    pub fn get(&self, id: i32) -> Option<&BlockState> {
+        self.states.get(usize::try_from(id).ok()?)?.as_ref()
+    }
+
+    /// Gets the default state of the block `name`, such as `minecraft:stone`.
+    pub fn default_state(&self, name: &str) -> Option<&BlockState> {
+        self.get(*self.defaults.get(name)?)
+    }
+
+    /// Finds the state of the block `name` whose properties have the given values. The
+    /// properties which are not given may have any value.
+    pub fn find(&self, name: &str, properties: &[(&str, &str)]) -> Option<&BlockState> {
+        self.states().find(|state| state.name == name && properties.iter().all(|(property, value)| state.property(property) == Some(*value)))
+    }
+
+    /// Iterates over the block states, in order of their IDs.
+    pub fn states(&self) -> impl Iterator<Item = &BlockState> {
+        self.states.iter().flatten()
+    }
+
+    /// The number of block states.
+    pub fn len(&self) -> usize {
+        self.states().count()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.states().next().is_none()
+    }
+}
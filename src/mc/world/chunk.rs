@@ -0,0 +1,490 @@
+use crate::mc::{error::MCTypeError, limits, mctypes::{MCBoolean, MCLong, MCShort, MCType, MCUnsignedByte, VarInt}, nbt::NbtTag, version::ProtocolVersion};
+
+/// The first protocol version (1.16.2) whose chunks this module can decode, which is
+/// the first to send biomes as a `VarInt` array.
+const PALETTED_SECTIONS_SINCE: i32 = 751;
+/// The first protocol version (1.17) sending the section bitmask as a bit set, and
+/// always sending full columns.
+const BITSET_MASK_SINCE: i32 = 755;
+/// The first protocol version (1.18) sending every section, with biomes of its own.
+const SECTION_BIOMES_SINCE: i32 = 757;
+
+/// The lowest block of the overworld since 1.18. Dimensions of older versions start at 0.
+pub const OVERWORLD_MIN_Y: i32 = -64;
+
+/// The number of blocks along each side of a section.
+pub const SECTION_WIDTH: i32 = 16;
+/// The number of block states in a section.
+const SECTION_BLOCKS: usize = 4096;
+/// The number of biomes in a section, one for each cell of 4×4×4 blocks.
+const SECTION_BIOMES: usize = 64;
+
+/// The palette mapping the entries of a `PalettedContainer` to values, such as block
+/// state IDs.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Palette {
+    /// Every entry has the same value, and no data is sent.
+    Single(i32),
+    /// Entries index into a list of the values used by the container.
+    Indirect(Vec<i32>),
+    /// Entries are values of the global palette, such as the block state registry.
+    Direct
+}
+
+/// A container of the block states or biomes of a section, packed into longs at a number
+/// of bits per entry depending on how many distinct values it holds.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PalettedContainer {
+    bits: u8,
+    palette: Palette,
+    data: Vec<u64>
+}
+
+/// The layout of the entries of a kind of `PalettedContainer`.
+struct ContainerLayout {
+    entries: usize,
+    min_indirect_bits: u8,
+    max_indirect_bits: u8
+}
+
+const BLOCK_STATES: ContainerLayout = ContainerLayout { entries: SECTION_BLOCKS, min_indirect_bits: 4, max_indirect_bits: 8 };
+const BIOMES: ContainerLayout = ContainerLayout { entries: SECTION_BIOMES, min_indirect_bits: 1, max_indirect_bits: 3 };
+
+impl PalettedContainer {
+    /// Creates a container whose every entry is `value`.
+    pub fn single(value: i32) -> Self {
+        PalettedContainer { bits: 0, palette: Palette::Single(value), data: Vec::new() }
+    }
+
+    pub fn palette(&self) -> &Palette {
+        &self.palette
+    }
+
+    /// The number of bits each entry is packed into, which is 0 for a single value.
+    pub fn bits(&self) -> u8 {
+        self.bits
+    }
+
+    /// Gets the value of the entry at `index`.
+    /// # Panics
+    /// This function panics if `index` is out of the bounds of the container.
+    pub fn get(&self, index: usize) -> i32 {
+        let entry = match self.bits {
+            0 => 0,
+            bits => {
+                let per_long = 64 / bits as usize;
+                let mask = (1u64 << bits) - 1;
+                (self.data[index / per_long] >> ((index % per_long) * bits as usize) & mask) as usize
+            }
+        };
+
+        match &self.palette {
+            Palette::Single(value) => *value,
+            Palette::Indirect(values) => values[entry],
+            Palette::Direct => entry as i32
+        }
+    }
+
+    /// Sets the value of the entry at `index`, repacking the container into more bits if
+    /// the value does not fit its palette.
+    fn set(&mut self, index: usize, value: i32, layout: &ContainerLayout) {
+        let entry = match &mut self.palette {
+            Palette::Single(single) if *single == value => return,
+            Palette::Indirect(values) => match values.iter().position(|palette_value| *palette_value == value) {
+                Some(entry) => Some(entry),
+                None if values.len() < 1 << self.bits => {
+                    values.push(value);
+                    Some(values.len() - 1)
+                },
+                None => None
+            },
+            Palette::Direct if value >= 0 && (value as u64) < 1 << self.bits => Some(value as usize),
+            _ => None
+        };
+
+        match entry {
+            Some(entry) => self.write(index, entry as u64),
+            None => {
+                let mut values: Vec<i32> = (0..layout.entries).map(|index| self.get(index)).collect();
+                values[index] = value;
+                *self = PalettedContainer::pack(&values, layout);
+            }
+        }
+    }
+
+    /// Writes the packed `entry` at `index`.
+    fn write(&mut self, index: usize, entry: u64) {
+        let (bits, per_long) = (self.bits as usize, 64 / self.bits as usize);
+        let shift = (index % per_long) * bits;
+        let long = &mut self.data[index / per_long];
+        *long = *long & !(((1u64 << bits) - 1) << shift) | entry << shift;
+    }
+
+    /// Packs `values` into the smallest container of the given layout.
+    fn pack(values: &[i32], layout: &ContainerLayout) -> Self {
+        let mut distinct: Vec<i32> = Vec::new();
+        for value in values {
+            if !distinct.contains(value) {
+                distinct.push(*value);
+            }
+        }
+        if distinct.len() == 1 {
+            return PalettedContainer::single(distinct[0]);
+        }
+
+        let bits_for = |count: usize| (usize::BITS - (count - 1).leading_zeros()) as u8;
+        let indirect_bits = bits_for(distinct.len()).max(layout.min_indirect_bits);
+        let (bits, palette) = match indirect_bits <= layout.max_indirect_bits {
+            true => (indirect_bits, Palette::Indirect(distinct)),
+            false => {
+                let max = values.iter().copied().max().unwrap_or_default().max(0) as usize;
+                (bits_for(max + 1).max(layout.max_indirect_bits + 1), Palette::Direct)
+            }
+        };
+
+        let per_long = 64 / bits as usize;
+        let mut container = PalettedContainer { bits, palette, data: vec![0; layout.entries.div_ceil(per_long)] };
+        for (index, value) in values.iter().enumerate() {
+            let entry = match &container.palette {
+                Palette::Indirect(distinct) => distinct.iter().position(|distinct| distinct == value).unwrap_or_default() as u64,
+                _ => *value as u64
+            };
+            container.write(index, entry);
+        }
+        container
+    }
+
+    /// Parses a container of the given layout from the front of `bytes`, returning the
+    /// container along with the bytes following it.
+    fn parse<'a>(bytes: &'a [u8], layout: &ContainerLayout) -> Result<(Self, &'a [u8]), MCTypeError> {
+        let (bits, rest) = MCUnsignedByte::parse(bytes)?;
+        let (bits, palette, rest) = match bits.value() {
+            0 => {
+                let (value, rest) = VarInt::parse(rest)?;
+                (0, Palette::Single(value.value()), rest)
+            },
+            bits if bits <= layout.max_indirect_bits => {
+                let (count, mut rest) = VarInt::parse(rest)?;
+                let count = limits::array_length(count.value())?;
+                if count == 0 || count > rest.len() {
+                    return Err(MCTypeError::OversizeLength(count as i64));
+                }
+                let mut values = Vec::with_capacity(count);
+                for _ in 0..count {
+                    let (value, after) = VarInt::parse(rest)?;
+                    values.push(value.value());
+                    rest = after;
+                }
+                (bits.max(layout.min_indirect_bits), Palette::Indirect(values), rest)
+            },
+            bits if bits <= 32 => (bits, Palette::Direct, rest),
+            bits => return Err(MCTypeError::InvalidValue(format!("Containers cannot pack entries into {} bits.", bits)))
+        };
+
+        let (length, mut rest) = VarInt::parse(rest)?;
+        let length = limits::array_length(length.value())?;
+        if length > rest.len() / 8 {
+            return Err(MCTypeError::OversizeLength(length as i64));
+        }
+        let mut data = Vec::with_capacity(length);
+        for _ in 0..length {
+            let (long, after) = MCLong::parse(rest)?;
+            data.push(long.value() as u64);
+            rest = after;
+        }
+
+        let container = PalettedContainer { bits, palette, data };
+        container.validate(layout)?;
+        Ok((container, rest))
+    }
+
+    /// Parses the block states of a section before 1.18, which are never a single value.
+    fn parse_masked(bytes: &[u8]) -> Result<(Self, &[u8]), MCTypeError> {
+        match bytes.first() {
+            Some(0) => Err(MCTypeError::InvalidValue("Containers cannot pack entries into 0 bits.".to_string())),
+            _ => PalettedContainer::parse(bytes, &BLOCK_STATES)
+        }
+    }
+
+    /// Checks that the data holds every entry of the layout, and that every entry is in
+    /// the palette, so that `get` cannot fail.
+    fn validate(&self, layout: &ContainerLayout) -> Result<(), MCTypeError> {
+        if self.bits == 0 {
+            return Ok(());
+        }
+
+        let per_long = 64 / self.bits as usize;
+        if self.data.len() < layout.entries.div_ceil(per_long) {
+            return Err(MCTypeError::InvalidValue(format!("Container of {} bits per entry holds only {} longs.", self.bits, self.data.len())));
+        }
+        if let Palette::Indirect(values) = &self.palette {
+            let mask = (1u64 << self.bits) - 1;
+            let out_of_palette = (0..layout.entries)
+                .any(|index| (self.data[index / per_long] >> ((index % per_long) * self.bits as usize) & mask) as usize >= values.len());
+            if out_of_palette {
+                return Err(MCTypeError::InvalidValue("Container entry is out of the bounds of its palette.".to_string()));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// A section of 16×16×16 blocks of a chunk column.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ChunkSection {
+    /// The number of blocks in the section which are not air.
+    pub block_count: i16,
+    pub block_states: PalettedContainer,
+    /// The biomes of the section, which are sent with the section since 1.18, and with
+    /// the column before.
+    pub biomes: Option<PalettedContainer>
+}
+
+impl ChunkSection {
+    /// Creates a section filled with air, the block state 0.
+    pub fn empty() -> Self {
+        ChunkSection { block_count: 0, block_states: PalettedContainer::single(0), biomes: None }
+    }
+
+    /// Gets the block state at the coordinates within the section, from 0 to 15.
+    pub fn get_block_state(&self, x: usize, y: usize, z: usize) -> i32 {
+        self.block_states.get((y * 16 + z) * 16 + x)
+    }
+
+    /// Sets the block state at the coordinates within the section, from 0 to 15,
+    /// counting the blocks which are not air.
+    pub fn set_block_state(&mut self, x: usize, y: usize, z: usize, state: i32) {
+        let index = (y * 16 + z) * 16 + x;
+        match (self.block_states.get(index), state) {
+            (0, 1..) => self.block_count += 1,
+            (1.., 0) => self.block_count -= 1,
+            _ => ()
+        }
+        self.block_states.set(index, state, &BLOCK_STATES);
+    }
+}
+
+/// A block entity of a chunk column, such as a chest or a sign.
+#[derive(Clone, Debug, PartialEq)]
+pub struct BlockEntity {
+    /// The world coordinates of the block entity.
+    pub x: i32,
+    pub y: i32,
+    pub z: i32,
+    /// The ID of the type of the block entity in the registry of the server's version.
+    /// Before 1.18, the type is instead named by the `id` tag of its NBT.
+    pub kind: Option<i32>,
+    pub nbt: Option<NbtTag>
+}
+
+/// A chunk column of 16×16 blocks, spanning the height of its dimension in sections.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Chunk {
+    pub x: i32,
+    pub z: i32,
+    /// The lowest block of the column.
+    pub min_y: i32,
+    /// Whether the column was sent in full. Servers before 1.17 may send only the
+    /// sections which changed, in which case those which were not sent are `None`.
+    /// Sections are never `None` in full columns.
+    pub full: bool,
+    /// The sections of the column, from the bottom up.
+    pub sections: Vec<Option<ChunkSection>>,
+    /// The biomes of the column before 1.18, one for each cell of 4×4×4 blocks from the
+    /// bottom up. Since 1.18, biomes are held by the sections.
+    pub biomes: Vec<i32>,
+    pub heightmaps: Option<NbtTag>,
+    pub block_entities: Vec<BlockEntity>
+}
+
+impl Chunk {
+    /// Gets the block state at the given coordinates, or `None` if the block is outside
+    /// of the column's height or in a section which was not sent. The `x` and `z`
+    /// coordinates are taken modulo 16, so either world coordinates or those within the
+    /// column may be given.
+    pub fn get_block_state(&self, x: i32, y: i32, z: i32) -> Option<i32> {
+        let (section, y) = self.section_at(y)?;
+        Some(section.get_block_state((x & 15) as usize, y, (z & 15) as usize))
+    }
+
+    /// Sets the block state at the given coordinates, as per `get_block_state`. Returns
+    /// whether the block was set, which it is not if it is outside of the column's height
+    /// or in a section which was not sent.
+    pub fn set_block_state(&mut self, x: i32, y: i32, z: i32, state: i32) -> bool {
+        let Some((index, y)) = self.section_index(y) else {
+            return false;
+        };
+        match &mut self.sections[index] {
+            Some(section) => {
+                section.set_block_state((x & 15) as usize, y, (z & 15) as usize, state);
+                true
+            },
+            None => false
+        }
+    }
+
+    /// Gets the biome at the given coordinates, as per `get_block_state`.
+    pub fn get_biome(&self, x: i32, y: i32, z: i32) -> Option<i32> {
+        let (section, local_y) = self.section_at(y)?;
+        let (x, z) = ((x & 15) as usize >> 2, (z & 15) as usize >> 2);
+        match &section.biomes {
+            Some(biomes) => Some(biomes.get(((local_y >> 2) * 4 + z) * 4 + x)),
+            None => self.biomes.get((((y - self.min_y) >> 2) as usize * 4 + z) * 4 + x).copied()
+        }
+    }
+
+    /// Gets the section holding the block at `y`, along with the block's height within it.
+    fn section_at(&self, y: i32) -> Option<(&ChunkSection, usize)> {
+        let (index, y) = self.section_index(y)?;
+        Some((self.sections[index].as_ref()?, y))
+    }
+
+    /// Gets the index of the section holding the block at `y`, along with the block's
+    /// height within it, if the column is high enough to hold it.
+    fn section_index(&self, y: i32) -> Option<(usize, usize)> {
+        let offset = y.checked_sub(self.min_y).filter(|offset| *offset >= 0)?;
+        let index = (offset / SECTION_WIDTH) as usize;
+        (index < self.sections.len()).then_some((index, (offset % SECTION_WIDTH) as usize))
+    }
+
+    /// Whether the chunks of `version` can be decoded, which they can since 1.16.2.
+    pub fn is_supported(version: ProtocolVersion) -> bool {
+        version.number() >= PALETTED_SECTIONS_SINCE
+    }
+
+    /// Parses the data of a Chunk Data packet encoded in the format of `version`, which
+    /// follows the coordinates of the column, in a dimension whose lowest block is at
+    /// `min_y`.
+    /// # Errors
+    /// This function will return an `MCTypeError` if the bytes do not encode a chunk
+    /// column, or an `InvalidValue` error for versions older than 1.16.2, whose chunks
+    /// cannot be decoded.
+    pub fn parse_for(x: i32, z: i32, bytes: &[u8], version: ProtocolVersion, min_y: i32) -> Result<Self, MCTypeError> {
+        if !Chunk::is_supported(version) {
+            return Err(MCTypeError::InvalidValue(format!("Chunks of protocol version {} cannot be decoded.", version.number())));
+        }
+
+        let mut chunk = Chunk { x, z, min_y, full: true, sections: Vec::new(), biomes: Vec::new(), heightmaps: None, block_entities: Vec::new() };
+        if version.number() >= SECTION_BIOMES_SINCE {
+            chunk.parse_sections(bytes, version)?;
+        } else {
+            chunk.parse_masked(bytes, version)?;
+        }
+        Ok(chunk)
+    }
+
+    /// Parses a column of 1.18 or later, which sends every section.
+    fn parse_sections(&mut self, bytes: &[u8], version: ProtocolVersion) -> Result<(), MCTypeError> {
+        let (heightmaps, rest) = NbtTag::parse_for(bytes, version)?;
+        let (mut data, rest) = read_data(rest)?;
+        while !data.is_empty() {
+            let (block_count, rest) = MCShort::parse(data)?;
+            let (block_states, rest) = PalettedContainer::parse(rest, &BLOCK_STATES)?;
+            let (biomes, rest) = PalettedContainer::parse(rest, &BIOMES)?;
+            self.sections.push(Some(ChunkSection { block_count: block_count.value(), block_states, biomes: Some(biomes) }));
+            data = rest;
+        }
+
+        let (count, mut rest) = VarInt::parse(rest)?;
+        for _ in 0..count.value() {
+            let (packed_xz, after) = MCUnsignedByte::parse(rest)?;
+            let (y, after) = MCShort::parse(after)?;
+            let (kind, after) = VarInt::parse(after)?;
+            let (nbt, after) = NbtTag::parse_for(after, version)?;
+            self.block_entities.push(BlockEntity {
+                x: self.x * SECTION_WIDTH + (packed_xz.value() >> 4) as i32,
+                y: y.value() as i32,
+                z: self.z * SECTION_WIDTH + (packed_xz.value() & 15) as i32,
+                kind: Some(kind.value()),
+                nbt
+            });
+            rest = after;
+        }
+
+        // The light of the column follows, which is not decoded.
+        self.heightmaps = heightmaps;
+        Ok(())
+    }
+
+    /// Parses a column of 1.16.2 to 1.17.1, which sends the sections named by a bitmask.
+    fn parse_masked(&mut self, bytes: &[u8], version: ProtocolVersion) -> Result<(), MCTypeError> {
+        let (mask, rest) = match version.number() >= BITSET_MASK_SINCE {
+            true => {
+                let (length, mut rest) = VarInt::parse(bytes)?;
+                let length = limits::array_length(length.value())?;
+                if length > rest.len() / 8 {
+                    return Err(MCTypeError::OversizeLength(length as i64));
+                }
+                let mut mask = Vec::with_capacity(length);
+                for _ in 0..length {
+                    let (long, after) = MCLong::parse(rest)?;
+                    mask.push(long.value() as u64);
+                    rest = after;
+                }
+                (mask, rest)
+            },
+            false => {
+                let (full, rest) = MCBoolean::parse(bytes)?;
+                let (mask, rest) = VarInt::parse(rest)?;
+                self.full = full.value();
+                (vec![mask.value() as u32 as u64], rest)
+            }
+        };
+
+        let (heightmaps, rest) = NbtTag::parse_for(rest, version)?;
+        let mut rest = rest;
+        if self.full {
+            let (length, mut after) = VarInt::parse(rest)?;
+            if length.value() < 0 || length.value() as usize > after.len() {
+                return Err(MCTypeError::OversizeLength(length.value() as i64));
+            }
+            for _ in 0..length.value() {
+                let (biome, next) = VarInt::parse(after)?;
+                self.biomes.push(biome.value());
+                after = next;
+            }
+            rest = after;
+        }
+
+        let (mut data, rest) = read_data(rest)?;
+        let sent = |index: usize| mask.get(index / 64).is_some_and(|long| long >> (index % 64) & 1 == 1);
+        let section_count = mask.iter().enumerate().rev()
+            .find(|(_, long)| **long != 0)
+            .map_or(0, |(index, long)| index * 64 + 64 - long.leading_zeros() as usize);
+        // Dimensions were 16 sections high before 1.17.
+        let section_count = match version.number() >= BITSET_MASK_SINCE {
+            true => section_count,
+            false => section_count.max(16)
+        };
+        for index in 0..section_count {
+            if !sent(index) {
+                self.sections.push(self.full.then(ChunkSection::empty));
+                continue;
+            }
+            let (block_count, rest) = MCShort::parse(data)?;
+            let (block_states, rest) = PalettedContainer::parse_masked(rest)?;
+            self.sections.push(Some(ChunkSection { block_count: block_count.value(), block_states, biomes: None }));
+            data = rest;
+        }
+
+        let (count, mut rest) = VarInt::parse(rest)?;
+        for _ in 0..count.value() {
+            let (nbt, after) = NbtTag::parse_for(rest, version)?;
+            let coordinate = |name| nbt.as_ref().and_then(|nbt| nbt.get(name)).and_then(NbtTag::as_i64).unwrap_or_default() as i32;
+            self.block_entities.push(BlockEntity { x: coordinate("x"), y: coordinate("y"), z: coordinate("z"), kind: None, nbt });
+            rest = after;
+        }
+
+        self.heightmaps = heightmaps;
+        Ok(())
+    }
+}
+
+/// Reads the length-prefixed data of the sections of a column.
+fn read_data(bytes: &[u8]) -> Result<(&[u8], &[u8]), MCTypeError> {
+    let (size, rest) = VarInt::parse(bytes)?;
+    if size.value() < 0 || size.value() as usize > rest.len() {
+        return Err(MCTypeError::OversizeLength(size.value() as i64));
+    }
+    Ok(rest.split_at(size.value() as usize))
+}
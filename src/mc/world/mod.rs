@@ -0,0 +1,175 @@
+//! The world around the player, as the server sends it.
+use std::{collections::HashMap, io, path::Path, sync::Arc};
+
+use self::{blocks::{BlockRegistry, BlockState}, chunk::{Chunk, SECTION_WIDTH}, schematic::{Region, Schematic}};
+use super::{error::MCTypeError, mctypes::MCPosition, packets::play::Clientbound, version::ProtocolVersion};
+
+pub mod blocks;
+pub mod chunk;
+pub mod schematic;
+
+/// The chunk columns loaded around the player, kept up to date with the block changes
+/// sent by the server. Block state IDs are named by the world's `BlockRegistry`, which
+/// should be loaded from the block report of the server's version.
+/// # Example
+/// ```no_run
+/// use mcclient::{Client, mc::{connection::Connection, mctypes::MCPosition, packets::play::Clientbound, version::ProtocolVersion, world::{World, blocks::BlockRegistry, chunk::OVERWORLD_MIN_Y}}};
+///
+/// let mut client = Client::login_offline("localhost", 25565, "Makoto").expect("Could not join");
+/// let mut world = World::new(ProtocolVersion::V1_19_3, OVERWORLD_MIN_Y);
+/// world.set_block_registry(BlockRegistry::load("blocks.json").expect("Could not load the block report").into());
+/// loop {
+///     let packet = Clientbound::decode(client.connection().sock().read().expect("Disconnected")).expect("Invalid packet");
+///     world.handle(&packet).expect("Invalid chunk");
+///     if let Some(block) = world.block_at(MCPosition::new(0, 64, 0)) {
+///         println!("Block at spawn: {}", block);
+///     }
+/// }
+/// ```
+#[derive(Clone, Debug)]
+pub struct World {
+    version: ProtocolVersion,
+    min_y: i32,
+    chunks: HashMap<(i32, i32), Chunk>,
+    blocks: Option<Arc<BlockRegistry>>
+}
+
+impl World {
+    /// Creates a world without chunks, whose packets are decoded in the format of
+    /// `version`, in a dimension whose lowest block is at `min_y`.
+    pub fn new(version: ProtocolVersion, min_y: i32) -> Self {
+        World { version, min_y, chunks: HashMap::new(), blocks: None }
+    }
+
+    pub fn version(&self) -> ProtocolVersion {
+        self.version
+    }
+
+    /// The lowest block of the dimension.
+    pub fn min_y(&self) -> i32 {
+        self.min_y
+    }
+
+    /// Gets the registry naming the block states of the world. Unless one is set, this is
+    /// `BlockRegistry::builtin`, which only names the first few.
+    pub fn block_registry(&self) -> &BlockRegistry {
+        self.blocks.as_deref().unwrap_or(BlockRegistry::builtin())
+    }
+
+    /// Sets the registry naming the block states of the world, which may be shared with
+    /// other worlds of the same version.
+    pub fn set_block_registry(&mut self, blocks: Arc<BlockRegistry>) {
+        self.blocks = Some(blocks);
+    }
+
+    /// Forgets every chunk, and sets the lowest block of the dimension, as when the
+    /// player changes dimensions.
+    pub fn reset(&mut self, min_y: i32) {
+        self.chunks.clear();
+        self.min_y = min_y;
+    }
+
+    /// Applies the Play packet `packet` to the world, if it changes it. The chunks of
+    /// versions before 1.16.2, which cannot be decoded, are ignored.
+    /// # Errors
+    /// This function will return an `MCTypeError` if the packet carries a chunk or block
+    /// changes which cannot be decoded.
+    pub fn handle(&mut self, packet: &Clientbound) -> Result<(), MCTypeError> {
+        match packet {
+            Clientbound::ChunkData(chunk) if Chunk::is_supported(self.version) => self.insert_chunk(chunk.chunk(self.version, self.min_y)?),
+            Clientbound::BlockUpdate(update) => {
+                self.set_block(update.position(self.version), update.block_state.value());
+            },
+            Clientbound::UpdateSectionBlocks(update) => {
+                for (position, state) in update.changes(self.version)? {
+                    self.set_block(position, state);
+                }
+            },
+            Clientbound::UnloadChunk(unload) => {
+                let (x, z) = unload.coordinates(self.version);
+                self.unload_chunk(x, z);
+            },
+            _ => ()
+        }
+        Ok(())
+    }
+
+    /// Loads `chunk`, replacing the column at its coordinates. Columns which were not sent
+    /// in full only replace the sections they carry.
+    pub fn insert_chunk(&mut self, chunk: Chunk) {
+        match self.chunks.get_mut(&(chunk.x, chunk.z)) {
+            Some(loaded) if !chunk.full => {
+                if loaded.sections.len() < chunk.sections.len() {
+                    loaded.sections.resize(chunk.sections.len(), None);
+                }
+                for (index, section) in chunk.sections.into_iter().enumerate() {
+                    if section.is_some() {
+                        loaded.sections[index] = section;
+                    }
+                }
+                loaded.block_entities.extend(chunk.block_entities);
+            },
+            _ => {
+                self.chunks.insert((chunk.x, chunk.z), chunk);
+            }
+        }
+    }
+
+    /// Forgets the column at the given chunk coordinates, returning it if it was loaded.
+    pub fn unload_chunk(&mut self, x: i32, z: i32) -> Option<Chunk> {
+        self.chunks.remove(&(x, z))
+    }
+
+    /// Gets the column at the given chunk coordinates, if it is loaded.
+    pub fn chunk(&self, x: i32, z: i32) -> Option<&Chunk> {
+        self.chunks.get(&(x, z))
+    }
+
+    /// Gets the column holding the block at `position`, if it is loaded.
+    pub fn chunk_at(&self, position: MCPosition) -> Option<&Chunk> {
+        self.chunk(position.x().div_euclid(SECTION_WIDTH), position.z().div_euclid(SECTION_WIDTH))
+    }
+
+    /// Iterates over the loaded columns, in no particular order.
+    pub fn chunks(&self) -> impl Iterator<Item = &Chunk> {
+        self.chunks.values()
+    }
+
+    /// The number of loaded columns.
+    pub fn len(&self) -> usize {
+        self.chunks.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.chunks.is_empty()
+    }
+
+    /// Gets the block at `position`, or `None` if its column is not loaded, the position
+    /// is outside of the column's height, or its state is not in the block registry.
+    pub fn block_at(&self, position: MCPosition) -> Option<&BlockState> {
+        self.block_registry().get(self.block_state_at(position)?)
+    }
+
+    /// Gets the ID of the block state at `position`, or `None` if its column is not
+    /// loaded, or the position is outside of the column's height.
+    pub fn block_state_at(&self, position: MCPosition) -> Option<i32> {
+        self.chunk_at(position)?.get_block_state(position.x(), position.y(), position.z())
+    }
+
+    /// Sets the block state at `position`. Returns whether the block was set, which it is
+    /// not if its column is not loaded, or the position is outside of the column's height.
+    pub fn set_block(&mut self, position: MCPosition, state: i32) -> bool {
+        let key = (position.x().div_euclid(SECTION_WIDTH), position.z().div_euclid(SECTION_WIDTH));
+        self.chunks.get_mut(&key)
+            .is_some_and(|chunk| chunk.set_block_state(position.x(), position.y(), position.z(), state))
+    }
+
+    /// Captures the blocks and block entities of the world within `region`, and saves them
+    /// to the file at `path`, as per `Schematic::capture` and `Schematic::save`.
+    /// # Errors
+    /// This function will return the errors of `Schematic::capture`, or an error if the
+    /// file cannot be written.
+    pub fn export_region<P: AsRef<Path>>(&self, region: Region, path: P) -> Result<(), io::Error> {
+        Schematic::capture(self, region, true)?.save(path)
+    }
+}
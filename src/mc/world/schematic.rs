@@ -0,0 +1,265 @@
+//! Snapshots of regions of the world, saved as Sponge schematics or as SNBT structures.
+use std::{collections::HashMap, fs::File, io::{self, BufWriter, Write}, path::Path};
+
+use flate2::{write::GzEncoder, Compression};
+
+use super::{World, blocks::BlockState, chunk::{BlockEntity, SECTION_WIDTH}};
+use crate::mc::{mctypes::{MCPosition, MCType, VarInt}, nbt::{NbtCompound, NbtTag}};
+
+/// The version of the Sponge schematic format schematics are written in.
+const SPONGE_VERSION: i32 = 2;
+
+/// A box of blocks, from its lowest to its highest corner, both included.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Region {
+    pub min: MCPosition,
+    pub max: MCPosition
+}
+
+impl Region {
+    /// Creates the region spanning the corners `a` and `b`, in any order.
+    pub fn new(a: MCPosition, b: MCPosition) -> Self {
+        Region {
+            min: MCPosition::new(a.x().min(b.x()), a.y().min(b.y()), a.z().min(b.z())),
+            max: MCPosition::new(a.x().max(b.x()), a.y().max(b.y()), a.z().max(b.z()))
+        }
+    }
+
+    /// The number of blocks the region spans along x, y and z.
+    pub fn size(&self) -> [i64; 3] {
+        [
+            self.max.x() as i64 - self.min.x() as i64 + 1,
+            self.max.y() as i64 - self.min.y() as i64 + 1,
+            self.max.z() as i64 - self.min.z() as i64 + 1
+        ]
+    }
+
+    /// Whether the block at `x`, `y` and `z` is within the region.
+    pub fn contains(&self, x: i32, y: i32, z: i32) -> bool {
+        (self.min.x()..=self.max.x()).contains(&x) && (self.min.y()..=self.max.y()).contains(&y) && (self.min.z()..=self.max.z()).contains(&z)
+    }
+}
+
+/// The blocks of a region of the world, captured with `Schematic::capture`. Blocks are
+/// numbered by their index in the palette, and ordered along x, then z, then y, as in
+/// Sponge schematics.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Schematic {
+    /// The world position of the lowest corner of the region.
+    pub origin: MCPosition,
+    /// The number of blocks the region spans along x, y and z.
+    pub size: [u16; 3],
+    pub palette: Vec<BlockState>,
+    pub blocks: Vec<u32>,
+    /// The block entities of the region, at their world coordinates.
+    pub block_entities: Vec<BlockEntity>,
+    /// The data version of the world's version, which programs loading the schematic
+    /// upgrade its blocks from.
+    pub data_version: Option<i32>
+}
+
+impl Schematic {
+    /// Captures the blocks of `world` within `region`, along with their block entities if
+    /// `block_entities` is set.
+    /// # Errors
+    /// This function will return an `InvalidInput` error if the region spans more than
+    /// 65535 blocks along an axis, or a block is not loaded, or an `InvalidData` error if
+    /// the state of a block is not in the block registry of the world.
+    pub fn capture(world: &World, region: Region, block_entities: bool) -> Result<Self, io::Error> {
+        let size = region.size().map(u16::try_from);
+        let [Ok(width), Ok(height), Ok(length)] = size else {
+            return Err(io::Error::new(io::ErrorKind::InvalidInput, "Schematics span at most 65535 blocks along each axis."));
+        };
+
+        let mut schematic = Schematic {
+            origin: region.min,
+            size: [width, height, length],
+            palette: Vec::new(),
+            blocks: Vec::with_capacity(width as usize * height as usize * length as usize),
+            block_entities: Vec::new(),
+            data_version: world.version().data_version()
+        };
+        let mut indices = HashMap::new();
+        for y in region.min.y()..=region.max.y() {
+            for z in region.min.z()..=region.max.z() {
+                for x in region.min.x()..=region.max.x() {
+                    let position = MCPosition::new(x, y, z);
+                    let state = world.block_state_at(position)
+                        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, format!("The block at {}, {}, {} is not loaded.", x, y, z)))?;
+                    let index = match indices.get(&state) {
+                        Some(index) => *index,
+                        None => {
+                            let block = world.block_registry().get(state)
+                                .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, format!("Block state {} is not in the block registry.", state)))?;
+                            schematic.palette.push(block.clone());
+                            indices.insert(state, schematic.palette.len() as u32 - 1);
+                            schematic.palette.len() as u32 - 1
+                        }
+                    };
+                    schematic.blocks.push(index);
+                }
+            }
+        }
+
+        if block_entities {
+            let (min_x, max_x) = (region.min.x().div_euclid(SECTION_WIDTH), region.max.x().div_euclid(SECTION_WIDTH));
+            let (min_z, max_z) = (region.min.z().div_euclid(SECTION_WIDTH), region.max.z().div_euclid(SECTION_WIDTH));
+            schematic.block_entities = world.chunks()
+                .filter(|chunk| (min_x..=max_x).contains(&chunk.x) && (min_z..=max_z).contains(&chunk.z))
+                .flat_map(|chunk| chunk.block_entities.iter())
+                .filter(|entity| region.contains(entity.x, entity.y, entity.z))
+                .cloned()
+                .collect();
+        }
+        Ok(schematic)
+    }
+
+    /// Gets the block at `x`, `y` and `z`, relative to the origin, if it is within the
+    /// region.
+    pub fn block(&self, x: u16, y: u16, z: u16) -> Option<&BlockState> {
+        let [width, height, length] = self.size;
+        if x >= width || y >= height || z >= length {
+            return None;
+        }
+        let index = (y as usize * length as usize + z as usize) * width as usize + x as usize;
+        self.palette.get(*self.blocks.get(index)? as usize)
+    }
+
+    /// Encodes the schematic as the root tag of a Sponge schematic of version 2, as read
+    /// by WorldEdit and most other editors.
+    pub fn to_sponge_nbt(&self) -> NbtTag {
+        let mut palette = NbtCompound::new();
+        for (index, block) in self.palette.iter().enumerate() {
+            palette.insert(block.to_string(), NbtTag::Int(index as i32));
+        }
+        let mut block_data = Vec::with_capacity(self.blocks.len());
+        for index in &self.blocks {
+            VarInt::from(*index as i32).encode(&mut block_data).expect("Writing to a Vec cannot fail.");
+        }
+        let block_entities = self.block_entities.iter().map(|entity| {
+            let mut compound = block_entity_data(entity);
+            compound.insert("Pos".to_owned(), NbtTag::IntArray(self.relative(entity).to_vec()));
+            compound.insert("Id".to_owned(), NbtTag::String(self.block_entity_id(entity)));
+            NbtTag::Compound(compound)
+        });
+
+        let [width, height, length] = self.size;
+        let mut schematic = NbtCompound::from([
+            ("Version".to_owned(), NbtTag::Int(SPONGE_VERSION)),
+            ("Width".to_owned(), NbtTag::Short(width as i16)),
+            ("Height".to_owned(), NbtTag::Short(height as i16)),
+            ("Length".to_owned(), NbtTag::Short(length as i16)),
+            ("PaletteMax".to_owned(), NbtTag::Int(self.palette.len() as i32)),
+            ("Palette".to_owned(), NbtTag::Compound(palette)),
+            ("BlockData".to_owned(), NbtTag::ByteArray(block_data.into_iter().map(|byte| byte as i8).collect())),
+            ("BlockEntities".to_owned(), NbtTag::List(block_entities.collect()))
+        ]);
+        if let Some(data_version) = self.data_version {
+            schematic.insert("DataVersion".to_owned(), NbtTag::Int(data_version));
+        }
+        NbtTag::Compound(schematic)
+    }
+
+    /// Encodes the schematic as the root tag of a structure, as saved by structure blocks
+    /// and written to SNBT by the data generator.
+    pub fn to_structure_nbt(&self) -> NbtTag {
+        let palette = self.palette.iter().map(|block| {
+            let mut state = NbtCompound::from([("Name".to_owned(), NbtTag::String(block.name.clone()))]);
+            if !block.properties.is_empty() {
+                let properties = block.properties.iter().map(|(name, value)| (name.clone(), NbtTag::String(value.clone()))).collect();
+                state.insert("Properties".to_owned(), NbtTag::Compound(properties));
+            }
+            NbtTag::Compound(state)
+        });
+        let block_entities: HashMap<[i32; 3], &BlockEntity> = self.block_entities.iter()
+            .map(|entity| (self.relative(entity), entity))
+            .collect();
+
+        let [width, height, length] = self.size;
+        let mut blocks = Vec::with_capacity(self.blocks.len());
+        for (index, state) in self.blocks.iter().enumerate() {
+            let x = index % width as usize;
+            let z = index / width as usize % length as usize;
+            let y = index / (width as usize * length as usize);
+            let position = [x as i32, y as i32, z as i32];
+            let mut block = NbtCompound::from([
+                ("pos".to_owned(), NbtTag::List(position.map(NbtTag::Int).to_vec())),
+                ("state".to_owned(), NbtTag::Int(*state as i32))
+            ]);
+            if let Some(entity) = block_entities.get(&position) {
+                let mut nbt = block_entity_data(entity);
+                nbt.insert("id".to_owned(), NbtTag::String(self.block_entity_id(entity)));
+                block.insert("nbt".to_owned(), NbtTag::Compound(nbt));
+            }
+            blocks.push(NbtTag::Compound(block));
+        }
+
+        let mut structure = NbtCompound::from([
+            ("size".to_owned(), NbtTag::List([width, height, length].map(|length| NbtTag::Int(length as i32)).to_vec())),
+            ("palette".to_owned(), NbtTag::List(palette.collect())),
+            ("blocks".to_owned(), NbtTag::List(blocks)),
+            ("entities".to_owned(), NbtTag::List(Vec::new()))
+        ]);
+        if let Some(data_version) = self.data_version {
+            structure.insert("DataVersion".to_owned(), NbtTag::Int(data_version));
+        }
+        NbtTag::Compound(structure)
+    }
+
+    /// Writes the schematic as a gzipped Sponge schematic, as per `to_sponge_nbt`.
+    /// # Errors
+    /// This function will return an error if `w` cannot be written to.
+    pub fn write_sponge<W: Write>(&self, w: W) -> Result<(), io::Error> {
+        let mut encoder = GzEncoder::new(w, Compression::default());
+        self.to_sponge_nbt().write_named("Schematic", &mut encoder)?;
+        encoder.finish()?.flush()
+    }
+
+    /// Writes the schematic as an SNBT structure, as per `to_structure_nbt`.
+    /// # Errors
+    /// This function will return an error if `w` cannot be written to.
+    pub fn write_snbt<W: Write>(&self, mut w: W) -> Result<(), io::Error> {
+        writeln!(w, "{}", self.to_structure_nbt())?;
+        w.flush()
+    }
+
+    /// Saves the schematic to the file at `path`, as an SNBT structure if its extension
+    /// is `snbt`, or as a Sponge schematic otherwise, which is usually given the extension
+    /// `schem`.
+    /// # Errors
+    /// This function will return an error if the file cannot be written.
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<(), io::Error> {
+        let path = path.as_ref();
+        let file = BufWriter::new(File::create(path)?);
+        match path.extension().is_some_and(|extension| extension.eq_ignore_ascii_case("snbt")) {
+            true => self.write_snbt(file),
+            false => self.write_sponge(file)
+        }
+    }
+
+    /// Gets the coordinates of `entity` relative to the origin.
+    fn relative(&self, entity: &BlockEntity) -> [i32; 3] {
+        [entity.x - self.origin.x(), entity.y - self.origin.y(), entity.z - self.origin.z()]
+    }
+
+    /// Names the type of `entity`, as its NBT does before 1.18. Since, servers only send
+    /// the ID of the type, so the type is named after the block it sits in instead, which
+    /// is the name of most types, though not of those shared by several blocks, such as
+    /// signs and beds.
+    fn block_entity_id(&self, entity: &BlockEntity) -> String {
+        if let Some(id) = entity.nbt.as_ref().and_then(|nbt| nbt.get("id")).and_then(NbtTag::as_str) {
+            return id.to_owned();
+        }
+        let [x, y, z] = self.relative(entity).map(|coordinate| coordinate as u16);
+        self.block(x, y, z).map(|block| block.name.clone()).unwrap_or_default()
+    }
+}
+
+/// Gets the tags of the NBT of `entity`, without those giving its position and type.
+fn block_entity_data(entity: &BlockEntity) -> NbtCompound {
+    let mut data = entity.nbt.as_ref().and_then(NbtTag::as_compound).cloned().unwrap_or_default();
+    for tag in ["x", "y", "z", "id"] {
+        data.remove(tag);
+    }
+    data
+}